@@ -0,0 +1,205 @@
+//! Background sync agent
+//!
+//! Pulls the sync remote on an interval and imports what changed, the same
+//! way `manifold sync pull all` does. Conflicts are saved for
+//! `manifold conflicts` and never resolved automatically. Each tick is
+//! recorded in the database's agent event log, shown by `manifold agent logs`.
+//!
+//! Agents run under an [`AgentManager`], which lends each tick a connection
+//! from one shared [`DbPool`] instead of opening a new one.
+
+use crate::collab::sync::{pull_specs, PullReport, SyncManager};
+use crate::commands::load_sync_config;
+use crate::config::ManifoldPaths;
+use crate::db::{Database, DbPool};
+use anyhow::{bail, Result};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Connections shared by the agents of one `AgentManager`
+pub const DEFAULT_POOL_SIZE: usize = 4;
+
+/// Pulls one remote branch into one manifold
+#[derive(Debug, Clone)]
+pub struct SyncAgent {
+    paths: ManifoldPaths,
+    remote: String,
+    branch: String,
+}
+
+impl SyncAgent {
+    pub fn new(paths: ManifoldPaths, remote: &str, branch: &str) -> Self {
+        Self {
+            paths,
+            remote: remote.to_string(),
+            branch: branch.to_string(),
+        }
+    }
+
+    /// Id the agent's events are logged under, e.g. `sync:origin/main`
+    pub fn id(&self) -> String {
+        format!("sync:{}/{}", self.remote, self.branch)
+    }
+
+    /// Pull and import once, logging the outcome as agent events
+    ///
+    /// Sync settings are reloaded every tick so `sync init` changes apply
+    /// without restarting the agent.
+    #[allow(dead_code)]
+    pub fn tick(&self) -> Result<PullReport> {
+        self.tick_with(&Database::open(&self.paths)?)
+    }
+
+    /// Like [`SyncAgent::tick`], on a connection the caller provides
+    pub fn tick_with(&self, db: &Database) -> Result<PullReport> {
+        let result = load_sync_config(&self.paths).and_then(|config| {
+            let manager = SyncManager::new(config);
+            pull_specs(&manager, db, &self.remote, &self.branch, None)
+        });
+
+        // Logging is best-effort; the pull already happened
+        if let Err(e) = self.log_outcome(db, &result) {
+            eprintln!("⚠ Failed to record agent event: {:#}", e);
+        }
+        result
+    }
+
+    fn log_outcome(&self, db: &Database, result: &Result<PullReport>) -> Result<()> {
+        let id = self.id();
+        let now = chrono::Utc::now().timestamp();
+        let report = match result {
+            Ok(report) => report,
+            Err(e) => return db.log_agent_event(&id, "error", &format!("{:#}", e), now),
+        };
+
+        db.log_agent_event(&id, "pull", &report.summary(), now)?;
+        for (spec_id, conflicts) in &report.conflicted {
+            let detail = format!("{}: {} conflict(s) saved", spec_id, conflicts.len());
+            db.log_agent_event(&id, "conflict", &detail, now)?;
+        }
+        for (spec_id, error) in &report.failed {
+            let detail = format!("{}: {}", spec_id, error);
+            db.log_agent_event(&id, "import_failed", &detail, now)?;
+        }
+        Ok(())
+    }
+}
+
+fn print_report(report: &PullReport) {
+    let stamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+    println!("[{}] sync: {}", stamp, report.summary());
+    for (spec_id, conflicts) in &report.conflicted {
+        println!(
+            "[{}]   ⚠ {}: {} conflict(s) saved, run 'manifold conflicts list'",
+            stamp,
+            spec_id,
+            conflicts.len()
+        );
+    }
+    for (spec_id, error) in &report.failed {
+        eprintln!("[{}]   ⚠ Failed to import {}: {}", stamp, spec_id, error);
+    }
+}
+
+/// Start pulling `remote`/`branch` into ~/.manifold every `interval_secs`
+///
+/// Runs on the current tokio runtime under its own [`AgentManager`]; abort
+/// the handle to stop it.
+pub fn spawn_sync_agent(interval_secs: u64, remote: &str, branch: &str) -> Result<JoinHandle<()>> {
+    let paths = ManifoldPaths::new()?;
+    let mut agents = AgentManager::new(&paths, DEFAULT_POOL_SIZE);
+    agents.spawn_sync(&paths, interval_secs, remote, branch)?;
+    Ok(tokio::spawn(agents.wait()))
+}
+
+/// Runs agents on the current tokio runtime, sharing one connection pool
+///
+/// Dropping the manager stops its agents.
+pub struct AgentManager {
+    pool: Arc<DbPool>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl AgentManager {
+    /// A manager whose agents share at most `pool_size` connections
+    pub fn new(paths: &ManifoldPaths, pool_size: usize) -> Self {
+        Self {
+            pool: DbPool::new(paths, pool_size),
+            handles: Vec::new(),
+        }
+    }
+
+    /// The pool agent ticks borrow their connections from
+    #[allow(dead_code)]
+    pub fn pool(&self) -> &Arc<DbPool> {
+        &self.pool
+    }
+
+    /// Run `task` every `interval` with a pooled connection
+    ///
+    /// Ticks run on the blocking thread pool, since SQLite (and git, for
+    /// sync) block. A failed tick is reported under `id` and the agent keeps
+    /// going.
+    pub fn spawn<F>(&mut self, id: &str, interval: Duration, task: F)
+    where
+        F: Fn(&Database) -> Result<()> + Send + Sync + 'static,
+    {
+        let pool = Arc::clone(&self.pool);
+        let task = Arc::new(task);
+        let id = id.to_string();
+        self.handles.push(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+
+                let (pool, task) = (Arc::clone(&pool), Arc::clone(&task));
+                let result = tokio::task::spawn_blocking(move || task(&*pool.get()?)).await;
+                let stamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+                match result {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => eprintln!("[{}] ⚠ {} failed: {:#}", stamp, id, e),
+                    Err(e) => eprintln!("[{}] ⚠ {} panicked: {}", stamp, id, e),
+                }
+            }
+        }));
+    }
+
+    /// Start pulling `remote`/`branch` into the manifold every `interval_secs`
+    pub fn spawn_sync(
+        &mut self,
+        paths: &ManifoldPaths,
+        interval_secs: u64,
+        remote: &str,
+        branch: &str,
+    ) -> Result<()> {
+        if interval_secs == 0 {
+            bail!("Sync agent interval must be at least 1 second");
+        }
+        // Fail now rather than on every tick
+        load_sync_config(paths)?;
+
+        let agent = SyncAgent::new(paths.clone(), remote, branch);
+        let id = agent.id();
+        self.spawn(&id, Duration::from_secs(interval_secs), move |db| {
+            print_report(&agent.tick_with(db)?);
+            Ok(())
+        });
+        Ok(())
+    }
+
+    /// Wait for every agent; they only finish if their task panics the runtime
+    pub async fn wait(mut self) {
+        for handle in std::mem::take(&mut self.handles) {
+            let _ = handle.await;
+        }
+    }
+}
+
+impl Drop for AgentManager {
+    fn drop(&mut self) {
+        for handle in &self.handles {
+            handle.abort();
+        }
+    }
+}
@@ -1,10 +1,13 @@
 //! Conflict detection and resolution
 
-use super::{Conflict, ConflictStatus, ResolutionStrategy};
+use super::{Conflict, ConflictGroup, ConflictStatus, ResolutionStrategy};
 use crate::models::SpecData;
 use anyhow::{anyhow, Result};
 use serde_json::Value;
 
+/// Top-level scalar fields compared as a whole by conflict detection
+const FIELD_CONFLICT_KEYS: [&str; 4] = ["name", "stage", "boundary", "project"];
+
 /// Conflict resolver for merging specs
 pub struct ConflictResolver;
 
@@ -21,26 +24,19 @@ impl ConflictResolver {
         // Serialize to JSON for comparison
         let local_json = serde_json::to_value(local)?;
         let remote_json = serde_json::to_value(remote)?;
-        let base_json = base.map(|b| serde_json::to_value(b)).transpose()?;
+        let base_json = base.map(serde_json::to_value).transpose()?;
 
         // Check for conflicts in key fields
-        conflicts.extend(Self::check_field_conflict(
-            &local.spec_id,
-            "name",
-            &local_json["name"],
-            &remote_json["name"],
-            base_json.as_ref().and_then(|b| b.get("name")),
-            now,
-        )?);
-
-        conflicts.extend(Self::check_field_conflict(
-            &local.spec_id,
-            "stage",
-            &local_json["stage"],
-            &remote_json["stage"],
-            base_json.as_ref().and_then(|b| b.get("stage")),
-            now,
-        )?);
+        for field in FIELD_CONFLICT_KEYS {
+            conflicts.extend(Self::check_field_conflict(
+                &local.spec_id,
+                field,
+                &local_json[field],
+                &remote_json[field],
+                base_json.as_ref().and_then(|b| b.get(field)),
+                now,
+            )?);
+        }
 
         // Check requirements conflicts
         conflicts.extend(Self::check_array_conflicts(
@@ -75,6 +71,159 @@ impl ConflictResolver {
         Ok(conflicts)
     }
 
+    /// Combine non-conflicting changes from both sides against `base`
+    ///
+    /// Call only after [`ConflictResolver::detect_conflicts`] found nothing.
+    /// The fields it checks take whichever side changed them; array items
+    /// are matched by id, so additions and deletions on either side carry
+    /// over. Everything else is kept from `local`.
+    pub fn merge_with_base(
+        local: &SpecData,
+        remote: &SpecData,
+        base: &SpecData,
+    ) -> Result<SpecData> {
+        let mut merged = serde_json::to_value(local)?;
+        let remote_json = serde_json::to_value(remote)?;
+        let base_json = serde_json::to_value(base)?;
+
+        for field in FIELD_CONFLICT_KEYS {
+            if merged[field] == base_json[field] {
+                merged[field] = remote_json[field].clone();
+            }
+        }
+
+        for field in ["requirements", "tasks", "decisions"] {
+            let nested = (field == "requirements").then_some("scenarios");
+            merged[field] = Self::merge_items(
+                &merged[field],
+                &remote_json[field],
+                &base_json[field],
+                nested,
+            );
+        }
+
+        Ok(serde_json::from_value(merged)?)
+    }
+
+    /// Merge two arrays of items by id against `base`, as in `merge_with_base`
+    ///
+    /// Items both sides changed keep the local version, except that when
+    /// they differ only in the `nested` array, that array is merged the
+    /// same way.
+    fn merge_items(local: &Value, remote: &Value, base: &Value, nested: Option<&str>) -> Value {
+        let find = |items: &Value, id: &str| {
+            items
+                .as_array()
+                .and_then(|arr| arr.iter().find(|item| item_id(item).as_deref() == Some(id)))
+                .cloned()
+        };
+
+        let local_items = local.as_array().cloned().unwrap_or_default();
+        let mut items = Vec::new();
+        for mut item in local_items {
+            let Some(id) = item_id(&item) else {
+                items.push(item);
+                continue;
+            };
+            let base_item = find(base, &id);
+            match (find(remote, &id), base_item) {
+                // Deleted remotely and untouched locally
+                (None, Some(base_item)) if base_item == item => {}
+                // Changed only remotely
+                (Some(remote_item), Some(base_item)) if base_item == item => {
+                    items.push(remote_item)
+                }
+                // Changed on both sides, only within the nested array
+                (Some(remote_item), Some(base_item))
+                    if nested.is_some_and(|key| differs_only_in(&item, &remote_item, key)) =>
+                {
+                    let key = nested.unwrap_or_default();
+                    item[key] =
+                        Self::merge_items(&item[key], &remote_item[key], &base_item[key], None);
+                    items.push(item)
+                }
+                _ => items.push(item),
+            }
+        }
+
+        // Items added remotely
+        for remote_item in remote.as_array().cloned().unwrap_or_default() {
+            if let Some(id) = item_id(&remote_item) {
+                let known = find(local, &id).is_some() || find(base, &id).is_some();
+                if !known {
+                    items.push(remote_item);
+                }
+            }
+        }
+
+        Value::Array(items)
+    }
+
+    /// Combine the requirements, tasks, and decisions of two specs by id
+    ///
+    /// Spec-level fields come from `a`. Items only one side has are kept,
+    /// identical items once, and requirements that differ only in tags get
+    /// both tag lists. Any other difference keeps `a`'s item and is returned
+    /// as a conflict with `a` as local and `b` as remote.
+    pub fn merge_specs(a: &SpecData, b: &SpecData) -> Result<(SpecData, Vec<Conflict>)> {
+        let mut a_json = serde_json::to_value(a)?;
+        let mut b_json = serde_json::to_value(b)?;
+        let item_id = |item: &Value| item.get("id").and_then(Value::as_str).map(str::to_string);
+
+        // Union tags on both sides first so tags alone never conflict
+        let b_requirements = b_json["requirements"].as_array_mut();
+        let a_requirements = a_json["requirements"].as_array_mut();
+        if let (Some(a_reqs), Some(b_reqs)) = (a_requirements, b_requirements) {
+            for b_req in b_reqs.iter_mut() {
+                let Some(id) = item_id(b_req) else { continue };
+                let Some(a_req) = a_reqs
+                    .iter_mut()
+                    .find(|r| item_id(r).as_deref() == Some(id.as_str()))
+                else {
+                    continue;
+                };
+                let mut tags = a_req["tags"].as_array().cloned().unwrap_or_default();
+                for tag in b_req["tags"].as_array().cloned().unwrap_or_default() {
+                    if !tags.contains(&tag) {
+                        tags.push(tag);
+                    }
+                }
+                a_req["tags"] = Value::Array(tags.clone());
+                b_req["tags"] = Value::Array(tags);
+            }
+        }
+
+        let local: SpecData = serde_json::from_value(a_json.clone())?;
+        let remote: SpecData = serde_json::from_value(b_json.clone())?;
+        // Without a base every differing item conflicts; spec-level fields
+        // are taken from `a`, so only item conflicts are kept
+        let conflicts: Vec<Conflict> = Self::detect_conflicts(&local, &remote, None)?
+            .into_iter()
+            .filter(|c| c.field_path.contains('/'))
+            .collect();
+
+        for field in ["requirements", "tasks", "decisions"] {
+            let known: Vec<String> = a_json[field]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(item_id)
+                .collect();
+            let added: Vec<Value> = b_json[field]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|item| item_id(item).is_some_and(|id| !known.contains(&id)))
+                .cloned()
+                .collect();
+            if let Some(items) = a_json[field].as_array_mut() {
+                items.extend(added);
+            }
+        }
+
+        Ok((serde_json::from_value(a_json)?, conflicts))
+    }
+
     /// Check if a single field has conflicts
     fn check_field_conflict(
         spec_id: &str,
@@ -183,10 +332,25 @@ impl ConflictResolver {
                     let base_item = base_map.get(id);
 
                     // Check if both changed
-                    let local_changed = base_item.map_or(true, |base| local_item != base);
-                    let remote_changed = base_item.map_or(true, |base| remote_item != base);
-
-                    if local_changed && remote_changed {
+                    let local_changed = base_item != Some(local_item);
+                    let remote_changed = base_item != Some(remote_item);
+
+                    // Requirements whose scenarios alone differ conflict
+                    // per scenario rather than as a whole
+                    if local_changed
+                        && remote_changed
+                        && field_name == "requirements"
+                        && differs_only_in(local_item, remote_item, "scenarios")
+                    {
+                        conflicts.extend(Self::check_array_conflicts(
+                            spec_id,
+                            &format!("{}/{}/scenarios", field_name, id),
+                            local_item.get("scenarios"),
+                            remote_item.get("scenarios"),
+                            base_item.and_then(|b| b.get("scenarios")),
+                            timestamp,
+                        )?);
+                    } else if local_changed && remote_changed {
                         conflicts.push(Conflict {
                             id: uuid::Uuid::new_v4().to_string(),
                             spec_id: spec_id.to_string(),
@@ -247,9 +411,26 @@ impl ConflictResolver {
                 // Attempt automatic merge for compatible changes
                 Self::auto_merge(conflict)
             }
+            // The suggestion comes from llm::suggest_resolution
+            ResolutionStrategy::Ai => match manual_value {
+                Some(value) => Ok((value, ConflictStatus::ResolvedManual)),
+                None => Err(anyhow!("AI resolution requires a suggested value")),
+            },
         }
     }
 
+    /// Interpret a typed manual resolution
+    ///
+    /// Input that parses as JSON is used as-is (so `42`, `true`, or an
+    /// object keep their type); anything else is taken as a plain string.
+    /// Blank input means null, i.e. delete the field or item.
+    pub fn parse_manual_value(input: &str) -> Value {
+        if input.trim().is_empty() {
+            return Value::Null;
+        }
+        serde_json::from_str(input).unwrap_or_else(|_| Value::String(input.to_string()))
+    }
+
     /// Attempt automatic merge
     fn auto_merge(conflict: &Conflict) -> Result<(Value, ConflictStatus)> {
         // For arrays, try to merge non-conflicting items
@@ -272,31 +453,105 @@ impl ConflictResolver {
             return Ok((Value::Array(merged), ConflictStatus::ResolvedManual));
         }
 
+        // For objects, take each side's changed keys relative to the base
+        if let (Some(local_obj), Some(remote_obj)) = (
+            conflict.local_value.as_object(),
+            conflict.remote_value.as_object(),
+        ) {
+            let empty = serde_json::Map::new();
+            let base_obj = conflict
+                .base_value
+                .as_ref()
+                .and_then(|v| v.as_object())
+                .unwrap_or(&empty);
+            return Self::merge_object_keys(&conflict.field_path, local_obj, remote_obj, base_obj)
+                .map(|merged| (Value::Object(merged), ConflictStatus::ResolvedManual));
+        }
+
         // For simple values, can't auto-merge
         Err(anyhow!("Cannot auto-merge this conflict type"))
     }
 
+    /// Key-level 3-way merge of two objects
+    ///
+    /// A key missing on one side counts as a deletion when it was in the
+    /// base. Fails only when both sides changed the same key differently.
+    fn merge_object_keys(
+        field_path: &str,
+        local: &serde_json::Map<String, Value>,
+        remote: &serde_json::Map<String, Value>,
+        base: &serde_json::Map<String, Value>,
+    ) -> Result<serde_json::Map<String, Value>> {
+        let mut keys: Vec<&String> = local
+            .keys()
+            .chain(remote.keys())
+            .chain(base.keys())
+            .collect();
+        keys.sort();
+        keys.dedup();
+
+        let mut merged = serde_json::Map::new();
+        let mut clashes = Vec::new();
+
+        for key in keys {
+            let (l, r, b) = (local.get(key), remote.get(key), base.get(key));
+            let chosen = if l == r || r == b {
+                l
+            } else if l == b {
+                r
+            } else {
+                clashes.push(key.as_str());
+                continue;
+            };
+            if let Some(value) = chosen {
+                merged.insert(key.clone(), value.clone());
+            }
+        }
+
+        if !clashes.is_empty() {
+            return Err(anyhow!(
+                "Cannot auto-merge '{}': both sides changed {}",
+                field_path,
+                clashes.join(", ")
+            ));
+        }
+
+        Ok(merged)
+    }
+
     /// Apply resolved conflicts to spec
     pub fn apply_resolutions(spec: &mut SpecData, resolutions: &[(String, Value)]) -> Result<()> {
         let mut spec_json = serde_json::to_value(&spec)?;
 
         for (field_path, value) in resolutions {
-            // Parse field path (e.g., "name" or "requirements/req-001")
-            let parts: Vec<&str> = field_path.split('/').collect();
-
-            if parts.len() == 1 {
-                // Top-level field
-                spec_json[parts[0]] = value.clone();
-            } else if parts.len() == 2 {
-                // Array item by ID
-                if let Some(array) = spec_json[parts[0]].as_array_mut() {
-                    let id = parts[1];
-                    if let Some(item) = array
+            // Field paths alternate object keys and array item ids, e.g.
+            // "name", "requirements/req-001", or
+            // "requirements/req-001/scenarios/sc-002". Items that no longer
+            // exist are skipped.
+            let mut segments = field_path.split('/').peekable();
+            let mut current = &mut spec_json;
+            while let Some(segment) = segments.next() {
+                let last = segments.peek().is_none();
+                let next = match current {
+                    Value::Array(items) => items
                         .iter_mut()
-                        .find(|item| item.get("id").and_then(|v| v.as_str()) == Some(id))
-                    {
-                        *item = value.clone();
+                        .find(|item| item.get("id").and_then(|v| v.as_str()) == Some(segment)),
+                    // A missing key at the end is set, like a top-level field
+                    Value::Object(map) => {
+                        if last {
+                            Some(map.entry(segment.to_string()).or_insert(Value::Null))
+                        } else {
+                            map.get_mut(segment)
+                        }
                     }
+                    _ => None,
+                };
+                match next {
+                    Some(next) => current = next,
+                    None => break,
+                }
+                if last {
+                    *current = value.clone();
                 }
             }
         }
@@ -306,6 +561,57 @@ impl ConflictResolver {
         Ok(())
     }
 
+    /// Machine-readable summary of a resolved conflict
+    pub fn resolution_json(
+        conflict: &Conflict,
+        strategy: &str,
+        resolved_value: &Value,
+        status: &ConflictStatus,
+    ) -> Value {
+        serde_json::json!({
+            "conflict_id": conflict.id,
+            "spec_id": conflict.spec_id,
+            "field_path": conflict.field_path,
+            "strategy": strategy,
+            "resolved_value": resolved_value,
+            "status": status.to_string(),
+        })
+    }
+
+    /// Group conflicts by spec id, in spec id order
+    ///
+    /// With `unresolved_only`, resolved conflicts are dropped from each group
+    /// (but still counted) and specs left with nothing to show are omitted.
+    pub fn group_by_spec(conflicts: Vec<Conflict>, unresolved_only: bool) -> Vec<ConflictGroup> {
+        let mut groups: std::collections::BTreeMap<String, ConflictGroup> = Default::default();
+
+        for conflict in conflicts {
+            let group = groups
+                .entry(conflict.spec_id.clone())
+                .or_insert_with(|| ConflictGroup {
+                    spec_id: conflict.spec_id.clone(),
+                    unresolved: 0,
+                    resolved: 0,
+                    conflicts: Vec::new(),
+                });
+
+            if conflict.status == ConflictStatus::Unresolved {
+                group.unresolved += 1;
+            } else {
+                group.resolved += 1;
+                if unresolved_only {
+                    continue;
+                }
+            }
+            group.conflicts.push(conflict);
+        }
+
+        groups
+            .into_values()
+            .filter(|group| !group.conflicts.is_empty())
+            .collect()
+    }
+
     /// Get conflict summary for display
     pub fn format_conflict(conflict: &Conflict) -> String {
         format!(
@@ -326,3 +632,22 @@ impl ConflictResolver {
         }
     }
 }
+
+fn item_id(item: &Value) -> Option<String> {
+    item.get("id")
+        .and_then(|id| id.as_str())
+        .map(str::to_string)
+}
+
+/// Whether two objects are equal apart from `key`
+fn differs_only_in(a: &Value, b: &Value, key: &str) -> bool {
+    match (a.as_object(), b.as_object()) {
+        (Some(a), Some(b)) => {
+            let (mut a, mut b) = (a.clone(), b.clone());
+            a.remove(key);
+            b.remove(key);
+            a == b
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,226 @@
+//! Semantic diff between two specs
+//!
+//! Requirements, tasks, and decisions are matched by id rather than by
+//! position, so reordering is not a change. Inside a changed item, nested
+//! arrays of objects with ids (scenarios) are matched the same way and
+//! every differing leaf is reported with its before and after value.
+
+use crate::models::SpecData;
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Top-level keys that are compared item by item
+pub const ITEM_SECTIONS: [&str; 3] = ["requirements", "tasks", "decisions"];
+
+/// Keys that change on every edit and say nothing about the spec itself
+const IGNORED_KEYS: [&str; 2] = ["$schema", "history"];
+
+/// One value that differs; `Null` stands for absent
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    /// Slash-separated path inside the item (or spec), e.g. `scenarios/sc-1/when`
+    pub path: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+/// An item present on both sides whose content differs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ItemDiff {
+    pub id: String,
+    /// Title on the newer side
+    pub title: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Differences in one of requirements, tasks, or decisions
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SectionDiff {
+    pub section: String,
+    pub added: Vec<Value>,
+    pub removed: Vec<Value>,
+    pub changed: Vec<ItemDiff>,
+}
+
+impl SectionDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Everything that differs between two specs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpecDiff {
+    pub from: String,
+    pub to: String,
+    /// Spec-level fields such as name, stage, or boundary
+    pub fields: Vec<FieldChange>,
+    /// One entry per item section, in [`ITEM_SECTIONS`] order
+    pub sections: Vec<SectionDiff>,
+}
+
+impl SpecDiff {
+    /// Compare `before` with `after`
+    pub fn between(before: &SpecData, after: &SpecData) -> Result<Self> {
+        let before_json = serde_json::to_value(before)?;
+        let after_json = serde_json::to_value(after)?;
+        let empty = Map::new();
+        let before_map = before_json.as_object().unwrap_or(&empty);
+        let after_map = after_json.as_object().unwrap_or(&empty);
+
+        let mut fields = Vec::new();
+        for key in union_keys(before_map, after_map) {
+            if IGNORED_KEYS.contains(&key.as_str()) || ITEM_SECTIONS.contains(&key.as_str()) {
+                continue;
+            }
+            diff_values(
+                &key,
+                before_map.get(&key).unwrap_or(&Value::Null),
+                after_map.get(&key).unwrap_or(&Value::Null),
+                &mut fields,
+            );
+        }
+
+        let sections = ITEM_SECTIONS
+            .iter()
+            .map(|section| {
+                diff_section(
+                    section,
+                    before_json.get(*section).unwrap_or(&Value::Null),
+                    after_json.get(*section).unwrap_or(&Value::Null),
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            from: before.spec_id.clone(),
+            to: after.spec_id.clone(),
+            fields,
+            sections,
+        })
+    }
+
+    /// Whether the two specs are equivalent
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty() && self.sections.iter().all(SectionDiff::is_empty)
+    }
+}
+
+fn item_id(item: &Value) -> Option<&str> {
+    item.get("id").and_then(Value::as_str)
+}
+
+/// Title of an item, falling back to its name (scenarios) or id
+pub fn item_title(item: &Value) -> String {
+    item.get("title")
+        .or_else(|| item.get("name"))
+        .and_then(Value::as_str)
+        .or_else(|| item_id(item))
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn diff_section(section: &str, before: &Value, after: &Value) -> SectionDiff {
+    let before_items = before.as_array().cloned().unwrap_or_default();
+    let after_items = after.as_array().cloned().unwrap_or_default();
+    let find = |items: &[Value], id: &str| items.iter().find(|i| item_id(i) == Some(id)).cloned();
+
+    let mut diff = SectionDiff {
+        section: section.to_string(),
+        ..Default::default()
+    };
+
+    for item in &before_items {
+        let Some(id) = item_id(item) else { continue };
+        match find(&after_items, id) {
+            None => diff.removed.push(item.clone()),
+            Some(after_item) => {
+                let mut changes = Vec::new();
+                diff_values("", item, &after_item, &mut changes);
+                if !changes.is_empty() {
+                    diff.changed.push(ItemDiff {
+                        id: id.to_string(),
+                        title: item_title(&after_item),
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+    for item in &after_items {
+        if let Some(id) = item_id(item) {
+            if find(&before_items, id).is_none() {
+                diff.added.push(item.clone());
+            }
+        }
+    }
+
+    diff
+}
+
+/// Keys of `a` in order, then keys only in `b`
+fn union_keys(a: &Map<String, Value>, b: &Map<String, Value>) -> Vec<String> {
+    let mut keys: Vec<String> = a.keys().cloned().collect();
+    keys.extend(b.keys().filter(|k| !a.contains_key(*k)).cloned());
+    keys
+}
+
+fn join_path(path: &str, segment: &str) -> String {
+    if path.is_empty() {
+        segment.to_string()
+    } else {
+        format!("{}/{}", path, segment)
+    }
+}
+
+fn is_keyed_array(items: &[Value]) -> bool {
+    !items.is_empty() && items.iter().all(|i| item_id(i).is_some())
+}
+
+fn diff_values(path: &str, before: &Value, after: &Value, out: &mut Vec<FieldChange>) {
+    if before == after {
+        return;
+    }
+
+    match (before, after) {
+        (Value::Object(a), Value::Object(b)) => {
+            for key in union_keys(a, b) {
+                diff_values(
+                    &join_path(path, &key),
+                    a.get(&key).unwrap_or(&Value::Null),
+                    b.get(&key).unwrap_or(&Value::Null),
+                    out,
+                );
+            }
+        }
+        // Match nested items (scenarios) by id, as for top-level sections
+        (Value::Array(a), Value::Array(b)) if is_keyed_array(a) && is_keyed_array(b) => {
+            for item in a {
+                let id = item_id(item).unwrap_or_default();
+                let other = b.iter().find(|i| item_id(i) == Some(id));
+                diff_values(
+                    &join_path(path, id),
+                    item,
+                    other.unwrap_or(&Value::Null),
+                    out,
+                );
+            }
+            for item in b {
+                let id = item_id(item).unwrap_or_default();
+                if !a.iter().any(|i| item_id(i) == Some(id)) {
+                    out.push(FieldChange {
+                        path: join_path(path, id),
+                        before: Value::Null,
+                        after: item.clone(),
+                    });
+                }
+            }
+        }
+        _ => out.push(FieldChange {
+            path: path.to_string(),
+            before: before.clone(),
+            after: after.clone(),
+        }),
+    }
+}
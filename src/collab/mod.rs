@@ -7,6 +7,9 @@ use std::path::PathBuf;
 use std::str::FromStr;
 
 pub mod conflicts;
+pub mod diff;
+pub mod notify;
+pub mod replay;
 pub mod reviews;
 pub mod sync;
 
@@ -92,6 +95,15 @@ pub struct Conflict {
     pub status: ConflictStatus,
 }
 
+/// A spec's conflicts, with counts taken before any status filter
+#[derive(Debug, Clone, Serialize)]
+pub struct ConflictGroup {
+    pub spec_id: String,
+    pub unresolved: usize,
+    pub resolved: usize,
+    pub conflicts: Vec<Conflict>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ConflictStatus {
@@ -180,4 +192,29 @@ pub enum ResolutionStrategy {
     Theirs, // Accept remote changes
     Manual, // User will resolve manually
     Merge,  // Attempt automatic merge
+    Ai,     // Merged value suggested by the LLM
+}
+
+impl std::fmt::Display for ResolutionStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResolutionStrategy::Ours => write!(f, "ours"),
+            ResolutionStrategy::Theirs => write!(f, "theirs"),
+            ResolutionStrategy::Manual => write!(f, "manual"),
+            ResolutionStrategy::Merge => write!(f, "merge"),
+            ResolutionStrategy::Ai => write!(f, "ai"),
+        }
+    }
+}
+
+/// How a conflict was resolved, kept for audit exports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictResolution {
+    pub conflict_id: String,
+    /// Strategy name: ours, theirs, manual, merge, or ai
+    pub strategy: String,
+    /// The value written to the spec
+    pub value: serde_json::Value,
+    pub resolved_by: String,
+    pub resolved_at: i64,
 }
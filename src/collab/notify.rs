@@ -0,0 +1,270 @@
+//! Review notifications
+//!
+//! Tells reviewers when a review is requested, configured under
+//! `[reviews.notify]`:
+//!
+//! ```toml
+//! [reviews.notify]
+//! webhook = "https://hooks.example.com/manifold-reviews"
+//!
+//! [reviews.notify.smtp]
+//! server = "smtp.example.com:587"
+//! from = "manifold@example.com"
+//! username = "manifold"
+//! ```
+//!
+//! Webhooks get a JSON POST; SMTP mails the reviewer a plain-text message.
+//! SMTP connections use STARTTLS unless `security` says otherwise
+//! (`"tls"` for implicit TLS, `"none"` for a trusted local relay), and log
+//! in when `username` is set, with the password from `password` or
+//! `$MANIFOLD_SMTP_PASSWORD`.
+//!
+//! Notifications are best-effort and run in the background: failures are
+//! reported on stderr, never fatal, and never hold up the request.
+
+use super::Review;
+use crate::util::block_on_fresh_runtime;
+use anyhow::{bail, Context, Result};
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// How long a webhook or SMTP exchange may take before it counts as failed
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Environment variable holding the SMTP password when config has none
+pub const SMTP_PASSWORD_ENV: &str = "MANIFOLD_SMTP_PASSWORD";
+
+/// `[reviews]` settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReviewsConfig {
+    #[serde(default)]
+    pub notify: NotifyConfig,
+}
+
+/// Where review notifications go; nothing is sent when both are unset
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotifyConfig {
+    /// URL to POST review events to as JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+    /// Mail server for notifying reviewers by email
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smtp: Option<SmtpConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmtpConfig {
+    /// Server as `host:port`, or just `host` for the security mode's port
+    pub server: String,
+    /// Sender address
+    pub from: String,
+    /// How the connection is secured
+    #[serde(default)]
+    pub security: SmtpSecurity,
+    /// Login for servers that require authentication
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Password for `username`; `$MANIFOLD_SMTP_PASSWORD` when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+/// Transport security for SMTP
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// Plain connection upgraded with STARTTLS, which must succeed
+    #[default]
+    Starttls,
+    /// TLS from the first byte
+    Tls,
+    /// No encryption, for a relay on a trusted network
+    None,
+}
+
+impl SmtpSecurity {
+    fn default_port(self) -> u16 {
+        match self {
+            SmtpSecurity::Starttls => 587,
+            SmtpSecurity::Tls => 465,
+            SmtpSecurity::None => 25,
+        }
+    }
+}
+
+/// Receives review events; every event defaults to doing nothing
+pub trait Notifier: Send + Sync {
+    /// A review was requested and saved
+    fn review_requested(&self, _review: &Review) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Posts review events to a webhook
+pub struct WebhookNotifier {
+    pub url: String,
+}
+
+impl Notifier for WebhookNotifier {
+    fn review_requested(&self, review: &Review) -> Result<()> {
+        let payload = json!({
+            "event": "review.requested",
+            "review_id": review.id,
+            "spec_id": review.spec_id,
+            "requester": review.requester,
+            "reviewer": review.reviewer,
+            "timestamp": review.requested_at,
+        });
+        let url = self.url.clone();
+
+        block_on_fresh_runtime(move || async move {
+            let response = reqwest::Client::builder()
+                .timeout(NOTIFY_TIMEOUT)
+                .build()?
+                .post(&url)
+                .json(&payload)
+                .send()
+                .await
+                .with_context(|| format!("Failed to reach {}", url))?;
+            if !response.status().is_success() {
+                bail!("{} responded with {}", url, response.status());
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Mails the reviewer through an SMTP server
+pub struct SmtpNotifier {
+    pub config: SmtpConfig,
+}
+
+impl Notifier for SmtpNotifier {
+    fn review_requested(&self, review: &Review) -> Result<()> {
+        let to: Mailbox = review
+            .reviewer
+            .parse()
+            .with_context(|| format!("Reviewer '{}' is not an email address", review.reviewer))?;
+        let body = format!(
+            "{} asked you to review spec {}.\n\n\
+             Approve: manifold review approve {}\n\
+             Reject:  manifold review reject {} --comment \"...\"\n",
+            review.requester, review.spec_id, review.id, review.id
+        );
+        let message = Message::builder()
+            .from(
+                self.config
+                    .from
+                    .parse()
+                    .with_context(|| format!("Invalid sender address '{}'", self.config.from))?,
+            )
+            .to(to)
+            .subject(format!("Review requested: {}", review.spec_id))
+            .header(ContentType::TEXT_PLAIN)
+            .body(body)
+            .context("Failed to build notification email")?;
+
+        self.transport()?
+            .send(&message)
+            .with_context(|| format!("Failed to send mail through {}", self.config.server))?;
+        Ok(())
+    }
+}
+
+impl SmtpNotifier {
+    fn transport(&self) -> Result<SmtpTransport> {
+        let config = &self.config;
+        let (host, port) = match config.server.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse()
+                    .with_context(|| format!("Invalid SMTP server '{}'", config.server))?,
+            ),
+            None => (config.server.as_str(), config.security.default_port()),
+        };
+
+        let mut builder = match config.security {
+            SmtpSecurity::Starttls => SmtpTransport::starttls_relay(host)?,
+            SmtpSecurity::Tls => SmtpTransport::relay(host)?,
+            SmtpSecurity::None => SmtpTransport::builder_dangerous(host),
+        }
+        .port(port)
+        .timeout(Some(NOTIFY_TIMEOUT));
+
+        if let Some(username) = &config.username {
+            let password = match &config.password {
+                Some(password) => password.clone(),
+                None => std::env::var(SMTP_PASSWORD_ENV).with_context(|| {
+                    format!(
+                        "SMTP password not set; add it to config or ${}",
+                        SMTP_PASSWORD_ENV
+                    )
+                })?,
+            };
+            builder = builder.credentials(Credentials::new(username.clone(), password));
+        }
+        Ok(builder.build())
+    }
+}
+
+/// Notifiers for everything configured in `[reviews.notify]`
+pub fn notifiers_from_config(config: &NotifyConfig) -> Vec<Arc<dyn Notifier>> {
+    let mut notifiers: Vec<Arc<dyn Notifier>> = Vec::new();
+    if let Some(url) = &config.webhook {
+        notifiers.push(Arc::new(WebhookNotifier { url: url.clone() }));
+    }
+    if let Some(smtp) = &config.smtp {
+        notifiers.push(Arc::new(SmtpNotifier {
+            config: smtp.clone(),
+        }));
+    }
+    notifiers
+}
+
+/// Notifications still being delivered in the background
+///
+/// Dropping this leaves them running detached; [`Notifications::wait`]
+/// lets a short-lived process finish delivering before it exits.
+#[must_use = "dropping detaches the notifications; call wait() before exiting"]
+pub struct Notifications {
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl Notifications {
+    /// Block until every notification has been delivered or has failed
+    pub fn wait(self) {
+        for handle in self.handles {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Tell every notifier about a review request, each on its own thread
+///
+/// Returns at once; failures are reported on stderr as they happen, so one
+/// slow or dead endpoint neither delays the caller nor hides the others.
+pub fn dispatch_review_requested(
+    notifiers: &[Arc<dyn Notifier>],
+    review: &Review,
+) -> Notifications {
+    let handles = notifiers
+        .iter()
+        .map(|notifier| {
+            let notifier = Arc::clone(notifier);
+            let review = review.clone();
+            std::thread::spawn(move || {
+                if let Err(e) = notifier.review_requested(&review) {
+                    eprintln!("⚠ Review notification failed: {:#}", e);
+                }
+            })
+        })
+        .collect();
+    Notifications { handles }
+}
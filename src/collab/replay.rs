@@ -0,0 +1,209 @@
+//! Reconstruct a spec as it was at a past time, for `manifold replay`
+//!
+//! Patch entries only describe changes, so there is nothing to invert in
+//! general. A replay rewinds the current spec when everything that changed
+//! since the requested time can be rewound; otherwise it starts from the
+//! latest snapshot taken at or before that time. Either way it moves what
+//! history does record to that time: the stage from workflow transitions,
+//! requirement titles and SHALL statements from their revisions, and the
+//! change log itself. Anything else that changed in between is reported in
+//! the notes rather than guessed.
+
+use anyhow::{bail, Result};
+
+use crate::db::{SpecSnapshot, WorkflowEventRow};
+use crate::models::{SpecData, WorkflowStage};
+
+/// A spec's reconstructed state at one point in time
+#[derive(Debug, Clone)]
+pub struct Replay {
+    pub spec: SpecData,
+    /// Snapshot the replay started from; None when rewound from the current spec
+    pub snapshot_id: Option<i64>,
+    /// What could not be reconstructed exactly
+    pub notes: Vec<String>,
+}
+
+/// Reconstruct `current` as it was at unix time `at`
+///
+/// `snapshots` and `events` are the spec's stored snapshots and workflow
+/// events, in any order.
+pub fn replay(
+    current: &SpecData,
+    snapshots: &[SpecSnapshot],
+    events: &[WorkflowEventRow],
+    at: i64,
+) -> Result<Replay> {
+    if at < current.history.created_at {
+        bail!(
+            "Spec {} did not exist yet at {}",
+            current.spec_id,
+            format_time(at)
+        );
+    }
+
+    let later_changes = current
+        .history
+        .patches
+        .iter()
+        .any(|p| p.timestamp > at && !is_replayable(&p.path));
+    let base = if later_changes {
+        snapshots
+            .iter()
+            .filter(|s| s.created_at <= at)
+            .max_by_key(|s| (s.created_at, s.id))
+    } else {
+        None
+    };
+    let (mut spec, base_time) = match base {
+        Some(snapshot) => (snapshot.data.clone(), snapshot.created_at),
+        None => (current.clone(), current.history.updated_at),
+    };
+    let mut notes = Vec::new();
+
+    if let Some(stage) = stage_at(events, base_time, at) {
+        spec.stage = stage;
+        spec.normalize_stages();
+    }
+
+    // Revisions live on the current spec, oldest first: a requirement's
+    // wording at `at` is the first revision superseded after it
+    for req in &mut spec.requirements {
+        let Some(latest) = current.requirements.iter().find(|r| r.id == req.id) else {
+            continue;
+        };
+        match latest.history.iter().find(|rev| rev.timestamp > at) {
+            Some(rev) => {
+                req.title = rev.title.clone();
+                req.shall = rev.shall.clone();
+            }
+            None => {
+                req.title = latest.title.clone();
+                req.shall = latest.shall.clone();
+            }
+        }
+        req.history.retain(|rev| rev.timestamp <= at);
+    }
+
+    // Changes the replay can't account for
+    let unreplayed: Vec<_> = current
+        .history
+        .patches
+        .iter()
+        .filter(|p| {
+            let between = if base.is_some() {
+                p.timestamp > base_time && p.timestamp <= at
+            } else {
+                p.timestamp > at
+            };
+            between && !is_replayable(&p.path)
+        })
+        .collect();
+    if !unreplayed.is_empty() {
+        let (which, shown) = match base {
+            Some(snapshot) => (
+                format!(
+                    "after snapshot {} ({})",
+                    snapshot.id,
+                    format_time(snapshot.created_at)
+                ),
+                "missing from",
+            ),
+            None => (format!("after {}", format_time(at)), "still included in"),
+        };
+        notes.push(format!(
+            "{} change(s) {} have no recorded values and are {} this view:",
+            unreplayed.len(),
+            which,
+            shown
+        ));
+        for patch in unreplayed {
+            notes.push(format!(
+                "  {} {} {} {}",
+                format_time(patch.timestamp),
+                patch.op,
+                patch.path,
+                patch.summary
+            ));
+        }
+    }
+    if base.is_none() && later_changes && !snapshots.is_empty() {
+        notes.push(format!(
+            "No snapshot was taken at or before {}; the earliest is from {}",
+            format_time(at),
+            format_time(snapshots.iter().map(|s| s.created_at).min().unwrap_or(0))
+        ));
+    }
+
+    spec.history.patches = current
+        .history
+        .patches
+        .iter()
+        .filter(|p| p.timestamp <= at)
+        .cloned()
+        .collect();
+    let floor = base.map_or(spec.history.created_at, |s| {
+        s.data.history.updated_at.min(at)
+    });
+    spec.history.updated_at = spec
+        .history
+        .patches
+        .iter()
+        .map(|p| p.timestamp)
+        .fold(floor, i64::max);
+
+    Ok(Replay {
+        spec,
+        snapshot_id: base.map(|s| s.id),
+        notes,
+    })
+}
+
+/// Stage at `at` according to the transition log, if it says anything
+///
+/// Moving forward from `base_time`, the last transition up to `at` wins;
+/// rewinding from a later `base_time`, the first transition after `at`
+/// tells where the spec came from.
+fn stage_at(events: &[WorkflowEventRow], base_time: i64, at: i64) -> Option<WorkflowStage> {
+    let mut transitions: Vec<(i64, i64, WorkflowStage, WorkflowStage)> = events
+        .iter()
+        .filter_map(|e| {
+            let mut parts = e.event.splitn(3, ':');
+            let kind = parts.next()?;
+            if kind != "transition" && kind != "skipped" {
+                return None;
+            }
+            let from = parts.next()?.parse().ok()?;
+            let to = parts.next()?.parse().ok()?;
+            Some((e.timestamp, e.id, from, to))
+        })
+        .collect();
+    transitions.sort_by_key(|(timestamp, id, _, _)| (*timestamp, *id));
+
+    if base_time <= at {
+        transitions
+            .into_iter()
+            .rev()
+            .find(|(t, ..)| *t > base_time && *t <= at)
+            .map(|(_, _, _, to)| to)
+    } else {
+        transitions
+            .into_iter()
+            .find(|(t, ..)| *t > at)
+            .map(|(_, _, from, _)| from)
+    }
+}
+
+/// Whether a patch path is one the replay reconstructs from other records
+fn is_replayable(path: &str) -> bool {
+    path == "/stage"
+        || path == "/stages_completed"
+        || (path.starts_with("/requirements/")
+            && (path.ends_with("/title") || path.ends_with("/shall")))
+}
+
+fn format_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
@@ -2,6 +2,7 @@
 
 use super::{Review, ReviewStatus};
 use anyhow::{anyhow, Result};
+use serde::Serialize;
 
 /// Review manager for approval workflows
 pub struct ReviewManager;
@@ -140,7 +141,7 @@ impl ReviewManager {
 }
 
 /// Review statistics
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct ReviewStats {
     pub total: usize,
     pub pending: usize,
@@ -1,6 +1,8 @@
 //! Git-based sync implementation
 
-use super::SyncConfig;
+use super::conflicts::ConflictResolver;
+use super::{Conflict, SyncConfig, SyncMetadata, SyncStatus};
+use crate::db::Database;
 use crate::models::SpecData;
 use anyhow::{anyhow, Context, Result};
 use std::fs;
@@ -26,7 +28,7 @@ impl SyncManager {
 
         // Initialize git repo
         let output = Command::new("git")
-            .args(&["init"])
+            .args(["init"])
             .current_dir(&self.config.repo_path)
             .output()
             .context("Failed to initialize git repository")?;
@@ -40,12 +42,12 @@ impl SyncManager {
 
         // Configure git user
         Command::new("git")
-            .args(&["config", "user.name", &self.config.commit_author])
+            .args(["config", "user.name", &self.config.commit_author])
             .current_dir(&self.config.repo_path)
             .output()?;
 
         Command::new("git")
-            .args(&["config", "user.email", &self.config.commit_email])
+            .args(["config", "user.email", &self.config.commit_email])
             .current_dir(&self.config.repo_path)
             .output()?;
 
@@ -56,6 +58,68 @@ impl SyncManager {
         Ok(())
     }
 
+    /// Clone an existing spec repository into the sync directory
+    ///
+    /// The directory must not exist yet or be empty, as with `git clone`.
+    pub fn clone_remote(&self, url: &str) -> Result<()> {
+        let repo_path = &self.config.repo_path;
+        if repo_path.exists() && fs::read_dir(repo_path)?.next().is_some() {
+            return Err(anyhow!(
+                "Sync directory {:?} already exists and is not empty",
+                repo_path
+            ));
+        }
+        if let Some(parent) = repo_path.parent() {
+            fs::create_dir_all(parent).context("Failed to create sync directory")?;
+        }
+
+        let output = Command::new("git")
+            .arg("clone")
+            .arg(url)
+            .arg(repo_path)
+            .output()
+            .context("Failed to clone remote repository")?;
+
+        if !output.status.success() {
+            return Err(anyhow!(
+                "Git clone failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        // Configure git user
+        Command::new("git")
+            .args(["config", "user.name", &self.config.commit_author])
+            .current_dir(repo_path)
+            .output()?;
+
+        Command::new("git")
+            .args(["config", "user.email", &self.config.commit_email])
+            .current_dir(repo_path)
+            .output()?;
+
+        println!("✓ Cloned {} into {:?}", url, repo_path);
+        Ok(())
+    }
+
+    /// Name of the checked-out branch
+    pub fn current_branch(&self) -> Result<String> {
+        let output = Command::new("git")
+            .args(["rev-parse", "--abbrev-ref", "HEAD"])
+            .current_dir(&self.config.repo_path)
+            .output()
+            .context("Failed to read current branch")?;
+
+        if !output.status.success() {
+            return Err(anyhow!(
+                "Git rev-parse failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
     /// Export spec to git repository as JSON file
     pub fn export_spec(&self, spec: &SpecData) -> Result<PathBuf> {
         let spec_file = self.config.repo_path.join(format!("{}.json", spec.spec_id));
@@ -77,7 +141,9 @@ impl SyncManager {
 
         let json = fs::read_to_string(&spec_file).context("Failed to read spec file")?;
 
-        let spec: SpecData = serde_json::from_str(&json).context("Failed to parse spec JSON")?;
+        let mut spec: SpecData =
+            serde_json::from_str(&json).context("Failed to parse spec JSON")?;
+        spec.fill_missing_decision_dates();
 
         Ok(spec)
     }
@@ -87,7 +153,7 @@ impl SyncManager {
         // Stage files
         for file in files {
             let output = Command::new("git")
-                .args(&["add", file.to_str().unwrap()])
+                .args(["add", file.to_str().unwrap()])
                 .current_dir(&self.config.repo_path)
                 .output()
                 .context("Failed to stage file")?;
@@ -102,7 +168,7 @@ impl SyncManager {
 
         // Commit
         let output = Command::new("git")
-            .args(&["commit", "-m", message])
+            .args(["commit", "-m", message])
             .current_dir(&self.config.repo_path)
             .output()
             .context("Failed to commit changes")?;
@@ -118,7 +184,7 @@ impl SyncManager {
 
         // Get commit hash
         let output = Command::new("git")
-            .args(&["rev-parse", "HEAD"])
+            .args(["rev-parse", "HEAD"])
             .current_dir(&self.config.repo_path)
             .output()
             .context("Failed to get commit hash")?;
@@ -130,7 +196,7 @@ impl SyncManager {
     /// Push changes to remote
     pub fn push(&self, remote: &str, branch: &str) -> Result<()> {
         let output = Command::new("git")
-            .args(&["push", remote, branch])
+            .args(["push", remote, branch])
             .current_dir(&self.config.repo_path)
             .output()
             .context("Failed to push to remote")?;
@@ -149,7 +215,7 @@ impl SyncManager {
     /// Pull changes from remote
     pub fn pull(&self, remote: &str, branch: &str) -> Result<()> {
         let output = Command::new("git")
-            .args(&["pull", remote, branch])
+            .args(["pull", remote, branch])
             .current_dir(&self.config.repo_path)
             .output()
             .context("Failed to pull from remote")?;
@@ -174,7 +240,7 @@ impl SyncManager {
     #[allow(dead_code)]
     pub fn status(&self) -> Result<Vec<String>> {
         let output = Command::new("git")
-            .args(&["status", "--porcelain"])
+            .args(["status", "--porcelain"])
             .current_dir(&self.config.repo_path)
             .output()
             .context("Failed to get git status")?;
@@ -201,7 +267,7 @@ impl SyncManager {
     pub fn is_modified(&self, spec_id: &str) -> Result<bool> {
         let spec_file = format!("{}.json", spec_id);
         let output = Command::new("git")
-            .args(&["status", "--porcelain", &spec_file])
+            .args(["status", "--porcelain", &spec_file])
             .current_dir(&self.config.repo_path)
             .output()
             .context("Failed to check modification status")?;
@@ -214,7 +280,7 @@ impl SyncManager {
     pub fn get_file_hash(&self, spec_id: &str) -> Result<String> {
         let spec_file = format!("{}.json", spec_id);
         let output = Command::new("git")
-            .args(&["hash-object", &spec_file])
+            .args(["hash-object", &spec_file])
             .current_dir(&self.config.repo_path)
             .output()
             .context("Failed to get file hash")?;
@@ -256,7 +322,7 @@ impl SyncManager {
     /// Add remote repository
     pub fn add_remote(&self, name: &str, url: &str) -> Result<()> {
         let output = Command::new("git")
-            .args(&["remote", "add", name, url])
+            .args(["remote", "add", name, url])
             .current_dir(&self.config.repo_path)
             .output()
             .context("Failed to add remote")?;
@@ -266,7 +332,7 @@ impl SyncManager {
             if stderr.contains("already exists") {
                 // Update existing remote
                 Command::new("git")
-                    .args(&["remote", "set-url", name, url])
+                    .args(["remote", "set-url", name, url])
                     .current_dir(&self.config.repo_path)
                     .output()?;
                 println!("✓ Updated remote '{}' to {}", name, url);
@@ -285,7 +351,7 @@ impl SyncManager {
     pub fn diff(&self, spec_id: &str, remote: &str, branch: &str) -> Result<String> {
         let spec_file = format!("{}.json", spec_id);
         let output = Command::new("git")
-            .args(&["diff", &format!("{}/{}", remote, branch), "--", &spec_file])
+            .args(["diff", &format!("{}/{}", remote, branch), "--", &spec_file])
             .current_dir(&self.config.repo_path)
             .output()
             .context("Failed to get diff")?;
@@ -293,3 +359,120 @@ impl SyncManager {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
 }
+
+/// What a pull did to the local database
+#[derive(Debug, Default)]
+pub struct PullReport {
+    /// Specs that did not exist locally
+    pub imported: Vec<String>,
+    /// Local specs replaced by the remote version
+    pub updated: Vec<String>,
+    /// Specs left untouched because both sides changed, with the saved conflicts
+    pub conflicted: Vec<(String, Vec<Conflict>)>,
+    /// Spec files that could not be read, with the reason
+    pub failed: Vec<(String, String)>,
+}
+
+impl PullReport {
+    /// Number of specs written to the database
+    pub fn pulled(&self) -> usize {
+        self.imported.len() + self.updated.len()
+    }
+
+    /// One-line summary, e.g. for the sync agent's log
+    pub fn summary(&self) -> String {
+        format!(
+            "{} imported, {} updated, {} conflicted, {} failed",
+            self.imported.len(),
+            self.updated.len(),
+            self.conflicted.len(),
+            self.failed.len()
+        )
+    }
+}
+
+/// `git pull`, then import `id` (or every spec file when `None`) into `db`
+///
+/// Specs that changed on both sides are not touched; their conflicts are
+/// saved for `manifold conflicts` instead of being resolved. A missing
+/// requested spec is an error, while unreadable files in a full pull are
+/// reported and skipped.
+pub fn pull_specs(
+    manager: &SyncManager,
+    db: &Database,
+    remote: &str,
+    branch: &str,
+    id: Option<&str>,
+) -> Result<PullReport> {
+    manager.pull(remote, branch)?;
+    import_specs(manager, db, branch, id)
+}
+
+/// Import `id` (or every spec file when `None`) from the working tree into `db`
+///
+/// Same conflict handling as [`pull_specs`], without fetching first; used
+/// directly after a clone.
+pub fn import_specs(
+    manager: &SyncManager,
+    db: &Database,
+    branch: &str,
+    id: Option<&str>,
+) -> Result<PullReport> {
+    let spec_ids = match id {
+        Some(id) => vec![id.to_string()],
+        None => manager.list_specs()?,
+    };
+
+    let mut report = PullReport::default();
+    for spec_id in &spec_ids {
+        let remote_spec = match manager.import_spec(spec_id) {
+            Ok(spec) => spec,
+            Err(e) if id.is_some() => return Err(e),
+            Err(e) => {
+                report.failed.push((spec_id.clone(), e.to_string()));
+                continue;
+            }
+        };
+
+        let local_spec = match db.get_spec(spec_id) {
+            Ok(Some(row)) => Some(row.parse()?),
+            _ => None,
+        };
+
+        let status = match local_spec {
+            Some(local_spec) => {
+                let conflicts =
+                    ConflictResolver::detect_conflicts(&local_spec, &remote_spec, None)?;
+                if conflicts.is_empty() {
+                    db.update_spec(&remote_spec)?;
+                    report.updated.push(spec_id.clone());
+                    SyncStatus::Synced
+                } else {
+                    for conflict in &conflicts {
+                        db.save_conflict(conflict)?;
+                    }
+                    report.conflicted.push((spec_id.clone(), conflicts));
+                    SyncStatus::Conflicted
+                }
+            }
+            None => {
+                db.insert_spec(&remote_spec)?;
+                report.imported.push(spec_id.clone());
+                SyncStatus::Synced
+            }
+        };
+
+        if let Ok(hash) = manager.get_file_hash(spec_id) {
+            let metadata = SyncMetadata {
+                spec_id: spec_id.clone(),
+                last_sync_timestamp: chrono::Utc::now().timestamp(),
+                last_sync_hash: hash,
+                remote_branch: Some(branch.to_string()),
+                sync_status: status,
+            };
+            let _ = db.save_sync_metadata(&metadata);
+        }
+    }
+
+    Ok(report)
+}
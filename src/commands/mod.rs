@@ -1,15 +1,35 @@
 //! CLI commands for manifold
 
 use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
 
 use crate::collab::conflicts::ConflictResolver;
+use crate::collab::notify::{
+    dispatch_review_requested, notifiers_from_config, Notifications, Notifier,
+};
 use crate::collab::reviews::ReviewManager;
-use crate::collab::sync::SyncManager;
-use crate::collab::{ResolutionStrategy, SyncConfig};
-use crate::config::{save_config, Config, ManifoldPaths};
-use crate::db::Database;
-use crate::models::{Boundary, SpecData, WorkflowStage};
-use crate::workflow::{WorkflowEngine, WorkflowError};
+use crate::collab::sync::{import_specs, pull_specs, PullReport, SyncManager};
+use crate::collab::{ConflictResolution, ResolutionStrategy, SyncConfig};
+use crate::config::{
+    load_config_from, load_profiles, save_config, save_config_to, save_profiles, Config,
+    ManifoldPaths, DEFAULT_PROFILE,
+};
+use crate::db::{Database, Page, SearchSources, SpecFilter, SpecOrder};
+use crate::export::{
+    self, AsciiDocRenderer, DotRenderer, ExportFormat, HtmlRenderer, MarkdownRenderer,
+    OpenApiCommentsRenderer, PdfRenderer, SpecSection, YamlRenderer,
+};
+use crate::models::{
+    Boundary, ManifoldV2, PatchEntry, Priority, Requirement, ScenarioPosition, SpecData, SpecRow,
+    Visibility, WorkflowStage,
+};
+use crate::search;
+use crate::templates::{self, SpecTemplate};
+use crate::validation::IdPatterns;
+use crate::workflow::{WorkflowEngine, WorkflowError, WorkflowEvent, WorkflowPolicy};
 
 // Operation enums for CLI subcommands
 // These are defined here (not in main.rs) so they're available in both library and binary contexts
@@ -24,6 +44,13 @@ pub enum SyncOperation {
         /// Remote URL (optional)
         remote: Option<String>,
     },
+    /// Clone an existing spec repository and import its specs
+    Clone {
+        /// Remote URL to clone
+        url: String,
+        /// Where to clone to (defaults to the sync directory)
+        path: Option<String>,
+    },
     /// Push spec(s) to git repository
     Push {
         /// Spec ID (or 'all' for all specs)
@@ -44,6 +71,15 @@ pub enum SyncOperation {
         /// Branch name
         branch: String,
     },
+    /// Keep pulling from the remote in the background
+    Agent {
+        /// Seconds between pulls
+        interval: u64,
+        /// Remote name
+        remote: String,
+        /// Branch name
+        branch: String,
+    },
     /// Show sync status
     Status,
     /// Show differences between local and remote
@@ -87,6 +123,10 @@ pub enum ReviewOperation {
         spec_id: Option<String>,
         /// Filter by status
         status: Option<String>,
+        /// Show the current user's inbox
+        mine: bool,
+        /// Show this reviewer's inbox
+        assigned_to: Option<String>,
     },
 }
 
@@ -97,25 +137,149 @@ pub enum ConflictOperation {
     List {
         /// Spec ID (optional)
         spec_id: Option<String>,
+        /// Output as JSON
+        json: bool,
+        /// Hide resolved conflicts
+        unresolved_only: bool,
     },
     /// Resolve a conflict
     Resolve {
         /// Conflict ID
         conflict_id: String,
-        /// Resolution strategy: ours, theirs, manual
-        strategy: String,
+        /// Resolution strategy: ours, theirs, manual, merge, or ai; prompts
+        /// interactively when unset
+        strategy: Option<String>,
+        /// Output as JSON
+        json: bool,
+    },
+}
+
+/// Spec template operations
+#[derive(Debug, Clone)]
+pub enum TemplateOperation {
+    /// Save a spec as a template
+    Save {
+        /// Spec ID to use as the template source
+        spec_id: String,
+        /// Template name
+        name: String,
+    },
+    /// List saved templates
+    List,
+}
+
+/// Manifold manifest operations
+#[derive(Debug, Clone)]
+pub enum ManifestOperation {
+    /// Show the manifest derived from stored specs
+    Show {
+        /// Output as JSON
+        json: bool,
+    },
+    /// Write a publishable bundle of public specs in public boundaries
+    Bundle {
+        /// Output file path
+        output: String,
+        /// Write gzip-compressed JSON
+        gzip: bool,
+    },
+}
+
+/// Import operations
+#[derive(Debug, Clone)]
+pub enum ImportOperation {
+    /// Import the specs in a bundle written by `manifest bundle`
+    Bundle {
+        /// Path to the bundle JSON, plain or gzipped
+        path: String,
+        /// Merge into existing specs instead of skipping them
+        merge: bool,
+    },
+    /// Create a spec from canonical JSON or YAML
+    Spec {
+        /// Path to the spec JSON, or "-" for stdin
+        path: String,
+        /// Keep the spec_id from the input instead of generating one
+        keep_id: bool,
+        /// Read the input as YAML instead of JSON
+        yaml: bool,
+    },
+}
+
+/// Snapshot operations
+#[derive(Debug, Clone)]
+pub enum SnapshotOperation {
+    /// Freeze the current version of a spec
+    Create { id: String, label: Option<String> },
+    /// List a spec's snapshots
+    List { id: String },
+    /// Put a spec back to a snapshot
+    Restore { id: String, snapshot_id: i64 },
+    /// Show what changed since a snapshot
+    Diff { id: String, snapshot_id: i64 },
+}
+
+/// Background agent operations
+#[derive(Debug, Clone)]
+pub enum AgentOperation {
+    /// Show the agent event log
+    Logs {
+        /// Only this agent's events, e.g. `sync:origin/main`
+        id: Option<String>,
+        /// Only events at or after this time
+        since: Option<String>,
+    },
+}
+
+/// Named manifold operations
+#[derive(Debug, Clone)]
+pub enum ProfileOperation {
+    /// Register a manifold home under a name
+    Add { name: String, path: String },
+    /// List profiles, marking the active one
+    List,
+    /// Make a profile the default for later commands
+    Use { name: String },
+}
+
+/// Scenario operations
+#[derive(Debug, Clone)]
+pub enum ScenarioOperation {
+    /// Reorder a scenario within its requirement
+    Move {
+        spec_id: String,
+        req_id: String,
+        scenario_id: String,
+        position: ScenarioPosition,
     },
 }
 
 /// Initialize manifold for first-time setup
-pub fn init() -> Result<()> {
+pub fn init(git: bool, remote: Option<&str>, repair: bool) -> Result<()> {
     let paths = ManifoldPaths::new()?;
 
+    if repair {
+        println!("Repairing manifold at {}...", paths.root.display());
+        for step in repair_installation(&paths)? {
+            println!("  {}", step);
+        }
+        println!();
+        println!("Manifold repaired; existing specs were left in place.");
+        return Ok(());
+    }
+
     if paths.is_initialized() {
         println!(
             "Manifold is already initialized at {}",
             paths.root.display()
         );
+        if git {
+            let sync_config = init_sync(&paths, paths.sync_dir(), remote.map(String::from))?;
+            println!(
+                "  Created sync repository at {}",
+                sync_config.repo_path.display()
+            );
+        }
         return Ok(());
     }
 
@@ -126,7 +290,10 @@ pub fn init() -> Result<()> {
     println!("  Created directory structure");
 
     // Create default config
-    let config = Config::default();
+    let config = Config {
+        manifold_id: Some(uuid::Uuid::new_v4().to_string()),
+        ..Config::default()
+    };
     save_config(&config)?;
     println!("  Created config.toml");
 
@@ -138,6 +305,15 @@ pub fn init() -> Result<()> {
     create_core_schema(&paths)?;
     println!("  Created core.json schema");
 
+    // Optionally set up the git sync repository
+    if git {
+        let sync_config = init_sync(&paths, paths.sync_dir(), remote.map(String::from))?;
+        println!(
+            "  Created sync repository at {}",
+            sync_config.repo_path.display()
+        );
+    }
+
     println!();
     println!("Manifold initialized successfully!");
     println!();
@@ -149,43 +325,329 @@ pub fn init() -> Result<()> {
     Ok(())
 }
 
+/// Bring a partial or damaged installation back to a usable state
+///
+/// Every step is idempotent and keeps existing data: missing directories
+/// and config are created, the schema file is rewritten, and the database
+/// gets any missing tables and indexes.
+fn repair_installation(paths: &ManifoldPaths) -> Result<Vec<String>> {
+    let mut steps = Vec::new();
+
+    paths.ensure_dirs()?;
+    steps.push("Ensured directory structure".to_string());
+
+    if paths.config.exists() {
+        load_config_from(&paths.config).with_context(|| {
+            format!(
+                "{} is not valid; fix or remove it and repair again",
+                paths.config.display()
+            )
+        })?;
+        steps.push("Kept existing config.toml".to_string());
+    } else {
+        let config = Config {
+            manifold_id: Some(uuid::Uuid::new_v4().to_string()),
+            ..Config::default()
+        };
+        save_config_to(&config, &paths.config)?;
+        steps.push("Created missing config.toml".to_string());
+    }
+
+    let existed = paths.db_file.exists();
+    let db = Database::init(paths)?;
+    let count = db.list_specs(None, None, SpecOrder::default())?.len();
+    steps.push(if existed {
+        format!(
+            "Brought database schema up to date ({} spec(s) kept)",
+            count
+        )
+    } else {
+        "Created missing database".to_string()
+    });
+
+    create_core_schema(paths)?;
+    steps.push("Rewrote core.json schema".to_string());
+
+    Ok(steps)
+}
+
+/// Initialize a git sync repository and persist its settings to config.toml
+pub fn init_sync(
+    paths: &ManifoldPaths,
+    repo_path: std::path::PathBuf,
+    remote: Option<String>,
+) -> Result<SyncConfig> {
+    let mut sync_config = SyncConfig::new(repo_path);
+    sync_config.remote_url = remote;
+
+    let manager = SyncManager::new(sync_config.clone());
+    manager.init()?;
+
+    if let Some(url) = &sync_config.remote_url {
+        manager.add_remote("origin", url)?;
+    }
+
+    let mut config = load_config_from(&paths.config)?;
+    config.sync = Some(sync_config.clone());
+    save_config_to(&config, &paths.config)?;
+
+    Ok(sync_config)
+}
+
+/// Clone an existing spec repository, import its specs, and persist the sync settings
+///
+/// Specs that already exist locally go through the same conflict detection
+/// as `sync pull`: unchanged or compatible ones are updated, the rest are
+/// left alone with their conflicts saved.
+pub fn clone_sync(
+    paths: &ManifoldPaths,
+    url: &str,
+    repo_path: std::path::PathBuf,
+) -> Result<(SyncConfig, PullReport)> {
+    let mut sync_config = SyncConfig::new(repo_path);
+    sync_config.remote_url = Some(url.to_string());
+
+    let manager = SyncManager::new(sync_config.clone());
+    manager.clone_remote(url)?;
+
+    let mut config = load_config_from(&paths.config)?;
+    config.sync = Some(sync_config.clone());
+    save_config_to(&config, &paths.config)?;
+
+    let branch = manager.current_branch()?;
+    let db = Database::open(paths)?;
+    let report = import_specs(&manager, &db, &branch, None)?;
+
+    Ok((sync_config, report))
+}
+
+/// Load the persisted sync configuration from the `[sync]` section of config.toml
+pub fn load_sync_config(paths: &ManifoldPaths) -> Result<SyncConfig> {
+    let config = load_config_from(&paths.config)?;
+    config.sync.context(
+        "Sync is not initialized. Run `manifold sync init --repo <path>` or `manifold init --git` first.",
+    )
+}
+
 /// Create a new spec
-pub fn new_spec(project_id: &str, name: Option<&str>, boundary: Option<&str>) -> Result<String> {
+pub fn new_spec(
+    project_id: &str,
+    name: Option<&str>,
+    boundary: Option<&str>,
+    template: Option<&str>,
+    owner: Option<&str>,
+    team: Option<&str>,
+) -> Result<String> {
     let paths = ManifoldPaths::new()?;
     ensure_initialized(&paths)?;
 
     let boundary = match boundary {
         Some(b) => b.parse::<Boundary>().map_err(|e| anyhow::anyhow!(e))?,
-        None => {
-            // Load default boundary from config
-            let config = crate::config::load_config()?;
-            match config.default_boundary {
-                crate::config::DefaultBoundary::Personal => Boundary::Personal,
-                crate::config::DefaultBoundary::Work => Boundary::Work,
-                crate::config::DefaultBoundary::Company => Boundary::Company,
-            }
+        None => load_config_from(&paths.config)?.default_boundary.into(),
+    };
+
+    let mut spec = build_new_spec(&paths, project_id, name, boundary, template)?;
+    spec.owner = owner.map(str::to_string);
+    spec.team = team.map(str::to_string);
+    store_new_spec(&paths, &spec, template)
+}
+
+/// Answers gathered by `manifold new --interactive`
+#[derive(Debug, Clone)]
+pub struct NewSpecAnswers {
+    pub project_id: String,
+    pub name: String,
+    pub boundary: Boundary,
+    /// Initial requirements as (title, SHALL statement) pairs
+    pub requirements: Vec<(String, String)>,
+}
+
+/// Create a new spec from prompted answers instead of flags
+///
+/// Every answer is validated as it is typed and asked again until it is
+/// acceptable; ending input (Ctrl-D) before the boundary cancels.
+pub fn new_spec_interactive(
+    template: Option<&str>,
+    owner: Option<&str>,
+    team: Option<&str>,
+) -> Result<String> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+    let config = load_config_from(&paths.config)?;
+    let ids = IdPatterns::from_config(&config.validation)?;
+
+    let mut rl = rustyline::DefaultEditor::new()?;
+    let mut ask = |prompt: &str| match rl.readline(prompt) {
+        Ok(line) => Ok(Some(line)),
+        Err(rustyline::error::ReadlineError::Eof)
+        | Err(rustyline::error::ReadlineError::Interrupted) => Ok(None),
+        Err(e) => Err(anyhow::anyhow!(e)),
+    };
+    let answers = prompt_new_spec(&mut ask, config.default_boundary.into())?;
+
+    let mut spec = build_new_spec(
+        &paths,
+        &answers.project_id,
+        Some(&answers.name),
+        answers.boundary,
+        template,
+    )?;
+    spec.owner = owner.map(str::to_string);
+    spec.team = team.map(str::to_string);
+    for (title, shall) in answers.requirements {
+        let id = ids.requirement.next_id(&spec);
+        spec.requirements.push(Requirement {
+            id,
+            capability: answers.project_id.clone(),
+            title,
+            shall,
+            rationale: None,
+            priority: Priority::default(),
+            tags: vec![],
+            scenarios: vec![],
+            history: vec![],
+            verification: Default::default(),
+        });
+    }
+    store_new_spec(&paths, &spec, template)
+}
+
+/// Ask for everything `new` needs, re-prompting on invalid input
+///
+/// `ask` shows a prompt and returns the typed line, or None once input ends.
+pub fn prompt_new_spec(
+    ask: &mut dyn FnMut(&str) -> Result<Option<String>>,
+    default_boundary: Boundary,
+) -> Result<NewSpecAnswers> {
+    let project_id = loop {
+        let answer = required(ask, "Project id (e.g. auric-raptor): ")?;
+        if answer.is_empty() {
+            println!("✗ Project id is required");
+        } else if answer.chars().any(char::is_whitespace) {
+            println!("✗ Project id can't contain spaces");
+        } else {
+            break answer;
         }
     };
 
+    let answer = required(ask, &format!("Name [{}]: ", project_id))?;
+    let name = if answer.is_empty() {
+        project_id.clone()
+    } else {
+        answer
+    };
+
+    let boundary = loop {
+        let answer = required(
+            ask,
+            &format!(
+                "Boundary (personal, work, company) [{}]: ",
+                default_boundary
+            ),
+        )?;
+        if answer.is_empty() {
+            break default_boundary;
+        }
+        match answer.parse::<Boundary>() {
+            Ok(boundary) => break boundary,
+            Err(e) => println!("✗ {}", e),
+        }
+    };
+
+    // Requirements are optional; a blank title (or end of input) finishes
+    let mut requirements = Vec::new();
+    loop {
+        let title = match ask("Requirement title (blank to finish): ")? {
+            Some(line) if !line.trim().is_empty() => line.trim().to_string(),
+            _ => break,
+        };
+        let shall = loop {
+            let answer = required(ask, "  SHALL statement: ")?;
+            let upper = answer.to_uppercase();
+            if upper.contains("SHALL") || upper.contains("MUST") {
+                break answer;
+            }
+            println!("✗ Requirement must use SHALL or MUST");
+        };
+        requirements.push((title, shall));
+    }
+
+    Ok(NewSpecAnswers {
+        project_id,
+        name,
+        boundary,
+        requirements,
+    })
+}
+
+/// A trimmed answer to a question that can't be skipped
+fn required(ask: &mut dyn FnMut(&str) -> Result<Option<String>>, prompt: &str) -> Result<String> {
+    ask(prompt)?
+        .map(|line| line.trim().to_string())
+        .context("Cancelled: no spec created")
+}
+
+/// A fresh spec for `project_id`, from a template if one is named
+fn build_new_spec(
+    paths: &ManifoldPaths,
+    project_id: &str,
+    name: Option<&str>,
+    boundary: Boundary,
+    template: Option<&str>,
+) -> Result<SpecData> {
     let spec_name = name.unwrap_or(project_id).to_string();
 
     // Generate spec_id
     let spec_id = crate::db::generate_spec_id(project_id);
-    let spec = SpecData::new(spec_id.clone(), project_id.to_string(), spec_name, boundary);
+    Ok(match template {
+        Some(template_name) => {
+            let template = templates::load_template(&paths.templates_dir(), template_name)?;
+            let ids = IdPatterns::from_config(&load_config_from(&paths.config)?.validation)?;
+            template.instantiate(spec_id, project_id.to_string(), spec_name, boundary, &ids)
+        }
+        None => SpecData::new(spec_id, project_id.to_string(), spec_name, boundary),
+    })
+}
 
-    let db = Database::open(&paths)?;
-    let id = db.insert_spec(&spec)?;
+/// Insert a spec built by `build_new_spec` and report what was created
+fn store_new_spec(
+    paths: &ManifoldPaths,
+    spec: &SpecData,
+    template: Option<&str>,
+) -> Result<String> {
+    let db = Database::open(paths)?;
+    let id = db.insert_spec(spec)?;
 
     println!("Created spec: {}", id);
-    println!("  Project:  {}", project_id);
+    println!("  Project:  {}", spec.project);
     println!("  Boundary: {}", spec.boundary);
     println!("  Stage:    {}", spec.stage);
+    if let Some(owner) = &spec.owner {
+        println!("  Owner:    {}", owner);
+    }
+    if let Some(team) = &spec.team {
+        println!("  Team:     {}", team);
+    }
+    if let Some(template_name) = template {
+        println!("  Template: {}", template_name);
+    }
+    if !spec.requirements.is_empty() {
+        println!("  Requirements: {}", spec.requirements.len());
+    }
 
     Ok(id)
 }
 
 /// List specs with optional filters
-pub fn list(boundary: Option<&str>, stage: Option<&str>) -> Result<()> {
+pub fn list(
+    boundary: Option<&str>,
+    stage: Option<&str>,
+    owner: Option<&str>,
+    team: Option<&str>,
+    sort: Option<&str>,
+    format: OutputFormat,
+    page: Page,
+) -> Result<()> {
     let paths = ManifoldPaths::new()?;
     ensure_initialized(&paths)?;
 
@@ -199,8 +661,35 @@ pub fn list(boundary: Option<&str>, stage: Option<&str>) -> Result<()> {
         None => None,
     };
 
-    let db = Database::open(&paths)?;
-    let specs = db.list_specs(boundary.as_ref(), stage.as_ref())?;
+    let order = match sort {
+        Some(s) => s.parse::<SpecOrder>().map_err(|e| anyhow::anyhow!(e))?,
+        None => SpecOrder::default(),
+    };
+
+    let filter = SpecFilter {
+        boundary,
+        stage,
+        owner: owner.map(str::to_string),
+        team: team.map(str::to_string),
+    };
+    let db = Database::open_readonly(&paths)?;
+
+    if let OutputFormat::Ndjson = format {
+        let result = db.each_spec_filtered(&filter, order, page, ndjson_writer());
+        return ignore_broken_pipe(result);
+    }
+
+    let mut specs = Vec::new();
+    db.each_spec_filtered(&filter, order, page, |spec| {
+        specs.push(spec);
+        Ok(())
+    })?;
+
+    if let OutputFormat::Json = format {
+        let json_specs: Vec<_> = specs.iter().map(|s| &s.data).collect();
+        println!("{}", serde_json::to_string_pretty(&json_specs)?);
+        return Ok(());
+    }
 
     if specs.is_empty() {
         println!("No specs found.");
@@ -208,6 +697,24 @@ pub fn list(boundary: Option<&str>, stage: Option<&str>) -> Result<()> {
         return Ok(());
     }
 
+    // Filters and the BOUNDARY/STAGE columns come from the index, so say
+    // when it disagrees with the spec itself
+    let stale = specs
+        .iter()
+        .filter(|spec| !spec.column_mismatches().is_empty())
+        .count();
+    if stale > 0 {
+        eprintln!(
+            "⚠ {} spec(s) have stage/boundary columns that differ from their JSON; run 'manifold doctor --fix'",
+            stale
+        );
+    }
+
+    if let OutputFormat::Tree = format {
+        print!("{}", render_spec_tree(&specs));
+        return Ok(());
+    }
+
     // Print header
     println!(
         "{:<30} {:<20} {:<12} {:<15}",
@@ -233,20 +740,125 @@ pub fn list(boundary: Option<&str>, stage: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+/// Group specs by boundary, then stage, keeping list order within a group
+///
+/// Boundaries and stages appear in their natural order; unknown values
+/// (old or hand-edited rows) sort after the known ones.
+fn render_spec_tree(specs: &[SpecRow]) -> String {
+    let boundary_rank = |b: &str| {
+        ["personal", "work", "company"]
+            .iter()
+            .position(|known| *known == b)
+            .unwrap_or(usize::MAX)
+    };
+    let stage_rank = |s: &str| {
+        s.parse::<WorkflowStage>()
+            .map(|stage| stage.order())
+            .unwrap_or(usize::MAX)
+    };
+
+    // Keyed by (rank, name) so known values sort naturally
+    type Stages<'a> = BTreeMap<(usize, &'a str), Vec<&'a SpecRow>>;
+    let mut groups: BTreeMap<(usize, &str), Stages> = BTreeMap::new();
+    for spec in specs {
+        groups
+            .entry((boundary_rank(&spec.boundary), spec.boundary.as_str()))
+            .or_default()
+            .entry((stage_rank(&spec.stage), spec.stage.as_str()))
+            .or_default()
+            .push(spec);
+    }
+
+    let mut out = String::new();
+    for ((_, boundary), stages) in &groups {
+        let total: usize = stages.values().map(Vec::len).sum();
+        out.push_str(&format!("{} ({})\n", boundary, total));
+
+        for (i, ((_, stage), rows)) in stages.iter().enumerate() {
+            let last_stage = i + 1 == stages.len();
+            let (branch, indent) = if last_stage {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            out.push_str(&format!("{}{} ({})\n", branch, stage, rows.len()));
+
+            for (j, spec) in rows.iter().enumerate() {
+                let leaf = if j + 1 == rows.len() {
+                    "└── "
+                } else {
+                    "├── "
+                };
+                let name = spec
+                    .data
+                    .get("name")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(&spec.project);
+                out.push_str(&format!("{}{}{}  {}\n", indent, leaf, spec.id, name));
+            }
+        }
+    }
+    out
+}
+
 /// Search specs using full-text search
-pub fn search(query: &str, format: OutputFormat) -> Result<()> {
+pub fn search(
+    query: &str,
+    boundary: Option<&str>,
+    stage: Option<&str>,
+    sources: SearchSources,
+    format: OutputFormat,
+    limit: Option<usize>,
+    offset: usize,
+) -> Result<()> {
     let paths = ManifoldPaths::new()?;
     ensure_initialized(&paths)?;
 
-    let db = Database::open(&paths)?;
-    let specs = db.search_specs(query)?;
+    let boundary = match boundary {
+        Some("all") | None => None,
+        Some(b) => Some(b.parse::<Boundary>().map_err(|e| anyhow::anyhow!(e))?),
+    };
+
+    let stage = match stage {
+        Some(s) => Some(s.parse::<WorkflowStage>().map_err(|e| anyhow::anyhow!(e))?),
+        None => None,
+    };
+
+    let page = Page { limit, offset };
+    let db = Database::open_readonly(&paths)?;
+
+    if let OutputFormat::Ndjson = format {
+        let result = db.each_search_result_in(
+            query,
+            boundary.as_ref(),
+            stage.as_ref(),
+            sources,
+            page,
+            ndjson_writer(),
+        );
+        return ignore_broken_pipe(result);
+    }
+
+    let mut specs = Vec::new();
+    db.each_search_result_in(
+        query,
+        boundary.as_ref(),
+        stage.as_ref(),
+        sources,
+        page,
+        |spec| {
+            specs.push(spec);
+            Ok(())
+        },
+    )?;
 
     match format {
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Ndjson => {
             let json_specs: Vec<_> = specs.iter().map(|s| &s.data).collect();
             let json = serde_json::to_string_pretty(&json_specs)?;
             println!("{}", json);
         }
+        OutputFormat::Tree => bail!("--format tree is only supported by list"),
         OutputFormat::Summary => {
             if specs.is_empty() {
                 println!("No specs found matching: {}", query);
@@ -283,156 +895,1563 @@ pub fn search(query: &str, format: OutputFormat) -> Result<()> {
     Ok(())
 }
 
-/// Show a spec by ID
-pub fn show(id: &str, format: OutputFormat) -> Result<()> {
+/// Full-text search reporting, per hit, JSON pointers to the matched fields
+pub fn search_highlight(
+    query: &str,
+    boundary: Option<&str>,
+    stage: Option<&str>,
+    limit: Option<usize>,
+    offset: usize,
+) -> Result<()> {
     let paths = ManifoldPaths::new()?;
     ensure_initialized(&paths)?;
 
-    let db = Database::open(&paths)?;
-    let spec = db
-        .get_spec(id)?
-        .with_context(|| format!("Spec not found: {}", id))?;
+    let boundary = match boundary {
+        Some("all") | None => None,
+        Some(b) => Some(b.parse::<Boundary>().map_err(|e| anyhow::anyhow!(e))?),
+    };
+
+    let stage = match stage {
+        Some(s) => Some(s.parse::<WorkflowStage>().map_err(|e| anyhow::anyhow!(e))?),
+        None => None,
+    };
+
+    let db = Database::open_readonly(&paths)?;
+    let hits = search::highlight_search(
+        &db,
+        query,
+        boundary.as_ref(),
+        stage.as_ref(),
+        Page { limit, offset },
+    )?;
+    println!("{}", serde_json::to_string_pretty(&hits)?);
+    Ok(())
+}
+
+/// Search spec JSON with a regex, reporting the matching fields
+pub fn search_regex(
+    pattern: &str,
+    fts: Option<&str>,
+    boundary: Option<&str>,
+    stage: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    let boundary = match boundary {
+        Some("all") | None => None,
+        Some(b) => Some(b.parse::<Boundary>().map_err(|e| anyhow::anyhow!(e))?),
+    };
+
+    let stage = match stage {
+        Some(s) => Some(s.parse::<WorkflowStage>().map_err(|e| anyhow::anyhow!(e))?),
+        None => None,
+    };
+
+    let re = search::compile_pattern(pattern)?;
+    let db = Database::open_readonly(&paths)?;
+    let matches = search::regex_search(&db, &re, fts, boundary.as_ref(), stage.as_ref())?;
 
     match format {
         OutputFormat::Json => {
-            let json = serde_json::to_string_pretty(&spec.data)?;
-            println!("{}", json);
+            println!("{}", serde_json::to_string_pretty(&matches)?);
+        }
+        OutputFormat::Ndjson => {
+            for m in &matches {
+                println!("{}", serde_json::to_string(m)?);
+            }
         }
+        OutputFormat::Tree => bail!("--format tree is only supported by list"),
         OutputFormat::Summary => {
-            print_spec_summary(&spec);
+            if matches.is_empty() {
+                println!("No fields match: {}", pattern);
+                return Ok(());
+            }
+
+            println!("Regex matches for: {}", pattern);
+            println!("{}", "=".repeat(60));
+            println!("Found {} field(s)", matches.len());
+            println!();
+            println!("{:<30} {:<35} VALUE", "ID", "FIELD");
+            println!("{}", "-".repeat(90));
+
+            for m in matches {
+                println!(
+                    "{:<30} {:<35} {}",
+                    truncate(&m.spec_id, 28),
+                    truncate(&m.path, 33),
+                    truncate(&m.value, 40)
+                );
+            }
         }
     }
 
     Ok(())
 }
 
-#[derive(Debug, Clone, Copy)]
-pub enum OutputFormat {
-    Json,
-    Summary,
+/// Rebuild the full-text search index
+pub fn reindex() -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    let db = Database::open(&paths)?;
+    let report = db.rebuild_fts()?;
+
+    println!("✓ Reindexed {} spec(s)", report.indexed);
+    if !report.skipped.is_empty() {
+        println!(
+            "⚠ Skipped {} unparseable spec(s): {}",
+            report.skipped.len(),
+            report.skipped.join(", ")
+        );
+    }
+    Ok(())
 }
 
-fn print_spec_summary(spec: &crate::models::SpecRow) {
-    let data = &spec.data;
+/// Check the database for drift that normal commands can't see
+///
+/// With `fix`, re-derives stale stage/boundary columns from the spec JSON
+/// and drops completed stages at or after each spec's current stage.
+pub fn doctor(fix: bool) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
 
-    println!("Spec: {}", spec.id);
-    println!("{}", "=".repeat(50));
+    let db = if fix {
+        Database::open(&paths)?
+    } else {
+        Database::open_readonly(&paths)?
+    };
 
-    if let Some(name) = data.get("name").and_then(|v| v.as_str()) {
-        println!("Name:     {}", name);
+    print!("Spec columns match spec JSON... ");
+    let mismatches = db.column_mismatches()?;
+    if mismatches.is_empty() {
+        println!("✓ ok");
+    } else {
+        println!("⚠ {} mismatch(es)", mismatches.len());
+        for mismatch in &mismatches {
+            println!("  ⚠ {}", mismatch);
+        }
     }
-    println!("Project:  {}", spec.project);
-    println!("Boundary: {}", spec.boundary);
-    println!("Stage:    {}", spec.stage);
 
-    // Show requirements summary (LLM-native format)
-    if let Some(reqs) = data.get("requirements").and_then(|v| v.as_array()) {
-        println!();
-        println!("Requirements: {}", reqs.len());
-        for (idx, req) in reqs.iter().take(5).enumerate() {
-            if let Some(title) = req.get("title").and_then(|v| v.as_str()) {
-                let id = req.get("id").and_then(|v| v.as_str()).unwrap_or("?");
-                println!("  {}: {}", id, title);
-            }
-            if idx == 4 && reqs.len() > 5 {
-                println!("  ... and {} more", reqs.len() - 5);
-            }
+    print!("Completed stages match current stage... ");
+    let stage_mismatches = db.stage_mismatches()?;
+    if stage_mismatches.is_empty() {
+        println!("✓ ok");
+    } else {
+        println!("⚠ {} mismatch(es)", stage_mismatches.len());
+        for mismatch in &stage_mismatches {
+            println!("  ⚠ {}", mismatch);
         }
     }
 
-    // Show tasks summary
-    if let Some(tasks) = data.get("tasks").and_then(|v| v.as_array()) {
-        println!();
-        println!("Tasks: {}", tasks.len());
-        let completed = tasks
-            .iter()
-            .filter(|t| t.get("status").and_then(|v| v.as_str()) == Some("completed"))
-            .count();
+    if mismatches.is_empty() && stage_mismatches.is_empty() {
+        return Ok(());
+    }
+    if !fix {
+        bail!(
+            "Found inconsistent specs; run 'manifold doctor --fix' to re-derive them from the JSON"
+        );
+    }
+
+    if !mismatches.is_empty() {
+        let fixed = db.repair_columns()?;
+        println!("✓ Re-derived {} column(s) from the spec JSON", fixed.len());
+    }
+    if !stage_mismatches.is_empty() {
+        let fixed = db.repair_stages()?;
+        println!(
+            "✓ Dropped stale completed stages from {} spec(s)",
+            fixed.len()
+        );
+    }
+    Ok(())
+}
+
+/// Print coverage for one spec, or every spec with an average
+pub fn stats(id: Option<&str>, json: bool) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    let db = Database::open_readonly(&paths)?;
+    let max_bytes = load_config_from(&paths.config)?.lint.max_spec_bytes();
+
+    if let Some(id) = id {
+        let spec = db
+            .get_spec_data(id)?
+            .with_context(|| format!("Spec not found: {}", id))?;
+        let report = crate::validation::coverage(&spec);
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            return Ok(());
+        }
+
+        println!("Coverage for: {}", id);
+        println!("{}", "=".repeat(50));
+        println!(
+            "Requirements with scenarios: {}/{}",
+            report.requirements_with_scenarios, report.requirements
+        );
+        println!(
+            "Requirements traced by tasks: {}/{}",
+            report.requirements_traced, report.requirements
+        );
+        println!(
+            "Tasks with acceptance:        {}/{}",
+            report.tasks_with_acceptance, report.tasks
+        );
+        println!(
+            "Requirements verified:        {}/{}",
+            report.requirements_verified, report.requirements
+        );
+        println!();
+        println!("Score: {:.0}%", report.score);
+
+        let size = crate::validation::spec_size(&spec);
+        println!(
+            "Size:  {} KiB (largest section: {})",
+            size.bytes / 1024,
+            size.largest_section
+        );
+        if let Some(warning) = crate::validation::lint_spec_size(&spec, max_bytes) {
+            println!("⚠ {}", warning);
+        }
+        return Ok(());
+    }
+
+    let specs = parse_rows_or_warn(&db.list_specs(None, None, SpecOrder::ProjectAsc)?);
+    let reports: Vec<_> = specs
+        .iter()
+        .map(|spec| (spec.spec_id.as_str(), crate::validation::coverage(spec)))
+        .collect();
+    let average = if reports.is_empty() {
+        0.0
+    } else {
+        reports.iter().map(|(_, r)| r.score).sum::<f64>() / reports.len() as f64
+    };
+
+    if json {
+        let specs: serde_json::Map<String, serde_json::Value> = reports
+            .iter()
+            .map(|(id, r)| Ok((id.to_string(), serde_json::to_value(r)?)))
+            .collect::<Result<_>>()?;
+        let out = serde_json::json!({ "average_score": average, "specs": specs });
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    println!("{:<30} COVERAGE  VERIFIED", "ID");
+    println!("{}", "-".repeat(50));
+    for (id, report) in &reports {
+        let verified = format!("{}/{}", report.requirements_verified, report.requirements);
+        println!(
+            "{:<30} {:>7.0}%  {:>8}",
+            truncate(id, 30),
+            report.score,
+            verified
+        );
+    }
+    println!();
+    println!("Average: {:.0}% across {} spec(s)", average, reports.len());
+
+    for spec in &specs {
+        if let Some(warning) = crate::validation::lint_spec_size(spec, max_bytes) {
+            println!("⚠ {}: {}", spec.spec_id, warning);
+        }
+    }
+    Ok(())
+}
+
+/// Print a quality report for one spec: workflow, coverage, task progress,
+/// traceability gaps, and open conflicts and reviews
+pub fn report(id: &str, json: bool) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    let db = Database::open_readonly(&paths)?;
+    let policy = load_config_from(&paths.config)?.workflow;
+    let spec = db
+        .get_spec_data(id)?
+        .with_context(|| format!("Spec not found: {}", id))?;
+    let report = crate::validation::report::SpecReport::build(
+        &spec,
+        &policy,
+        &db.get_conflicts(id)?,
+        &db.get_reviews(id)?,
+    );
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!("Report for: {} ({})", report.name, report.spec_id);
+    println!("{}", "=".repeat(50));
+
+    println!("Workflow");
+    println!(
+        "  Stage:    {} ({:.0}% through the workflow)",
+        report.workflow.stage, report.workflow.percent
+    );
+    match (&report.workflow.next_stage, &report.workflow.blocker) {
+        (None, _) => println!("  Next:     none, workflow complete"),
+        (Some(next), None) => println!("  Next:     {} (ready to advance)", next),
+        (Some(next), Some(blocker)) => println!("  Next:     {} (blocked: {})", next, blocker),
+    }
+    println!();
+
+    let coverage = &report.coverage;
+    println!("Coverage: {:.0}%", coverage.score);
+    println!(
+        "  Requirements with scenarios: {}/{}",
+        coverage.requirements_with_scenarios, coverage.requirements
+    );
+    println!(
+        "  Requirements traced:         {}/{}",
+        coverage.requirements_traced, coverage.requirements
+    );
+    println!(
+        "  Requirements verified:       {}/{}",
+        coverage.requirements_verified, coverage.requirements
+    );
+    println!(
+        "  Scenarios implemented:       {}/{} ({:.0}%)",
+        report.scenarios.covered, report.scenarios.scenarios, report.scenarios.percent
+    );
+    println!(
+        "  Tasks with acceptance:       {}/{}",
+        coverage.tasks_with_acceptance, coverage.tasks
+    );
+    println!();
+
+    let tasks = &report.tasks;
+    println!(
+        "Tasks: {}/{} completed ({:.0}%), {} in progress, {} pending, {} blocked",
+        tasks.completed,
+        tasks.total,
+        tasks.percent,
+        tasks.in_progress,
+        tasks.pending,
+        tasks.blocked
+    );
+    println!();
+
+    if report.gaps.is_empty() {
+        println!("Traceability: ✓ no gaps");
+    } else {
+        println!("Traceability: {} gap(s)", report.gaps.len());
+        for gap in &report.gaps {
+            println!("  ⚠ {}", gap.message);
+        }
+    }
+    println!();
+
+    if report.open_conflicts.is_empty() {
+        println!("Conflicts: none open");
+    } else {
+        println!(
+            "Conflicts: {} open, run 'manifold conflicts list'",
+            report.open_conflicts.len()
+        );
+        for path in &report.open_conflicts {
+            println!("  ⚠ {}", path);
+        }
+    }
+    println!("Reviews: {}", report.reviews.format());
+    for review in &report.pending_reviews {
+        println!("  ⏳ {} awaiting {}", review.id, review.reviewer);
+    }
+
+    Ok(())
+}
+
+/// Show a spec by ID
+///
+/// With `compact`, JSON is printed canonically: sorted keys on one line.
+pub fn show(id: &str, format: OutputFormat, compact: bool) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    let db = Database::open_readonly(&paths)?;
+    let spec = db
+        .get_spec(id)?
+        .with_context(|| format!("Spec not found: {}", id))?;
+
+    match format {
+        OutputFormat::Json if compact => {
+            println!("{}", crate::models::canonical_json(&spec.data));
+        }
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&spec.data)?;
+            println!("{}", json);
+        }
+        OutputFormat::Ndjson => {
+            println!("{}", serde_json::to_string(&spec.data)?);
+        }
+        OutputFormat::Tree => bail!("--format tree is only supported by list"),
+        OutputFormat::Summary => {
+            print_spec_summary(&spec);
+        }
+    }
+
+    Ok(())
+}
+
+/// Print a spec as it was at a past time, reconstructed from its history
+pub fn replay(id: &str, at: &str, json: bool) -> Result<()> {
+    let at = parse_time("--at", at)?;
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    let db = Database::open_readonly(&paths)?;
+    let current = db
+        .get_spec_data(id)?
+        .with_context(|| format!("Spec not found: {}", id))?;
+    let snapshots = db.get_snapshots(id)?;
+    let events = db.get_workflow_events(id, None, None, None)?;
+    let replay = crate::collab::replay::replay(&current, &snapshots, &events, at)?;
+
+    if json {
+        let output = serde_json::json!({
+            "spec_id": id,
+            "at": at,
+            "snapshot_id": replay.snapshot_id,
+            "notes": replay.notes,
+            "spec": replay.spec,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let spec = &replay.spec;
+    println!("Spec: {} as of {}", id, format_timestamp(at));
+    match replay.snapshot_id {
+        Some(snapshot_id) => println!("(from snapshot {})", snapshot_id),
+        None => println!("(rewound from the current version)"),
+    }
+    println!("{}", "=".repeat(50));
+    println!("Name:     {}", spec.name);
+    println!("Project:  {}", spec.project);
+    println!("Boundary: {}", spec.boundary);
+    println!("Stage:    {}", spec.stage);
+    println!();
+    println!("Requirements: {}", spec.requirements.len());
+    for req in &spec.requirements {
+        println!("  {}: {}", req.id, req.title);
+    }
+    println!("Tasks: {}", spec.tasks.len());
+    println!("Decisions: {}", spec.decisions.len());
+    println!("Changes so far: {}", spec.history.patches.len());
+
+    if !replay.notes.is_empty() {
+        println!();
+        println!("⚠ Not an exact reconstruction:");
+        for note in &replay.notes {
+            println!("  {}", note);
+        }
+    }
+    Ok(())
+}
+
+/// Print a spec as Markdown, optionally only some sections
+pub fn show_markdown(id: &str, sections: &[SpecSection]) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    let db = Database::open_readonly(&paths)?;
+    let spec = db
+        .get_spec_data(id)?
+        .with_context(|| format!("Spec not found: {}", id))?;
+
+    if sections.is_empty() {
+        print!("{}", MarkdownRenderer::render_spec(&spec, false));
+    } else {
+        print!(
+            "{}",
+            MarkdownRenderer::render_sections(&spec, sections, false)
+        );
+    }
+    Ok(())
+}
+
+/// Show a single requirement, optionally with its superseded wording
+pub fn requirement(id: &str, req_id: &str, history: bool) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    let db = Database::open_readonly(&paths)?;
+    let spec = db
+        .get_spec_data(id)?
+        .with_context(|| format!("Spec not found: {}", id))?;
+    let req = spec
+        .requirements
+        .iter()
+        .find(|r| r.id == req_id)
+        .with_context(|| format!("Requirement {} not found in {}", req_id, id))?;
+
+    println!("{}: {}", req.id, req.title);
+    println!("  Priority:   {}", req.priority);
+    println!("  Capability: {}", req.capability);
+    println!("  Verified:   {}", req.verification);
+    println!("  {}", req.shall);
+
+    if history {
+        println!();
+        if req.history.is_empty() {
+            println!("No previous revisions");
+        } else {
+            println!("Revisions (oldest first):");
+            for revision in &req.history {
+                println!(
+                    "{} | {} | {}",
+                    format_timestamp(revision.timestamp),
+                    revision.actor,
+                    revision.title
+                );
+                println!("  {}", revision.shall);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Record how far a requirement's acceptance has got
+pub fn verify(id: &str, req_id: &str, status: &str) -> Result<()> {
+    let status: crate::models::VerificationStatus =
+        status.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+    let actor = current_actor(&load_config_from(&paths.config)?);
+
+    let db = Database::open(&paths)?;
+    let mut spec = db
+        .get_spec_data(id)?
+        .with_context(|| format!("Spec not found: {}", id))?;
+    let index = spec
+        .requirements
+        .iter()
+        .position(|r| r.id == req_id)
+        .with_context(|| format!("Requirement {} not found in {}", req_id, id))?;
+
+    if spec.requirements[index].verification == status {
+        println!("{} is already {}", req_id, status);
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    spec.requirements[index].verification = status;
+    spec.history.updated_at = now;
+    spec.history.patches.push(PatchEntry {
+        timestamp: now,
+        actor,
+        op: "replace".to_string(),
+        path: format!("/requirements/{}/verification", index),
+        summary: format!("Marked {} {}", req_id, status),
+    });
+    db.update_spec(&spec)?;
+
+    let verified = spec
+        .requirements
+        .iter()
+        .filter(|r| r.verification == crate::models::VerificationStatus::Verified)
+        .count();
+    println!(
+        "✓ {} is {} ({}/{} requirements verified)",
+        req_id,
+        status,
+        verified,
+        spec.requirements.len()
+    );
+    Ok(())
+}
+
+/// Set who owns a spec, and optionally its team
+pub fn set_owner(id: &str, owner: &str, team: Option<&str>) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+    let actor = current_actor(&load_config_from(&paths.config)?);
+
+    let db = Database::open(&paths)?;
+    let mut spec = db
+        .get_spec_data(id)?
+        .with_context(|| format!("Spec not found: {}", id))?;
+
+    let now = chrono::Utc::now().timestamp();
+    let mut changed = Vec::new();
+    if spec.owner.as_deref() != Some(owner) {
+        spec.owner = Some(owner.to_string());
+        changed.push(("/owner", format!("Set owner to {}", owner)));
+    }
+    if let Some(team) = team.filter(|t| spec.team.as_deref() != Some(*t)) {
+        spec.team = Some(team.to_string());
+        changed.push(("/team", format!("Set team to {}", team)));
+    }
+    if changed.is_empty() {
+        println!("{} is already owned by {}", id, owner);
+        return Ok(());
+    }
+
+    for (path, summary) in changed {
+        spec.history.patches.push(PatchEntry {
+            timestamp: now,
+            actor: actor.clone(),
+            op: "replace".to_string(),
+            path: path.to_string(),
+            summary,
+        });
+    }
+    spec.history.updated_at = now;
+    db.update_spec(&spec)?;
+
+    match &spec.team {
+        Some(team) => println!("✓ {} is owned by {} ({})", id, owner, team),
+        None => println!("✓ {} is owned by {}", id, owner),
+    }
+    Ok(())
+}
+
+/// Scenario command handler
+pub fn scenario_command(operation: ScenarioOperation) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+    let actor = current_actor(&load_config_from(&paths.config)?);
+    let db = Database::open(&paths)?;
+
+    match operation {
+        ScenarioOperation::Move {
+            spec_id,
+            req_id,
+            scenario_id,
+            position,
+        } => {
+            let mut spec = db
+                .get_spec_data(&spec_id)?
+                .with_context(|| format!("Spec not found: {}", spec_id))?;
+            let req_index = spec
+                .requirements
+                .iter()
+                .position(|r| r.id == req_id)
+                .with_context(|| format!("Requirement {} not found in {}", req_id, spec_id))?;
+
+            let req = &mut spec.requirements[req_index];
+            let before: Vec<String> = req.scenarios.iter().map(|s| s.id.clone()).collect();
+            let index = req.move_scenario(&scenario_id, &position)?;
+            let order: Vec<String> = req.scenarios.iter().map(|s| s.id.clone()).collect();
+            if order == before {
+                println!("{} is already there", scenario_id);
+                return Ok(());
+            }
+
+            let (relation, anchor) = match &position {
+                ScenarioPosition::Before(anchor) => ("before", anchor),
+                ScenarioPosition::After(anchor) => ("after", anchor),
+            };
+            let now = chrono::Utc::now().timestamp();
+            spec.history.updated_at = now;
+            spec.history.patches.push(PatchEntry {
+                timestamp: now,
+                actor,
+                op: "move".to_string(),
+                path: format!("/requirements/{}/scenarios/{}", req_index, index),
+                summary: format!("Moved {}/{} {} {}", req_id, scenario_id, relation, anchor),
+            });
+            db.update_spec(&spec)?;
+
+            println!(
+                "✓ Moved {} {} {} in {}: {}",
+                scenario_id,
+                relation,
+                anchor,
+                req_id,
+                order.join(", ")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum OutputFormat {
+    Json,
+    /// One compact JSON object per line, written as rows are fetched
+    Ndjson,
+    Summary,
+    /// Specs nested under boundary and stage headings
+    Tree,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(OutputFormat::Json),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            "summary" => Ok(OutputFormat::Summary),
+            "tree" => Ok(OutputFormat::Tree),
+            _ => Err(format!(
+                "Invalid format: {}. Use summary, json, ndjson, or tree",
+                s
+            )),
+        }
+    }
+}
+
+/// Row callback that writes each spec to stdout as a single JSON line
+fn ndjson_writer() -> impl FnMut(SpecRow) -> Result<()> {
+    let mut out = std::io::stdout().lock();
+    move |spec| {
+        let mut line = serde_json::to_string(&spec.data)?;
+        line.push('\n');
+        out.write_all(line.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// A closed pipe (e.g. `| head`) just means the reader has seen enough
+fn ignore_broken_pipe(result: Result<()>) -> Result<()> {
+    match result {
+        Err(e)
+            if e.downcast_ref::<std::io::Error>()
+                .is_some_and(|io| io.kind() == std::io::ErrorKind::BrokenPipe) =>
+        {
+            Ok(())
+        }
+        other => other,
+    }
+}
+
+/// Print ids one per line for shell completion
+///
+/// Prints nothing when manifold is not initialized, so a TAB press never
+/// shows an error.
+pub fn complete_ids(kind: &str) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    if !paths.is_initialized() {
+        return Ok(());
+    }
+
+    let db = Database::open_readonly(&paths)?;
+    let ids = match kind {
+        "specs" => db.list_spec_ids()?,
+        "projects" => db.list_project_ids()?,
+        other => bail!("Unknown completion kind: {}", other),
+    };
+
+    for id in ids {
+        println!("{}", id);
+    }
+    Ok(())
+}
+
+/// Detects settled changes to a polled value, such as a spec's `updated_at`
+///
+/// A new value is only reported once it has stayed the same for the
+/// debounce window, so a burst of writes causes a single re-render.
+#[derive(Debug, Clone)]
+pub struct ChangeDetector<T = i64> {
+    last_seen: T,
+    pending: Option<(T, std::time::Instant)>,
+    debounce: std::time::Duration,
+}
+
+impl<T: PartialEq + Clone> ChangeDetector<T> {
+    pub fn new(updated_at: T, debounce: std::time::Duration) -> Self {
+        Self {
+            last_seen: updated_at,
+            pending: None,
+            debounce,
+        }
+    }
+
+    /// Record a polled value; true when a change is ready to show
+    pub fn observe(&mut self, updated_at: T, now: std::time::Instant) -> bool {
+        if updated_at == self.last_seen {
+            self.pending = None;
+            return false;
+        }
+
+        match &self.pending {
+            Some((ts, since)) if *ts == updated_at => {
+                if now.duration_since(*since) >= self.debounce {
+                    self.last_seen = updated_at;
+                    self.pending = None;
+                    true
+                } else {
+                    false
+                }
+            }
+            // First sight of this value, or it moved again: restart
+            _ if self.debounce.is_zero() => {
+                self.last_seen = updated_at;
+                true
+            }
+            _ => {
+                self.pending = Some((updated_at, now));
+                false
+            }
+        }
+    }
+}
+
+/// Re-render a spec summary whenever it changes, until interrupted
+pub fn watch(id: &str, interval_ms: u64, debounce_ms: u64) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    let db = Database::open_readonly(&paths)?;
+    let render = |spec: &SpecRow| {
+        // Clear the screen and home the cursor
+        print!("\x1B[2J\x1B[H");
+        print_spec_summary(spec);
+        println!();
+        println!("Watching {} for changes (Ctrl-C to stop)", id);
+        let _ = std::io::stdout().flush();
+    };
+
+    let spec = db
+        .get_spec(id)?
+        .with_context(|| format!("Spec not found: {}", id))?;
+    render(&spec);
+
+    let interval = std::time::Duration::from_millis(interval_ms);
+    let mut detector = ChangeDetector::new(
+        spec.updated_at,
+        std::time::Duration::from_millis(debounce_ms),
+    );
+
+    loop {
+        std::thread::sleep(interval);
+        let updated_at = db
+            .spec_updated_at(id)?
+            .with_context(|| format!("Spec {} was deleted", id))?;
+        if detector.observe(updated_at, std::time::Instant::now()) {
+            if let Some(spec) = db.get_spec(id)? {
+                render(&spec);
+            }
+        }
+    }
+}
+
+fn print_spec_summary(spec: &crate::models::SpecRow) {
+    let data = &spec.data;
+
+    println!("Spec: {}", spec.id);
+    println!("{}", "=".repeat(50));
+
+    // Still show what the raw JSON has, but make the problem visible
+    if let Err(e) = spec.parse() {
+        println!("⚠ {}", e);
+    }
+
+    if let Some(name) = data.get("name").and_then(|v| v.as_str()) {
+        println!("Name:     {}", name);
+    }
+    println!("Project:  {}", spec.project);
+    println!("Boundary: {}", spec.boundary);
+    println!("Stage:    {}", spec.stage);
+
+    // Show requirements summary (LLM-native format)
+    if let Some(reqs) = data.get("requirements").and_then(|v| v.as_array()) {
+        println!();
+        println!("Requirements: {}", reqs.len());
+        for (idx, req) in reqs.iter().take(5).enumerate() {
+            if let Some(title) = req.get("title").and_then(|v| v.as_str()) {
+                let id = req.get("id").and_then(|v| v.as_str()).unwrap_or("?");
+                println!("  {}: {}", id, title);
+            }
+            if idx == 4 && reqs.len() > 5 {
+                println!("  ... and {} more", reqs.len() - 5);
+            }
+        }
+    }
+
+    // Show tasks summary
+    if let Some(tasks) = data.get("tasks").and_then(|v| v.as_array()) {
+        println!();
+        println!("Tasks: {}", tasks.len());
+        let completed = tasks
+            .iter()
+            .filter(|t| t.get("status").and_then(|v| v.as_str()) == Some("completed"))
+            .count();
         println!("  Completed: {}/{}", completed, tasks.len());
     }
 
-    // Timestamps
-    println!();
-    println!("Created:  {}", format_timestamp(spec.created_at));
-    println!("Updated:  {}", format_timestamp(spec.updated_at));
+    // Timestamps
+    println!();
+    println!("Created:  {}", format_timestamp(spec.created_at));
+    println!("Updated:  {}", format_timestamp(spec.updated_at));
+}
+
+fn format_timestamp(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        s.to_string()
+    } else {
+        // Count chars, not bytes, so multi-byte text never splits mid-char
+        let head: String = s.chars().take(max - 3).collect();
+        format!("{}...", head)
+    }
+}
+
+/// Parse rows for multi-spec output, skipping unparseable ones with a warning
+fn parse_rows_or_warn(rows: &[SpecRow]) -> Vec<SpecData> {
+    rows.iter()
+        .filter_map(|row| match row.parse() {
+            Ok(spec) => Some(spec),
+            Err(e) => {
+                eprintln!("⚠ Skipping: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+fn ensure_initialized(paths: &ManifoldPaths) -> Result<()> {
+    if !paths.is_initialized() {
+        bail!("Manifold not initialized. Run `manifold init` first.");
+    }
+    Ok(())
+}
+
+fn create_core_schema(paths: &ManifoldPaths) -> Result<()> {
+    let schema = include_str!("../../schemas/core.json");
+    std::fs::write(paths.schemas.join("core.json"), schema)
+        .context("Failed to write core.json schema")?;
+    Ok(())
+}
+
+/// How a `validate` run ended, mapped to the process exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationOutcome {
+    Passed,
+    SchemaFailed,
+    /// Schema passed but lint warned under `--strict`
+    StrictWarnings,
+}
+
+impl ValidationOutcome {
+    /// 0 pass, 1 schema failure, 2 warnings in strict mode
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ValidationOutcome::Passed => 0,
+            ValidationOutcome::SchemaFailed => 1,
+            ValidationOutcome::StrictWarnings => 2,
+        }
+    }
+
+    /// Message to report on stderr when the run failed
+    pub fn failure(self) -> Option<&'static str> {
+        match self {
+            ValidationOutcome::Passed => None,
+            ValidationOutcome::SchemaFailed => Some("Schema validation failed"),
+            ValidationOutcome::StrictWarnings => {
+                Some("Validation failed in strict mode due to warnings")
+            }
+        }
+    }
+}
+
+/// Validate a spec against the schema and lint it
+///
+/// Only lookup and database errors are returned as `Err`; a failing spec is
+/// reported through the outcome so callers can pick the exit code.
+pub fn validate(id: &str, strict: bool, fix: bool, json: bool) -> Result<ValidationOutcome> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    let config = load_config_from(&paths.config)?;
+    let ids = IdPatterns::from_config(&config.validation)?;
+    let db = if fix {
+        Database::open(&paths)?
+    } else {
+        Database::open_readonly(&paths)?
+    };
+    let mut spec = db
+        .get_spec_data(id)?
+        .with_context(|| format!("Spec not found: {}", id))?;
+
+    if !json {
+        println!("Validating spec: {}", id);
+        println!();
+    }
+
+    // Mechanical fixes go first so schema validation sees the corrected ids
+    let mut applied: Option<Vec<String>> = None;
+    if fix {
+        if !json {
+            print!("Fixing... ");
+        }
+        let fixes = crate::validation::fix::fix_spec(&mut spec, &ids);
+        if !fixes.is_empty() {
+            let now = chrono::Utc::now().timestamp();
+            let actor = current_actor(&config);
+            for fix in &fixes {
+                spec.history.patches.push(PatchEntry {
+                    timestamp: now,
+                    actor: actor.clone(),
+                    op: fix.op.clone(),
+                    path: fix.path.clone(),
+                    summary: fix.summary.clone(),
+                });
+            }
+            spec.history.updated_at = now;
+            db.update_spec(&spec)?;
+        }
+
+        if !json {
+            if fixes.is_empty() {
+                println!("✓ nothing to fix");
+            } else {
+                println!("✓ applied {} fix(es)", fixes.len());
+                for fix in &fixes {
+                    println!("  ✓ {}", fix.summary);
+                }
+            }
+        }
+        applied = Some(fixes.into_iter().map(|f| f.summary).collect());
+    }
+
+    let errors = match crate::validation::validate_spec(&spec, &ids) {
+        Ok(_) => Vec::new(),
+        Err(e) => crate::validation::error_messages(&e),
+    };
+    // Lint only specs that pass the schema, as before
+    let warnings = if errors.is_empty() {
+        let mut warnings = crate::validation::lint_spec(&spec);
+        warnings.extend(crate::validation::lint_spec_size(
+            &spec,
+            config.lint.max_spec_bytes(),
+        ));
+        warnings.extend(crate::validation::lint_similar_requirements(
+            &spec,
+            config.lint.similarity_threshold,
+        ));
+        warnings
+    } else {
+        Vec::new()
+    };
+
+    let outcome = if !errors.is_empty() {
+        ValidationOutcome::SchemaFailed
+    } else if strict && !warnings.is_empty() {
+        ValidationOutcome::StrictWarnings
+    } else {
+        ValidationOutcome::Passed
+    };
+
+    if json {
+        let mut report = serde_json::json!({
+            "spec_id": id,
+            "schema_valid": errors.is_empty(),
+            "errors": errors,
+            "warnings": warnings,
+            "strict": strict,
+        });
+        if let Some(fixes) = applied {
+            report["fixes"] = serde_json::json!(fixes);
+        }
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(outcome);
+    }
+
+    // Schema validation
+    print!("Schema validation... ");
+    if !errors.is_empty() {
+        println!("✗ failed");
+        println!("Schema validation failed:");
+        for error in &errors {
+            println!("{}", error);
+        }
+        return Ok(outcome);
+    }
+    println!("✓ passed");
+
+    // Linting
+    print!("Linting... ");
+    if warnings.is_empty() {
+        println!("✓ no warnings");
+    } else {
+        println!("⚠ {} warning(s)", warnings.len());
+        for warning in &warnings {
+            println!("  ⚠ {}", warning);
+        }
+    }
+
+    if outcome == ValidationOutcome::Passed {
+        println!();
+        println!("Validation complete!");
+    }
+    Ok(outcome)
+}
+
+/// Validate and lint every spec, in parallel, with one compiled schema
+///
+/// Results are reported sorted by spec id; the outcome is the worst of any
+/// spec's, so one failing spec fails the run.
+pub fn validate_all(strict: bool, json: bool) -> Result<ValidationOutcome> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    let config = load_config_from(&paths.config)?;
+    let ids = IdPatterns::from_config(&config.validation)?;
+    let validator = crate::validation::SpecValidator::load(&ids)?;
+    let db = Database::open_readonly(&paths)?;
+    let rows = db.list_specs(None, None, SpecOrder::default())?;
+    let specs = parse_rows_or_warn(&rows);
+
+    let results = crate::validation::validate_all(&specs, &validator, &config.lint);
+    let failed = results.iter().filter(|r| !r.schema_valid).count();
+    let warned = results.iter().filter(|r| !r.warnings.is_empty()).count();
+    let outcome = if failed > 0 {
+        ValidationOutcome::SchemaFailed
+    } else if strict && warned > 0 {
+        ValidationOutcome::StrictWarnings
+    } else {
+        ValidationOutcome::Passed
+    };
+
+    if json {
+        let report = serde_json::json!({
+            "strict": strict,
+            "specs": results,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(outcome);
+    }
+
+    for result in &results {
+        if !result.schema_valid {
+            println!("✗ {}", result.spec_id);
+            for error in &result.errors {
+                println!("    {}", error);
+            }
+        } else if !result.warnings.is_empty() {
+            println!(
+                "⚠ {} ({} warning(s))",
+                result.spec_id,
+                result.warnings.len()
+            );
+            for warning in &result.warnings {
+                println!("    {}", warning);
+            }
+        } else {
+            println!("✓ {}", result.spec_id);
+        }
+    }
+    println!();
+    println!(
+        "Validated {} spec(s): {} failed, {} with warnings",
+        results.len(),
+        failed,
+        warned
+    );
+    Ok(outcome)
+}
+
+/// Rendering switches for `export`
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    /// Only include specs in this boundary ('all' only)
+    pub boundary: Option<Boundary>,
+    /// Render requirements and tasks as tables
+    pub tables: bool,
+    pub anonymize: bool,
+    /// Prepend YAML front matter (single-spec Markdown only)
+    pub front_matter: bool,
+    /// Add a table of contents ('all' Markdown only)
+    pub toc: bool,
+    /// Append resolved conflicts (single-spec Markdown only)
+    pub resolved_conflicts: bool,
+    /// Only render these sections (single-spec Markdown only)
+    pub sections: Vec<SpecSection>,
+}
+
+/// What `export --watch` polls: row count and newest `updated_at`
+///
+/// For 'all' this covers every spec, so a change outside `--boundary` also
+/// regenerates the output; for one spec the count is always 1.
+pub fn export_stamp(db: &Database, id: &str) -> Result<(i64, i64)> {
+    if id == "all" {
+        return db.specs_stamp();
+    }
+    let updated_at = db
+        .spec_updated_at(id)?
+        .with_context(|| format!("Spec {} was deleted", id))?;
+    Ok((1, updated_at))
+}
+
+/// Export once, then again whenever the exported spec(s) change, until interrupted
+pub fn export_watch(
+    id: &str,
+    output: &str,
+    options: ExportOptions,
+    interval_ms: u64,
+    debounce_ms: u64,
+) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    let db = Database::open_readonly(&paths)?;
+    let mut detector = ChangeDetector::new(
+        export_stamp(&db, id)?,
+        std::time::Duration::from_millis(debounce_ms),
+    );
+    export(id, output, options.clone())?;
+    println!("Watching {} for changes (Ctrl-C to stop)", id);
+
+    let interval = std::time::Duration::from_millis(interval_ms);
+    loop {
+        std::thread::sleep(interval);
+        if !detector.observe(export_stamp(&db, id)?, std::time::Instant::now()) {
+            continue;
+        }
+
+        println!(
+            "[{}] Change detected, regenerating {}",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+            output
+        );
+        // A failed regeneration (e.g. a spec mid-edit) shouldn't stop the watch
+        if let Err(e) = export(id, output, options.clone()) {
+            eprintln!("⚠ Export failed: {:#}", e);
+        }
+    }
+}
+
+/// Export a spec (or 'all' specs) to a file in the given format
+pub fn export(id: &str, output: &str, options: ExportOptions) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+    let ExportOptions {
+        format,
+        boundary,
+        tables,
+        anonymize,
+        front_matter,
+        toc,
+        resolved_conflicts,
+        sections,
+    } = options;
+
+    if front_matter && (id == "all" || format != ExportFormat::Markdown) {
+        bail!("--front-matter is only supported for single-spec Markdown exports");
+    }
+    if toc && (id != "all" || format != ExportFormat::Markdown) {
+        bail!("--toc is only supported for 'all' Markdown exports");
+    }
+    if resolved_conflicts && (id == "all" || format != ExportFormat::Markdown) {
+        bail!("--include-resolved-conflicts is only supported for single-spec Markdown exports");
+    }
+    if !sections.is_empty() && (id == "all" || format != ExportFormat::Markdown) {
+        bail!("--section is only supported for single-spec Markdown exports");
+    }
+    if tables && format == ExportFormat::Dot {
+        bail!("--tables is not supported for DOT exports");
+    }
+    if tables && format == ExportFormat::AsciiDoc {
+        bail!("--tables is not supported for AsciiDoc exports");
+    }
+    if tables && format == ExportFormat::OpenApiComments {
+        bail!("--tables is not supported for OpenAPI comment exports");
+    }
+    if format == ExportFormat::Yaml && (id == "all" || tables || anonymize) {
+        bail!("YAML exports are single, unmodified specs; drop 'all', --tables, and --anonymize");
+    }
+    if boundary.is_some() && id != "all" {
+        bail!("--boundary is only supported for 'all' exports");
+    }
+
+    let db = Database::open_readonly(&paths)?;
+    let output_path = std::path::Path::new(output);
+
+    if id == "all" {
+        // Export all specs
+        let spec_rows = db.list_specs(boundary.as_ref(), None, SpecOrder::default())?;
+        let mut specs = parse_rows_or_warn(&spec_rows);
+        if anonymize {
+            specs = specs.iter().map(export::anonymize).collect();
+        }
+
+        match format {
+            ExportFormat::Html => HtmlRenderer::export_multi(&specs, output_path, tables)?,
+            ExportFormat::Pdf => PdfRenderer::export_multi(&specs, output_path, tables)?,
+            ExportFormat::Dot => DotRenderer::export_multi(&specs, output_path)?,
+            ExportFormat::AsciiDoc => AsciiDocRenderer::export_multi(&specs, output_path)?,
+            ExportFormat::OpenApiComments => {
+                OpenApiCommentsRenderer::export_multi(&specs, output_path)?
+            }
+            _ => MarkdownRenderer::export_multi(&specs, output_path, tables, toc)?,
+        }
+        println!("✓ Exported {} specs to {}", specs.len(), output);
+    } else {
+        // Export single spec
+        let mut spec = db
+            .get_spec_data(id)?
+            .with_context(|| format!("Spec not found: {}", id))?;
+        if anonymize {
+            spec = export::anonymize(&spec);
+        }
+
+        match format {
+            ExportFormat::Html => HtmlRenderer::export_to_file(&spec, output_path, tables)?,
+            ExportFormat::Pdf => PdfRenderer::export_to_file(&spec, output_path, tables)?,
+            ExportFormat::Dot => DotRenderer::export_to_file(&spec, output_path)?,
+            ExportFormat::AsciiDoc => AsciiDocRenderer::export_to_file(&spec, output_path)?,
+            ExportFormat::Yaml => YamlRenderer::export_to_file(&spec, output_path)?,
+            ExportFormat::OpenApiComments => {
+                OpenApiCommentsRenderer::export_to_file(&spec, output_path)?
+            }
+            _ if !sections.is_empty() => {
+                let mut markdown = String::new();
+                if front_matter {
+                    markdown.push_str(&MarkdownRenderer::render_front_matter(&spec));
+                }
+                markdown.push_str(&MarkdownRenderer::render_sections(&spec, &sections, tables));
+                export::ensure_parent_dir(output_path)?;
+                std::fs::write(output_path, markdown).context("Failed to write Markdown file")?;
+            }
+            _ => MarkdownRenderer::export_to_file(&spec, output_path, tables, front_matter)?,
+        }
+        if resolved_conflicts {
+            let section =
+                MarkdownRenderer::render_resolved_conflicts(&db.get_resolved_conflicts(id)?);
+            let mut file = std::fs::OpenOptions::new()
+                .append(true)
+                .open(output_path)
+                .context("Failed to append resolved conflicts")?;
+            write!(file, "\n{}", section)?;
+        }
+        println!("✓ Exported spec {} to {}", id, output);
+    }
+
+    Ok(())
 }
 
-fn format_timestamp(ts: i64) -> String {
-    chrono::DateTime::from_timestamp(ts, 0)
-        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
-        .unwrap_or_else(|| "unknown".to_string())
-}
+/// Write a Markdown report of what changed from spec `from` to spec `to`
+pub fn export_diff(from: &str, to: &str, output: &str, anonymize: bool) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
 
-fn truncate(s: &str, max: usize) -> String {
-    if s.len() <= max {
-        s.to_string()
+    let db = Database::open_readonly(&paths)?;
+    let load = |id: &str| -> Result<SpecData> {
+        let spec = db
+            .get_spec_data(id)?
+            .with_context(|| format!("Spec not found: {}", id))?;
+        Ok(if anonymize {
+            export::anonymize(&spec)
+        } else {
+            spec
+        })
+    };
+
+    let diff = crate::collab::diff::SpecDiff::between(&load(from)?, &load(to)?)?;
+    std::fs::write(output, MarkdownRenderer::render_diff(&diff))
+        .with_context(|| format!("Failed to write {}", output))?;
+
+    if diff.is_empty() {
+        println!(
+            "✓ No differences between {} and {}; wrote {}",
+            from, to, output
+        );
     } else {
-        format!("{}...", &s[..max - 3])
+        println!("✓ Wrote change report {} → {} to {}", from, to, output);
     }
+    Ok(())
 }
 
-fn ensure_initialized(paths: &ManifoldPaths) -> Result<()> {
-    if !paths.is_initialized() {
-        bail!("Manifold not initialized. Run `manifold init` first.");
+/// Agent command handler
+pub fn agent_command(operation: AgentOperation) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    match operation {
+        AgentOperation::Logs { id, since } => {
+            let since = since
+                .as_deref()
+                .map(|value| parse_time("--since", value))
+                .transpose()?;
+            let db = Database::open_readonly(&paths)?;
+            let events = db.get_agent_events(id.as_deref(), since)?;
+
+            if events.is_empty() {
+                println!("No agent events recorded");
+            }
+            for event in events {
+                println!(
+                    "{} | {} | {} | {}",
+                    format_timestamp(event.timestamp),
+                    event.agent_id,
+                    event.kind,
+                    event.detail
+                );
+            }
+        }
     }
-    Ok(())
-}
 
-fn create_core_schema(paths: &ManifoldPaths) -> Result<()> {
-    let schema = include_str!("../../schemas/core.json");
-    std::fs::write(paths.schemas.join("core.json"), schema)
-        .context("Failed to write core.json schema")?;
     Ok(())
 }
 
-/// Validate a spec against the schema
-pub fn validate(id: &str, strict: bool) -> Result<()> {
+/// Snapshot command handler
+pub fn snapshot_command(operation: SnapshotOperation) -> Result<()> {
     let paths = ManifoldPaths::new()?;
     ensure_initialized(&paths)?;
+    let actor = current_actor(&load_config_from(&paths.config)?);
 
-    let db = Database::open(&paths)?;
-    let spec_row = db
-        .get_spec(id)?
-        .with_context(|| format!("Spec not found: {}", id))?;
+    match operation {
+        SnapshotOperation::Create { id, label } => {
+            let db = Database::open(&paths)?;
+            let spec = db
+                .get_spec_data(&id)?
+                .with_context(|| format!("Spec not found: {}", id))?;
+            let snapshot_id = db.save_snapshot(&spec, label.as_deref(), &actor)?;
+            println!("✓ Created snapshot {} of {}", snapshot_id, id);
+        }
 
-    // Parse spec data
-    let spec: SpecData =
-        serde_json::from_value(spec_row.data).context("Failed to parse spec data")?;
+        SnapshotOperation::List { id } => {
+            let db = Database::open_readonly(&paths)?;
+            if db.spec_updated_at(&id)?.is_none() {
+                bail!("Spec not found: {}", id);
+            }
+            let snapshots = db.get_snapshots(&id)?;
+            if snapshots.is_empty() {
+                println!("No snapshots of {}", id);
+                println!("Create one with: manifold snapshot create {}", id);
+                return Ok(());
+            }
 
-    println!("Validating spec: {}", id);
-    println!();
+            println!(
+                "{:<6} {:<24} {:<16} {:<12} LABEL",
+                "ID", "CREATED", "BY", "STAGE"
+            );
+            println!("{}", "-".repeat(77));
+            for snapshot in snapshots {
+                println!(
+                    "{:<6} {:<24} {:<16} {:<12} {}",
+                    snapshot.id,
+                    format_timestamp(snapshot.created_at),
+                    truncate(&snapshot.created_by, 14),
+                    snapshot.data.stage,
+                    snapshot.label.as_deref().unwrap_or("")
+                );
+            }
+        }
 
-    // Schema validation
-    print!("Schema validation... ");
-    match crate::validation::validate_spec(&spec) {
-        Ok(_) => println!("✓ passed"),
-        Err(e) => {
-            println!("✗ failed");
-            println!("{}", e);
-            bail!("Schema validation failed");
+        SnapshotOperation::Restore { id, snapshot_id } => {
+            let db = Database::open(&paths)?;
+            let backup_id = restore_snapshot(&db, &id, snapshot_id, &actor)?;
+            println!("✓ Restored {} to snapshot {}", id, snapshot_id);
+            println!(
+                "  The previous version was saved as snapshot {} (restore it to undo)",
+                backup_id
+            );
+        }
+
+        SnapshotOperation::Diff { id, snapshot_id } => {
+            let db = Database::open_readonly(&paths)?;
+            let snapshot = db
+                .get_snapshot(&id, snapshot_id)?
+                .with_context(|| format!("Snapshot {} not found for {}", snapshot_id, id))?;
+            let current = db
+                .get_spec_data(&id)?
+                .with_context(|| format!("Spec not found: {}", id))?;
+
+            let diff = crate::collab::diff::SpecDiff::between(&snapshot.data, &current)?;
+            print!("{}", MarkdownRenderer::render_diff(&diff));
         }
     }
 
-    // Linting
-    print!("Linting... ");
-    let warnings = crate::validation::lint_spec(&spec);
-    if warnings.is_empty() {
-        println!("✓ no warnings");
-    } else {
-        println!("⚠ {} warning(s)", warnings.len());
-        for warning in &warnings {
-            println!("  ⚠ {}", warning);
+    Ok(())
+}
+
+/// Profile command handler
+pub fn profile_command(operation: ProfileOperation) -> Result<()> {
+    let mut profiles = load_profiles()?;
+
+    match operation {
+        ProfileOperation::Add { name, path } => {
+            let root = Path::new(&path);
+            let root = if root.is_absolute() {
+                root.to_path_buf()
+            } else {
+                std::env::current_dir()?.join(root)
+            };
+            profiles.add(&name, root.clone())?;
+            save_profiles(&profiles)?;
+            println!("✓ Added profile {} at {}", name, root.display());
+            if !root.join("config.toml").exists() {
+                println!("  Initialize it with: manifold --profile {} init", name);
+            }
+        }
+
+        ProfileOperation::List => {
+            let active = profiles.active.as_deref().unwrap_or(DEFAULT_PROFILE);
+            let default_root = profiles.root(DEFAULT_PROFILE)?;
+            let entries = std::iter::once((DEFAULT_PROFILE, &default_root))
+                .chain(profiles.profiles.iter().map(|(n, r)| (n.as_str(), r)));
+            for (name, root) in entries {
+                let marker = if name == active { "*" } else { " " };
+                println!("{} {:<20} {}", marker, name, root.display());
+            }
         }
-        if strict {
-            bail!("Validation failed in strict mode due to warnings");
+
+        ProfileOperation::Use { name } => {
+            profiles.activate(&name)?;
+            save_profiles(&profiles)?;
+            println!(
+                "✓ Using profile {} ({})",
+                name,
+                profiles.root(&name)?.display()
+            );
         }
     }
 
-    println!();
-    println!("Validation complete!");
     Ok(())
 }
 
+/// Replace a spec with one of its snapshots, keeping the spec's history
+///
+/// The current version is snapshotted first so the restore can be undone;
+/// returns that snapshot's id.
+pub fn restore_snapshot(db: &Database, id: &str, snapshot_id: i64, actor: &str) -> Result<i64> {
+    let current = db
+        .get_spec_data(id)?
+        .with_context(|| format!("Spec not found: {}", id))?;
+    let snapshot = db
+        .get_snapshot(id, snapshot_id)?
+        .with_context(|| format!("Snapshot {} not found for {}", snapshot_id, id))?;
+
+    let backup_label = format!("before restoring snapshot {}", snapshot_id);
+    let backup_id = db.save_snapshot(&current, Some(&backup_label), actor)?;
+
+    let now = chrono::Utc::now().timestamp();
+    let mut restored = snapshot.data;
+    restored.history = current.history;
+    restored.history.updated_at = now;
+    restored.history.patches.push(PatchEntry {
+        timestamp: now,
+        actor: actor.to_string(),
+        op: "replace".to_string(),
+        path: String::new(),
+        summary: match &snapshot.label {
+            Some(label) => format!("Restored snapshot {} ({})", snapshot_id, label),
+            None => format!("Restored snapshot {}", snapshot_id),
+        },
+    });
+    db.update_spec(&restored)?;
+
+    Ok(backup_id)
+}
+
 /// Join (merge) a spec into another boundary
 pub fn join(source_id: &str, target_boundary: &str, dedup: bool) -> Result<()> {
     let paths = ManifoldPaths::new()?;
@@ -442,6 +2461,7 @@ pub fn join(source_id: &str, target_boundary: &str, dedup: bool) -> Result<()> {
         .parse::<Boundary>()
         .map_err(|e| anyhow::anyhow!(e))?;
 
+    let actor = current_actor(&load_config_from(&paths.config)?);
     let db = Database::open(&paths)?;
 
     // Get source spec
@@ -449,8 +2469,7 @@ pub fn join(source_id: &str, target_boundary: &str, dedup: bool) -> Result<()> {
         .get_spec(source_id)?
         .with_context(|| format!("Source spec not found: {}", source_id))?;
 
-    let mut source_spec: SpecData =
-        serde_json::from_value(source_row.data).context("Failed to parse source spec")?;
+    let mut source_spec: SpecData = source_row.parse()?;
 
     println!("Joining spec: {} → {}", source_id, target_boundary);
     println!("  Source boundary: {}", source_spec.boundary);
@@ -464,7 +2483,11 @@ pub fn join(source_id: &str, target_boundary: &str, dedup: bool) -> Result<()> {
     // Deduplication: check for existing specs in target boundary with same project
     if dedup {
         print!("Checking for duplicates... ");
-        let existing = db.list_specs(Some(&target_boundary), std::option::Option::None)?;
+        let existing = db.list_specs(
+            Some(&target_boundary),
+            std::option::Option::None,
+            SpecOrder::default(),
+        )?;
         let duplicates: Vec<_> = existing
             .iter()
             .filter(|s| s.project == source_spec.project)
@@ -499,7 +2522,7 @@ pub fn join(source_id: &str, target_boundary: &str, dedup: bool) -> Result<()> {
     source_spec.history.updated_at = chrono::Utc::now().timestamp();
     source_spec.history.patches.push(crate::models::PatchEntry {
         timestamp: chrono::Utc::now().timestamp(),
-        actor: "user".to_string(),
+        actor,
         op: "join".to_string(),
         path: "/boundary".to_string(),
         summary: format!("Joined from {} to {}", old_boundary, source_spec.boundary),
@@ -518,21 +2541,163 @@ pub fn join(source_id: &str, target_boundary: &str, dedup: bool) -> Result<()> {
     Ok(())
 }
 
+/// Where `merge` puts the combined spec
+#[derive(Debug, Clone, Default)]
+pub struct MergeOptions {
+    /// Update the first spec instead of creating a new one
+    pub in_place: bool,
+    /// Name for a new spec; defaults to the first spec's name
+    pub name: Option<String>,
+}
+
+/// Merge spec `id_b` into `id_a` (or a new spec) and record the overlap
+///
+/// Items whose ids collide with different content keep `id_a`'s version and
+/// are saved as conflicts on the resulting spec, so `manifold conflicts
+/// resolve` can pick "ours" (`id_a`) or "theirs" (`id_b`). Returns the
+/// resulting spec and its conflicts; `id_b` is left unchanged.
+pub fn merge_specs(
+    db: &Database,
+    actor: &str,
+    id_a: &str,
+    id_b: &str,
+    options: &MergeOptions,
+) -> Result<(SpecData, Vec<crate::collab::Conflict>)> {
+    if id_a == id_b {
+        bail!("Cannot merge a spec with itself");
+    }
+    let a = db
+        .get_spec_data(id_a)?
+        .with_context(|| format!("Spec not found: {}", id_a))?;
+    let b = db
+        .get_spec_data(id_b)?
+        .with_context(|| format!("Spec not found: {}", id_b))?;
+
+    let (mut merged, mut conflicts) = ConflictResolver::merge_specs(&a, &b)?;
+
+    // New content may not have been through the later stages yet
+    if b.stage.order() < merged.stage.order() {
+        merged.stage = b.stage.clone();
+        merged.stages_completed = b.stages_completed.clone();
+    }
+
+    if !options.in_place {
+        merged.spec_id = crate::db::generate_spec_id(&merged.project);
+        if let Some(name) = &options.name {
+            merged.name = name.clone();
+        }
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    merged.history.updated_at = now;
+    merged.history.patches.push(PatchEntry {
+        timestamp: now,
+        actor: actor.to_string(),
+        op: "merge".to_string(),
+        path: "/".to_string(),
+        summary: format!(
+            "Merged {} into {} ({} conflict(s))",
+            id_b,
+            id_a,
+            conflicts.len()
+        ),
+    });
+
+    db.in_transaction(|db| {
+        if options.in_place {
+            db.update_spec(&merged)?;
+        } else {
+            db.insert_spec(&merged)?;
+        }
+        for conflict in &mut conflicts {
+            conflict.spec_id = merged.spec_id.clone();
+            db.save_conflict(conflict)?;
+        }
+        Ok(())
+    })?;
+
+    Ok((merged, conflicts))
+}
+
+/// Merge two specs and print what happened
+pub fn merge(id_a: &str, id_b: &str, options: MergeOptions) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    let config = load_config_from(&paths.config)?;
+    let actor = current_actor(&config);
+    let db = Database::open(&paths)?;
+    let (merged, conflicts) = merge_specs(&db, &actor, id_a, id_b, &options)?;
+
+    if options.in_place {
+        println!("✓ Merged {} into {}", id_b, id_a);
+    } else {
+        println!(
+            "✓ Merged {} and {} into new spec: {}",
+            id_a, id_b, merged.spec_id
+        );
+    }
+    println!(
+        "  {} requirement(s), {} task(s), {} decision(s)",
+        merged.requirements.len(),
+        merged.tasks.len(),
+        merged.decisions.len()
+    );
+
+    if !conflicts.is_empty() {
+        println!();
+        println!(
+            "⚠ {} conflicting item(s) kept {}'s version:",
+            conflicts.len(),
+            id_a
+        );
+        for conflict in &conflicts {
+            println!("  - {}", conflict.field_path);
+        }
+        println!();
+        println!(
+            "Run 'manifold conflicts list {}' to review; 'theirs' takes {}'s version",
+            merged.spec_id, id_b
+        );
+    }
+
+    // Requirements with different ids can still say the same thing
+    let similar =
+        crate::validation::find_similar_requirements(&merged, config.lint.similarity_threshold);
+    if !similar.is_empty() {
+        println!();
+        println!("⚠ {} likely duplicate requirement pair(s):", similar.len());
+        for pair in &similar {
+            println!(
+                "  - {} ~ {} ({:.0}% similar)",
+                pair.first,
+                pair.second,
+                pair.similarity * 100.0
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// Workflow operations: advance stage or show history
 pub fn workflow(id: &str, operation: WorkflowOperation) -> Result<()> {
     let paths = ManifoldPaths::new()?;
     ensure_initialized(&paths)?;
 
+    let config = load_config_from(&paths.config)?;
+    let actor = current_actor(&config);
+    let policy = config.workflow;
     let db = Database::open(&paths)?;
-    let spec_row = db
-        .get_spec(id)?
+    let mut spec = db
+        .get_spec_data(id)?
         .with_context(|| format!("Spec not found: {}", id))?;
 
-    let mut spec: SpecData =
-        serde_json::from_value(spec_row.data).context("Failed to parse spec data")?;
-
     match operation {
-        WorkflowOperation::Advance { target_stage } => {
+        WorkflowOperation::Advance {
+            target_stage,
+            skip_reason,
+        } => {
             println!("Current stage: {}", spec.stage);
 
             let target_stage = match target_stage {
@@ -541,7 +2706,7 @@ pub fn workflow(id: &str, operation: WorkflowOperation) -> Result<()> {
                     .map_err(|e| anyhow::anyhow!(e))?,
                 None => {
                     // Auto-advance to next stage
-                    match WorkflowEngine::can_advance(&spec) {
+                    match WorkflowEngine::can_advance_with(&spec, &policy) {
                         Ok(next) => next,
                         Err(e) => {
                             println!("✗ Cannot advance: {}", e);
@@ -555,65 +2720,54 @@ pub fn workflow(id: &str, operation: WorkflowOperation) -> Result<()> {
             println!();
 
             // Validate and execute transition
-            match WorkflowEngine::advance_stage(&spec, target_stage) {
+            let result = match &skip_reason {
+                Some(reason) => WorkflowEngine::skip_to_with(&spec, target_stage, reason, &policy),
+                None => WorkflowEngine::advance_stage_with(&spec, target_stage, &policy),
+            };
+            match result {
                 Ok(transition) => {
                     println!("✓ Validation passed");
-
-                    // Update spec
-                    let old_stage = spec.stage.clone();
-                    if !spec.stages_completed.contains(&old_stage) {
-                        spec.stages_completed.push(old_stage.clone());
+                    for waived in &transition.waived {
+                        println!("⚠ Waived: {}", waived);
                     }
-                    spec.stage = transition.to.clone();
-                    spec.history.updated_at = chrono::Utc::now().timestamp();
-
-                    // Log event
-                    let timestamp = spec.history.updated_at;
-                    db.log_workflow_event(
-                        &spec.spec_id,
-                        &transition.to.to_string(),
-                        &transition.event.as_string(),
-                        "user",
-                        timestamp,
-                        Some(&format!(
-                            "Advanced from {} to {}",
-                            transition.from, transition.to
-                        )),
-                    )?;
 
-                    // Update database
-                    db.update_spec(&spec)?;
+                    crate::workflow::apply_transition(
+                        &db,
+                        &mut spec,
+                        &transition,
+                        &actor,
+                        &policy,
+                    )?;
 
                     println!("✓ Advanced to stage: {}", spec.stage);
                     println!();
-                    println!("Stages completed: {:?}", spec.stages_completed);
+                    println!("Stages completed: {:?}", spec.completed_stages());
                 }
                 Err(e) => {
                     println!("✗ Transition failed: {}", e);
-
-                    // Log failed validation
-                    if let WorkflowError::ValidationFailed(msg) = &e {
-                        db.log_workflow_event(
-                            &spec.spec_id,
-                            &spec.stage.to_string(),
-                            &format!("validation_failed:{}", msg),
-                            "user",
-                            chrono::Utc::now().timestamp(),
-                            Some(&e.to_string()),
-                        )?;
-                    }
-
+                    log_validation_failure(&db, &spec, &e, &actor)?;
                     return Err(e.into());
                 }
             }
         }
 
-        WorkflowOperation::History => {
+        WorkflowOperation::History { limit, since, json } => {
+            let since = since
+                .as_deref()
+                .map(|value| parse_time("--since", value))
+                .transpose()?;
+            let events = db.get_workflow_events(id, limit, since, None)?;
+
+            if json {
+                let events: Vec<serde_json::Value> =
+                    events.iter().map(workflow_event_json).collect();
+                println!("{}", serde_json::to_string_pretty(&events)?);
+                return Ok(());
+            }
+
             println!("Workflow history for: {}", id);
             println!("{}", "=".repeat(80));
 
-            let events = db.get_workflow_events(id)?;
-
             if events.is_empty() {
                 println!("No workflow events recorded");
             } else {
@@ -636,10 +2790,10 @@ pub fn workflow(id: &str, operation: WorkflowOperation) -> Result<()> {
             println!("Workflow status for: {}", id);
             println!("{}", "=".repeat(50));
             println!("Current stage: {}", spec.stage);
-            println!("Stages completed: {:?}", spec.stages_completed);
+            println!("Stages completed: {:?}", spec.completed_stages());
             println!();
 
-            match WorkflowEngine::can_advance(&spec) {
+            match WorkflowEngine::can_advance_with(&spec, &policy) {
                 Ok(next_stage) => {
                     println!("✓ Can advance to: {}", next_stage);
                 }
@@ -653,13 +2807,239 @@ pub fn workflow(id: &str, operation: WorkflowOperation) -> Result<()> {
     Ok(())
 }
 
+/// One `workflow history --json` entry
+///
+/// `detail` is the logged description, or the event's own payload (such as a
+/// validation message) when none was logged. Events this version doesn't
+/// recognize keep their stored text as the detail, typed `unknown`.
+fn workflow_event_json(row: &crate::db::WorkflowEventRow) -> serde_json::Value {
+    let (event_type, payload) = match row.event.parse::<WorkflowEvent>() {
+        Ok(event) => (event.event_type(), event.payload()),
+        Err(_) => ("unknown", row.event.clone()),
+    };
+    serde_json::json!({
+        "timestamp": row.timestamp,
+        "stage": row.stage,
+        "event_type": event_type,
+        "detail": row.details.clone().unwrap_or(payload),
+        "actor": row.actor,
+    })
+}
+
+/// Record a failed validation in the workflow event log
+fn log_validation_failure(
+    db: &Database,
+    spec: &SpecData,
+    error: &WorkflowError,
+    actor: &str,
+) -> Result<()> {
+    if let WorkflowError::ValidationFailed(msg) = error {
+        db.log_workflow_event(
+            &spec.spec_id,
+            &spec.stage.to_string(),
+            &format!("validation_failed:{}", msg),
+            actor,
+            chrono::Utc::now().timestamp(),
+            Some(&error.to_string()),
+        )?;
+    }
+    Ok(())
+}
+
+/// Filters and safety switches for `workflow --all --operation advance`
+#[derive(Debug, Clone, Default)]
+pub struct BulkAdvanceOptions {
+    /// Only consider specs in this boundary
+    pub boundary: Option<Boundary>,
+    /// Only advance specs whose next stage is this one
+    pub target_stage: Option<WorkflowStage>,
+    /// Report what would happen without changing anything
+    pub dry_run: bool,
+    /// Also push specs through the manual approval gate
+    pub include_approval: bool,
+    /// Configured gates, such as minimum coverage
+    pub policy: WorkflowPolicy,
+    /// Who the transitions are attributed to; resolved like `--actor` when
+    /// unset
+    pub actor: Option<String>,
+}
+
+/// A spec that was (or, in a dry run, would be) advanced
+#[derive(Debug, Clone)]
+pub struct AdvancedSpec {
+    pub spec_id: String,
+    pub from: WorkflowStage,
+    pub to: WorkflowStage,
+}
+
+/// A spec that could not be advanced, with the reason
+#[derive(Debug, Clone)]
+pub struct BlockedSpec {
+    pub spec_id: String,
+    pub stage: WorkflowStage,
+    pub reason: String,
+}
+
+/// Summary of a bulk advance run
+#[derive(Debug, Clone, Default)]
+pub struct BulkAdvanceReport {
+    pub advanced: Vec<AdvancedSpec>,
+    pub blocked: Vec<BlockedSpec>,
+}
+
+/// Advance every matching spec that passes validation by one stage
+///
+/// Specs already implemented are skipped. Specs waiting on approval are
+/// reported as blocked unless `include_approval` is set.
+pub fn bulk_advance(db: &Database, options: &BulkAdvanceOptions) -> Result<BulkAdvanceReport> {
+    let actor = options
+        .actor
+        .clone()
+        .unwrap_or_else(|| current_actor(&Config::default()));
+    let actor = actor.as_str();
+    // Only specs one step before the target can move into it
+    let source_stage = match &options.target_stage {
+        Some(target) if target.order() == 0 => {
+            bail!("No stage advances into {}", target)
+        }
+        Some(target) => Some(WorkflowStage::all()[target.order() - 1].clone()),
+        None => None,
+    };
+
+    let rows = db.list_specs(
+        options.boundary.as_ref(),
+        source_stage.as_ref(),
+        SpecOrder::default(),
+    )?;
+    let mut report = BulkAdvanceReport::default();
+
+    for row in rows {
+        // One corrupt row shouldn't stop the rest from advancing
+        let mut spec = match row.parse() {
+            Ok(spec) => spec,
+            Err(e) => {
+                eprintln!("⚠ Skipping: {}", e);
+                continue;
+            }
+        };
+
+        if spec.stage == WorkflowStage::Implemented {
+            continue;
+        }
+
+        if spec.stage == WorkflowStage::Approval && !options.include_approval {
+            report.blocked.push(BlockedSpec {
+                spec_id: spec.spec_id.clone(),
+                stage: spec.stage.clone(),
+                reason: "Awaiting manual approval (use --include-approval)".to_string(),
+            });
+            continue;
+        }
+
+        match WorkflowEngine::can_advance_with(&spec, &options.policy) {
+            Ok(next) => {
+                let transition = WorkflowEngine::advance_stage_with(&spec, next, &options.policy)?;
+                if !options.dry_run {
+                    crate::workflow::apply_transition(
+                        db,
+                        &mut spec,
+                        &transition,
+                        actor,
+                        &options.policy,
+                    )?;
+                }
+                report.advanced.push(AdvancedSpec {
+                    spec_id: spec.spec_id.clone(),
+                    from: transition.from,
+                    to: transition.to,
+                });
+            }
+            Err(e) => {
+                if !options.dry_run {
+                    log_validation_failure(db, &spec, &e, actor)?;
+                }
+                report.blocked.push(BlockedSpec {
+                    spec_id: spec.spec_id.clone(),
+                    stage: spec.stage.clone(),
+                    reason: e.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Advance all ready specs and print a summary
+pub fn workflow_advance_all(mut options: BulkAdvanceOptions) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+    let config = load_config_from(&paths.config)?;
+    options.actor = Some(current_actor(&config));
+    options.policy = config.workflow;
+
+    let db = Database::open(&paths)?;
+    let report = bulk_advance(&db, &options)?;
+
+    if options.dry_run {
+        println!("Dry run: no specs were changed");
+        println!();
+    }
+
+    let verb = if options.dry_run {
+        "Would advance"
+    } else {
+        "Advanced"
+    };
+    println!("{} {} spec(s):", verb, report.advanced.len());
+    for spec in &report.advanced {
+        println!("  ✓ {}: {} → {}", spec.spec_id, spec.from, spec.to);
+    }
+
+    println!();
+    println!("Blocked {} spec(s):", report.blocked.len());
+    for spec in &report.blocked {
+        println!("  ✗ {} ({}): {}", spec.spec_id, spec.stage, spec.reason);
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Clone)]
 pub enum WorkflowOperation {
-    Advance { target_stage: Option<String> },
-    History,
+    Advance {
+        target_stage: Option<String>,
+        /// Justification for skipping stages on the way to `target_stage`
+        skip_reason: Option<String>,
+    },
+    History {
+        limit: Option<usize>,
+        since: Option<String>,
+        json: bool,
+    },
     Status,
 }
 
+/// Parse a time flag value: unix seconds, `YYYY-MM-DD`, or an RFC 3339 timestamp
+fn parse_time(flag: &str, value: &str) -> Result<i64> {
+    if let Ok(ts) = value.parse::<i64>() {
+        return Ok(ts);
+    }
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.timestamp());
+    }
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        if let Some(dt) = date.and_hms_opt(0, 0, 0) {
+            return Ok(dt.and_utc().timestamp());
+        }
+    }
+    bail!(
+        "Invalid {} value: {}. Use unix seconds, YYYY-MM-DD, or RFC 3339",
+        flag,
+        value
+    )
+}
+
 // Collaboration commands
 
 /// Sync command handler
@@ -669,19 +3049,9 @@ pub async fn sync_command(operation: SyncOperation) -> Result<()> {
 
     match operation {
         SyncOperation::Init { repo, remote } => {
-            let repo_path = std::path::PathBuf::from(&repo);
-            let mut config = SyncConfig::new(repo_path);
-
-            if let Some(url) = remote {
-                config.remote_url = Some(url.clone());
-            }
-
-            let manager = SyncManager::new(config.clone());
-            manager.init()?;
-
-            if let Some(url) = &config.remote_url {
-                manager.add_remote("origin", url)?;
-            }
+            let repo_path =
+                std::path::absolute(&repo).context("Failed to resolve sync repository path")?;
+            let config = init_sync(&paths, repo_path, remote)?;
 
             println!("✓ Sync repository initialized");
             println!("  Path: {}", repo);
@@ -690,26 +3060,47 @@ pub async fn sync_command(operation: SyncOperation) -> Result<()> {
             }
         }
 
+        SyncOperation::Clone { url, path } => {
+            let repo_path = match path {
+                Some(path) => {
+                    std::path::absolute(&path).context("Failed to resolve sync repository path")?
+                }
+                None => paths.sync_dir(),
+            };
+            let (config, report) = clone_sync(&paths, &url, repo_path)?;
+
+            for (spec_id, error) in &report.failed {
+                eprintln!("⚠ Failed to import {}: {}", spec_id, error);
+            }
+            for (spec_id, _) in &report.conflicted {
+                println!("⚠ Conflict detected in spec: {}", spec_id);
+                println!("  Run 'manifold conflicts list' to review");
+            }
+
+            println!("✓ Sync repository cloned");
+            println!("  Path: {}", config.repo_path.display());
+            println!("  Remote: {}", url);
+            println!("  {}", report.summary());
+        }
+
         SyncOperation::Push {
             id,
             message,
             remote,
             branch,
         } => {
-            // Load sync config (in real impl, this would be stored)
-            let sync_dir = paths.root.join("sync");
-            let config = SyncConfig::new(sync_dir);
+            let config = load_sync_config(&paths)?;
             let manager = SyncManager::new(config);
 
             let db = Database::open(&paths)?;
 
             if id == "all" {
                 // Push all specs
-                let specs = db.list_specs(None, None)?;
+                let specs = db.list_specs(None, None, SpecOrder::default())?;
                 let mut pushed_count = 0;
 
                 for spec_row in specs {
-                    let spec: SpecData = serde_json::from_value(spec_row.data)?;
+                    let spec: SpecData = spec_row.parse()?;
                     manager.export_spec(&spec)?;
                     pushed_count += 1;
                 }
@@ -727,8 +3118,7 @@ pub async fn sync_command(operation: SyncOperation) -> Result<()> {
                 }
             } else {
                 // Push single spec
-                let spec_row = db.get_spec(&id)?.context("Spec not found")?;
-                let spec: SpecData = serde_json::from_value(spec_row.data)?;
+                let spec = db.get_spec_data(&id)?.context("Spec not found")?;
 
                 let file_path = manager.export_spec(&spec)?;
                 let commit_msg = message.unwrap_or_else(|| format!("Update spec: {}", id));
@@ -755,154 +3145,73 @@ pub async fn sync_command(operation: SyncOperation) -> Result<()> {
         }
 
         SyncOperation::Pull { id, remote, branch } => {
-            let sync_dir = paths.root.join("sync");
-            let config = SyncConfig::new(sync_dir);
-            let manager = SyncManager::new(config);
-
-            manager.pull(&remote, &branch)?;
-
-            let db = Database::open(&paths)?;
-
-            if id == "all" {
-                // Pull all specs
-                let spec_ids = manager.list_specs()?;
-                let mut pulled_count = 0;
-
-                for spec_id in &spec_ids {
-                    match manager.import_spec(&spec_id) {
-                        Ok(remote_spec) => {
-                            // Check for conflicts
-                            if let Ok(Some(local_row)) = db.get_spec(&spec_id) {
-                                let local_spec: SpecData = serde_json::from_value(local_row.data)?;
-
-                                let conflicts = ConflictResolver::detect_conflicts(
-                                    &local_spec,
-                                    &remote_spec,
-                                    None,
-                                )?;
-
-                                if !conflicts.is_empty() {
-                                    println!("⚠ Conflict detected in spec: {}", spec_id);
-                                    for conflict in &conflicts {
-                                        db.save_conflict(conflict)?;
-                                    }
-                                    println!("  Run 'manifold conflicts list' to review");
-
-                                    // Save metadata with conflicted status
-                                    if let Ok(hash) = manager.get_file_hash(&spec_id) {
-                                        let metadata = crate::collab::SyncMetadata {
-                                            spec_id: spec_id.clone(),
-                                            last_sync_timestamp: chrono::Utc::now().timestamp(),
-                                            last_sync_hash: hash,
-                                            remote_branch: Some(branch.clone()),
-                                            sync_status: crate::collab::SyncStatus::Conflicted,
-                                        };
-                                        let _ = db.save_sync_metadata(&metadata);
-                                    }
-                                } else {
-                                    db.update_spec(&remote_spec)?;
-                                    pulled_count += 1;
-
-                                    // Save metadata with synced status
-                                    if let Ok(hash) = manager.get_file_hash(&spec_id) {
-                                        let metadata = crate::collab::SyncMetadata {
-                                            spec_id: spec_id.clone(),
-                                            last_sync_timestamp: chrono::Utc::now().timestamp(),
-                                            last_sync_hash: hash,
-                                            remote_branch: Some(branch.clone()),
-                                            sync_status: crate::collab::SyncStatus::Synced,
-                                        };
-                                        let _ = db.save_sync_metadata(&metadata);
-                                    }
-                                }
-                            } else {
-                                db.insert_spec(&remote_spec)?;
-                                pulled_count += 1;
-                            }
-                        }
-                        Err(e) => {
-                            eprintln!("⚠ Failed to import {}: {}", spec_id, e);
-                        }
-                    }
-                }
+            let config = load_sync_config(&paths)?;
+            let manager = SyncManager::new(config);
+            let db = Database::open(&paths)?;
 
-                println!("✓ Pulled {} specs", pulled_count);
-            } else {
-                // Pull single spec
-                let remote_spec = manager.import_spec(&id)?;
+            if id == "all" {
+                let report = pull_specs(&manager, &db, &remote, &branch, None)?;
 
-                if let Ok(Some(local_row)) = db.get_spec(&id) {
-                    let local_spec: SpecData = serde_json::from_value(local_row.data)?;
+                for (spec_id, error) in &report.failed {
+                    eprintln!("⚠ Failed to import {}: {}", spec_id, error);
+                }
+                for (spec_id, _) in &report.conflicted {
+                    println!("⚠ Conflict detected in spec: {}", spec_id);
+                    println!("  Run 'manifold conflicts list' to review");
+                }
 
-                    let conflicts =
-                        ConflictResolver::detect_conflicts(&local_spec, &remote_spec, None)?;
+                println!("✓ Pulled {} specs", report.pulled());
+            } else {
+                let report = pull_specs(&manager, &db, &remote, &branch, Some(&id))?;
 
-                    if !conflicts.is_empty() {
-                        println!("⚠ Conflict detected in spec: {}", id);
-                        for conflict in &conflicts {
-                            db.save_conflict(conflict)?;
-                            println!("  {}", ConflictResolver::format_conflict(conflict));
-                        }
-                        println!();
-                        println!("Run 'manifold conflicts resolve <conflict-id>' to resolve");
-
-                        // Save metadata with conflicted status
-                        if let Ok(hash) = manager.get_file_hash(&id) {
-                            let metadata = crate::collab::SyncMetadata {
-                                spec_id: id.clone(),
-                                last_sync_timestamp: chrono::Utc::now().timestamp(),
-                                last_sync_hash: hash,
-                                remote_branch: Some(branch.clone()),
-                                sync_status: crate::collab::SyncStatus::Conflicted,
-                            };
-                            let _ = db.save_sync_metadata(&metadata);
-                        }
-                    } else {
-                        db.update_spec(&remote_spec)?;
-                        println!("✓ Pulled spec: {}", id);
-
-                        // Save metadata with synced status
-                        if let Ok(hash) = manager.get_file_hash(&id) {
-                            let metadata = crate::collab::SyncMetadata {
-                                spec_id: id.clone(),
-                                last_sync_timestamp: chrono::Utc::now().timestamp(),
-                                last_sync_hash: hash,
-                                remote_branch: Some(branch.clone()),
-                                sync_status: crate::collab::SyncStatus::Synced,
-                            };
-                            let _ = db.save_sync_metadata(&metadata);
-                        }
+                if let Some((_, conflicts)) = report.conflicted.first() {
+                    println!("⚠ Conflict detected in spec: {}", id);
+                    for conflict in conflicts {
+                        println!("  {}", ConflictResolver::format_conflict(conflict));
                     }
+                    println!();
+                    println!("Run 'manifold conflicts resolve <conflict-id>' to resolve");
+                } else if report.imported.is_empty() {
+                    println!("✓ Pulled spec: {}", id);
                 } else {
-                    db.insert_spec(&remote_spec)?;
                     println!("✓ Pulled new spec: {}", id);
-
-                    // Save metadata for new spec
-                    if let Ok(hash) = manager.get_file_hash(&id) {
-                        let metadata = crate::collab::SyncMetadata {
-                            spec_id: id.clone(),
-                            last_sync_timestamp: chrono::Utc::now().timestamp(),
-                            last_sync_hash: hash,
-                            remote_branch: Some(branch.clone()),
-                            sync_status: crate::collab::SyncStatus::Synced,
-                        };
-                        let _ = db.save_sync_metadata(&metadata);
-                    }
                 }
             }
         }
 
+        SyncOperation::Agent {
+            interval,
+            remote,
+            branch,
+        } => {
+            let agent = crate::agent::spawn_sync_agent(interval, &remote, &branch)?;
+            println!(
+                "✓ Sync agent pulling {}/{} every {}s (Ctrl-C to stop)",
+                remote, branch, interval
+            );
+
+            tokio::select! {
+                _ = agent => {}
+                _ = tokio::signal::ctrl_c() => println!("\nSync agent stopped"),
+            }
+        }
+
         SyncOperation::Status => {
-            let sync_dir = paths.root.join("sync");
-            let config = SyncConfig::new(sync_dir);
-            let manager = SyncManager::new(config);
+            let config = load_sync_config(&paths)?;
+            let manager = SyncManager::new(config.clone());
             let db = Database::open(&paths)?;
 
             println!("Sync status:");
             println!("{}", "=".repeat(60));
+            println!("Repository: {}", config.repo_path.display());
+            println!(
+                "Remote:     {}",
+                config.remote_url.as_deref().unwrap_or("(none)")
+            );
+            println!();
 
             // Get all specs
-            let specs = db.list_specs(None, None)?;
+            let specs = db.list_specs(None, None, SpecOrder::default())?;
 
             if specs.is_empty() {
                 println!("No specs to sync");
@@ -977,8 +3286,7 @@ pub async fn sync_command(operation: SyncOperation) -> Result<()> {
         }
 
         SyncOperation::Diff { id, remote, branch } => {
-            let sync_dir = paths.root.join("sync");
-            let config = SyncConfig::new(sync_dir);
+            let config = load_sync_config(&paths)?;
             let repo_path = config.repo_path.clone();
             let manager = SyncManager::new(config);
 
@@ -987,7 +3295,7 @@ pub async fn sync_command(operation: SyncOperation) -> Result<()> {
 
             // Fetch from remote first to ensure we have latest
             let output = std::process::Command::new("git")
-                .args(&["fetch", &remote])
+                .args(["fetch", &remote])
                 .current_dir(&repo_path)
                 .output()
                 .context("Failed to fetch from remote")?;
@@ -1019,32 +3327,50 @@ pub async fn sync_command(operation: SyncOperation) -> Result<()> {
     Ok(())
 }
 
+/// Save a review request for `spec_id` and notify the reviewer
+///
+/// Notification is best-effort and runs in the background: the request
+/// stands whatever happens to it, and failing notifiers are reported on
+/// stderr. The returned [`Notifications`] can be waited on before exiting.
+pub fn request_review(
+    db: &Database,
+    notifiers: &[Arc<dyn Notifier>],
+    spec_id: &str,
+    requester: &str,
+    reviewer: &str,
+) -> Result<(crate::collab::Review, Notifications)> {
+    db.get_spec(spec_id)?.context("Spec not found")?;
+
+    let review = ReviewManager::create_review(
+        spec_id.to_string(),
+        requester.to_string(),
+        reviewer.to_string(),
+    );
+    db.save_review(&review)?;
+
+    let notifications = dispatch_review_requested(notifiers, &review);
+    Ok((review, notifications))
+}
+
 /// Review command handler
 pub fn review_command(operation: ReviewOperation) -> Result<()> {
     let paths = ManifoldPaths::new()?;
     ensure_initialized(&paths)?;
     let db = Database::open(&paths)?;
-
-    // Get current user (in real impl, this would come from config)
-    let current_user = std::env::var("USER").unwrap_or_else(|_| "user".to_string());
+    let config = load_config_from(&paths.config)?;
+    let current_user = current_actor(&config);
 
     match operation {
         ReviewOperation::Request { spec_id, reviewer } => {
-            // Check spec exists
-            db.get_spec(&spec_id)?.context("Spec not found")?;
-
-            let review = ReviewManager::create_review(
-                spec_id.clone(),
-                current_user.clone(),
-                reviewer.clone(),
-            );
-
-            db.save_review(&review)?;
+            let notifiers = notifiers_from_config(&config.reviews.notify);
+            let (review, notifications) =
+                request_review(&db, &notifiers, &spec_id, &current_user, &reviewer)?;
 
             println!("✓ Review requested");
             println!("  Review ID: {}", review.id);
             println!("  Spec: {}", spec_id);
             println!("  Reviewer: {}", reviewer);
+            notifications.wait();
         }
 
         ReviewOperation::Approve { review_id, comment } => {
@@ -1067,12 +3393,40 @@ pub fn review_command(operation: ReviewOperation) -> Result<()> {
             println!("{}", ReviewManager::format_review(&review));
         }
 
-        ReviewOperation::List { spec_id, status } => {
-            let reviews = if let Some(spec_id) = spec_id {
-                db.get_reviews(&spec_id)?
+        ReviewOperation::List {
+            spec_id,
+            status,
+            mine,
+            assigned_to,
+        } => {
+            let reviewer = if mine {
+                Some(current_user)
             } else {
-                // Would need to add a method to get all reviews
-                Vec::new()
+                assigned_to
+            };
+
+            if let Some(reviewer) = reviewer {
+                let mut inbox = review_inbox(&db, &reviewer)?;
+                if let Some(spec_id) = &spec_id {
+                    inbox.retain(|r| &r.spec_id == spec_id);
+                }
+
+                if inbox.is_empty() {
+                    println!("✓ No pending reviews for {}", reviewer);
+                } else {
+                    println!("Review inbox for {} ({} pending):", reviewer, inbox.len());
+                    println!("{}", "=".repeat(60));
+                    for review in &inbox {
+                        println!("{}", ReviewManager::format_review(review));
+                        println!();
+                    }
+                }
+                return Ok(());
+            }
+
+            let reviews = match spec_id {
+                Some(spec_id) => db.get_reviews(&spec_id)?,
+                None => db.get_all_reviews()?,
             };
 
             let filtered: Vec<_> = if let Some(status_filter) = status {
@@ -1103,6 +3457,44 @@ pub fn review_command(operation: ReviewOperation) -> Result<()> {
     Ok(())
 }
 
+/// Actor given by the global `--actor` flag
+static ACTOR_OVERRIDE: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+/// Attribute this process's changes to `actor`, ahead of env and config
+pub fn set_actor(actor: String) {
+    let _ = ACTOR_OVERRIDE.set(actor);
+}
+
+/// Who changes are attributed to: `--actor`, then $MANIFOLD_ACTOR, then
+/// `user` in config, then $USER
+pub(crate) fn current_actor(config: &Config) -> String {
+    let non_empty = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+    ACTOR_OVERRIDE
+        .get()
+        .cloned()
+        .or_else(|| non_empty("MANIFOLD_ACTOR"))
+        .or_else(|| config.user.clone())
+        .or_else(|| non_empty("USER"))
+        .unwrap_or_else(|| "user".to_string())
+}
+
+/// [`current_actor`] tagged with the channel the change came through, such
+/// as `alice (mcp)`
+pub(crate) fn channel_actor(config: &Config, channel: &str) -> String {
+    format!("{} ({})", current_actor(config), channel)
+}
+
+/// Pending reviews assigned to `reviewer`, oldest request first
+pub fn review_inbox(db: &Database, reviewer: &str) -> Result<Vec<crate::collab::Review>> {
+    let mut inbox: Vec<_> = db
+        .get_all_reviews()?
+        .into_iter()
+        .filter(|r| r.reviewer == reviewer && r.status == crate::collab::ReviewStatus::Pending)
+        .collect();
+    inbox.sort_by_key(|r| r.requested_at);
+    Ok(inbox)
+}
+
 /// Conflict command handler
 pub fn conflict_command(operation: ConflictOperation) -> Result<()> {
     let paths = ManifoldPaths::new()?;
@@ -1110,22 +3502,36 @@ pub fn conflict_command(operation: ConflictOperation) -> Result<()> {
     let db = Database::open(&paths)?;
 
     match operation {
-        ConflictOperation::List { spec_id } => {
-            let conflicts = if let Some(spec_id) = spec_id {
-                db.get_conflicts(&spec_id)?
-            } else {
-                // Would need method to get all conflicts
-                Vec::new()
-            };
+        ConflictOperation::List {
+            spec_id,
+            json,
+            unresolved_only,
+        } => {
+            let mut conflicts = db.get_all_conflicts()?;
+            if let Some(spec_id) = &spec_id {
+                conflicts.retain(|c| &c.spec_id == spec_id);
+            }
+            let groups = ConflictResolver::group_by_spec(conflicts, unresolved_only);
 
-            if conflicts.is_empty() {
+            if json {
+                let conflicts: Vec<_> = groups.iter().flat_map(|g| &g.conflicts).collect();
+                println!("{}", serde_json::to_string_pretty(&conflicts)?);
+            } else if groups.is_empty() {
                 println!("✓ No conflicts");
             } else {
-                println!("Conflicts:");
+                println!("Conflicts in {} spec(s):", groups.len());
                 println!("{}", "=".repeat(60));
-                for conflict in conflicts {
-                    println!("ID: {}", conflict.id);
-                    println!("{}", ConflictResolver::format_conflict(&conflict));
+                for group in &groups {
+                    println!(
+                        "{} ({} unresolved, {} resolved)",
+                        group.spec_id, group.unresolved, group.resolved
+                    );
+                    for conflict in &group.conflicts {
+                        println!("  ID: {} [{}]", conflict.id, conflict.status);
+                        for line in ConflictResolver::format_conflict(conflict).lines() {
+                            println!("  {}", line);
+                        }
+                    }
                     println!();
                 }
             }
@@ -1134,43 +3540,654 @@ pub fn conflict_command(operation: ConflictOperation) -> Result<()> {
         ConflictOperation::Resolve {
             conflict_id,
             strategy,
+            json,
         } => {
-            let conflicts = db.get_conflicts("")?; // Get all conflicts
-            let conflict = conflicts
-                .iter()
-                .find(|c| c.id == conflict_id)
-                .context("Conflict not found")?;
+            let conflict = db
+                .get_conflict_by_id(&conflict_id)?
+                .with_context(|| format!("Conflict not found: {}", conflict_id))?;
+            if conflict.status != crate::collab::ConflictStatus::Unresolved {
+                bail!(
+                    "Conflict {} is already resolved ({})",
+                    conflict_id,
+                    conflict.status
+                );
+            }
+
+            let config = load_config_from(&paths.config)?;
+            let Some(strategy) = strategy else {
+                if json {
+                    bail!("--json needs a --strategy; interactive resolution prints text");
+                }
+                match prompt_resolution(&conflict)? {
+                    Some((resolution_strategy, resolved_value, status)) => {
+                        apply_conflict_resolution(
+                            &db,
+                            &config,
+                            &conflict,
+                            resolution_strategy,
+                            &resolved_value,
+                            &status,
+                        )?;
+                        println!("✓ Conflict resolved with strategy: {}", resolution_strategy);
+                        println!("  Status: {}", status);
+                    }
+                    None => println!("Conflict left unresolved"),
+                }
+                return Ok(());
+            };
 
             let resolution_strategy = match strategy.as_str() {
                 "ours" => ResolutionStrategy::Ours,
                 "theirs" => ResolutionStrategy::Theirs,
                 "manual" => ResolutionStrategy::Manual,
                 "merge" => ResolutionStrategy::Merge,
-                _ => bail!("Invalid strategy. Use: ours, theirs, manual, or merge"),
+                "ai" => ResolutionStrategy::Ai,
+                _ => bail!("Invalid strategy. Use: ours, theirs, manual, merge, or ai"),
             };
 
-            println!("Resolving conflict:");
-            println!("{}", ConflictResolver::format_conflict(conflict));
-            println!();
+            if !json {
+                println!("Resolving conflict:");
+                println!("{}", ConflictResolver::format_conflict(&conflict));
+                println!();
+            }
+
+            let suggestion = if let ResolutionStrategy::Ai = resolution_strategy {
+                let llm = crate::llm::LlmConfig::from_settings(&config.llm);
+                if !llm.is_enabled() {
+                    bail!("--strategy ai needs an LLM; set OPENAI_API_KEY");
+                }
+                let value = crate::llm::suggest_resolution_blocking(&llm, &conflict)?;
+                if !json {
+                    println!("AI suggestion: {}", value);
+                }
+                Some(value)
+            } else {
+                None
+            };
 
             let (resolved_value, status) =
-                ConflictResolver::resolve_conflict(conflict, resolution_strategy, None)?;
+                ConflictResolver::resolve_conflict(&conflict, resolution_strategy, suggestion)?;
+            apply_conflict_resolution(
+                &db,
+                &config,
+                &conflict,
+                resolution_strategy,
+                &resolved_value,
+                &status,
+            )?;
+
+            if json {
+                let output = ConflictResolver::resolution_json(
+                    &conflict,
+                    &strategy,
+                    &resolved_value,
+                    &status,
+                );
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            } else {
+                println!("✓ Conflict resolved with strategy: {}", strategy);
+                println!("  Status: {}", status);
+            }
+        }
+    }
+
+    Ok(())
+}
 
-            // Update conflict status
-            db.update_conflict_status(&conflict_id, &status)?;
+/// Apply a conflict's resolved value to its spec and record the resolution
+///
+/// Runs in one transaction, and the resolution is recorded only after the
+/// spec update succeeds, so a failed patch leaves the conflict unresolved
+/// for another attempt. A conflict that is already resolved is rejected and
+/// its spec left untouched.
+pub fn apply_conflict_resolution(
+    db: &Database,
+    config: &Config,
+    conflict: &crate::collab::Conflict,
+    strategy: ResolutionStrategy,
+    resolved_value: &serde_json::Value,
+    status: &crate::collab::ConflictStatus,
+) -> Result<()> {
+    apply_conflict_resolutions(
+        db,
+        &current_actor(config),
+        &[(
+            conflict.clone(),
+            strategy,
+            resolved_value.clone(),
+            status.clone(),
+        )],
+    )
+}
 
-            // Apply resolution to spec
-            let spec_row = db.get_spec(&conflict.spec_id)?.context("Spec not found")?;
-            let mut spec: SpecData = serde_json::from_value(spec_row.data)?;
+/// Apply several resolved conflicts as `actor`, all or nothing
+///
+/// Same ordering as `apply_conflict_resolution`, but one failure rolls back
+/// every spec update and resolution in the batch.
+pub fn apply_conflict_resolutions(
+    db: &Database,
+    actor: &str,
+    resolutions: &[(
+        crate::collab::Conflict,
+        ResolutionStrategy,
+        serde_json::Value,
+        crate::collab::ConflictStatus,
+    )],
+) -> Result<()> {
+    let now = chrono::Utc::now().timestamp();
+    db.in_transaction(|db| {
+        for (conflict, strategy, resolved_value, status) in resolutions {
+            let mut spec = db
+                .get_spec_data(&conflict.spec_id)?
+                .context("Spec not found")?;
 
             ConflictResolver::apply_resolutions(
                 &mut spec,
-                &[(conflict.field_path.clone(), resolved_value)],
+                &[(conflict.field_path.clone(), resolved_value.clone())],
             )?;
+            spec.history.updated_at = now;
+            spec.history.patches.push(PatchEntry {
+                timestamp: now,
+                actor: actor.to_string(),
+                op: "resolve".to_string(),
+                path: format!("/{}", conflict.field_path),
+                summary: format!("Resolved conflict {} ({})", conflict.id, strategy),
+            });
             db.update_spec(&spec)?;
 
-            println!("✓ Conflict resolved with strategy: {}", strategy);
-            println!("  Status: {}", status);
+            db.record_conflict_resolution(
+                status,
+                &ConflictResolution {
+                    conflict_id: conflict.id.clone(),
+                    strategy: strategy.to_string(),
+                    value: resolved_value.clone(),
+                    resolved_by: actor.to_string(),
+                    resolved_at: now,
+                },
+            )?;
+        }
+        Ok(())
+    })
+}
+
+/// Ask on the terminal how to resolve `conflict`, like the TUI popup
+///
+/// Returns None when the user quits without choosing.
+fn prompt_resolution(
+    conflict: &crate::collab::Conflict,
+) -> Result<
+    Option<(
+        ResolutionStrategy,
+        serde_json::Value,
+        crate::collab::ConflictStatus,
+    )>,
+> {
+    let pretty = |value: &serde_json::Value| {
+        serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+    };
+    println!("Conflict {} in {}:", conflict.id, conflict.spec_id);
+    println!("Field: {}", conflict.field_path);
+    println!();
+    match &conflict.base_value {
+        Some(base) => println!("Base:\n{}\n", pretty(base)),
+        None => println!("Base: (unknown)\n"),
+    }
+    println!("Local (ours):\n{}\n", pretty(&conflict.local_value));
+    println!("Remote (theirs):\n{}\n", pretty(&conflict.remote_value));
+
+    let mut rl = rustyline::DefaultEditor::new()?;
+    loop {
+        let choice =
+            match rl.readline("Resolve with [o]urs, [t]heirs, [m]erge, ma[n]ual, or [q]uit: ") {
+                Ok(line) => line.trim().to_lowercase(),
+                Err(rustyline::error::ReadlineError::Interrupted)
+                | Err(rustyline::error::ReadlineError::Eof) => return Ok(None),
+                Err(e) => return Err(e.into()),
+            };
+
+        let (strategy, manual_value) = match choice.as_str() {
+            "o" | "ours" => (ResolutionStrategy::Ours, None),
+            "t" | "theirs" => (ResolutionStrategy::Theirs, None),
+            "m" | "merge" => (ResolutionStrategy::Merge, None),
+            "n" | "manual" => {
+                println!("Enter JSON, or plain text for a string; leave empty to delete");
+                match rl.readline("Value> ") {
+                    Ok(line) => (
+                        ResolutionStrategy::Manual,
+                        Some(ConflictResolver::parse_manual_value(&line)),
+                    ),
+                    Err(rustyline::error::ReadlineError::Interrupted)
+                    | Err(rustyline::error::ReadlineError::Eof) => continue,
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            "q" | "quit" => return Ok(None),
+            _ => {
+                println!("Unknown choice '{}'", choice);
+                continue;
+            }
+        };
+
+        match ConflictResolver::resolve_conflict(conflict, strategy, manual_value) {
+            Ok((value, status)) => return Ok(Some((strategy, value, status))),
+            // Typically a merge that can't be done automatically
+            Err(e) => println!("✗ {}; pick another strategy", e),
+        }
+    }
+}
+
+/// Handle template commands
+pub fn template_command(operation: TemplateOperation) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    match operation {
+        TemplateOperation::Save { spec_id, name } => {
+            let db = Database::open_readonly(&paths)?;
+            let spec = db
+                .get_spec_data(&spec_id)?
+                .context(format!("Spec not found: {}", spec_id))?;
+
+            let ids = IdPatterns::from_config(&load_config_from(&paths.config)?.validation)?;
+            let template = SpecTemplate::from_spec(&name, &spec, &ids)?;
+            let path = templates::save_template(&paths.templates_dir(), &template)?;
+
+            println!("✓ Saved template '{}' from {}", name, spec_id);
+            println!("  Requirements: {}", template.requirements.len());
+            println!("  Tasks:        {}", template.tasks.len());
+            println!("  Decisions:    {}", template.decisions.len());
+            println!("  Path:         {}", path.display());
+        }
+        TemplateOperation::List => {
+            let names = templates::list_templates(&paths.templates_dir())?;
+
+            if names.is_empty() {
+                println!("No templates saved.");
+                println!("Save one with: manifold template save <spec-id> <name>");
+            } else {
+                println!("Templates:");
+                for name in names {
+                    println!("  {}", name);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle manifest operations
+pub fn manifest_command(operation: ManifestOperation) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    match operation {
+        ManifestOperation::Show { json } => {
+            let db = Database::open_readonly(&paths)?;
+            let manifest = load_manifest(&paths, &db)?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&manifest)?);
+                return Ok(());
+            }
+
+            println!("Manifold {}", manifest.manifold_id);
+            println!("{}", "=".repeat(60));
+
+            if manifest.boundaries.is_empty() {
+                println!("No specs yet.");
+                return Ok(());
+            }
+
+            println!(
+                "{:<12} {:<12} {:>6}  PROJECTS",
+                "BOUNDARY", "VISIBILITY", "SPECS"
+            );
+            println!("{}", "-".repeat(60));
+            for (boundary, config) in &manifest.boundaries {
+                println!(
+                    "{:<12} {:<12} {:>6}  {}",
+                    boundary.to_string(),
+                    config.visibility.to_string(),
+                    config.spec_count,
+                    config.projects.join(", ")
+                );
+            }
+            println!();
+            println!("Total: {} spec(s)", manifest.spec_count());
+        }
+        ManifestOperation::Bundle { output, gzip } => {
+            let db = Database::open_readonly(&paths)?;
+            let manifest = load_manifest(&paths, &db)?;
+            let specs = parse_rows_or_warn(&db.list_specs(None, None, SpecOrder::ProjectAsc)?);
+
+            let build = export::bundle::build_public_bundle(&manifest, specs);
+            let output_path = std::path::Path::new(&output);
+            if gzip {
+                export::bundle::write_bundle_gzip(&build.bundle, output_path)?;
+            } else {
+                export::bundle::write_bundle(&build.bundle, output_path)?;
+            }
+
+            println!(
+                "✓ Bundled {} public spec(s) to {}",
+                build.bundle.specs.len(),
+                output
+            );
+            if !build.skipped.is_empty() {
+                println!(
+                    "⚠ Skipped {} private spec(s) in public boundaries: {}",
+                    build.skipped.len(),
+                    build.skipped.join(", ")
+                );
+                println!("  Include one with: manifold publish <id>");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Write a byte-level copy of the database to `path`
+pub fn backup(path: &str) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    let db = Database::open_readonly(&paths)?;
+    db.backup(Path::new(path))?;
+
+    println!("✓ Backed up {} to {}", paths.db_file.display(), path);
+    Ok(())
+}
+
+/// Replace the database with a backup, after confirming unless `yes`
+pub fn restore(path: &str, yes: bool) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    if !yes {
+        print!(
+            "Replace every spec in {} with {}? This cannot be undone. [y/N] ",
+            paths.db_file.display(),
+            path
+        );
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let mut db = Database::open(&paths)?;
+    db.restore(Path::new(path))?;
+
+    let count = db.list_specs(None, None, SpecOrder::default())?.len();
+    println!("✓ Restored {} spec(s) from {}", count, path);
+    Ok(())
+}
+
+/// Build the manifest, assigning a manifold id on first use
+pub fn load_manifest(paths: &ManifoldPaths, db: &Database) -> Result<ManifoldV2> {
+    let mut config = load_config_from(&paths.config)?;
+    let manifold_id = match &config.manifold_id {
+        Some(id) => id.clone(),
+        None => {
+            let id = uuid::Uuid::new_v4().to_string();
+            config.manifold_id = Some(id.clone());
+            save_config_to(&config, &paths.config)?;
+            id
+        }
+    };
+
+    db.manifold_v2(&manifold_id, &config.visibility)
+}
+
+/// Mark a spec public (or private again) for published bundles
+pub fn publish(id: &str, private: bool, yes: bool) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+    let actor = current_actor(&load_config_from(&paths.config)?);
+
+    let db = Database::open(&paths)?;
+    let mut spec = db
+        .get_spec_data(id)?
+        .with_context(|| format!("Spec not found: {}", id))?;
+
+    let target = if private {
+        Visibility::Private
+    } else {
+        Visibility::Public
+    };
+    if spec.visibility == target {
+        println!("Spec {} is already {}", id, target);
+        return Ok(());
+    }
+
+    // Going public exposes the spec, so ask first
+    if target == Visibility::Public && !yes {
+        print!(
+            "Publish {} ({} boundary)? It will be included in published bundles. [y/N] ",
+            id, spec.boundary
+        );
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    spec.visibility = target;
+    spec.history.updated_at = now;
+    spec.history.patches.push(PatchEntry {
+        timestamp: now,
+        actor,
+        op: "replace".to_string(),
+        path: "/visibility".to_string(),
+        summary: format!("Set visibility to {}", target),
+    });
+    db.update_spec(&spec)?;
+
+    println!("✓ Spec {} is now {}", id, target);
+    Ok(())
+}
+
+/// What happened to each spec in an imported bundle
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    /// New specs inserted
+    pub imported: Vec<String>,
+    /// Existing specs updated with the bundle's non-conflicting changes
+    pub merged: Vec<String>,
+    /// Existing specs the bundle had nothing new for
+    pub unchanged: Vec<String>,
+    /// Existing specs left alone because `merge` was off
+    pub skipped: Vec<String>,
+    /// Existing specs with conflicts recorded, and how many
+    pub conflicted: Vec<(String, usize)>,
+}
+
+/// Import a bundle's specs into the database
+///
+/// New ids are inserted. Existing ids are skipped unless `merge` is set; then
+/// conflicts are detected against the version last imported from the same
+/// manifold and recorded rather than overwriting local edits.
+pub fn import_bundle(
+    db: &Database,
+    bundle: &export::bundle::Bundle,
+    merge: bool,
+) -> Result<ImportReport> {
+    let source = bundle.manifest.manifold_id.as_str();
+    let mut report = ImportReport::default();
+
+    for remote in &bundle.specs {
+        let id = remote.spec_id.clone();
+        let Some(local_row) = db.get_spec(&id)? else {
+            db.insert_spec(remote)?;
+            db.save_import_base(source, remote)?;
+            report.imported.push(id);
+            continue;
+        };
+
+        if !merge {
+            report.skipped.push(id);
+            continue;
+        }
+
+        let local: SpecData = local_row.parse()?;
+        let base = db.get_import_base(&id, source)?;
+        let conflicts = ConflictResolver::detect_conflicts(&local, remote, base.as_ref())?;
+
+        if !conflicts.is_empty() {
+            for conflict in &conflicts {
+                db.save_conflict(conflict)?;
+            }
+            report.conflicted.push((id, conflicts.len()));
+            continue;
+        }
+
+        let mut merged = match &base {
+            Some(base) => ConflictResolver::merge_with_base(&local, remote, base)?,
+            None => local.clone(),
+        };
+        if serde_json::to_value(&merged)? == serde_json::to_value(&local)? {
+            report.unchanged.push(id);
+        } else {
+            let now = chrono::Utc::now().timestamp();
+            merged.history.updated_at = now;
+            merged.history.patches.push(PatchEntry {
+                timestamp: now,
+                actor: "import".to_string(),
+                op: "merge".to_string(),
+                path: "/".to_string(),
+                summary: format!("Merged changes from bundle {}", source),
+            });
+            db.update_spec(&merged)?;
+            report.merged.push(id);
+        }
+        db.save_import_base(source, remote)?;
+    }
+
+    Ok(report)
+}
+
+/// Create a spec from canonical spec JSON
+///
+/// The JSON is deserialized and schema-validated before anything is written.
+/// Unless `keep_id` is set, a fresh spec_id is generated from the project, so
+/// the same document can be imported repeatedly. Returns the new spec's id.
+pub fn import_spec_json(
+    db: &Database,
+    json: &str,
+    keep_id: bool,
+    ids: &IdPatterns,
+) -> Result<String> {
+    serde_json::from_str::<serde_json::Value>(json).context("Invalid JSON in spec input")?;
+    // Deserialize from the text, not the value, so errors carry line/column
+    let spec: SpecData =
+        serde_json::from_str(json).context("Input is not a valid manifold spec")?;
+
+    store_imported_spec(db, spec, keep_id, ids)
+}
+
+/// Create a spec from the YAML written by `export --format yaml`
+///
+/// Same validation and id handling as [`import_spec_json`].
+pub fn import_spec_yaml(
+    db: &Database,
+    yaml: &str,
+    keep_id: bool,
+    ids: &IdPatterns,
+) -> Result<String> {
+    let spec = YamlRenderer::parse_spec(yaml)?;
+    store_imported_spec(db, spec, keep_id, ids)
+}
+
+fn store_imported_spec(
+    db: &Database,
+    mut spec: SpecData,
+    keep_id: bool,
+    ids: &IdPatterns,
+) -> Result<String> {
+    if !keep_id {
+        spec.spec_id = crate::db::generate_spec_id(&spec.project);
+    }
+    crate::validation::validate_spec(&spec, ids)?;
+
+    if db.get_spec(&spec.spec_id)?.is_some() {
+        bail!(
+            "Spec {} already exists (drop --keep-id to create a copy)",
+            spec.spec_id
+        );
+    }
+
+    spec.fill_missing_decision_dates();
+    db.insert_spec(&spec)
+}
+
+/// Handle import subcommands
+pub fn import_command(operation: ImportOperation) -> Result<()> {
+    let paths = ManifoldPaths::new()?;
+    ensure_initialized(&paths)?;
+
+    match operation {
+        ImportOperation::Bundle { path, merge } => {
+            let bundle = export::bundle::read_bundle(std::path::Path::new(&path))?;
+            let db = Database::open(&paths)?;
+            let report = import_bundle(&db, &bundle, merge)?;
+
+            println!(
+                "✓ Imported {} new spec(s) from {}",
+                report.imported.len(),
+                bundle.manifest.manifold_id
+            );
+            for id in &report.merged {
+                println!("  ✓ Merged: {}", id);
+            }
+            for id in &report.unchanged {
+                println!("  · Unchanged: {}", id);
+            }
+            for (id, count) in &report.conflicted {
+                println!("  ⚠ {} conflict(s) in {}", count, id);
+            }
+            if !report.conflicted.is_empty() {
+                println!("  Run 'manifold conflicts list' to review");
+            }
+            if !report.skipped.is_empty() {
+                println!(
+                    "  ⚠ Skipped {} existing spec(s) (use --merge): {}",
+                    report.skipped.len(),
+                    report.skipped.join(", ")
+                );
+            }
+        }
+        ImportOperation::Spec {
+            path,
+            keep_id,
+            yaml,
+        } => {
+            let input = if path == "-" {
+                let mut input = String::new();
+                std::io::Read::read_to_string(&mut std::io::stdin(), &mut input)
+                    .context("Failed to read spec from stdin")?;
+                input
+            } else {
+                std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read spec from {}", path))?
+            };
+
+            let db = Database::open(&paths)?;
+            let ids = IdPatterns::from_config(&load_config_from(&paths.config)?.validation)?;
+            let id = if yaml {
+                import_spec_yaml(&db, &input, keep_id, &ids)?
+            } else {
+                import_spec_json(&db, &input, keep_id, &ids)?
+            };
+            println!("✓ Created spec: {}", id);
         }
     }
 
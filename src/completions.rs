@@ -0,0 +1,162 @@
+//! Dynamic shell completion
+//!
+//! clap_complete only knows the static shape of the CLI. The scripts it
+//! generates are extended here so positional spec and project ids are
+//! completed by calling the hidden `manifold __complete <kind>` helper.
+
+use clap_complete::Shell;
+use regex::Regex;
+
+/// Subcommands whose first positional argument is a spec id
+pub const SPEC_ID_COMMANDS: &[&str] = &[
+    "show",
+    "watch",
+    "stats",
+    "requirements",
+    "validate",
+    "join",
+    "workflow",
+    "edit",
+    "export",
+    "publish",
+];
+
+/// Nested subcommands whose first positional argument is a spec id
+pub const NESTED_SPEC_ID_COMMANDS: &[(&str, &[&str])] = &[
+    ("sync", &["push", "pull", "diff"]),
+    ("review", &["request", "list"]),
+    ("conflicts", &["list"]),
+    ("template", &["save"]),
+    ("snapshot", &["create", "list", "restore", "diff"]),
+];
+
+/// Subcommands whose first positional argument is a project id
+pub const PROJECT_ID_COMMANDS: &[&str] = &["new"];
+
+/// Extend a generated completion script with dynamic id completion
+///
+/// Shells without a hook (powershell, elvish) get the script unchanged.
+pub fn add_dynamic_ids(shell: Shell, bin: &str, script: String) -> String {
+    match shell {
+        Shell::Bash => bash(bin, script),
+        Shell::Zsh => zsh(bin, script),
+        Shell::Fish => fish(bin, script),
+        _ => script,
+    }
+}
+
+fn bash(bin: &str, script: String) -> String {
+    let mut cases = String::new();
+    cases.push_str(&format!(
+        "        {}) kind=specs ;;\n",
+        SPEC_ID_COMMANDS.join("|")
+    ));
+    cases.push_str(&format!(
+        "        {}) kind=projects ;;\n",
+        PROJECT_ID_COMMANDS.join("|")
+    ));
+    for (parent, children) in NESTED_SPEC_ID_COMMANDS {
+        cases.push_str(&format!(
+            "        {}) case \"${{COMP_WORDS[2]}}\" in {}) kind=specs; depth=3 ;; esac ;;\n",
+            parent,
+            children.join("|")
+        ));
+    }
+
+    let wrapper = format!(
+        r#"
+_{bin}_dynamic() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local kind="" depth=2
+    case "${{COMP_WORDS[1]}}" in
+{cases}    esac
+    if [[ -n "$kind" && "$cur" != -* ]]; then
+        # Only the first positional after the subcommand is an id
+        local i positionals=0
+        for (( i=depth; i<COMP_CWORD; i++ )); do
+            [[ "${{COMP_WORDS[i]}}" != -* ]] && ((positionals++))
+        done
+        if [[ $positionals -eq 0 ]]; then
+            COMPREPLY=( $(compgen -W "$({bin} __complete "$kind" 2>/dev/null)" -- "$cur") )
+            return 0
+        fi
+    fi
+    _{bin} "$@"
+}}
+"#
+    );
+
+    let script = script.replace(
+        &format!("complete -F _{} ", bin),
+        &format!("complete -F _{}_dynamic ", bin),
+    );
+    format!("{}{}", wrapper, script)
+}
+
+fn zsh(bin: &str, script: String) -> String {
+    let helpers = format!(
+        r#"
+_{bin}_spec_ids() {{
+    local -a ids
+    ids=(${{(f)"$({bin} __complete specs 2>/dev/null)"}})
+    compadd -a ids
+}}
+
+_{bin}_project_ids() {{
+    local -a ids
+    ids=(${{(f)"$({bin} __complete projects 2>/dev/null)"}})
+    compadd -a ids
+}}
+"#
+    );
+
+    let spec_arg = Regex::new(r"(?m)^('::?(?:id|spec_id|source_id) -- .*):_default'").unwrap();
+    let project_arg = Regex::new(r"(?m)^('::?project_id -- .*):_default'").unwrap();
+    let script = spec_arg.replace_all(&script, format!("$1:_{}_spec_ids'", bin));
+    let script = project_arg.replace_all(&script, format!("$1:_{}_project_ids'", bin));
+
+    // Helpers must be defined before the generated completion function runs
+    let anchor = format!("\n_{}() {{", bin);
+    match script.find(&anchor) {
+        Some(pos) => format!("{}{}{}", &script[..pos], helpers, &script[pos..]),
+        None => format!("{}{}", script, helpers),
+    }
+}
+
+fn fish(bin: &str, mut script: String) -> String {
+    let line = |condition: String, kind: &str| {
+        format!(
+            "complete -c {bin} -n \"{condition}\" -f -a \"({bin} __complete {kind} 2>/dev/null)\"\n"
+        )
+    };
+
+    script.push('\n');
+    script.push_str(&line(
+        format!(
+            "__fish_{}_using_subcommand {}",
+            bin,
+            SPEC_ID_COMMANDS.join(" ")
+        ),
+        "specs",
+    ));
+    script.push_str(&line(
+        format!(
+            "__fish_{}_using_subcommand {}",
+            bin,
+            PROJECT_ID_COMMANDS.join(" ")
+        ),
+        "projects",
+    ));
+    for (parent, children) in NESTED_SPEC_ID_COMMANDS {
+        script.push_str(&line(
+            format!(
+                "__fish_{}_using_subcommand {}; and __fish_seen_subcommand_from {}",
+                bin,
+                parent,
+                children.join(" ")
+            ),
+            "specs",
+        ));
+    }
+    script
+}
@@ -2,10 +2,17 @@
 //!
 //! Handles the ~/.manifold/ directory structure and config.toml
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::collab::notify::ReviewsConfig;
+use crate::collab::SyncConfig;
+use crate::models::{Boundary, BoundaryVisibility};
+use crate::tui::theme::ThemeConfig;
+use crate::workflow::WorkflowPolicy;
 
 /// Default boundary for new specs
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -27,30 +34,224 @@ impl std::fmt::Display for DefaultBoundary {
     }
 }
 
+impl From<DefaultBoundary> for Boundary {
+    fn from(boundary: DefaultBoundary) -> Self {
+        match boundary {
+            DefaultBoundary::Personal => Boundary::Personal,
+            DefaultBoundary::Work => Boundary::Work,
+            DefaultBoundary::Company => Boundary::Company,
+        }
+    }
+}
+
 /// Main configuration structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
+    #[serde(default)]
     pub default_boundary: DefaultBoundary,
+    /// Who changes and reviews are attributed to; defaults to $USER
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
     #[serde(default)]
     pub llm: LlmConfig,
     #[serde(default)]
     pub mcp: McpConfig,
+    /// Git sync repository settings, set by `init --git`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync: Option<SyncConfig>,
+    /// Stable identifier for this manifold, assigned on first use
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifold_id: Option<String>,
+    /// Per-boundary visibility overrides for the manifest
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub visibility: BTreeMap<Boundary, BoundaryVisibility>,
+    /// Extra workflow gates, such as a minimum coverage for approval
+    #[serde(default)]
+    pub workflow: WorkflowPolicy,
+    #[serde(default)]
+    pub tui: TuiConfig,
+    #[serde(default)]
+    pub lint: LintConfig,
+    #[serde(default)]
+    pub validation: ValidationConfig,
+    /// Review notifications
+    #[serde(default)]
+    pub reviews: ReviewsConfig,
 }
 
-impl Default for Config {
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmConfig {
+    pub endpoint: Option<String>,
+    pub model: Option<String>,
+    /// Sampling temperature for chat; low values keep spec edits predictable
+    #[serde(default = "default_llm_temperature")]
+    pub temperature: f64,
+    /// Upper bound on tokens in each reply
+    #[serde(default = "default_llm_max_tokens")]
+    pub max_tokens: u32,
+    /// Nucleus sampling cutoff; 1.0 disables it
+    #[serde(default = "default_llm_top_p")]
+    pub top_p: f64,
+}
+
+fn default_llm_temperature() -> f64 {
+    0.2
+}
+
+fn default_llm_max_tokens() -> u32 {
+    1500
+}
+
+fn default_llm_top_p() -> f64 {
+    1.0
+}
+
+impl Default for LlmConfig {
     fn default() -> Self {
         Self {
-            default_boundary: DefaultBoundary::default(),
-            llm: LlmConfig::default(),
-            mcp: McpConfig::default(),
+            endpoint: None,
+            model: None,
+            temperature: default_llm_temperature(),
+            max_tokens: default_llm_max_tokens(),
+            top_p: default_llm_top_p(),
         }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct LlmConfig {
-    pub endpoint: Option<String>,
-    pub model: Option<String>,
+pub struct TuiConfig {
+    /// Colors per semantic role, on top of a preset
+    #[serde(default)]
+    pub theme: ThemeConfig,
+    /// Seconds between checks for spec changes made elsewhere; 0 disables
+    #[serde(default)]
+    pub refresh_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintConfig {
+    /// Warn when a spec's stored JSON is larger than this
+    #[serde(default = "default_max_spec_kb")]
+    pub max_spec_kb: usize,
+    /// Warn when two requirements' SHALL statements are at least this
+    /// similar (0.0 to 1.0); above 1.0 disables the check
+    #[serde(default = "default_similarity_threshold")]
+    pub similarity_threshold: f64,
+}
+
+fn default_max_spec_kb() -> usize {
+    crate::validation::DEFAULT_MAX_SPEC_BYTES / 1024
+}
+
+fn default_similarity_threshold() -> f64 {
+    0.8
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            max_spec_kb: default_max_spec_kb(),
+            similarity_threshold: default_similarity_threshold(),
+        }
+    }
+}
+
+impl LintConfig {
+    pub fn max_spec_bytes(&self) -> usize {
+        self.max_spec_kb * 1024
+    }
+}
+
+/// Id schemes checked by `validate` and used for generated ids
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ValidationConfig {
+    /// Zero-pad generated id numbers to this many digits, e.g. 3 for REQ-001
+    #[serde(default)]
+    pub id_digits: usize,
+    #[serde(default)]
+    pub id_patterns: IdPatternConfig,
+    #[serde(default)]
+    pub id_prefixes: IdPrefixConfig,
+}
+
+/// Regex each kind of id must match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdPatternConfig {
+    #[serde(default = "default_requirement_pattern")]
+    pub requirement: String,
+    #[serde(default = "default_scenario_pattern")]
+    pub scenario: String,
+    #[serde(default = "default_task_pattern")]
+    pub task: String,
+    #[serde(default = "default_decision_pattern")]
+    pub decision: String,
+}
+
+fn default_requirement_pattern() -> String {
+    "^req-[0-9]+$".to_string()
+}
+
+fn default_scenario_pattern() -> String {
+    "^sc-[0-9]+$".to_string()
+}
+
+fn default_task_pattern() -> String {
+    "^task-[0-9]+$".to_string()
+}
+
+fn default_decision_pattern() -> String {
+    "^dec-[0-9]+$".to_string()
+}
+
+impl Default for IdPatternConfig {
+    fn default() -> Self {
+        Self {
+            requirement: default_requirement_pattern(),
+            scenario: default_scenario_pattern(),
+            task: default_task_pattern(),
+            decision: default_decision_pattern(),
+        }
+    }
+}
+
+/// Prefix of generated ids, written `<prefix>-<n>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdPrefixConfig {
+    #[serde(default = "default_requirement_prefix")]
+    pub requirement: String,
+    #[serde(default = "default_scenario_prefix")]
+    pub scenario: String,
+    #[serde(default = "default_task_prefix")]
+    pub task: String,
+    #[serde(default = "default_decision_prefix")]
+    pub decision: String,
+}
+
+fn default_requirement_prefix() -> String {
+    "req".to_string()
+}
+
+fn default_scenario_prefix() -> String {
+    "sc".to_string()
+}
+
+fn default_task_prefix() -> String {
+    "task".to_string()
+}
+
+fn default_decision_prefix() -> String {
+    "dec".to_string()
+}
+
+impl Default for IdPrefixConfig {
+    fn default() -> Self {
+        Self {
+            requirement: default_requirement_prefix(),
+            scenario: default_scenario_prefix(),
+            task: default_task_prefix(),
+            decision: default_decision_prefix(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -68,13 +269,127 @@ impl Default for McpConfig {
     }
 }
 
-/// Returns the path to the manifold home directory (~/.manifold)
-pub fn manifold_home() -> Result<PathBuf> {
+/// Profile name that always means ~/.manifold
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Profile given by the global `--profile` flag
+static PROFILE_OVERRIDE: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+/// Resolve this process's manifold through `profile`, ahead of env and the
+/// active profile
+pub fn set_profile(profile: String) {
+    let _ = PROFILE_OVERRIDE.set(profile);
+}
+
+/// Named manifolds, stored in ~/.manifold/profiles.toml
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ProfilesConfig {
+    /// Profile used when neither `--profile` nor $MANIFOLD_HOME is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<String>,
+    /// Profile name to manifold home directory
+    #[serde(default)]
+    pub profiles: BTreeMap<String, PathBuf>,
+}
+
+impl ProfilesConfig {
+    /// Home directory for `name`, where `default` is ~/.manifold
+    pub fn root(&self, name: &str) -> Result<PathBuf> {
+        if name == DEFAULT_PROFILE {
+            return default_manifold_home();
+        }
+        self.profiles.get(name).cloned().with_context(|| {
+            format!(
+                "Unknown profile '{}'. Add it with 'manifold profile add {} <path>'",
+                name, name
+            )
+        })
+    }
+
+    /// Register `name` at `root`, which no other profile may share
+    pub fn add(&mut self, name: &str, root: PathBuf) -> Result<()> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            bail!(
+                "Invalid profile name '{}'. Use letters, digits, '-', or '_'",
+                name
+            );
+        }
+        if name == DEFAULT_PROFILE || self.profiles.contains_key(name) {
+            bail!("Profile '{}' already exists", name);
+        }
+        if root == default_manifold_home()? {
+            bail!("{} is the default manifold", root.display());
+        }
+        if let Some((other, _)) = self.profiles.iter().find(|(_, r)| **r == root) {
+            bail!("{} is already used by profile '{}'", root.display(), other);
+        }
+        self.profiles.insert(name.to_string(), root);
+        Ok(())
+    }
+
+    /// Make `name` the active profile; `default` clears it
+    pub fn activate(&mut self, name: &str) -> Result<()> {
+        self.root(name)?;
+        self.active = (name != DEFAULT_PROFILE).then(|| name.to_string());
+        Ok(())
+    }
+}
+
+/// Location of the profile list, which lives with the default manifold
+pub fn profiles_path() -> Result<PathBuf> {
+    Ok(default_manifold_home()?.join("profiles.toml"))
+}
+
+/// Load the profile list, empty if none has been saved
+pub fn load_profiles() -> Result<ProfilesConfig> {
+    let path = profiles_path()?;
+    if !path.exists() {
+        return Ok(ProfilesConfig::default());
+    }
+    let content = fs::read_to_string(&path).context("Failed to read profiles.toml")?;
+    toml::from_str(&content).context("Failed to parse profiles.toml")
+}
+
+/// Save the profile list
+pub fn save_profiles(profiles: &ProfilesConfig) -> Result<()> {
+    let path = profiles_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create manifold root")?;
+    }
+    let content = toml::to_string_pretty(profiles).context("Failed to serialize profiles")?;
+    fs::write(path, content).context("Failed to write profiles.toml")?;
+    Ok(())
+}
+
+fn default_manifold_home() -> Result<PathBuf> {
     let home = dirs::home_dir().context("Could not determine home directory")?;
     Ok(home.join(".manifold"))
 }
 
+/// Returns the path to the manifold home directory
+///
+/// `--profile` wins, then $MANIFOLD_HOME, then the active profile, then
+/// ~/.manifold.
+pub fn manifold_home() -> Result<PathBuf> {
+    if let Some(name) = PROFILE_OVERRIDE.get() {
+        return load_profiles()?.root(name);
+    }
+    if let Some(home) = std::env::var_os("MANIFOLD_HOME").filter(|h| !h.is_empty()) {
+        return Ok(PathBuf::from(home));
+    }
+    let profiles = load_profiles()?;
+    match &profiles.active {
+        Some(name) => profiles.root(name),
+        None => default_manifold_home(),
+    }
+}
+
 /// Returns paths to all manifold directories
+#[derive(Debug, Clone)]
 pub struct ManifoldPaths {
     pub root: PathBuf,
     pub config: PathBuf,
@@ -104,7 +419,7 @@ impl ManifoldPaths {
         fs::create_dir_all(&self.root).context("Failed to create manifold root")?;
         fs::create_dir_all(&self.db).context("Failed to create db directory")?;
         fs::create_dir_all(&self.schemas).context("Failed to create schemas directory")?;
-        fs::create_dir_all(&self.schemas.join("plugins"))
+        fs::create_dir_all(self.schemas.join("plugins"))
             .context("Failed to create plugins directory")?;
         fs::create_dir_all(&self.exports).context("Failed to create exports directory")?;
         fs::create_dir_all(&self.cache).context("Failed to create cache directory")?;
@@ -115,23 +430,43 @@ impl ManifoldPaths {
     pub fn is_initialized(&self) -> bool {
         self.config.exists() && self.db_file.exists()
     }
+
+    /// Default location of the git sync repository
+    pub fn sync_dir(&self) -> PathBuf {
+        self.root.join("sync")
+    }
+
+    /// Directory holding saved spec templates
+    pub fn templates_dir(&self) -> PathBuf {
+        self.root.join("templates")
+    }
 }
 
 /// Load configuration from disk
 /// Used for default boundary, LLM settings, and MCP server config
 pub fn load_config() -> Result<Config> {
     let paths = ManifoldPaths::new()?;
-    if !paths.config.exists() {
+    load_config_from(&paths.config)
+}
+
+/// Load configuration from a specific config.toml path
+pub fn load_config_from(path: &Path) -> Result<Config> {
+    if !path.exists() {
         return Ok(Config::default());
     }
-    let content = fs::read_to_string(&paths.config).context("Failed to read config.toml")?;
+    let content = fs::read_to_string(path).context("Failed to read config.toml")?;
     toml::from_str(&content).context("Failed to parse config.toml")
 }
 
 /// Save configuration to disk
 pub fn save_config(config: &Config) -> Result<()> {
     let paths = ManifoldPaths::new()?;
+    save_config_to(config, &paths.config)
+}
+
+/// Save configuration to a specific config.toml path
+pub fn save_config_to(config: &Config, path: &Path) -> Result<()> {
     let content = toml::to_string_pretty(config).context("Failed to serialize config")?;
-    fs::write(&paths.config, content).context("Failed to write config.toml")?;
+    fs::write(path, content).context("Failed to write config.toml")?;
     Ok(())
 }
@@ -2,34 +2,300 @@
 //!
 //! Handles all database operations including FTS5 indexing
 
-use anyhow::{Context, Result};
-use rusqlite::{params, Connection};
-
-use crate::collab::{Conflict, ConflictStatus, Review, ReviewStatus, SyncMetadata, SyncStatus};
+use anyhow::{bail, Context, Result};
+use rusqlite::{params, Connection, DatabaseName, OpenFlags};
+#[cfg(test)]
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::collab::{
+    Conflict, ConflictResolution, ConflictStatus, Review, ReviewStatus, SyncMetadata, SyncStatus,
+};
 use crate::config::ManifoldPaths;
-use crate::models::{Boundary, SpecData, SpecRow, WorkflowStage};
+use crate::models::{
+    Boundary, BoundaryVisibility, ColumnMismatch, ManifoldV2, SpecData, SpecRow, StageMismatch,
+    WorkflowStage,
+};
+
+pub mod pool;
+
+pub use pool::DbPool;
+
+/// Database errors callers may want to tell apart
+#[derive(Debug, thiserror::Error)]
+pub enum DbError {
+    #[error("Spec already exists: {0}")]
+    SpecAlreadyExists(String),
+    #[error("Conflict {0} is already resolved")]
+    ConflictAlreadyResolved(String),
+}
+
+/// Sort order for spec listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpecOrder {
+    /// Most recently updated first
+    #[default]
+    UpdatedDesc,
+    /// Most recently created first
+    CreatedDesc,
+    /// Alphabetical by project
+    ProjectAsc,
+    /// Canonical workflow order (requirements first)
+    StageOrder,
+}
+
+impl SpecOrder {
+    /// All orderings, in the order the TUI cycles through them
+    pub fn all() -> [SpecOrder; 4] {
+        [
+            SpecOrder::UpdatedDesc,
+            SpecOrder::CreatedDesc,
+            SpecOrder::ProjectAsc,
+            SpecOrder::StageOrder,
+        ]
+    }
+
+    /// The ordering after this one, wrapping around
+    pub fn next(&self) -> SpecOrder {
+        let all = Self::all();
+        let idx = all.iter().position(|o| o == self).unwrap_or(0);
+        all[(idx + 1) % all.len()]
+    }
+
+    /// SQL ORDER BY clause (without the keywords)
+    fn order_clause(&self) -> String {
+        match self {
+            SpecOrder::UpdatedDesc => "updated_at DESC".to_string(),
+            SpecOrder::CreatedDesc => "created_at DESC".to_string(),
+            SpecOrder::ProjectAsc => "project ASC, updated_at DESC".to_string(),
+            SpecOrder::StageOrder => {
+                let cases: Vec<String> = WorkflowStage::all()
+                    .iter()
+                    .map(|stage| format!("WHEN '{}' THEN {}", stage, stage.order()))
+                    .collect();
+                format!(
+                    "CASE stage {} ELSE {} END ASC, updated_at DESC",
+                    cases.join(" "),
+                    WorkflowStage::all().len()
+                )
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for SpecOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpecOrder::UpdatedDesc => write!(f, "updated"),
+            SpecOrder::CreatedDesc => write!(f, "created"),
+            SpecOrder::ProjectAsc => write!(f, "project"),
+            SpecOrder::StageOrder => write!(f, "stage"),
+        }
+    }
+}
+
+impl std::str::FromStr for SpecOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "updated" => Ok(SpecOrder::UpdatedDesc),
+            "created" => Ok(SpecOrder::CreatedDesc),
+            "project" => Ok(SpecOrder::ProjectAsc),
+            "stage" => Ok(SpecOrder::StageOrder),
+            _ => Err(format!(
+                "Invalid sort order: {}. Use: updated, created, project, stage",
+                s
+            )),
+        }
+    }
+}
+
+/// Outcome of rebuilding the full-text index
+#[derive(Debug, Clone, Default)]
+pub struct ReindexReport {
+    /// Number of specs written to the index
+    pub indexed: usize,
+    /// Ids of rows left out because their JSON doesn't parse
+    pub skipped: Vec<String>,
+}
+
+/// Pagination window for spec listings, applied in SQL
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Page {
+    /// Maximum number of rows to return (all rows when None)
+    pub limit: Option<usize>,
+    /// Number of rows to skip
+    pub offset: usize,
+}
+
+impl Page {
+    fn sql_clause(&self) -> String {
+        // SQLite needs a LIMIT before OFFSET; -1 means no limit
+        let limit = self.limit.map(|l| l as i64).unwrap_or(-1);
+        format!(" LIMIT {} OFFSET {}", limit, self.offset)
+    }
+}
+
+/// Text besides the spec body that full-text search also looks in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchSources {
+    /// Decision title, context, outcome, rationale, and rejected alternatives
+    pub decisions: bool,
+    /// Comments left on the spec's reviews
+    pub reviews: bool,
+}
+
+impl SearchSources {
+    /// Values of the `source` column in `spec_notes_fts` to match
+    fn names(&self) -> Vec<&'static str> {
+        let mut names = Vec::new();
+        if self.decisions {
+            names.push("decisions");
+        }
+        if self.reviews {
+            names.push("reviews");
+        }
+        names
+    }
+}
+
+impl std::str::FromStr for SearchSources {
+    type Err = String;
+
+    /// Parse a comma-separated list such as `decisions,reviews`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut sources = SearchSources::default();
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            match part.to_lowercase().as_str() {
+                "decisions" | "decision" => sources.decisions = true,
+                "reviews" | "review" => sources.reviews = true,
+                _ => {
+                    return Err(format!(
+                        "Invalid search source: {}. Use: decisions, reviews",
+                        part
+                    ))
+                }
+            }
+        }
+        Ok(sources)
+    }
+}
+
+/// Which specs a listing includes, applied in SQL
+#[derive(Debug, Clone, Default)]
+pub struct SpecFilter {
+    pub boundary: Option<Boundary>,
+    pub stage: Option<WorkflowStage>,
+    /// Exact match on the spec's owner
+    pub owner: Option<String>,
+    /// Exact match on the spec's team
+    pub team: Option<String>,
+}
 
 /// Database wrapper
 pub struct Database {
     conn: Connection,
+    /// Number of spec rows whose JSON data has been deserialized
+    #[cfg(test)]
+    spec_rows_loaded: Cell<usize>,
+    /// Opened with SQLITE_OPEN_READ_ONLY; mutating methods refuse to run
+    read_only: bool,
 }
 
 impl Database {
     /// Open an existing database
     pub fn open(paths: &ManifoldPaths) -> Result<Self> {
         let conn = Connection::open(&paths.db_file).context("Failed to open manifold database")?;
-        Ok(Self { conn })
+        Ok(Self {
+            conn,
+            #[cfg(test)]
+            spec_rows_loaded: Cell::new(0),
+            read_only: false,
+        })
+    }
+
+    /// Open an existing database for reading only
+    ///
+    /// Takes no write locks, so readers such as `watch` never block a
+    /// writer in another process. Mutating methods return an error.
+    pub fn open_readonly(paths: &ManifoldPaths) -> Result<Self> {
+        let conn = Connection::open_with_flags(
+            &paths.db_file,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+        .context("Failed to open manifold database read-only")?;
+        Ok(Self {
+            conn,
+            #[cfg(test)]
+            spec_rows_loaded: Cell::new(0),
+            read_only: true,
+        })
+    }
+
+    fn ensure_writable(&self, operation: &str) -> Result<()> {
+        if self.read_only {
+            bail!("Cannot {}: database was opened read-only", operation);
+        }
+        Ok(())
     }
 
     /// Invalidate cached reads to see changes from other processes (e.g., MCP server)
     /// This should be called before reading data that may have been modified externally
     pub fn invalidate_cache(&self) -> Result<()> {
+        // A read-only handle holds no cached write state to flush
+        if self.read_only {
+            return Ok(());
+        }
         // Execute a write statement to force SQLite to release any cached read locks
         // and see the latest committed data from other connections
         self.conn.execute_batch("BEGIN IMMEDIATE; COMMIT;")?;
         Ok(())
     }
 
+    /// Copy the whole database, byte for byte, to `dest`
+    ///
+    /// Uses SQLite's online backup API, so it's safe while other
+    /// connections are writing. Any existing file at `dest` is replaced.
+    pub fn backup(&self, dest: &Path) -> Result<()> {
+        self.conn
+            .backup(DatabaseName::Main, dest, None)
+            .with_context(|| format!("Failed to back up database to {}", dest.display()))
+    }
+
+    /// Replace the database's contents with a backup made by [`Database::backup`]
+    pub fn restore(&mut self, src: &Path) -> Result<()> {
+        self.ensure_writable("restore")?;
+        if !src.is_file() {
+            bail!("Backup file not found: {}", src.display());
+        }
+
+        // Refuse anything that isn't a manifold database before overwriting
+        let source = Connection::open_with_flags(src, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .with_context(|| format!("Failed to open backup {}", src.display()))?;
+        let has_specs: bool = source
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'specs')",
+                [],
+                |row| row.get(0),
+            )
+            .with_context(|| format!("{} is not a SQLite database", src.display()))?;
+        if !has_specs {
+            bail!("{} is not a manifold database backup", src.display());
+        }
+        drop(source);
+
+        self.conn
+            .restore(
+                DatabaseName::Main,
+                src,
+                None::<fn(rusqlite::backup::Progress)>,
+            )
+            .with_context(|| format!("Failed to restore database from {}", src.display()))?;
+        Ok(())
+    }
+
     /// Initialize a new database with schema
     pub fn init(paths: &ManifoldPaths) -> Result<Self> {
         let conn =
@@ -140,6 +406,83 @@ impl Database {
         )
         .context("Failed to create reviews table")?;
 
+        // Create import bases table (merge base per import source)
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS import_bases (
+                spec_id      TEXT NOT NULL,
+                source       TEXT NOT NULL,
+                data         TEXT NOT NULL,
+                imported_at  INTEGER NOT NULL,
+                PRIMARY KEY (spec_id, source)
+            )
+            "#,
+            [],
+        )
+        .context("Failed to create import_bases table")?;
+
+        // Create conflict resolutions table (audit of how each conflict was resolved)
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS conflict_resolutions (
+                conflict_id  TEXT PRIMARY KEY,
+                strategy     TEXT NOT NULL,
+                value        TEXT NOT NULL,
+                resolved_by  TEXT NOT NULL,
+                resolved_at  INTEGER NOT NULL,
+                FOREIGN KEY (conflict_id) REFERENCES conflicts(id)
+            )
+            "#,
+            [],
+        )
+        .context("Failed to create conflict_resolutions table")?;
+
+        // Create spec snapshots table
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS spec_snapshots (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                spec_id     TEXT NOT NULL,
+                label       TEXT,
+                data        TEXT NOT NULL,
+                created_by  TEXT NOT NULL,
+                created_at  INTEGER NOT NULL,
+                FOREIGN KEY (spec_id) REFERENCES specs(id)
+            )
+            "#,
+            [],
+        )
+        .context("Failed to create spec_snapshots table")?;
+
+        // Create agent events table
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS agent_events (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                agent_id    TEXT NOT NULL,
+                kind        TEXT NOT NULL,
+                detail      TEXT NOT NULL,
+                timestamp   INTEGER NOT NULL
+            )
+            "#,
+            [],
+        )
+        .context("Failed to create agent_events table")?;
+
+        // Create FTS5 table for decisions and review comments
+        conn.execute(
+            r#"
+            CREATE VIRTUAL TABLE IF NOT EXISTS spec_notes_fts USING fts5(
+                id UNINDEXED,
+                source UNINDEXED,
+                content,
+                tokenize = 'unicode61'
+            )
+            "#,
+            [],
+        )
+        .context("Failed to create spec_notes_fts table")?;
+
         // Create indexes
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_specs_project ON specs(project)",
@@ -174,12 +517,32 @@ impl Database {
             [],
         )?;
 
-        Ok(Self { conn })
+        Ok(Self {
+            conn,
+            #[cfg(test)]
+            spec_rows_loaded: Cell::new(0),
+            read_only: false,
+        })
     }
 
     /// Insert a new spec
+    ///
+    /// Fails with [`DbError::SpecAlreadyExists`] if the id is taken. The row
+    /// and its search index entry are written in one transaction.
     pub fn insert_spec(&self, spec: &SpecData) -> Result<String> {
+        self.ensure_writable("insert spec")?;
+        self.in_transaction(|db| db.insert_spec_row(spec))
+    }
+
+    fn insert_spec_row(&self, spec: &SpecData) -> Result<String> {
         let id = spec.spec_id.clone();
+        let exists = self
+            .conn
+            .prepare("SELECT 1 FROM specs WHERE id = ?1")?
+            .exists(params![id])?;
+        if exists {
+            return Err(DbError::SpecAlreadyExists(id).into());
+        }
         let data_json = serde_json::to_string(spec).context("Failed to serialize spec")?;
 
         self.conn
@@ -208,12 +571,14 @@ impl Database {
                 params![id, spec.project, spec.boundary.to_string(), spec.name, content],
             )
             .context("Failed to index spec in FTS")?;
+        self.index_decisions(spec)?;
 
         Ok(id)
     }
 
     /// Update an existing spec
     pub fn update_spec(&self, spec: &SpecData) -> Result<()> {
+        self.ensure_writable("update spec")?;
         let id = &spec.spec_id;
         let data_json = serde_json::to_string(spec).context("Failed to serialize spec")?;
 
@@ -247,29 +612,146 @@ impl Database {
                 params![id, spec.project, spec.boundary.to_string(), spec.name, content],
             )
             .context("Failed to update FTS index")?;
+        self.index_decisions(spec)?;
 
         Ok(())
     }
 
+    /// Run `f` in one transaction, committing only if it succeeds
+    ///
+    /// Writes made through `self` inside `f` are rolled back on error. Called
+    /// inside another transaction, `f` joins it, and the outer call decides.
+    pub fn in_transaction<T>(&self, f: impl FnOnce(&Self) -> Result<T>) -> Result<T> {
+        self.ensure_writable("start a transaction")?;
+        if !self.conn.is_autocommit() {
+            return f(self);
+        }
+        let tx = self.conn.unchecked_transaction()?;
+        let value = f(self)?;
+        tx.commit()?;
+        Ok(value)
+    }
+
+    /// Rebuild the FTS index from the specs table
+    /// Repairs drift from interrupted updates or external edits; runs in one
+    /// transaction so search never sees a half-built index
+    pub fn rebuild_fts(&self) -> Result<ReindexReport> {
+        self.ensure_writable("rebuild search index")?;
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM specs_fts", [])
+            .context("Failed to clear FTS index")?;
+        tx.execute("DELETE FROM spec_notes_fts", [])
+            .context("Failed to clear decision and review index")?;
+
+        let mut report = ReindexReport::default();
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO specs_fts (id, project, boundary, name, content) VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            self.each_spec(None, None, SpecOrder::ProjectAsc, Page::default(), |row| {
+                let spec = match row.parse() {
+                    Ok(spec) => spec,
+                    Err(_) => {
+                        report.skipped.push(row.id);
+                        return Ok(());
+                    }
+                };
+                insert
+                    .execute(params![
+                        row.id,
+                        spec.project,
+                        spec.boundary.to_string(),
+                        spec.name,
+                        extract_searchable_content(&spec)
+                    ])
+                    .context("Failed to index spec in FTS")?;
+                self.index_decisions(&spec)?;
+                report.indexed += 1;
+                Ok(())
+            })?;
+        }
+        let reviewed: Vec<String> = self
+            .conn
+            .prepare("SELECT DISTINCT spec_id FROM reviews")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        for spec_id in reviewed {
+            self.index_reviews(&spec_id)?;
+        }
+
+        tx.commit()?;
+        Ok(report)
+    }
+
+    /// Specs whose stage or boundary column disagrees with their JSON body
+    pub fn column_mismatches(&self) -> Result<Vec<ColumnMismatch>> {
+        let mut mismatches = Vec::new();
+        self.each_spec(None, None, SpecOrder::ProjectAsc, Page::default(), |row| {
+            mismatches.extend(row.column_mismatches());
+            Ok(())
+        })?;
+        Ok(mismatches)
+    }
+
+    /// Specs whose stored `stages_completed` disagrees with their stage
+    pub fn stage_mismatches(&self) -> Result<Vec<StageMismatch>> {
+        let mut mismatches = Vec::new();
+        self.each_spec(None, None, SpecOrder::ProjectAsc, Page::default(), |row| {
+            mismatches.extend(row.stage_mismatch());
+            Ok(())
+        })?;
+        Ok(mismatches)
+    }
+
+    /// Rewrite `stages_completed` to the stages before each spec's stage
+    /// Returns the mismatches that were fixed
+    pub fn repair_stages(&self) -> Result<Vec<StageMismatch>> {
+        self.ensure_writable("repair completed stages")?;
+        let mismatches = self.stage_mismatches()?;
+
+        let tx = self.conn.unchecked_transaction()?;
+        for mismatch in &mismatches {
+            let expected = serde_json::to_string(&mismatch.expected)?;
+            tx.execute(
+                "UPDATE specs SET data = json_set(data, '$.stages_completed', json(?2)) WHERE id = ?1",
+                params![mismatch.spec_id, expected],
+            )
+            .with_context(|| format!("Failed to repair {}", mismatch.spec_id))?;
+        }
+        tx.commit()?;
+
+        Ok(mismatches)
+    }
+
+    /// Re-derive stage and boundary columns from each spec's JSON
+    /// Returns the mismatches that were fixed; the JSON is never changed
+    pub fn repair_columns(&self) -> Result<Vec<ColumnMismatch>> {
+        self.ensure_writable("repair spec columns")?;
+        let mismatches = self.column_mismatches()?;
+
+        let tx = self.conn.unchecked_transaction()?;
+        for mismatch in &mismatches {
+            // Column names come from SpecRow::column_mismatches, never input
+            let sql = match mismatch.column.as_str() {
+                "stage" => "UPDATE specs SET stage = ?2 WHERE id = ?1",
+                "boundary" => "UPDATE specs SET boundary = ?2 WHERE id = ?1",
+                other => bail!("Unknown spec column: {}", other),
+            };
+            tx.execute(sql, params![mismatch.spec_id, mismatch.json_value])
+                .with_context(|| format!("Failed to repair {}", mismatch.spec_id))?;
+        }
+        tx.commit()?;
+
+        Ok(mismatches)
+    }
+
     /// Get a spec by ID
     pub fn get_spec(&self, id: &str) -> Result<Option<SpecRow>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, project, boundary, data, stage, updated_at, created_at FROM specs WHERE id = ?1",
         )?;
 
-        let result = stmt.query_row(params![id], |row| {
-            let data_str: String = row.get(3)?;
-            let data: serde_json::Value = serde_json::from_str(&data_str).unwrap_or_default();
-            Ok(SpecRow {
-                id: row.get(0)?,
-                project: row.get(1)?,
-                boundary: row.get(2)?,
-                data,
-                stage: row.get(4)?,
-                updated_at: row.get(5)?,
-                created_at: row.get(6)?,
-            })
-        });
+        let result = stmt.query_row(params![id], |row| self.spec_from_row(row));
 
         match result {
             Ok(row) => Ok(Some(row)),
@@ -278,87 +760,334 @@ impl Database {
         }
     }
 
+    /// Get a spec by ID, deserialized
+    ///
+    /// `Ok(None)` means there is no such spec; a row whose JSON doesn't
+    /// deserialize is an error rather than `None`.
+    pub fn get_spec_data(&self, id: &str) -> Result<Option<SpecData>> {
+        self.get_spec(id)?.map(|row| row.parse()).transpose()
+    }
+
+    /// Read only a spec's updated_at, for cheap change polling
+    pub fn spec_updated_at(&self, id: &str) -> Result<Option<i64>> {
+        let result = self.conn.query_row(
+            "SELECT updated_at FROM specs WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        );
+
+        match result {
+            Ok(ts) => Ok(Some(ts)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Row count and newest updated_at across all specs, for cheap change polling
+    pub fn specs_stamp(&self) -> Result<(i64, i64)> {
+        let stamp = self.conn.query_row(
+            "SELECT COUNT(*), COALESCE(MAX(updated_at), 0) FROM specs",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(stamp)
+    }
+
+    /// List spec ids only, for cheap lookups such as shell completion
+    pub fn list_spec_ids(&self) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare("SELECT id FROM specs ORDER BY id")?;
+        let ids = stmt.query_map([], |row| row.get(0))?;
+        ids.collect::<rusqlite::Result<Vec<String>>>()
+            .map_err(Into::into)
+    }
+
+    /// List distinct project ids
+    pub fn list_project_ids(&self) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT DISTINCT project FROM specs ORDER BY project")?;
+        let ids = stmt.query_map([], |row| row.get(0))?;
+        ids.collect::<rusqlite::Result<Vec<String>>>()
+            .map_err(Into::into)
+    }
+
+    /// Derive the manifold manifest from stored specs
+    /// Aggregates in SQL so spec documents are never loaded
+    pub fn manifold_v2(
+        &self,
+        manifold_id: &str,
+        visibility: &BTreeMap<Boundary, BoundaryVisibility>,
+    ) -> Result<ManifoldV2> {
+        let mut stmt = self.conn.prepare(
+            "SELECT boundary, project, COUNT(*) FROM specs GROUP BY boundary, project ORDER BY boundary, project",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?;
+
+        let mut manifest = ManifoldV2::new(manifold_id.to_string());
+        for row in rows {
+            let (boundary, project, count) = row?;
+            let boundary = boundary
+                .parse::<Boundary>()
+                .map_err(|e| anyhow::anyhow!(e))?;
+            manifest.add_specs(boundary, &project, count as usize, visibility);
+        }
+        Ok(manifest)
+    }
+
     /// List all specs with optional filters
     pub fn list_specs(
         &self,
         boundary: Option<&Boundary>,
         stage: Option<&WorkflowStage>,
+        order: SpecOrder,
+    ) -> Result<Vec<SpecRow>> {
+        let mut specs = Vec::new();
+        self.each_spec(boundary, stage, order, Page::default(), |spec| {
+            specs.push(spec);
+            Ok(())
+        })?;
+        Ok(specs)
+    }
+
+    /// List specs matching every set field of `filter`
+    #[allow(dead_code)]
+    pub fn list_specs_filtered(
+        &self,
+        filter: &SpecFilter,
+        order: SpecOrder,
     ) -> Result<Vec<SpecRow>> {
+        let mut specs = Vec::new();
+        self.each_spec_filtered(filter, order, Page::default(), |spec| {
+            specs.push(spec);
+            Ok(())
+        })?;
+        Ok(specs)
+    }
+
+    /// Visit specs one row at a time, without collecting them
+    /// Used for streaming output of large listings
+    pub fn each_spec<F>(
+        &self,
+        boundary: Option<&Boundary>,
+        stage: Option<&WorkflowStage>,
+        order: SpecOrder,
+        page: Page,
+        f: F,
+    ) -> Result<()>
+    where
+        F: FnMut(SpecRow) -> Result<()>,
+    {
+        let filter = SpecFilter {
+            boundary: boundary.cloned(),
+            stage: stage.cloned(),
+            ..Default::default()
+        };
+        self.each_spec_filtered(&filter, order, page, f)
+    }
+
+    /// Visit specs matching `filter` one row at a time
+    pub fn each_spec_filtered<F>(
+        &self,
+        filter: &SpecFilter,
+        order: SpecOrder,
+        page: Page,
+        mut f: F,
+    ) -> Result<()>
+    where
+        F: FnMut(SpecRow) -> Result<()>,
+    {
         let mut query = String::from(
             "SELECT id, project, boundary, data, stage, updated_at, created_at FROM specs WHERE 1=1",
         );
         let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
 
-        if let Some(b) = boundary {
+        if let Some(b) = &filter.boundary {
             query.push_str(" AND boundary = ?");
             params_vec.push(Box::new(b.to_string()));
         }
 
-        if let Some(s) = stage {
+        if let Some(s) = &filter.stage {
             query.push_str(" AND stage = ?");
             params_vec.push(Box::new(s.to_string()));
         }
 
-        query.push_str(" ORDER BY updated_at DESC");
+        // Owner and team only live in the JSON, so filter on it directly
+        if let Some(owner) = &filter.owner {
+            query.push_str(" AND json_extract(data, '$.owner') = ?");
+            params_vec.push(Box::new(owner.clone()));
+        }
+
+        if let Some(team) = &filter.team {
+            query.push_str(" AND json_extract(data, '$.team') = ?");
+            params_vec.push(Box::new(team.clone()));
+        }
+
+        query.push_str(" ORDER BY ");
+        query.push_str(&order.order_clause());
+        query.push_str(&page.sql_clause());
 
         let mut stmt = self.conn.prepare(&query)?;
         let params_refs: Vec<&dyn rusqlite::ToSql> =
             params_vec.iter().map(|p| p.as_ref()).collect();
 
-        let rows = stmt.query_map(params_refs.as_slice(), |row| {
-            let data_str: String = row.get(3)?;
-            let data: serde_json::Value = serde_json::from_str(&data_str).unwrap_or_default();
-            Ok(SpecRow {
-                id: row.get(0)?,
-                project: row.get(1)?,
-                boundary: row.get(2)?,
-                data,
-                stage: row.get(4)?,
-                updated_at: row.get(5)?,
-                created_at: row.get(6)?,
-            })
-        })?;
+        let rows = stmt.query_map(params_refs.as_slice(), |row| self.spec_from_row(row))?;
 
-        let mut specs = Vec::new();
         for row in rows {
-            specs.push(row?);
+            f(row?)?;
         }
-        Ok(specs)
+        Ok(())
     }
 
     /// Search specs using FTS5 (full-text search)
     /// This is designed for future CLI/TUI search features
     /// Full-text search across spec data using FTS5
+    #[allow(dead_code)]
     pub fn search_specs(&self, query: &str) -> Result<Vec<SpecRow>> {
-        let mut stmt = self.conn.prepare(
+        self.search_specs_filtered(query, None, None)
+    }
+
+    /// Full-text search restricted to a boundary and/or stage
+    /// Filters run in SQL so non-matching rows are never loaded
+    pub fn search_specs_filtered(
+        &self,
+        query: &str,
+        boundary: Option<&Boundary>,
+        stage: Option<&WorkflowStage>,
+    ) -> Result<Vec<SpecRow>> {
+        let mut specs = Vec::new();
+        self.each_search_result(query, boundary, stage, Page::default(), |spec| {
+            specs.push(spec);
+            Ok(())
+        })?;
+        Ok(specs)
+    }
+
+    /// Full-text search that also looks in decisions and/or review comments
+    #[allow(dead_code)]
+    pub fn search_specs_including(
+        &self,
+        query: &str,
+        sources: SearchSources,
+    ) -> Result<Vec<SpecRow>> {
+        let mut specs = Vec::new();
+        self.each_search_result_in(query, None, None, sources, Page::default(), |spec| {
+            specs.push(spec);
+            Ok(())
+        })?;
+        Ok(specs)
+    }
+
+    /// Visit search results one row at a time, best match first
+    pub fn each_search_result<F>(
+        &self,
+        query: &str,
+        boundary: Option<&Boundary>,
+        stage: Option<&WorkflowStage>,
+        page: Page,
+        f: F,
+    ) -> Result<()>
+    where
+        F: FnMut(SpecRow) -> Result<()>,
+    {
+        self.each_search_result_in(query, boundary, stage, SearchSources::default(), page, f)
+    }
+
+    /// Like [`Database::each_search_result`], also matching `sources`
+    ///
+    /// A spec matched in several places appears once, ranked by its best hit.
+    pub fn each_search_result_in<F>(
+        &self,
+        query: &str,
+        boundary: Option<&Boundary>,
+        stage: Option<&WorkflowStage>,
+        sources: SearchSources,
+        page: Page,
+        mut f: F,
+    ) -> Result<()>
+    where
+        F: FnMut(SpecRow) -> Result<()>,
+    {
+        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(query.to_string())];
+        let mut matches = String::from("SELECT id, rank FROM specs_fts WHERE specs_fts MATCH ?");
+        let names = sources.names();
+        if !names.is_empty() && self.has_table("spec_notes_fts")? {
+            let quoted: Vec<String> = names.iter().map(|n| format!("'{}'", n)).collect();
+            matches.push_str(&format!(
+                " UNION ALL SELECT id, rank FROM spec_notes_fts \
+                 WHERE spec_notes_fts MATCH ? AND source IN ({})",
+                quoted.join(", ")
+            ));
+            params_vec.push(Box::new(query.to_string()));
+        }
+
+        let mut sql = format!(
             r#"
             SELECT s.id, s.project, s.boundary, s.data, s.stage, s.updated_at, s.created_at
             FROM specs s
-            INNER JOIN specs_fts f ON s.id = f.id
-            WHERE specs_fts MATCH ?1
-            ORDER BY rank
+            INNER JOIN ({}) f ON s.id = f.id
+            WHERE 1 = 1
             "#,
-        )?;
+            matches
+        );
 
-        let rows = stmt.query_map(params![query], |row| {
-            let data_str: String = row.get(3)?;
-            let data: serde_json::Value = serde_json::from_str(&data_str).unwrap_or_default();
-            Ok(SpecRow {
-                id: row.get(0)?,
-                project: row.get(1)?,
-                boundary: row.get(2)?,
-                data,
-                stage: row.get(4)?,
-                updated_at: row.get(5)?,
-                created_at: row.get(6)?,
-            })
-        })?;
+        if let Some(b) = boundary {
+            sql.push_str(" AND s.boundary = ?");
+            params_vec.push(Box::new(b.to_string()));
+        }
+
+        if let Some(st) = stage {
+            sql.push_str(" AND s.stage = ?");
+            params_vec.push(Box::new(st.to_string()));
+        }
+
+        sql.push_str(" GROUP BY s.id ORDER BY MIN(f.rank)");
+        sql.push_str(&page.sql_clause());
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            params_vec.iter().map(|p| p.as_ref()).collect();
+
+        let rows = stmt.query_map(params_refs.as_slice(), |row| self.spec_from_row(row))?;
 
-        let mut specs = Vec::new();
         for row in rows {
-            specs.push(row?);
+            f(row?)?;
         }
-        Ok(specs)
+        Ok(())
+    }
+
+    /// Map a `specs` row (in standard column order) to a SpecRow
+    ///
+    /// A `data` column that is not JSON at all is an error naming the spec;
+    /// JSON that no longer matches SpecData is left for `SpecRow::parse`.
+    fn spec_from_row(&self, row: &rusqlite::Row) -> rusqlite::Result<SpecRow> {
+        let id: String = row.get(0)?;
+        let data_str: String = row.get(3)?;
+        let data: serde_json::Value = serde_json::from_str(&data_str)
+            .map_err(|e| corrupt_column(3, "data", &format!("spec {}", id), e))?;
+        #[cfg(test)]
+        self.spec_rows_loaded.set(self.spec_rows_loaded.get() + 1);
+        Ok(SpecRow {
+            id,
+            project: row.get(1)?,
+            boundary: row.get(2)?,
+            data,
+            stage: row.get(4)?,
+            updated_at: row.get(5)?,
+            created_at: row.get(6)?,
+        })
+    }
+
+    /// Number of spec rows deserialized by this connection so far
+    /// Instrumentation hook for checking that filters run in SQL
+    #[cfg(test)]
+    fn spec_rows_loaded(&self) -> usize {
+        self.spec_rows_loaded.get()
     }
 
     /// Log a workflow event
@@ -371,6 +1100,7 @@ impl Database {
         timestamp: i64,
         details: Option<&str>,
     ) -> Result<()> {
+        self.ensure_writable("log workflow event")?;
         self.conn
             .execute(
                 r#"
@@ -383,18 +1113,46 @@ impl Database {
         Ok(())
     }
 
-    /// Get workflow events for a spec
-    pub fn get_workflow_events(&self, spec_id: &str) -> Result<Vec<WorkflowEventRow>> {
-        let mut stmt = self.conn.prepare(
-            r#"
-            SELECT id, spec_id, stage, event, actor, timestamp, details
-            FROM workflow_events
-            WHERE spec_id = ?1
-            ORDER BY timestamp DESC
-            "#,
-        )?;
+    /// Get workflow events for a spec, newest first
+    ///
+    /// `since` keeps events at or after the given unix timestamp, `event_prefix`
+    /// keeps events whose type starts with the prefix (e.g. `transition:`), and
+    /// `limit` caps the number of rows returned.
+    pub fn get_workflow_events(
+        &self,
+        spec_id: &str,
+        limit: Option<usize>,
+        since: Option<i64>,
+        event_prefix: Option<&str>,
+    ) -> Result<Vec<WorkflowEventRow>> {
+        let mut query = String::from(
+            "SELECT id, spec_id, stage, event, actor, timestamp, details FROM workflow_events WHERE spec_id = ?",
+        );
+        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(spec_id.to_string())];
 
-        let rows = stmt.query_map(params![spec_id], |row| {
+        if let Some(since) = since {
+            query.push_str(" AND timestamp >= ?");
+            params_vec.push(Box::new(since));
+        }
+
+        if let Some(prefix) = event_prefix {
+            query.push_str(" AND substr(event, 1, length(?)) = ?");
+            params_vec.push(Box::new(prefix.to_string()));
+            params_vec.push(Box::new(prefix.to_string()));
+        }
+
+        query.push_str(" ORDER BY timestamp DESC, id DESC");
+
+        if let Some(limit) = limit {
+            query.push_str(" LIMIT ?");
+            params_vec.push(Box::new(limit as i64));
+        }
+
+        let mut stmt = self.conn.prepare(&query)?;
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            params_vec.iter().map(|p| p.as_ref()).collect();
+
+        let rows = stmt.query_map(params_refs.as_slice(), |row| {
             Ok(WorkflowEventRow {
                 id: row.get(0)?,
                 spec_id: row.get(1)?,
@@ -418,6 +1176,7 @@ impl Database {
     /// Save sync metadata for git-based collaboration
     /// Used by sync push/pull to track sync state
     pub fn save_sync_metadata(&self, metadata: &SyncMetadata) -> Result<()> {
+        self.ensure_writable("save sync metadata")?;
         self.conn
             .execute(
                 r#"
@@ -437,6 +1196,44 @@ impl Database {
         Ok(())
     }
 
+    /// Remember the version of a spec last imported from `source`
+    ///
+    /// The next import from the same source merges against it.
+    pub fn save_import_base(&self, source: &str, spec: &SpecData) -> Result<()> {
+        self.ensure_writable("save import base")?;
+        let data = serde_json::to_string(spec).context("Failed to serialize spec")?;
+        self.conn
+            .execute(
+                r#"
+                INSERT OR REPLACE INTO import_bases (spec_id, source, data, imported_at)
+                VALUES (?1, ?2, ?3, ?4)
+                "#,
+                params![spec.spec_id, source, data, chrono::Utc::now().timestamp()],
+            )
+            .context("Failed to save import base")?;
+        Ok(())
+    }
+
+    /// The version of a spec last imported from `source`, if any
+    pub fn get_import_base(&self, spec_id: &str, source: &str) -> Result<Option<SpecData>> {
+        if !self.has_table("import_bases")? {
+            return Ok(None);
+        }
+        let result = self.conn.query_row(
+            "SELECT data FROM import_bases WHERE spec_id = ?1 AND source = ?2",
+            params![spec_id, source],
+            |row| row.get::<_, String>(0),
+        );
+
+        match result {
+            Ok(data) => Ok(Some(serde_json::from_str(&data).with_context(|| {
+                format!("Import base for {} could not be parsed", spec_id)
+            })?)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// Get sync metadata for a spec
     /// Used by sync status to show last sync info
     pub fn get_sync_metadata(&self, spec_id: &str) -> Result<Option<SyncMetadata>> {
@@ -466,6 +1263,7 @@ impl Database {
 
     /// Save conflict
     pub fn save_conflict(&self, conflict: &Conflict) -> Result<()> {
+        self.ensure_writable("save conflict")?;
         self.conn
             .execute(
                 r#"
@@ -479,7 +1277,7 @@ impl Database {
                     conflict.field_path,
                     serde_json::to_string(&conflict.local_value)?,
                     serde_json::to_string(&conflict.remote_value)?,
-                    conflict.base_value.as_ref().map(|v| serde_json::to_string(v).ok()).flatten(),
+                    conflict.base_value.as_ref().and_then(|v| serde_json::to_string(v).ok()),
                     conflict.detected_at,
                     conflict.status.to_string()
                 ],
@@ -494,22 +1292,7 @@ impl Database {
             "SELECT id, spec_id, field_path, local_value, remote_value, base_value, detected_at, status FROM conflicts WHERE spec_id = ?1 AND status = 'unresolved'",
         )?;
 
-        let rows = stmt.query_map(params![spec_id], |row| {
-            let base_value_str: Option<String> = row.get(5)?;
-            Ok(Conflict {
-                id: row.get(0)?,
-                spec_id: row.get(1)?,
-                field_path: row.get(2)?,
-                local_value: serde_json::from_str(&row.get::<_, String>(3)?).unwrap_or_default(),
-                remote_value: serde_json::from_str(&row.get::<_, String>(4)?).unwrap_or_default(),
-                base_value: base_value_str.and_then(|s| serde_json::from_str(&s).ok()),
-                detected_at: row.get(6)?,
-                status: row
-                    .get::<_, String>(7)?
-                    .parse()
-                    .unwrap_or(ConflictStatus::Unresolved),
-            })
-        })?;
+        let rows = stmt.query_map(params![spec_id], conflict_from_row)?;
 
         let mut conflicts = Vec::new();
         for row in rows {
@@ -518,8 +1301,38 @@ impl Database {
         Ok(conflicts)
     }
 
+    /// Get conflicts of every status across all specs, grouped by spec
+    pub fn get_all_conflicts(&self) -> Result<Vec<Conflict>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, spec_id, field_path, local_value, remote_value, base_value, detected_at, status FROM conflicts ORDER BY spec_id, detected_at",
+        )?;
+
+        let rows = stmt.query_map([], conflict_from_row)?;
+
+        let mut conflicts = Vec::new();
+        for row in rows {
+            conflicts.push(row?);
+        }
+        Ok(conflicts)
+    }
+
+    /// Get a single conflict by id, regardless of its status
+    pub fn get_conflict_by_id(&self, conflict_id: &str) -> Result<Option<Conflict>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, spec_id, field_path, local_value, remote_value, base_value, detected_at, status FROM conflicts WHERE id = ?1",
+        )?;
+
+        match stmt.query_row(params![conflict_id], conflict_from_row) {
+            Ok(conflict) => Ok(Some(conflict)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// Update conflict status
+    #[allow(dead_code)]
     pub fn update_conflict_status(&self, conflict_id: &str, status: &ConflictStatus) -> Result<()> {
+        self.ensure_writable("update conflict status")?;
         self.conn
             .execute(
                 "UPDATE conflicts SET status = ?1 WHERE id = ?2",
@@ -529,8 +1342,258 @@ impl Database {
         Ok(())
     }
 
+    /// Mark a conflict resolved and record how, for audit exports
+    ///
+    /// Fails with [`DbError::ConflictAlreadyResolved`] unless the conflict is
+    /// still unresolved, so a resolution is never applied or recorded twice.
+    pub fn record_conflict_resolution(
+        &self,
+        status: &ConflictStatus,
+        resolution: &ConflictResolution,
+    ) -> Result<()> {
+        self.ensure_writable("record conflict resolution")?;
+        let updated = self
+            .conn
+            .execute(
+                "UPDATE conflicts SET status = ?1 WHERE id = ?2 AND status = ?3",
+                params![
+                    status.to_string(),
+                    resolution.conflict_id,
+                    ConflictStatus::Unresolved.to_string()
+                ],
+            )
+            .context("Failed to update conflict status")?;
+        if updated == 0 {
+            return Err(DbError::ConflictAlreadyResolved(resolution.conflict_id.clone()).into());
+        }
+        self.conn
+            .execute(
+                r#"
+                INSERT OR REPLACE INTO conflict_resolutions
+                (conflict_id, strategy, value, resolved_by, resolved_at)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                "#,
+                params![
+                    resolution.conflict_id,
+                    resolution.strategy,
+                    serde_json::to_string(&resolution.value)?,
+                    resolution.resolved_by,
+                    resolution.resolved_at
+                ],
+            )
+            .context("Failed to record conflict resolution")?;
+        Ok(())
+    }
+
+    /// Resolved conflicts for a spec, oldest first, with how each was
+    /// resolved when that was recorded
+    pub fn get_resolved_conflicts(
+        &self,
+        spec_id: &str,
+    ) -> Result<Vec<(Conflict, Option<ConflictResolution>)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, spec_id, field_path, local_value, remote_value, base_value, detected_at, status FROM conflicts WHERE spec_id = ?1 AND status != 'unresolved' ORDER BY detected_at",
+        )?;
+        let conflicts = stmt
+            .query_map(params![spec_id], conflict_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let has_resolutions = self.has_table("conflict_resolutions")?;
+
+        let mut resolved = Vec::new();
+        for conflict in conflicts {
+            let resolution = if has_resolutions {
+                self.get_conflict_resolution(&conflict.id)?
+            } else {
+                None
+            };
+            resolved.push((conflict, resolution));
+        }
+        Ok(resolved)
+    }
+
+    fn get_conflict_resolution(&self, conflict_id: &str) -> Result<Option<ConflictResolution>> {
+        let result = self.conn.query_row(
+            "SELECT conflict_id, strategy, value, resolved_by, resolved_at FROM conflict_resolutions WHERE conflict_id = ?1",
+            params![conflict_id],
+            |row| {
+                let conflict_id: String = row.get(0)?;
+                let value: String = row.get(2)?;
+                let value = serde_json::from_str(&value).map_err(|e| {
+                    corrupt_column(2, "value", &format!("conflict resolution {}", conflict_id), e)
+                })?;
+                Ok(ConflictResolution {
+                    conflict_id,
+                    strategy: row.get(1)?,
+                    value,
+                    resolved_by: row.get(3)?,
+                    resolved_at: row.get(4)?,
+                })
+            },
+        );
+
+        match result {
+            Ok(resolution) => Ok(Some(resolution)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Store an immutable copy of `spec` as it is now, returning its id
+    pub fn save_snapshot(&self, spec: &SpecData, label: Option<&str>, actor: &str) -> Result<i64> {
+        self.ensure_writable("save snapshot")?;
+        let data = serde_json::to_string(spec).context("Failed to serialize spec")?;
+        self.conn
+            .execute(
+                r#"
+                INSERT INTO spec_snapshots (spec_id, label, data, created_by, created_at)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                "#,
+                params![
+                    spec.spec_id,
+                    label,
+                    data,
+                    actor,
+                    chrono::Utc::now().timestamp()
+                ],
+            )
+            .context("Failed to save snapshot")?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Snapshots of a spec, oldest first
+    pub fn get_snapshots(&self, spec_id: &str) -> Result<Vec<SpecSnapshot>> {
+        if !self.has_table("spec_snapshots")? {
+            return Ok(Vec::new());
+        }
+        let mut stmt = self.conn.prepare(
+            "SELECT id, spec_id, label, data, created_by, created_at FROM spec_snapshots WHERE spec_id = ?1 ORDER BY id",
+        )?;
+        let rows = stmt
+            .query_map(params![spec_id], snapshot_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows.into_iter().map(parse_snapshot).collect()
+    }
+
+    /// One snapshot of a spec
+    pub fn get_snapshot(&self, spec_id: &str, snapshot_id: i64) -> Result<Option<SpecSnapshot>> {
+        if !self.has_table("spec_snapshots")? {
+            return Ok(None);
+        }
+        let result = self.conn.query_row(
+            "SELECT id, spec_id, label, data, created_by, created_at FROM spec_snapshots WHERE spec_id = ?1 AND id = ?2",
+            params![spec_id, snapshot_id],
+            snapshot_row,
+        );
+
+        match result {
+            Ok(row) => Ok(Some(parse_snapshot(row)?)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Record something a background agent did
+    pub fn log_agent_event(
+        &self,
+        agent_id: &str,
+        kind: &str,
+        detail: &str,
+        timestamp: i64,
+    ) -> Result<()> {
+        self.ensure_writable("log agent event")?;
+        self.conn
+            .execute(
+                r#"
+                INSERT INTO agent_events (agent_id, kind, detail, timestamp)
+                VALUES (?1, ?2, ?3, ?4)
+                "#,
+                params![agent_id, kind, detail, timestamp],
+            )
+            .context("Failed to log agent event")?;
+        Ok(())
+    }
+
+    /// Agent events, newest first
+    ///
+    /// `agent_id` keeps one agent's events and `since` keeps events at or
+    /// after the given unix timestamp.
+    pub fn get_agent_events(
+        &self,
+        agent_id: Option<&str>,
+        since: Option<i64>,
+    ) -> Result<Vec<AgentEventRow>> {
+        if !self.has_table("agent_events")? {
+            return Ok(Vec::new());
+        }
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT id, agent_id, kind, detail, timestamp FROM agent_events
+            WHERE (?1 IS NULL OR agent_id = ?1) AND (?2 IS NULL OR timestamp >= ?2)
+            ORDER BY timestamp DESC, id DESC
+            "#,
+        )?;
+        let rows = stmt
+            .query_map(params![agent_id, since], |row| {
+                Ok(AgentEventRow {
+                    id: row.get(0)?,
+                    agent_id: row.get(1)?,
+                    kind: row.get(2)?,
+                    detail: row.get(3)?,
+                    timestamp: row.get(4)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+
+    /// Replace one spec's entry for `source` in the notes index
+    fn index_note(&self, spec_id: &str, source: &str, content: &str) -> Result<()> {
+        self.conn
+            .execute(
+                "DELETE FROM spec_notes_fts WHERE id = ?1 AND source = ?2",
+                params![spec_id, source],
+            )
+            .context("Failed to delete from notes index")?;
+        if !content.is_empty() {
+            self.conn
+                .execute(
+                    "INSERT INTO spec_notes_fts (id, source, content) VALUES (?1, ?2, ?3)",
+                    params![spec_id, source, content],
+                )
+                .context("Failed to index notes")?;
+        }
+        Ok(())
+    }
+
+    fn index_decisions(&self, spec: &SpecData) -> Result<()> {
+        self.index_note(&spec.spec_id, "decisions", &extract_decision_content(spec))
+    }
+
+    fn index_reviews(&self, spec_id: &str) -> Result<()> {
+        let comments: Vec<String> = self
+            .get_reviews(spec_id)?
+            .into_iter()
+            .filter_map(|review| review.comment)
+            .collect();
+        self.index_note(spec_id, "reviews", &comments.join(" "))
+    }
+
+    /// Whether the schema has `name`
+    ///
+    /// Databases created by an older manifold get newer tables from
+    /// `init --repair`; until then, reads treat a missing table as empty.
+    fn has_table(&self, name: &str) -> Result<bool> {
+        Ok(self.conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+            params![name],
+            |row| row.get(0),
+        )?)
+    }
+
     /// Save review
     pub fn save_review(&self, review: &Review) -> Result<()> {
+        self.ensure_writable("save review")?;
         self.conn
             .execute(
                 r#"
@@ -550,6 +1613,7 @@ impl Database {
                 ],
             )
             .context("Failed to save review")?;
+        self.index_reviews(&review.spec_id)?;
         Ok(())
     }
 
@@ -582,6 +1646,35 @@ impl Database {
         Ok(reviews)
     }
 
+    /// Get reviews across all specs, most recently requested first
+    pub fn get_all_reviews(&self) -> Result<Vec<Review>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, spec_id, requester, reviewer, status, comment, requested_at, reviewed_at FROM reviews ORDER BY requested_at DESC",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(Review {
+                id: row.get(0)?,
+                spec_id: row.get(1)?,
+                requester: row.get(2)?,
+                reviewer: row.get(3)?,
+                status: row
+                    .get::<_, String>(4)?
+                    .parse()
+                    .unwrap_or(ReviewStatus::Pending),
+                comment: row.get(5)?,
+                requested_at: row.get(6)?,
+                reviewed_at: row.get(7)?,
+            })
+        })?;
+
+        let mut reviews = Vec::new();
+        for row in rows {
+            reviews.push(row?);
+        }
+        Ok(reviews)
+    }
+
     /// Get review by ID
     pub fn get_review(&self, review_id: &str) -> Result<Option<Review>> {
         let mut stmt = self.conn.prepare(
@@ -612,6 +1705,88 @@ impl Database {
     }
 }
 
+/// Map a `conflicts` row (in standard column order) to a Conflict
+fn conflict_from_row(row: &rusqlite::Row) -> rusqlite::Result<Conflict> {
+    let id: String = row.get(0)?;
+    let owner = format!("conflict {}", id);
+    let json_column = |index: usize, column: &str, text: &str| {
+        serde_json::from_str(text).map_err(|e| corrupt_column(index, column, &owner, e))
+    };
+    let local_value = json_column(3, "local_value", &row.get::<_, String>(3)?)?;
+    let remote_value = json_column(4, "remote_value", &row.get::<_, String>(4)?)?;
+    let base_value = match row.get::<_, Option<String>>(5)? {
+        Some(text) => Some(json_column(5, "base_value", &text)?),
+        None => None,
+    };
+    let status: ConflictStatus = row
+        .get::<_, String>(7)?
+        .parse()
+        .map_err(|e: String| corrupt_column(7, "status", &owner, e))?;
+    Ok(Conflict {
+        id,
+        spec_id: row.get(1)?,
+        field_path: row.get(2)?,
+        local_value,
+        remote_value,
+        base_value,
+        detected_at: row.get(6)?,
+        status,
+    })
+}
+
+/// Conversion error for a stored column that no longer decodes
+fn corrupt_column(
+    index: usize,
+    column: &str,
+    owner: &str,
+    err: impl std::fmt::Display,
+) -> rusqlite::Error {
+    rusqlite::Error::FromSqlConversionFailure(
+        index,
+        rusqlite::types::Type::Text,
+        format!("corrupt column {} for {}: {}", column, owner, err).into(),
+    )
+}
+
+/// A frozen copy of a spec, taken by `manifold snapshot create`
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct SpecSnapshot {
+    pub id: i64,
+    pub spec_id: String,
+    pub label: Option<String>,
+    pub created_by: String,
+    pub created_at: i64,
+    pub data: SpecData,
+}
+
+type SnapshotRow = (i64, String, Option<String>, String, String, i64);
+
+fn snapshot_row(row: &rusqlite::Row) -> rusqlite::Result<SnapshotRow> {
+    Ok((
+        row.get(0)?,
+        row.get(1)?,
+        row.get(2)?,
+        row.get(3)?,
+        row.get(4)?,
+        row.get(5)?,
+    ))
+}
+
+fn parse_snapshot(row: SnapshotRow) -> Result<SpecSnapshot> {
+    let (id, spec_id, label, data, created_by, created_at) = row;
+    let data = serde_json::from_str(&data)
+        .with_context(|| format!("Snapshot {} of {} could not be parsed", id, spec_id))?;
+    Ok(SpecSnapshot {
+        id,
+        spec_id,
+        label,
+        created_by,
+        created_at,
+        data,
+    })
+}
+
 /// Database row for workflow events
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -625,6 +1800,17 @@ pub struct WorkflowEventRow {
     pub details: Option<String>,
 }
 
+/// Database row for background agent events
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct AgentEventRow {
+    pub id: i64,
+    pub agent_id: String,
+    pub kind: String,
+    pub detail: String,
+    pub timestamp: i64,
+}
+
 /// Generate a human-readable spec ID like "auric-raptor-torque"
 pub fn generate_spec_id(project: &str) -> String {
     let adjectives = [
@@ -656,6 +1842,8 @@ pub fn generate_spec_id(project: &str) -> String {
 fn extract_searchable_content(spec: &SpecData) -> String {
     let mut content = Vec::new();
     content.push(spec.name.clone());
+    content.extend(spec.owner.clone());
+    content.extend(spec.team.clone());
 
     for req in &spec.requirements {
         content.push(req.title.clone());
@@ -675,13 +1863,87 @@ fn extract_searchable_content(spec: &SpecData) -> String {
     for task in &spec.tasks {
         content.push(task.title.clone());
         content.push(task.description.clone());
+        content.extend(task.acceptance.clone());
     }
 
     for decision in &spec.decisions {
         content.push(decision.title.clone());
         content.push(decision.context.clone());
         content.push(decision.decision.clone());
+        content.extend(decision.alternatives_rejected.clone());
     }
 
     content.join(" ")
 }
+
+/// Everything a spec's decisions say, for `search --include decisions`
+fn extract_decision_content(spec: &SpecData) -> String {
+    let mut content = Vec::new();
+    for decision in &spec.decisions {
+        content.push(decision.title.clone());
+        content.push(decision.context.clone());
+        content.push(decision.decision.clone());
+        content.push(decision.rationale.clone());
+        content.extend(decision.alternatives_rejected.clone());
+    }
+    content.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, Database) {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = ManifoldPaths {
+            root: temp_dir.path().to_path_buf(),
+            config: temp_dir.path().join("config.toml"),
+            db: temp_dir.path().join("db"),
+            db_file: temp_dir.path().join("db/manifold.db"),
+            schemas: temp_dir.path().join("schemas"),
+            exports: temp_dir.path().join("exports"),
+            cache: temp_dir.path().join("cache"),
+        };
+        std::fs::create_dir_all(&paths.db).unwrap();
+        let db = Database::init(&paths).unwrap();
+        (temp_dir, db)
+    }
+
+    #[test]
+    fn test_filtered_search_loads_only_matching_rows() {
+        let (_temp, db) = setup();
+
+        let boundaries = [Boundary::Personal, Boundary::Work, Boundary::Company];
+        for i in 0..300 {
+            let mut spec = SpecData::new(
+                format!("spec-{}", i),
+                "search-project".to_string(),
+                format!("Telemetry pipeline {}", i),
+                boundaries[i % boundaries.len()].clone(),
+            );
+            if i % 2 == 0 {
+                spec.stage = WorkflowStage::Design;
+            }
+            db.insert_spec(&spec).unwrap();
+        }
+
+        let before = db.spec_rows_loaded();
+        let results = db
+            .search_specs_filtered("telemetry", Some(&Boundary::Work), None)
+            .unwrap();
+        assert_eq!(results.len(), 100);
+        assert_eq!(db.spec_rows_loaded() - before, results.len());
+
+        let before = db.spec_rows_loaded();
+        let results = db
+            .search_specs_filtered(
+                "telemetry",
+                Some(&Boundary::Work),
+                Some(&WorkflowStage::Design),
+            )
+            .unwrap();
+        assert_eq!(results.len(), 50);
+        assert_eq!(db.spec_rows_loaded() - before, results.len());
+    }
+}
@@ -0,0 +1,112 @@
+//! Shared pool of database connections for long-running agents
+//!
+//! Agents tick on their own threads; rather than each opening a fresh
+//! connection per tick, they borrow one from a bounded pool and hand it back
+//! when the tick ends. Pooled connections wait on a busy database instead of
+//! failing immediately, so agents writing at the same moment take turns.
+
+use anyhow::Result;
+use std::ops::Deref;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+use super::Database;
+use crate::config::ManifoldPaths;
+
+/// How long a pooled connection waits for another writer's lock
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A bounded set of read-write connections to one manifold database
+pub struct DbPool {
+    paths: ManifoldPaths,
+    max_size: usize,
+    state: Mutex<PoolState>,
+    returned: Condvar,
+}
+
+#[derive(Default)]
+struct PoolState {
+    idle: Vec<Database>,
+    /// Connections opened so far, idle or checked out
+    open: usize,
+}
+
+impl DbPool {
+    /// A pool of at most `max_size` connections (at least one), opened lazily
+    pub fn new(paths: &ManifoldPaths, max_size: usize) -> Arc<Self> {
+        Arc::new(Self {
+            paths: paths.clone(),
+            max_size: max_size.max(1),
+            state: Mutex::new(PoolState::default()),
+            returned: Condvar::new(),
+        })
+    }
+
+    /// Borrow a connection, blocking while all of them are checked out
+    pub fn get(self: &Arc<Self>) -> Result<PooledDatabase> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            if let Some(db) = state.idle.pop() {
+                return Ok(self.wrap(db));
+            }
+            if state.open < self.max_size {
+                state.open += 1;
+                drop(state);
+                return match self.connect() {
+                    Ok(db) => Ok(self.wrap(db)),
+                    Err(e) => {
+                        self.state.lock().unwrap_or_else(|e| e.into_inner()).open -= 1;
+                        self.returned.notify_one();
+                        Err(e)
+                    }
+                };
+            }
+            state = self.returned.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Connections opened so far
+    #[allow(dead_code)]
+    pub fn size(&self) -> usize {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).open
+    }
+
+    fn connect(&self) -> Result<Database> {
+        let db = Database::open(&self.paths)?;
+        db.conn.busy_timeout(BUSY_TIMEOUT)?;
+        Ok(db)
+    }
+
+    fn wrap(self: &Arc<Self>, db: Database) -> PooledDatabase {
+        PooledDatabase {
+            db: Some(db),
+            pool: Arc::clone(self),
+        }
+    }
+}
+
+/// A connection checked out of a [`DbPool`], returned when dropped
+pub struct PooledDatabase {
+    db: Option<Database>,
+    pool: Arc<DbPool>,
+}
+
+impl Deref for PooledDatabase {
+    type Target = Database;
+
+    fn deref(&self) -> &Database {
+        self.db
+            .as_ref()
+            .expect("pooled connection is present until drop")
+    }
+}
+
+impl Drop for PooledDatabase {
+    fn drop(&mut self) {
+        if let Some(db) = self.db.take() {
+            let mut state = self.pool.state.lock().unwrap_or_else(|e| e.into_inner());
+            state.idle.push(db);
+            self.pool.returned.notify_one();
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! AsciiDoc rendering for specs
+//!
+//! Mirrors the Markdown layout for toolchains such as Antora or DocBook
+//! pipelines: one section per requirement, decision, and task, with
+//! requirement priorities shown as admonitions.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use super::ensure_parent_dir;
+use crate::models::{Decision, Priority, Requirement, SpecData, Task, WorkflowStage};
+
+/// AsciiDoc renderer for specs
+pub struct AsciiDocRenderer;
+
+impl AsciiDocRenderer {
+    /// Render a spec as a standalone AsciiDoc document
+    pub fn render_spec(spec: &SpecData) -> String {
+        let mut doc = format!("= {}\n", spec.name);
+        doc.push_str(":toc:\n\n");
+        doc.push_str(&Self::render_body(spec, 1));
+        doc.push_str(&format!(
+            "'''\n\n_Generated by Manifold on {}_\n",
+            chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+        ));
+        doc
+    }
+
+    /// Render several specs as one document, a level-1 section per spec
+    pub fn render_multi(specs: &[SpecData]) -> String {
+        let mut doc = String::from("= Manifold Specification Collection\n:toc:\n\n");
+        doc.push_str(&format!(
+            "Generated on {}\n\n",
+            chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+        ));
+        for spec in specs {
+            doc.push_str(&format!("== {}\n\n", spec.name));
+            doc.push_str(&Self::render_body(spec, 2));
+            doc.push_str("<<<\n\n");
+        }
+        doc
+    }
+
+    /// Metadata and sections for one spec, with top sections at `level`
+    fn render_body(spec: &SpecData, level: usize) -> String {
+        let section =
+            |depth: usize, title: &str| format!("{} {}\n\n", "=".repeat(level + depth), title);
+        let mut doc = String::new();
+
+        doc.push_str(&format!("Project:: {}\n", spec.project));
+        doc.push_str(&format!("Spec ID:: {}\n", spec.spec_id));
+        doc.push_str(&format!("Boundary:: {}\n", spec.boundary));
+        doc.push_str(&format!("Stage:: {}\n\n", spec.stage));
+
+        doc.push_str(&section(1, "Workflow Status"));
+        doc.push_str(&Self::render_workflow_status(spec));
+
+        doc.push_str(&section(1, "Requirements"));
+        if spec.requirements.is_empty() {
+            doc.push_str("_No requirements defined yet._\n\n");
+        }
+        for req in &spec.requirements {
+            doc.push_str(&section(2, &format!("{} - {}", req.id, req.title)));
+            doc.push_str(&Self::render_requirement(req, &section));
+        }
+
+        doc.push_str(&section(1, "Design Decisions"));
+        if spec.decisions.is_empty() {
+            doc.push_str("_No design decisions documented yet._\n\n");
+        }
+        for decision in &spec.decisions {
+            doc.push_str(&section(
+                2,
+                &format!("{} - {}", decision.id, decision.title),
+            ));
+            doc.push_str(&Self::render_decision(decision));
+        }
+
+        doc.push_str(&section(1, "Tasks"));
+        if spec.tasks.is_empty() {
+            doc.push_str("_No tasks defined yet._\n\n");
+        }
+        for task in &spec.tasks {
+            doc.push_str(&section(2, &format!("{} - {}", task.id, task.title)));
+            doc.push_str(&Self::render_task(task));
+        }
+
+        doc
+    }
+
+    fn render_workflow_status(spec: &SpecData) -> String {
+        let stages: Vec<String> = WorkflowStage::all()
+            .iter()
+            .map(|stage| {
+                if *stage == spec.stage {
+                    format!("*{}*", stage.to_string().to_uppercase())
+                } else if spec.completed_stages().contains(stage) {
+                    format!("✓ {}", stage)
+                } else {
+                    stage.to_string()
+                }
+            })
+            .collect();
+
+        let mut doc = format!("{}\n\n", stages.join(" → "));
+        doc.push_str(&format!("* Requirements: {}\n", spec.requirements.len()));
+        doc.push_str(&format!("* Tasks: {}\n", spec.tasks.len()));
+        doc.push_str(&format!("* Decisions: {}\n\n", spec.decisions.len()));
+        doc
+    }
+
+    fn render_requirement(req: &Requirement, section: &dyn Fn(usize, &str) -> String) -> String {
+        let mut doc = String::new();
+
+        doc.push_str(&format!(
+            "{}: Priority: {}\n\n",
+            Self::priority_admonition(&req.priority),
+            req.priority
+        ));
+        doc.push_str(&format!("Capability:: {}\n", req.capability));
+        if !req.tags.is_empty() {
+            doc.push_str(&format!("Tags:: {}\n", req.tags.join(", ")));
+        }
+        doc.push('\n');
+
+        doc.push_str(&format!("[quote]\n____\n{}\n____\n\n", req.shall));
+
+        if let Some(rationale) = &req.rationale {
+            doc.push_str(&format!(".Rationale\n{}\n\n", rationale));
+        }
+
+        if !req.scenarios.is_empty() {
+            doc.push_str(&section(3, "Scenarios"));
+            for scenario in &req.scenarios {
+                doc.push_str(&format!("*{}* ({})\n\n", scenario.name, scenario.id));
+                doc.push_str("GIVEN::\n");
+                for given in &scenario.given {
+                    doc.push_str(&format!("* {}\n", given));
+                }
+                doc.push_str(&format!("WHEN:: {}\n", scenario.when));
+                doc.push_str("THEN::\n");
+                for then in &scenario.then {
+                    doc.push_str(&format!("* {}\n", then));
+                }
+                if !scenario.edge_cases.is_empty() {
+                    doc.push_str("EDGE CASES::\n");
+                    for edge in &scenario.edge_cases {
+                        doc.push_str(&format!("* {}\n", edge));
+                    }
+                }
+                doc.push('\n');
+            }
+        }
+
+        doc
+    }
+
+    fn render_decision(decision: &Decision) -> String {
+        let mut doc = String::new();
+
+        doc.push_str(&format!("Date:: {}\n\n", decision.date));
+        doc.push_str(&format!(".Context\n{}\n\n", decision.context));
+        doc.push_str(&format!(
+            ".Decision\n[quote]\n____\n{}\n____\n\n",
+            decision.decision
+        ));
+        doc.push_str(&format!(".Rationale\n{}\n\n", decision.rationale));
+
+        if !decision.alternatives_rejected.is_empty() {
+            doc.push_str(".Alternatives Considered\n");
+            for alt in &decision.alternatives_rejected {
+                doc.push_str(&format!("* {}\n", alt));
+            }
+            doc.push('\n');
+        }
+
+        doc
+    }
+
+    fn render_task(task: &Task) -> String {
+        let mut doc = String::new();
+
+        doc.push_str(&format!("Status:: {}\n", task.status));
+        if !task.requirement_ids.is_empty() {
+            doc.push_str(&format!(
+                "Traces to:: {}\n",
+                task.requirement_ids.join(", ")
+            ));
+        }
+        if !task.scenario_ids.is_empty() {
+            doc.push_str(&format!("Scenarios:: {}\n", task.scenario_ids.join(", ")));
+        }
+        if let Some(assignee) = &task.assignee {
+            doc.push_str(&format!("Assignee:: {}\n", assignee));
+        }
+        doc.push('\n');
+
+        doc.push_str(&format!("{}\n\n", task.description));
+
+        if !task.acceptance.is_empty() {
+            doc.push_str(".Acceptance Criteria\n");
+            for criteria in &task.acceptance {
+                doc.push_str(&format!("* {}\n", criteria));
+            }
+            doc.push('\n');
+        }
+
+        doc
+    }
+
+    /// Admonition label for a priority: must-haves stand out the most
+    fn priority_admonition(priority: &Priority) -> &'static str {
+        match priority {
+            Priority::Must => "IMPORTANT",
+            Priority::Should => "NOTE",
+            Priority::Could => "TIP",
+            Priority::Wont => "CAUTION",
+        }
+    }
+
+    /// Export a single spec to an AsciiDoc file
+    pub fn export_to_file(spec: &SpecData, output_path: &Path) -> Result<()> {
+        ensure_parent_dir(output_path)?;
+        fs::write(output_path, Self::render_spec(spec)).context("Failed to write AsciiDoc file")?;
+        Ok(())
+    }
+
+    /// Export multiple specs to a single AsciiDoc file
+    pub fn export_multi(specs: &[SpecData], output_path: &Path) -> Result<()> {
+        ensure_parent_dir(output_path)?;
+        fs::write(output_path, Self::render_multi(specs))
+            .context("Failed to write multi-spec AsciiDoc file")?;
+        Ok(())
+    }
+}
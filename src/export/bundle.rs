@@ -0,0 +1,113 @@
+//! Publishable JSON bundles
+//!
+//! A bundle is what leaves the machine when a manifold is published: the
+//! manifest restricted to public boundaries, plus the public specs in them.
+//! Private specs are never bundled, even inside a public boundary.
+
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// First two bytes of every gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+use super::ensure_parent_dir;
+use crate::models::{BoundaryVisibility, ManifoldV2, SpecData, Visibility};
+
+/// Manifest and specs ready to publish
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bundle {
+    /// Manifest covering only the bundled specs
+    pub manifest: ManifoldV2,
+    pub specs: Vec<SpecData>,
+}
+
+/// Result of building a bundle
+#[derive(Debug, Clone)]
+pub struct BundleBuild {
+    pub bundle: Bundle,
+    /// Ids of private specs left out of public boundaries
+    pub skipped: Vec<String>,
+}
+
+/// Build a bundle of the public specs in public boundaries
+///
+/// Boundary visibility is taken from `manifest`. Specs in boundaries that
+/// are not public are out of scope and not reported as skipped.
+pub fn build_public_bundle(manifest: &ManifoldV2, specs: Vec<SpecData>) -> BundleBuild {
+    let visibility: BTreeMap<_, _> = manifest
+        .boundaries
+        .iter()
+        .map(|(boundary, config)| (boundary.clone(), config.visibility))
+        .collect();
+
+    let mut bundle = Bundle {
+        manifest: ManifoldV2::new(manifest.manifold_id.clone()),
+        specs: Vec::new(),
+    };
+    let mut skipped = Vec::new();
+
+    for spec in specs {
+        if visibility.get(&spec.boundary) != Some(&BoundaryVisibility::Public) {
+            continue;
+        }
+        if spec.visibility != Visibility::Public {
+            skipped.push(spec.spec_id);
+            continue;
+        }
+        bundle
+            .manifest
+            .add_specs(spec.boundary.clone(), &spec.project, 1, &visibility);
+        bundle.specs.push(spec);
+    }
+
+    BundleBuild { bundle, skipped }
+}
+
+/// Write a bundle as pretty-printed JSON
+pub fn write_bundle(bundle: &Bundle, output_path: &Path) -> Result<()> {
+    ensure_parent_dir(output_path)?;
+    let json = serde_json::to_string_pretty(bundle)?;
+    fs::write(output_path, json)
+        .with_context(|| format!("Failed to write bundle to {}", output_path.display()))?;
+    Ok(())
+}
+
+/// Write a bundle as gzip-compressed compact JSON, e.g. `bundle.json.gz`
+pub fn write_bundle_gzip(bundle: &Bundle, output_path: &Path) -> Result<()> {
+    ensure_parent_dir(output_path)?;
+    let file = fs::File::create(output_path)
+        .with_context(|| format!("Failed to write bundle to {}", output_path.display()))?;
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    serde_json::to_writer(&mut encoder, bundle)?;
+    encoder
+        .finish()
+        .and_then(|mut file| file.flush())
+        .with_context(|| format!("Failed to write bundle to {}", output_path.display()))?;
+    Ok(())
+}
+
+/// Read a bundle written by [`write_bundle`] or [`write_bundle_gzip`]
+///
+/// Compression is detected from the gzip magic bytes, not the file name.
+pub fn read_bundle(path: &Path) -> Result<Bundle> {
+    let bytes =
+        fs::read(path).with_context(|| format!("Failed to read bundle from {}", path.display()))?;
+    let json = if bytes.starts_with(&GZIP_MAGIC) {
+        let mut json = Vec::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_end(&mut json)
+            .with_context(|| format!("Failed to decompress bundle {}", path.display()))?;
+        json
+    } else {
+        bytes
+    };
+    serde_json::from_slice(&json)
+        .with_context(|| format!("{} is not a manifold bundle", path.display()))
+}
@@ -0,0 +1,134 @@
+//! Graphviz DOT rendering for specs
+//!
+//! Draws requirements and the tasks that implement them, with an edge from
+//! each requirement to each of its tasks. Render with e.g.
+//! `dot -Tsvg spec.dot -o spec.svg`.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use super::ensure_parent_dir;
+use crate::models::SpecData;
+
+/// DOT renderer for specs
+pub struct DotRenderer;
+
+impl DotRenderer {
+    /// Render a spec as a standalone digraph
+    pub fn render_spec(spec: &SpecData) -> String {
+        let mut dot = format!("digraph {} {{\n", quote(&spec.spec_id));
+        dot.push_str(&Self::header(&spec.name));
+        dot.push_str(&Self::render_body(spec, "", "  "));
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Render several specs as one digraph with a cluster per spec
+    ///
+    /// Node ids are prefixed with the spec id, since requirement and task
+    /// ids repeat across specs.
+    pub fn render_multi(specs: &[SpecData]) -> String {
+        let mut dot = String::from("digraph \"manifold\" {\n");
+        dot.push_str(&Self::header("Manifold specs"));
+        for spec in specs {
+            dot.push_str(&format!(
+                "\n  subgraph {} {{\n",
+                quote(&format!("cluster_{}", spec.spec_id))
+            ));
+            dot.push_str(&format!("    label={};\n", quote(&spec.name)));
+            dot.push_str(&Self::render_body(
+                spec,
+                &format!("{}/", spec.spec_id),
+                "    ",
+            ));
+            dot.push_str("  }\n");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn header(label: &str) -> String {
+        format!(
+            "  label={};\n  rankdir=LR;\n  node [fontname=\"Helvetica\"];\n",
+            quote(label)
+        )
+    }
+
+    /// Nodes and edges for one spec, with node ids prefixed by `prefix`
+    fn render_body(spec: &SpecData, prefix: &str, indent: &str) -> String {
+        let node_id = |id: &str| quote(&format!("{}{}", prefix, id));
+        let mut dot = String::new();
+
+        for req in &spec.requirements {
+            dot.push_str(&format!(
+                "{}{} [label={}, shape=box];\n",
+                indent,
+                node_id(&req.id),
+                quote(&format!("{}\n{}", req.id, req.title))
+            ));
+        }
+        for task in &spec.tasks {
+            dot.push_str(&format!(
+                "{}{} [label={}, shape=ellipse];\n",
+                indent,
+                node_id(&task.id),
+                quote(&format!("{}\n{}", task.id, task.title))
+            ));
+        }
+
+        for task in &spec.tasks {
+            for req_id in &task.requirement_ids {
+                // Dangling references stay visible instead of vanishing
+                if !spec.requirements.iter().any(|r| &r.id == req_id) {
+                    dot.push_str(&format!(
+                        "{}{} [label={}, shape=box, style=dashed, color=red];\n",
+                        indent,
+                        node_id(req_id),
+                        quote(&format!("{}\n(missing)", req_id))
+                    ));
+                }
+                dot.push_str(&format!(
+                    "{}{} -> {};\n",
+                    indent,
+                    node_id(req_id),
+                    node_id(&task.id)
+                ));
+            }
+        }
+
+        dot
+    }
+
+    /// Export a single spec to a DOT file
+    pub fn export_to_file(spec: &SpecData, output_path: &Path) -> Result<()> {
+        ensure_parent_dir(output_path)?;
+        fs::write(output_path, Self::render_spec(spec)).context("Failed to write DOT file")?;
+        Ok(())
+    }
+
+    /// Export multiple specs to a single DOT file
+    pub fn export_multi(specs: &[SpecData], output_path: &Path) -> Result<()> {
+        ensure_parent_dir(output_path)?;
+        fs::write(output_path, Self::render_multi(specs))
+            .context("Failed to write multi-spec DOT file")?;
+        Ok(())
+    }
+}
+
+/// Quote text as a DOT string id, so any spec content is a safe node id
+pub(crate) fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => {}
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
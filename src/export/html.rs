@@ -0,0 +1,373 @@
+//! HTML rendering for specs
+//!
+//! Produces a self-contained HTML document (inline styles, no external assets)
+//! suitable for sharing or feeding into the PDF converter
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use super::ensure_parent_dir;
+use crate::models::{Decision, Requirement, SpecData, Task, WorkflowStage};
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; max-width: 920px; margin: 2em auto; padding: 0 1em; color: #222; line-height: 1.5; }
+h1, h2, h3 { line-height: 1.25; }
+h2 { border-bottom: 1px solid #ddd; padding-bottom: 0.2em; margin-top: 2em; }
+blockquote { border-left: 4px solid #ccc; margin: 0.5em 0; padding: 0.2em 1em; color: #444; }
+table { border-collapse: collapse; width: 100%; margin: 1em 0; }
+th, td { border: 1px solid #ccc; padding: 0.4em 0.6em; text-align: left; vertical-align: top; }
+th { background: #f4f4f4; }
+.meta td:first-child { font-weight: bold; width: 12em; }
+.stages span { display: inline-block; padding: 0.2em 0.6em; margin-right: 0.3em; border-radius: 3px; background: #eee; }
+.stages .current { background: #2b6cb0; color: #fff; font-weight: bold; }
+.stages .completed { background: #c6f6d5; }
+.empty { color: #777; font-style: italic; }
+.spec { page-break-after: always; }
+footer { margin-top: 3em; color: #777; font-size: 0.9em; }
+"#;
+
+/// HTML renderer for specs
+pub struct HtmlRenderer;
+
+impl HtmlRenderer {
+    /// Render a spec to a complete HTML document
+    pub fn render_spec(spec: &SpecData, with_tables: bool) -> String {
+        Self::document(&spec.name, &Self::render_spec_body(spec, with_tables))
+    }
+
+    /// Render the body section for a single spec (no `<html>` wrapper)
+    fn render_spec_body(spec: &SpecData, with_tables: bool) -> String {
+        let mut html = String::new();
+
+        html.push_str(&format!(
+            "<section class=\"spec\" id=\"{}\">\n",
+            escape(&spec.spec_id)
+        ));
+        html.push_str(&format!("<h1>{}</h1>\n", escape(&spec.name)));
+
+        // Metadata
+        html.push_str("<table class=\"meta\">\n");
+        for (label, value) in [
+            ("Project", spec.project.clone()),
+            ("Spec ID", spec.spec_id.clone()),
+            ("Boundary", spec.boundary.to_string()),
+            ("Stage", spec.stage.to_string()),
+        ] {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td></tr>\n",
+                label,
+                escape(&value)
+            ));
+        }
+        html.push_str("</table>\n");
+
+        // Workflow Status
+        html.push_str("<h2>Workflow Status</h2>\n");
+        html.push_str(&Self::render_workflow_status(spec));
+
+        // Requirements
+        html.push_str("<h2>Requirements</h2>\n");
+        if spec.requirements.is_empty() {
+            html.push_str("<p class=\"empty\">No requirements defined yet.</p>\n");
+        } else if with_tables {
+            html.push_str(&Self::render_requirements_table(&spec.requirements));
+        } else {
+            html.push_str(&Self::render_requirements_list(&spec.requirements));
+        }
+
+        // Design Decisions
+        html.push_str("<h2>Design Decisions</h2>\n");
+        if spec.decisions.is_empty() {
+            html.push_str("<p class=\"empty\">No design decisions documented yet.</p>\n");
+        } else {
+            html.push_str(&Self::render_decisions(&spec.decisions));
+        }
+
+        // Tasks
+        html.push_str("<h2>Tasks</h2>\n");
+        if spec.tasks.is_empty() {
+            html.push_str("<p class=\"empty\">No tasks defined yet.</p>\n");
+        } else {
+            html.push_str(&Self::render_tasks_table(&spec.tasks));
+        }
+
+        // Change History
+        html.push_str("<h2>Change History</h2>\n");
+        html.push_str(&Self::render_history(spec));
+
+        html.push_str("</section>\n");
+        html
+    }
+
+    /// Render workflow stages as inline badges
+    fn render_workflow_status(spec: &SpecData) -> String {
+        let mut html = String::from("<p class=\"stages\">");
+        for stage in WorkflowStage::all() {
+            let class = if stage == spec.stage {
+                "current"
+            } else if spec.completed_stages().contains(&stage) {
+                "completed"
+            } else {
+                "pending"
+            };
+            html.push_str(&format!("<span class=\"{}\">{}</span>", class, stage));
+        }
+        html.push_str("</p>\n");
+
+        html.push_str(&format!(
+            "<ul><li><strong>Requirements:</strong> {}</li><li><strong>Tasks:</strong> {}</li><li><strong>Decisions:</strong> {}</li></ul>\n",
+            spec.requirements.len(),
+            spec.tasks.len(),
+            spec.decisions.len()
+        ));
+
+        html
+    }
+
+    /// Render requirements with full scenario detail
+    fn render_requirements_list(requirements: &[Requirement]) -> String {
+        let mut html = String::new();
+
+        for req in requirements {
+            html.push_str(&format!(
+                "<h3 id=\"{}\">{} - {}</h3>\n",
+                escape(&req.id),
+                escape(&req.id),
+                escape(&req.title)
+            ));
+            html.push_str(&format!(
+                "<p><strong>Capability:</strong> {}<br><strong>Priority:</strong> {}</p>\n",
+                escape(&req.capability),
+                req.priority
+            ));
+            html.push_str(&format!(
+                "<blockquote>{}</blockquote>\n",
+                escape(&req.shall)
+            ));
+
+            if let Some(rationale) = &req.rationale {
+                html.push_str(&format!(
+                    "<p><strong>Rationale:</strong> {}</p>\n",
+                    escape(rationale)
+                ));
+            }
+
+            if !req.tags.is_empty() {
+                html.push_str(&format!(
+                    "<p><strong>Tags:</strong> {}</p>\n",
+                    escape(&req.tags.join(", "))
+                ));
+            }
+
+            for scenario in &req.scenarios {
+                html.push_str(&format!(
+                    "<p><strong>{}</strong> ({})</p>\n<ul>\n",
+                    escape(&scenario.name),
+                    escape(&scenario.id)
+                ));
+                html.push_str(&format!(
+                    "<li><strong>GIVEN</strong> {}</li>\n",
+                    escape(&scenario.given.join("; "))
+                ));
+                html.push_str(&format!(
+                    "<li><strong>WHEN</strong> {}</li>\n",
+                    escape(&scenario.when)
+                ));
+                html.push_str(&format!(
+                    "<li><strong>THEN</strong> {}</li>\n",
+                    escape(&scenario.then.join("; "))
+                ));
+                if !scenario.edge_cases.is_empty() {
+                    html.push_str(&format!(
+                        "<li><strong>EDGE CASES</strong> {}</li>\n",
+                        escape(&scenario.edge_cases.join("; "))
+                    ));
+                }
+                html.push_str("</ul>\n");
+            }
+        }
+
+        html
+    }
+
+    /// Render requirements as a summary table
+    fn render_requirements_table(requirements: &[Requirement]) -> String {
+        let mut html = String::from(
+            "<table>\n<tr><th>ID</th><th>Title</th><th>Priority</th><th>SHALL Statement</th><th>Scenarios</th></tr>\n",
+        );
+
+        for req in requirements {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape(&req.id),
+                escape(&req.title),
+                req.priority,
+                escape(&req.shall),
+                req.scenarios.len()
+            ));
+        }
+
+        html.push_str("</table>\n");
+        html
+    }
+
+    /// Render design decisions
+    fn render_decisions(decisions: &[Decision]) -> String {
+        let mut html = String::new();
+
+        for decision in decisions {
+            html.push_str(&format!(
+                "<h3 id=\"{}\">{} - {}</h3>\n",
+                escape(&decision.id),
+                escape(&decision.id),
+                escape(&decision.title)
+            ));
+            html.push_str(&format!(
+                "<p><strong>Date:</strong> {}</p>\n",
+                escape(&decision.date)
+            ));
+            html.push_str(&format!(
+                "<p><strong>Context:</strong> {}</p>\n",
+                escape(&decision.context)
+            ));
+            html.push_str(&format!(
+                "<blockquote>{}</blockquote>\n",
+                escape(&decision.decision)
+            ));
+            html.push_str(&format!(
+                "<p><strong>Rationale:</strong> {}</p>\n",
+                escape(&decision.rationale)
+            ));
+
+            if !decision.alternatives_rejected.is_empty() {
+                html.push_str("<p><strong>Alternatives Considered:</strong></p>\n<ul>\n");
+                for alt in &decision.alternatives_rejected {
+                    html.push_str(&format!("<li>{}</li>\n", escape(alt)));
+                }
+                html.push_str("</ul>\n");
+            }
+        }
+
+        html
+    }
+
+    /// Render tasks as a table
+    fn render_tasks_table(tasks: &[Task]) -> String {
+        let mut html = String::from(
+            "<table>\n<tr><th>ID</th><th>Title</th><th>Status</th><th>Requirements</th><th>Assignee</th></tr>\n",
+        );
+
+        for task in tasks {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}<br><small>{}</small></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape(&task.id),
+                escape(&task.title),
+                escape(&task.description),
+                task.status,
+                escape(&task.requirement_ids.join(", ")),
+                escape(task.assignee.as_deref().unwrap_or("-"))
+            ));
+        }
+
+        html.push_str("</table>\n");
+        html
+    }
+
+    /// Render change history
+    fn render_history(spec: &SpecData) -> String {
+        let format_ts = |ts: i64| {
+            chrono::DateTime::from_timestamp(ts, 0)
+                .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+                .unwrap_or_else(|| "unknown".to_string())
+        };
+
+        let mut html = format!(
+            "<ul><li><strong>Created:</strong> {}</li><li><strong>Last Updated:</strong> {}</li></ul>\n",
+            format_ts(spec.history.created_at),
+            format_ts(spec.history.updated_at)
+        );
+
+        if !spec.history.patches.is_empty() {
+            html.push_str(
+                "<table>\n<tr><th>When</th><th>Actor</th><th>Op</th><th>Summary</th></tr>\n",
+            );
+            for patch in spec.history.patches.iter().rev().take(10) {
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    format_ts(patch.timestamp),
+                    escape(&patch.actor),
+                    escape(&patch.op),
+                    escape(&patch.summary)
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+
+        html
+    }
+
+    /// Wrap body content in a full HTML document
+    fn document(title: &str, body: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}<footer>Generated by Manifold on {}</footer>\n</body>\n</html>\n",
+            escape(title),
+            STYLE,
+            body,
+            chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+        )
+    }
+
+    /// Render multiple specs into a single HTML document
+    pub fn render_multi(specs: &[SpecData], with_tables: bool) -> String {
+        let mut body = String::from("<h1>Manifold Specification Collection</h1>\n<ol>\n");
+        for spec in specs {
+            body.push_str(&format!(
+                "<li><a href=\"#{}\">{}</a> - {} ({})</li>\n",
+                escape(&spec.spec_id),
+                escape(&spec.name),
+                escape(&spec.project),
+                spec.stage
+            ));
+        }
+        body.push_str("</ol>\n");
+
+        for spec in specs {
+            body.push_str(&Self::render_spec_body(spec, with_tables));
+        }
+
+        Self::document("Manifold Specification Collection", &body)
+    }
+
+    /// Export spec to HTML file
+    pub fn export_to_file(spec: &SpecData, output_path: &Path, with_tables: bool) -> Result<()> {
+        let html = Self::render_spec(spec, with_tables);
+        ensure_parent_dir(output_path)?;
+        fs::write(output_path, html).context("Failed to write HTML file")?;
+        Ok(())
+    }
+
+    /// Export multiple specs to a single HTML document
+    pub fn export_multi(specs: &[SpecData], output_path: &Path, with_tables: bool) -> Result<()> {
+        let html = Self::render_multi(specs, with_tables);
+        ensure_parent_dir(output_path)?;
+        fs::write(output_path, html).context("Failed to write multi-spec HTML file")?;
+        Ok(())
+    }
+}
+
+/// Escape text for safe inclusion in HTML
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
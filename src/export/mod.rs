@@ -1,16 +1,37 @@
 //! Markdown rendering and export for specs
 //!
-//! Converts JSON-canonical specs to human-readable Markdown documentation
+//! Converts JSON-canonical specs to human-readable Markdown documentation,
+//! with HTML and PDF output built on top
 
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
-use crate::models::{Decision, Priority, Requirement, SpecData, Task, TaskStatus};
+use crate::collab::diff::{self, FieldChange, SpecDiff};
+use crate::collab::{Conflict, ConflictResolution, ConflictStatus};
+use crate::models::{
+    Decision, Priority, Requirement, Scenario, SpecData, Task, TaskStatus, WorkflowStage,
+};
+
+pub mod asciidoc;
+pub mod bundle;
+pub mod dot;
+pub mod html;
+pub mod openapi;
+pub mod pdf;
+pub mod yaml;
+
+pub use asciidoc::AsciiDocRenderer;
+pub use dot::DotRenderer;
+pub use html::HtmlRenderer;
+pub use openapi::OpenApiCommentsRenderer;
+pub use pdf::PdfRenderer;
+pub use yaml::YamlRenderer;
 
 /// Export format options
 /// Designed for future CLI/API export options
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(dead_code)]
 pub enum ExportFormat {
     /// Single spec to Markdown
@@ -19,6 +40,109 @@ pub enum ExportFormat {
     MultiMarkdown,
     /// Spec with table formatting
     MarkdownTables,
+    /// Self-contained HTML document
+    Html,
+    /// PDF rendered from HTML (requires the `pdf` feature)
+    Pdf,
+    /// Graphviz graph of requirement → task edges
+    Dot,
+    /// AsciiDoc document mirroring the Markdown layout
+    AsciiDoc,
+    /// The full canonical spec as YAML, importable again
+    Yaml,
+    /// OpenAPI `description`/`x-acceptance` snippets for `api` requirements
+    OpenApiComments,
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "markdown" | "md" => Ok(ExportFormat::Markdown),
+            "html" => Ok(ExportFormat::Html),
+            "pdf" => Ok(ExportFormat::Pdf),
+            "dot" => Ok(ExportFormat::Dot),
+            "asciidoc" | "adoc" => Ok(ExportFormat::AsciiDoc),
+            "yaml" | "yml" => Ok(ExportFormat::Yaml),
+            "openapi-comments" => Ok(ExportFormat::OpenApiComments),
+            _ => Err(format!(
+                "Invalid export format: {}. Use: markdown, html, pdf, dot, asciidoc, yaml, openapi-comments",
+                s
+            )),
+        }
+    }
+}
+
+/// A part of a spec that can be rendered on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecSection {
+    Requirements,
+    Tasks,
+    Decisions,
+    /// Every requirement's scenarios, grouped by requirement
+    Scenarios,
+}
+
+impl SpecSection {
+    fn heading(self) -> &'static str {
+        match self {
+            SpecSection::Requirements => "Requirements",
+            SpecSection::Tasks => "Tasks",
+            SpecSection::Decisions => "Design Decisions",
+            SpecSection::Scenarios => "Scenarios",
+        }
+    }
+}
+
+impl std::str::FromStr for SpecSection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "requirements" => Ok(SpecSection::Requirements),
+            "tasks" => Ok(SpecSection::Tasks),
+            "decisions" => Ok(SpecSection::Decisions),
+            "scenarios" => Ok(SpecSection::Scenarios),
+            _ => Err(format!(
+                "Invalid section: {}. Use: requirements, tasks, decisions, scenarios",
+                s
+            )),
+        }
+    }
+}
+
+/// Create the parent directory of an output path if needed
+pub(crate) fn ensure_parent_dir(output_path: &Path) -> Result<()> {
+    if let Some(parent) = output_path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent).context("Failed to create output directory")?;
+        }
+    }
+    Ok(())
+}
+
+/// Placeholder written over actor names when anonymizing
+pub const ANONYMOUS_ACTOR: &str = "anonymous";
+
+/// Return a copy of a spec with personal data removed
+///
+/// The spec owner and task assignees are cleared, patch actors are replaced with a placeholder,
+/// and patch summaries are dropped. The input spec is left untouched.
+pub fn anonymize(spec: &SpecData) -> SpecData {
+    let mut spec = spec.clone();
+    spec.owner = None;
+
+    for task in &mut spec.tasks {
+        task.assignee = None;
+    }
+
+    for patch in &mut spec.history.patches {
+        patch.actor = ANONYMOUS_ACTOR.to_string();
+        patch.summary = String::new();
+    }
+
+    spec
 }
 
 /// Markdown renderer for specs
@@ -37,7 +161,7 @@ impl MarkdownRenderer {
         md.push_str(&format!("> **Stage:** {}  \n", spec.stage));
         md.push_str(&format!(
             "> **Stages Completed:** {:?}  \n",
-            spec.stages_completed
+            spec.completed_stages()
         ));
         md.push_str("\n---\n\n");
 
@@ -53,7 +177,7 @@ impl MarkdownRenderer {
         // Workflow Status
         md.push_str("## Workflow Status\n\n");
         md.push_str(&Self::render_workflow_status(spec));
-        md.push_str("\n");
+        md.push('\n');
 
         // Requirements
         md.push_str("## Requirements\n\n");
@@ -85,6 +209,9 @@ impl MarkdownRenderer {
             } else {
                 md.push_str(&Self::render_tasks_list(&spec.tasks));
             }
+            if spec.tasks.iter().any(|t| !t.scenario_ids.is_empty()) {
+                md.push_str(&Self::render_traceability_matrix(spec));
+            }
         }
 
         // Change History
@@ -103,21 +230,18 @@ impl MarkdownRenderer {
 
     /// Render workflow status with visual progress
     fn render_workflow_status(spec: &SpecData) -> String {
-        let stages = ["requirements", "design", "tasks", "approval", "implemented"];
-        let current_stage = spec.stage.to_string();
+        let stages = WorkflowStage::all();
+        let completed = spec.completed_stages();
 
         let mut status = String::new();
         status.push_str("```\n");
 
         for (i, stage) in stages.iter().enumerate() {
-            let is_current = *stage == current_stage;
-            let is_completed = spec
-                .stages_completed
-                .iter()
-                .any(|s| s.to_string() == *stage);
+            let is_current = *stage == spec.stage;
+            let is_completed = completed.contains(stage);
 
             if is_current {
-                status.push_str(&format!("  [{:^15}]", stage.to_uppercase()));
+                status.push_str(&format!("  [{:^15}]", stage.to_string().to_uppercase()));
             } else if is_completed {
                 status.push_str(&format!("  ✓ {:^13}", stage));
             } else {
@@ -176,29 +300,92 @@ impl MarkdownRenderer {
             if !req.scenarios.is_empty() {
                 md.push_str("#### Scenarios\n\n");
                 for scenario in &req.scenarios {
-                    md.push_str(&format!("**{}** ({})\n\n", scenario.name, scenario.id));
+                    md.push_str(&Self::render_scenario(scenario));
+                }
+            }
 
-                    md.push_str("- **GIVEN**\n");
-                    for given in &scenario.given {
-                        md.push_str(&format!("  - {}\n", given));
-                    }
-                    md.push_str(&format!("- **WHEN** {}\n", scenario.when));
-                    md.push_str("- **THEN**\n");
-                    for then in &scenario.then {
-                        md.push_str(&format!("  - {}\n", then));
-                    }
+            md.push_str("---\n\n");
+        }
+
+        md
+    }
+
+    /// Render one scenario's GIVEN/WHEN/THEN block
+    fn render_scenario(scenario: &Scenario) -> String {
+        let mut md = format!("**{}** ({})\n\n", scenario.name, scenario.id);
+
+        md.push_str("- **GIVEN**\n");
+        for given in &scenario.given {
+            md.push_str(&format!("  - {}\n", given));
+        }
+        md.push_str(&format!("- **WHEN** {}\n", scenario.when));
+        md.push_str("- **THEN**\n");
+        for then in &scenario.then {
+            md.push_str(&format!("  - {}\n", then));
+        }
 
-                    if !scenario.edge_cases.is_empty() {
-                        md.push_str("- **EDGE CASES**\n");
-                        for edge in &scenario.edge_cases {
-                            md.push_str(&format!("  - {}\n", edge));
+        if !scenario.edge_cases.is_empty() {
+            md.push_str("- **EDGE CASES**\n");
+            for edge in &scenario.edge_cases {
+                md.push_str(&format!("  - {}\n", edge));
+            }
+        }
+        md.push('\n');
+        md
+    }
+
+    /// Render only the given sections of a spec, under its title
+    ///
+    /// Sections appear in the order given; repeats are rendered once.
+    pub fn render_sections(spec: &SpecData, sections: &[SpecSection], with_tables: bool) -> String {
+        let mut md = format!("# {}\n\n", spec.name);
+        let mut seen = Vec::new();
+
+        for section in sections {
+            if seen.contains(section) {
+                continue;
+            }
+            seen.push(*section);
+
+            md.push_str(&format!("## {}\n\n", section.heading()));
+            match section {
+                SpecSection::Requirements if spec.requirements.is_empty() => {
+                    md.push_str("*No requirements defined yet.*\n\n");
+                }
+                SpecSection::Requirements if with_tables => {
+                    md.push_str(&Self::render_requirements_table(&spec.requirements));
+                }
+                SpecSection::Requirements => {
+                    md.push_str(&Self::render_requirements_list(&spec.requirements));
+                }
+                SpecSection::Tasks if spec.tasks.is_empty() => {
+                    md.push_str("*No tasks defined yet.*\n\n");
+                }
+                SpecSection::Tasks if with_tables => {
+                    md.push_str(&Self::render_tasks_table(&spec.tasks));
+                }
+                SpecSection::Tasks => md.push_str(&Self::render_tasks_list(&spec.tasks)),
+                SpecSection::Decisions if spec.decisions.is_empty() => {
+                    md.push_str("*No design decisions documented yet.*\n\n");
+                }
+                SpecSection::Decisions => md.push_str(&Self::render_decisions(&spec.decisions)),
+                SpecSection::Scenarios => {
+                    let with_scenarios: Vec<_> = spec
+                        .requirements
+                        .iter()
+                        .filter(|req| !req.scenarios.is_empty())
+                        .collect();
+                    if with_scenarios.is_empty() {
+                        md.push_str("*No scenarios defined yet.*\n\n");
+                    }
+                    for req in with_scenarios {
+                        md.push_str(&format!("### {} - {}\n\n", req.id, req.title));
+                        for scenario in &req.scenarios {
+                            md.push_str(&Self::render_scenario(scenario));
                         }
                     }
-                    md.push_str("\n");
                 }
             }
-
-            md.push_str("---\n\n");
         }
 
         md
@@ -220,7 +407,7 @@ impl MarkdownRenderer {
             ));
         }
 
-        md.push_str("\n");
+        md.push('\n');
 
         // Detailed sections follow
         for req in requirements {
@@ -235,7 +422,7 @@ impl MarkdownRenderer {
                 for scenario in &req.scenarios {
                     md.push_str(&format!("- {} ({})\n", scenario.name, scenario.id));
                 }
-                md.push_str("\n");
+                md.push('\n');
             }
         }
 
@@ -264,7 +451,7 @@ impl MarkdownRenderer {
                 for alt in &decision.alternatives_rejected {
                     md.push_str(&format!("- {}\n", alt));
                 }
-                md.push_str("\n");
+                md.push('\n');
             }
 
             md.push_str("---\n\n");
@@ -294,6 +481,13 @@ impl MarkdownRenderer {
                 ));
             }
 
+            if !task.scenario_ids.is_empty() {
+                md.push_str(&format!(
+                    "**Scenarios:** {}\n\n",
+                    task.scenario_ids.join(", ")
+                ));
+            }
+
             if let Some(assignee) = &task.assignee {
                 md.push_str(&format!("**Assignee:** {}\n\n", assignee));
             }
@@ -303,7 +497,7 @@ impl MarkdownRenderer {
                 for criteria in &task.acceptance {
                     md.push_str(&format!("- {}\n", criteria));
                 }
-                md.push_str("\n");
+                md.push('\n');
             }
 
             md.push_str("---\n\n");
@@ -330,7 +524,47 @@ impl MarkdownRenderer {
             ));
         }
 
-        md.push_str("\n");
+        md.push('\n');
+        md
+    }
+
+    /// Render which tasks implement each scenario, flagging uncovered ones
+    pub fn render_traceability_matrix(spec: &SpecData) -> String {
+        let mut md = String::new();
+
+        md.push_str("### Traceability Matrix\n\n");
+        md.push_str("| Scenario | Name | Tasks |\n");
+        md.push_str("|---|---|---|\n");
+
+        let mut uncovered = 0;
+        for req in &spec.requirements {
+            for scenario in &req.scenarios {
+                let scenario_ref = format!("{}/{}", req.id, scenario.id);
+                let tasks: Vec<&str> = spec
+                    .tasks
+                    .iter()
+                    .filter(|t| t.scenario_ids.contains(&scenario_ref))
+                    .map(|t| t.id.as_str())
+                    .collect();
+                let tasks = if tasks.is_empty() {
+                    uncovered += 1;
+                    "⚠️ uncovered".to_string()
+                } else {
+                    tasks.join(", ")
+                };
+                md.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    scenario_ref, scenario.name, tasks
+                ));
+            }
+        }
+
+        let total: usize = spec.requirements.iter().map(|r| r.scenarios.len()).sum();
+        md.push_str(&format!(
+            "\n**Scenario coverage:** {}/{} scenarios have a task\n\n",
+            total - uncovered,
+            total
+        ));
         md
     }
 
@@ -370,6 +604,155 @@ impl MarkdownRenderer {
         md
     }
 
+    /// Render an audit section of resolved conflicts
+    ///
+    /// Conflicts resolved before resolutions were recorded fall back to the
+    /// side their status names, with no resolver or time.
+    pub fn render_resolved_conflicts(
+        resolved: &[(Conflict, Option<ConflictResolution>)],
+    ) -> String {
+        let mut md = String::from("## Resolved Conflicts\n\n");
+        if resolved.is_empty() {
+            md.push_str("*No resolved conflicts*\n");
+            return md;
+        }
+
+        md.push_str("| Field | Chosen Value | Strategy | Resolved |\n");
+        md.push_str("|---|---|---|---|\n");
+
+        for (conflict, resolution) in resolved {
+            let (value, strategy, when) = match resolution {
+                Some(r) => {
+                    let when = chrono::DateTime::from_timestamp(r.resolved_at, 0)
+                        .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+                        .unwrap_or_else(|| "unknown".to_string());
+                    (
+                        r.value.to_string(),
+                        r.strategy.clone(),
+                        format!("{} by {}", when, r.resolved_by),
+                    )
+                }
+                None => {
+                    let (value, strategy) = match conflict.status {
+                        ConflictStatus::ResolvedLocal => (conflict.local_value.to_string(), "ours"),
+                        ConflictStatus::ResolvedRemote => {
+                            (conflict.remote_value.to_string(), "theirs")
+                        }
+                        _ => ("(not recorded)".to_string(), "manual"),
+                    };
+                    (value, strategy.to_string(), "unknown".to_string())
+                }
+            };
+
+            md.push_str(&format!(
+                "| `{}` | {} | {} | {} |\n",
+                conflict.field_path,
+                value.replace('|', "\\|"),
+                strategy,
+                when
+            ));
+        }
+
+        md
+    }
+
+    /// Render a change report between two specs
+    ///
+    /// Added and removed items are listed by id and title; changed items
+    /// show each differing field as a before → after pair.
+    pub fn render_diff(diff: &SpecDiff) -> String {
+        let mut md = format!("# Changes: {} → {}\n\n", diff.from, diff.to);
+
+        if diff.is_empty() {
+            md.push_str("*No differences.*\n");
+            return md;
+        }
+
+        md.push_str("| Section | Added | Removed | Changed |\n");
+        md.push_str("|---|---|---|---|\n");
+        for section in &diff.sections {
+            md.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                Self::section_heading(&section.section),
+                section.added.len(),
+                section.removed.len(),
+                section.changed.len()
+            ));
+        }
+        md.push('\n');
+
+        if !diff.fields.is_empty() {
+            md.push_str("## Spec\n\n");
+            md.push_str(&Self::render_field_changes(&diff.fields));
+        }
+
+        for section in diff.sections.iter().filter(|s| !s.is_empty()) {
+            md.push_str(&format!(
+                "## {}\n\n",
+                Self::section_heading(&section.section)
+            ));
+
+            if !section.added.is_empty() {
+                md.push_str("### Added\n\n");
+                for item in &section.added {
+                    md.push_str(&Self::diff_item_line(item));
+                }
+                md.push('\n');
+            }
+            if !section.removed.is_empty() {
+                md.push_str("### Removed\n\n");
+                for item in &section.removed {
+                    md.push_str(&Self::diff_item_line(item));
+                }
+                md.push('\n');
+            }
+            if !section.changed.is_empty() {
+                md.push_str("### Changed\n\n");
+                for item in &section.changed {
+                    md.push_str(&format!("#### {} - {}\n\n", item.id, item.title));
+                    md.push_str(&Self::render_field_changes(&item.changes));
+                }
+            }
+        }
+
+        md
+    }
+
+    fn section_heading(section: &str) -> &str {
+        match section {
+            "requirements" => "Requirements",
+            "tasks" => "Tasks",
+            "decisions" => "Design Decisions",
+            other => other,
+        }
+    }
+
+    fn diff_item_line(item: &serde_json::Value) -> String {
+        let id = item.get("id").and_then(|v| v.as_str()).unwrap_or("?");
+        let mut line = format!("- **{}** {}\n", id, diff::item_title(item));
+        if let Some(shall) = item.get("shall").and_then(|v| v.as_str()) {
+            line.push_str(&format!("  > {}\n", shall));
+        }
+        line
+    }
+
+    fn render_field_changes(changes: &[FieldChange]) -> String {
+        let snippet = |value: &serde_json::Value| match value {
+            serde_json::Value::Null => "*(none)*".to_string(),
+            serde_json::Value::String(s) => format!("> {}", s.replace('\n', "\n  > ")),
+            other => format!("`{}`", other),
+        };
+
+        let mut md = String::new();
+        for change in changes {
+            md.push_str(&format!("- `{}`\n", change.path));
+            md.push_str(&format!("  - Before: {}\n", snippet(&change.before)));
+            md.push_str(&format!("  - After: {}\n", snippet(&change.after)));
+        }
+        md.push('\n');
+        md
+    }
+
     /// Get emoji for priority
     fn priority_emoji(priority: &Priority) -> &'static str {
         match priority {
@@ -390,62 +773,199 @@ impl MarkdownRenderer {
         }
     }
 
-    /// Export spec to Markdown file
-    pub fn export_to_file(spec: &SpecData, output_path: &Path, with_tables: bool) -> Result<()> {
-        let markdown = Self::render_spec(spec, with_tables);
+    /// Render a YAML front matter block for static-site generators
+    ///
+    /// Strings are emitted as double-quoted JSON strings, which YAML reads
+    /// verbatim, so names containing colons, quotes, or `#` stay valid.
+    pub fn render_front_matter(spec: &SpecData) -> String {
+        let quote = |s: &str| serde_json::Value::String(s.to_string()).to_string();
+        let date = |ts: i64| {
+            chrono::DateTime::from_timestamp(ts, 0)
+                .map(|dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+                .unwrap_or_default()
+        };
+
+        let mut tags: Vec<&str> = spec
+            .requirements
+            .iter()
+            .flat_map(|r| r.tags.iter().map(String::as_str))
+            .collect();
+        tags.sort_unstable();
+        tags.dedup();
+        let tags: Vec<String> = tags.into_iter().map(quote).collect();
+
+        let mut fm = String::from("---\n");
+        fm.push_str(&format!("spec_id: {}\n", quote(&spec.spec_id)));
+        fm.push_str(&format!("name: {}\n", quote(&spec.name)));
+        fm.push_str(&format!(
+            "boundary: {}\n",
+            quote(&spec.boundary.to_string())
+        ));
+        fm.push_str(&format!("stage: {}\n", quote(&spec.stage.to_string())));
+        fm.push_str(&format!(
+            "created: {}\n",
+            quote(&date(spec.history.created_at))
+        ));
+        fm.push_str(&format!(
+            "updated: {}\n",
+            quote(&date(spec.history.updated_at))
+        ));
+        fm.push_str(&format!("tags: [{}]\n", tags.join(", ")));
+        fm.push_str("---\n\n");
+        fm
+    }
 
-        // Ensure parent directory exists
-        if let Some(parent) = output_path.parent() {
-            fs::create_dir_all(parent).context("Failed to create output directory")?;
+    /// Export spec to Markdown file, optionally preceded by YAML front matter
+    pub fn export_to_file(
+        spec: &SpecData,
+        output_path: &Path,
+        with_tables: bool,
+        front_matter: bool,
+    ) -> Result<()> {
+        let mut markdown = String::new();
+        if front_matter {
+            markdown.push_str(&Self::render_front_matter(spec));
         }
+        markdown.push_str(&Self::render_spec(spec, with_tables));
+
+        // Ensure parent directory exists
+        ensure_parent_dir(output_path)?;
 
         fs::write(output_path, markdown).context("Failed to write Markdown file")?;
 
         Ok(())
     }
 
-    /// Export multiple specs to a single Markdown document
-    pub fn export_multi(specs: &[SpecData], output_path: &Path, with_tables: bool) -> Result<()> {
-        let mut md = String::new();
-
-        // Document header
-        md.push_str("# Manifold Specification Collection\n\n");
-        md.push_str(&format!(
+    /// Render multiple specs as one Markdown document
+    ///
+    /// Specs sharing a name get their id appended so every spec heading is
+    /// distinct. With `toc`, a table of contents links to each spec heading
+    /// using the anchors GitHub generates for the whole document.
+    pub fn render_multi(specs: &[SpecData], with_tables: bool, toc: bool) -> String {
+        let mut header = String::new();
+        header.push_str("# Manifold Specification Collection\n\n");
+        header.push_str(&format!(
             "> Generated on {}\n\n",
             chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
         ));
-        md.push_str("---\n\n");
+        header.push_str("---\n\n");
+        if toc {
+            header.push_str("## Specifications\n\n");
+        }
 
-        // Table of contents
-        md.push_str("## Specifications\n\n");
+        let mut name_counts: HashMap<&str, usize> = HashMap::new();
+        for spec in specs {
+            *name_counts.entry(spec.name.as_str()).or_default() += 1;
+        }
+
+        let mut slugger = Slugger::default();
+        slugger.scan(&header);
+
+        let mut entries = String::new();
+        let mut body = String::new();
         for (i, spec) in specs.iter().enumerate() {
-            md.push_str(&format!(
+            let mut rendered = Self::render_spec(spec, with_tables);
+            let heading = if name_counts[spec.name.as_str()] > 1 {
+                let heading = format!("{} ({})", spec.name, spec.spec_id);
+                rendered = rendered.replacen(
+                    &format!("# {}\n", spec.name),
+                    &format!("# {}\n", heading),
+                    1,
+                );
+                heading
+            } else {
+                spec.name.clone()
+            };
+
+            // The spec heading is the first heading in its rendering
+            let anchors = slugger.scan(&rendered);
+            entries.push_str(&format!(
                 "{}. [{}](#{}) - {} ({})\n",
                 i + 1,
-                spec.name,
-                Self::anchor(&spec.name),
+                heading,
+                anchors.first().cloned().unwrap_or_default(),
                 spec.project,
                 spec.stage
             ));
+
+            body.push_str(&rendered);
+            body.push_str("\n\\pagebreak\n\n");
         }
-        md.push_str("\n---\n\n");
 
-        // Individual specs
-        for spec in specs {
-            md.push_str(&Self::render_spec(spec, with_tables));
-            md.push_str("\n\\pagebreak\n\n");
+        let mut md = header;
+        if toc {
+            md.push_str(&entries);
+            md.push_str("\n---\n\n");
         }
+        md.push_str(&body);
+        md
+    }
 
+    /// Export multiple specs to a single Markdown document
+    pub fn export_multi(
+        specs: &[SpecData],
+        output_path: &Path,
+        with_tables: bool,
+        toc: bool,
+    ) -> Result<()> {
+        let md = Self::render_multi(specs, with_tables, toc);
+        ensure_parent_dir(output_path)?;
         fs::write(output_path, md).context("Failed to write multi-spec Markdown file")?;
 
         Ok(())
     }
+}
+
+/// GitHub-style heading anchors, deduplicated across a document
+#[derive(Debug, Default)]
+pub struct Slugger {
+    seen: HashMap<String, usize>,
+}
 
-    /// Create anchor link from text
-    fn anchor(text: &str) -> String {
-        text.to_lowercase()
+impl Slugger {
+    /// Anchor for a heading: lowercased, punctuation dropped, spaces to dashes
+    pub fn slug(text: &str) -> String {
+        text.trim()
+            .to_lowercase()
             .chars()
-            .map(|c| if c.is_alphanumeric() { c } else { '-' })
+            .filter_map(|c| match c {
+                ' ' => Some('-'),
+                c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+                _ => None,
+            })
             .collect()
     }
+
+    /// Anchor for the next heading with this text; repeats get -1, -2, ...
+    pub fn next(&mut self, text: &str) -> String {
+        let base = Self::slug(text);
+        let count = self.seen.entry(base.clone()).or_insert(0);
+        let anchor = if *count == 0 {
+            base
+        } else {
+            format!("{}-{}", base, count)
+        };
+        *count += 1;
+        anchor
+    }
+
+    /// Assign anchors to every ATX heading in `markdown`, in order
+    pub fn scan(&mut self, markdown: &str) -> Vec<String> {
+        let mut anchors = Vec::new();
+        let mut in_fence = false;
+        for line in markdown.lines() {
+            if line.starts_with("```") {
+                in_fence = !in_fence;
+                continue;
+            }
+            if in_fence {
+                continue;
+            }
+            let level = line.chars().take_while(|&c| c == '#').count();
+            if (1..=6).contains(&level) && line[level..].starts_with(' ') {
+                anchors.push(self.next(&line[level..]));
+            }
+        }
+        anchors
+    }
 }
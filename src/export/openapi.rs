@@ -0,0 +1,96 @@
+//! OpenAPI annotations for API requirements
+//!
+//! Emits one YAML document per requirement tagged `api`, holding the fields
+//! to paste into an OpenAPI operation: the SHALL text as `description`, the
+//! requirement id as `x-requirement`, and its scenarios as `x-acceptance`.
+//! Requirements without the tag are left out.
+
+use anyhow::{Context, Result};
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::Path;
+
+use super::ensure_parent_dir;
+use crate::models::{Requirement, Scenario, SpecData};
+
+/// Tag that marks a requirement as part of an API contract
+pub const API_TAG: &str = "api";
+
+/// OpenAPI annotation renderer for specs
+pub struct OpenApiCommentsRenderer;
+
+impl OpenApiCommentsRenderer {
+    /// Render the annotations for one spec's `api` requirements
+    pub fn render_spec(spec: &SpecData) -> Result<String> {
+        let mut out = String::new();
+        for req in spec.requirements.iter().filter(|r| is_api(r)) {
+            out.push_str(&format!(
+                "---\n# {} / {}: {}\n",
+                spec.spec_id, req.id, req.title
+            ));
+            out.push_str(
+                &serde_yaml::to_string(&annotation(spec, req))
+                    .context("Failed to serialize OpenAPI annotation")?,
+            );
+        }
+        Ok(out)
+    }
+
+    /// Render annotations for several specs, in order
+    pub fn render_multi(specs: &[SpecData]) -> Result<String> {
+        let mut out = String::new();
+        for spec in specs {
+            out.push_str(&Self::render_spec(spec)?);
+        }
+        Ok(out)
+    }
+
+    /// Export a single spec's annotations to a file
+    pub fn export_to_file(spec: &SpecData, output_path: &Path) -> Result<()> {
+        ensure_parent_dir(output_path)?;
+        fs::write(output_path, Self::render_spec(spec)?)
+            .context("Failed to write OpenAPI annotations")?;
+        Ok(())
+    }
+
+    /// Export several specs' annotations to one file
+    pub fn export_multi(specs: &[SpecData], output_path: &Path) -> Result<()> {
+        ensure_parent_dir(output_path)?;
+        fs::write(output_path, Self::render_multi(specs)?)
+            .context("Failed to write OpenAPI annotations")?;
+        Ok(())
+    }
+}
+
+fn is_api(req: &Requirement) -> bool {
+    req.tags.iter().any(|t| t.eq_ignore_ascii_case(API_TAG))
+}
+
+fn annotation(spec: &SpecData, req: &Requirement) -> Mapping {
+    let mut fields = Mapping::new();
+    fields.insert("description".into(), req.shall.clone().into());
+    fields.insert("x-requirement".into(), req.id.clone().into());
+    fields.insert("x-spec".into(), spec.spec_id.clone().into());
+    if !req.scenarios.is_empty() {
+        fields.insert(
+            "x-acceptance".into(),
+            Value::Sequence(req.scenarios.iter().map(acceptance).collect()),
+        );
+    }
+    fields
+}
+
+fn acceptance(scenario: &Scenario) -> Value {
+    let lines =
+        |items: &[String]| Value::Sequence(items.iter().cloned().map(Value::from).collect());
+    let mut fields = Mapping::new();
+    fields.insert("id".into(), scenario.id.clone().into());
+    fields.insert("name".into(), scenario.name.clone().into());
+    fields.insert("given".into(), lines(&scenario.given));
+    fields.insert("when".into(), scenario.when.clone().into());
+    fields.insert("then".into(), lines(&scenario.then));
+    if !scenario.edge_cases.is_empty() {
+        fields.insert("edge_cases".into(), lines(&scenario.edge_cases));
+    }
+    Value::Mapping(fields)
+}
@@ -0,0 +1,93 @@
+//! PDF export via an HTML-to-PDF step
+//!
+//! Specs are rendered with [`HtmlRenderer`] and converted by `wkhtmltopdf`.
+//! The converter is only wired in when manifold is built with the `pdf`
+//! cargo feature, so the default build carries no extra dependencies.
+
+use anyhow::Result;
+use std::path::Path;
+
+use super::html::HtmlRenderer;
+use crate::models::SpecData;
+
+/// Environment variable overriding the `wkhtmltopdf` binary location
+#[cfg(feature = "pdf")]
+pub const WKHTMLTOPDF_ENV: &str = "MANIFOLD_WKHTMLTOPDF";
+
+/// PDF renderer for specs
+pub struct PdfRenderer;
+
+impl PdfRenderer {
+    /// Export spec to PDF file
+    pub fn export_to_file(spec: &SpecData, output_path: &Path, with_tables: bool) -> Result<()> {
+        let html = HtmlRenderer::render_spec(spec, with_tables);
+        html_to_pdf(&html, output_path)
+    }
+
+    /// Export multiple specs to a single PDF document
+    pub fn export_multi(specs: &[SpecData], output_path: &Path, with_tables: bool) -> Result<()> {
+        let html = HtmlRenderer::render_multi(specs, with_tables);
+        html_to_pdf(&html, output_path)
+    }
+}
+
+/// Convert an HTML document to PDF using `wkhtmltopdf`
+#[cfg(feature = "pdf")]
+pub fn html_to_pdf(html: &str, output_path: &Path) -> Result<()> {
+    use anyhow::{anyhow, bail, Context};
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    super::ensure_parent_dir(output_path)?;
+
+    let binary = std::env::var(WKHTMLTOPDF_ENV).unwrap_or_else(|_| "wkhtmltopdf".to_string());
+
+    // Read HTML from stdin ("-") and write the PDF directly to the output path
+    let mut child = Command::new(&binary)
+        .args(["--quiet", "--encoding", "utf-8", "-"])
+        .arg(output_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                anyhow!(
+                    "PDF export requires `wkhtmltopdf`, which was not found on PATH. \
+                     Install it or set {} to its location.",
+                    WKHTMLTOPDF_ENV
+                )
+            } else {
+                anyhow!("Failed to start {}: {}", binary, e)
+            }
+        })?;
+
+    child
+        .stdin
+        .take()
+        .context("Failed to open wkhtmltopdf stdin")?
+        .write_all(html.as_bytes())
+        .context("Failed to send HTML to wkhtmltopdf")?;
+
+    let output = child
+        .wait_with_output()
+        .context("Failed to wait for wkhtmltopdf")?;
+
+    if !output.status.success() {
+        bail!(
+            "wkhtmltopdf failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(())
+}
+
+/// Convert an HTML document to PDF (unavailable without the `pdf` feature)
+#[cfg(not(feature = "pdf"))]
+pub fn html_to_pdf(_html: &str, _output_path: &Path) -> Result<()> {
+    anyhow::bail!(
+        "PDF export is not available in this build. \
+         Rebuild manifold with `cargo build --features pdf` and install `wkhtmltopdf`."
+    )
+}
@@ -0,0 +1,33 @@
+//! YAML form of the canonical spec, for editing outside the tool
+//!
+//! Unlike the document formats this is the full spec, not a rendering of it:
+//! `manifold import spec --yaml` reads it back into the same `SpecData`.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use super::ensure_parent_dir;
+use crate::models::SpecData;
+
+/// YAML serializer for canonical specs
+pub struct YamlRenderer;
+
+impl YamlRenderer {
+    /// Serialize a spec as YAML, field for field
+    pub fn render_spec(spec: &SpecData) -> Result<String> {
+        serde_yaml::to_string(spec).context("Failed to serialize spec as YAML")
+    }
+
+    /// Parse a spec written by `render_spec` (or by hand)
+    pub fn parse_spec(yaml: &str) -> Result<SpecData> {
+        serde_yaml::from_str(yaml).context("Input is not a valid manifold spec")
+    }
+
+    /// Export a single spec to a YAML file
+    pub fn export_to_file(spec: &SpecData, output_path: &Path) -> Result<()> {
+        ensure_parent_dir(output_path)?;
+        fs::write(output_path, Self::render_spec(spec)?).context("Failed to write YAML file")?;
+        Ok(())
+    }
+}
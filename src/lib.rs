@@ -1,13 +1,18 @@
 //! Manifold - A local-first, MCP-native, JSON-canonical specification engine
 
+pub mod agent;
 pub mod collab;
 pub mod commands;
+pub mod completions;
 pub mod config;
 pub mod db;
 pub mod export;
 pub mod llm;
 pub mod mcp;
 pub mod models;
+pub mod search;
+pub mod templates;
 pub mod tui;
+pub mod util;
 pub mod validation;
 pub mod workflow;
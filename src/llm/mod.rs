@@ -3,13 +3,16 @@
 //! Provides interactive session for editing specs with LLM assistance
 
 use anyhow::{Context, Result};
+use async_trait::async_trait;
 use rustyline::{error::ReadlineError, DefaultEditor};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
 
+use crate::collab::Conflict;
 use crate::config::ManifoldPaths;
 use crate::db::Database;
 use crate::models::SpecData;
+use crate::util::block_on_fresh_runtime;
 
 /// LLM API configuration
 #[derive(Debug, Clone)]
@@ -17,20 +20,85 @@ pub struct LlmConfig {
     pub api_url: String,
     pub api_key: String,
     pub model: String,
+    pub temperature: f64,
+    pub max_tokens: u32,
+    pub top_p: f64,
 }
 
 impl Default for LlmConfig {
     fn default() -> Self {
+        let settings = crate::config::LlmConfig::default();
         Self {
             api_url: std::env::var("OPENAI_API_BASE")
                 .unwrap_or_else(|_| "https://api.openai.com/v1".to_string()),
             api_key: std::env::var("OPENAI_API_KEY")
                 .unwrap_or_else(|_| "sk-dummy-key-for-testing".to_string()),
             model: std::env::var("OPENAI_MODEL").unwrap_or_else(|_| "gpt-4".to_string()),
+            temperature: settings.temperature,
+            max_tokens: settings.max_tokens,
+            top_p: settings.top_p,
         }
     }
 }
 
+impl LlmConfig {
+    /// Settings from config.toml, falling back to the OPENAI_* variables
+    pub fn from_settings(settings: &crate::config::LlmConfig) -> Self {
+        let defaults = Self::default();
+        Self {
+            api_url: settings.endpoint.clone().unwrap_or(defaults.api_url),
+            api_key: defaults.api_key,
+            model: settings.model.clone().unwrap_or(defaults.model),
+            temperature: settings.temperature,
+            max_tokens: settings.max_tokens,
+            top_p: settings.top_p,
+        }
+    }
+
+    /// Change one sampling parameter by name, as typed after `/set`
+    ///
+    /// Accepts `temp`/`temperature` (0-2), `max_tokens` (at least 1), and
+    /// `top_p` (0-1).
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "temp" | "temperature" => {
+                let temperature: f64 = value
+                    .parse()
+                    .with_context(|| format!("Invalid temperature: {}", value))?;
+                if !(0.0..=2.0).contains(&temperature) {
+                    anyhow::bail!("Temperature must be between 0 and 2");
+                }
+                self.temperature = temperature;
+            }
+            "max_tokens" => {
+                let max_tokens: u32 = value
+                    .parse()
+                    .with_context(|| format!("Invalid max_tokens: {}", value))?;
+                if max_tokens == 0 {
+                    anyhow::bail!("max_tokens must be at least 1");
+                }
+                self.max_tokens = max_tokens;
+            }
+            "top_p" => {
+                let top_p: f64 = value
+                    .parse()
+                    .with_context(|| format!("Invalid top_p: {}", value))?;
+                if !(0.0..=1.0).contains(&top_p) {
+                    anyhow::bail!("top_p must be between 0 and 1");
+                }
+                self.top_p = top_p;
+            }
+            _ => anyhow::bail!("Unknown setting: {}. Use temp, max_tokens, or top_p", key),
+        }
+        Ok(())
+    }
+
+    /// Whether a real API key is set (the placeholder doesn't count)
+    pub fn is_enabled(&self) -> bool {
+        !self.api_key.is_empty() && self.api_key != "sk-dummy-key-for-testing"
+    }
+}
+
 /// LLM chat message
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessage {
@@ -49,14 +117,90 @@ struct ChatChoice {
     message: ChatMessage,
 }
 
+/// Sends chat completion requests to an LLM
+///
+/// Takes the full request body so alternative clients (e.g. in tests) see
+/// exactly what would go over the wire.
+#[async_trait]
+pub trait ChatClient: Send + Sync {
+    async fn complete(&self, request: &Value) -> Result<ChatMessage>;
+}
+
+/// OpenAI-compatible HTTP chat client
+pub struct HttpChatClient {
+    client: reqwest::Client,
+    config: LlmConfig,
+}
+
+impl HttpChatClient {
+    pub fn new(config: LlmConfig) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            config,
+        }
+    }
+}
+
+#[async_trait]
+impl ChatClient for HttpChatClient {
+    async fn complete(&self, request: &Value) -> Result<ChatMessage> {
+        let response = self
+            .client
+            .post(format!("{}/chat/completions", self.config.api_url))
+            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .header("Content-Type", "application/json")
+            .json(request)
+            .send()
+            .await
+            .context("Failed to send request to LLM API")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            anyhow::bail!("LLM API error ({}): {}", status, error_text);
+        }
+
+        let completion: ChatCompletionResponse = response
+            .json()
+            .await
+            .context("Failed to parse LLM response")?;
+
+        Ok(completion
+            .choices
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("No response from LLM"))?
+            .message
+            .clone())
+    }
+}
+
+/// One critique item returned by `/suggest`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Suggestion {
+    /// missing_scenario, weak_shall, orphan_requirement, or other
+    pub category: String,
+    /// Requirement/task id the suggestion is about, if any
+    #[serde(default)]
+    pub target: Option<String>,
+    pub suggestion: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SuggestionList {
+    suggestions: Vec<Suggestion>,
+}
+
 /// Interactive LLM editing session
 pub struct LlmSession {
     spec_id: String,
     db: Database,
     llm_config: LlmConfig,
     conversation_history: Vec<ChatMessage>,
-    client: reqwest::Client,
+    client: Box<dyn ChatClient>,
     llm_enabled: bool,
+    workflow_policy: crate::workflow::WorkflowPolicy,
+    /// Who changes made in the session are attributed to
+    actor: String,
 }
 
 impl LlmSession {
@@ -66,32 +210,59 @@ impl LlmSession {
 
         // Load config and use it for LLM settings
         let config = crate::config::load_config()?;
-        let llm_config = LlmConfig {
-            api_url: config.llm.endpoint.unwrap_or_else(|| {
-                std::env::var("OPENAI_API_BASE")
-                    .unwrap_or_else(|_| "https://api.openai.com/v1".to_string())
-            }),
-            api_key: std::env::var("OPENAI_API_KEY")
-                .unwrap_or_else(|_| "sk-dummy-key-for-testing".to_string()),
-            model: config.llm.model.unwrap_or_else(|| {
-                std::env::var("OPENAI_MODEL").unwrap_or_else(|_| "gpt-4".to_string())
-            }),
-        };
+        let llm_config = LlmConfig::from_settings(&config.llm);
 
         // Check if API key is set (allow dummy key for testing)
-        let llm_enabled =
-            !llm_config.api_key.is_empty() && llm_config.api_key != "sk-dummy-key-for-testing";
+        let llm_enabled = llm_config.is_enabled();
 
         Ok(Self {
             spec_id,
             db,
+            client: Box::new(HttpChatClient::new(llm_config.clone())),
             llm_config,
             conversation_history: Vec::new(),
-            client: reqwest::Client::new(),
             llm_enabled,
+            actor: crate::commands::channel_actor(&config, "llm"),
+            workflow_policy: config.workflow,
         })
     }
 
+    /// Create a session that talks to the LLM through `client`
+    #[allow(dead_code)]
+    pub fn with_client(spec_id: String, db: Database, client: Box<dyn ChatClient>) -> Self {
+        Self {
+            spec_id,
+            db,
+            llm_config: LlmConfig::default(),
+            conversation_history: Vec::new(),
+            client,
+            llm_enabled: true,
+            workflow_policy: crate::workflow::WorkflowPolicy::default(),
+            actor: crate::commands::channel_actor(&crate::config::Config::default(), "llm"),
+        }
+    }
+
+    /// Use `llm_config` for model and sampling parameters
+    #[allow(dead_code)]
+    pub fn with_llm_config(mut self, llm_config: LlmConfig) -> Self {
+        self.llm_config = llm_config;
+        self
+    }
+
+    /// Use `policy` for workflow gates and transition hooks
+    #[allow(dead_code)]
+    pub fn with_workflow_policy(mut self, policy: crate::workflow::WorkflowPolicy) -> Self {
+        self.workflow_policy = policy;
+        self
+    }
+
+    /// Attribute changes made in the session to `actor`
+    #[allow(dead_code)]
+    pub fn with_actor(mut self, actor: String) -> Self {
+        self.actor = actor;
+        self
+    }
+
     /// Start the interactive editing loop
     pub async fn run(&mut self) -> Result<()> {
         // Load initial spec
@@ -118,6 +289,8 @@ impl LlmSession {
         println!("  /status     - Show current spec status");
         println!("  /advance    - Advance workflow stage");
         println!("  /show       - Show full spec JSON");
+        println!("  /suggest    - Critique the spec without editing it");
+        println!("  /set        - Set temp, max_tokens, or top_p (e.g. /set temp 0.2)");
         println!("  /exit       - Exit session");
         println!();
 
@@ -239,7 +412,7 @@ Be concise and practical. Focus on quality requirements engineering practices."#
     }
 
     /// Send a message to the LLM and get response
-    async fn chat(&mut self, user_message: &str) -> Result<String> {
+    pub async fn chat(&mut self, user_message: &str) -> Result<String> {
         // Add user message to history
         self.conversation_history.push(ChatMessage {
             role: "user".to_string(),
@@ -250,41 +423,12 @@ Be concise and practical. Focus on quality requirements engineering practices."#
         let request_body = json!({
             "model": self.llm_config.model,
             "messages": self.conversation_history,
-            "temperature": 0.7,
-            "max_tokens": 1500,
+            "temperature": self.llm_config.temperature,
+            "max_tokens": self.llm_config.max_tokens,
+            "top_p": self.llm_config.top_p,
         });
 
-        // Call LLM API
-        let response = self
-            .client
-            .post(format!("{}/chat/completions", self.llm_config.api_url))
-            .header(
-                "Authorization",
-                format!("Bearer {}", self.llm_config.api_key),
-            )
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await
-            .context("Failed to send request to LLM API")?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("LLM API error ({}): {}", status, error_text);
-        }
-
-        let completion: ChatCompletionResponse = response
-            .json()
-            .await
-            .context("Failed to parse LLM response")?;
-
-        let assistant_message = completion
-            .choices
-            .first()
-            .ok_or_else(|| anyhow::anyhow!("No response from LLM"))?
-            .message
-            .clone();
+        let assistant_message = self.client.complete(&request_body).await?;
 
         // Add to history
         self.conversation_history.push(assistant_message.clone());
@@ -292,8 +436,8 @@ Be concise and practical. Focus on quality requirements engineering practices."#
         Ok(assistant_message.content)
     }
 
-    /// Handle slash commands
-    async fn handle_command(&mut self, command: &str) -> Result<bool> {
+    /// Handle slash commands, returning whether the session should end
+    pub async fn handle_command(&mut self, command: &str) -> Result<bool> {
         let parts: Vec<&str> = command.split_whitespace().collect();
         let cmd = parts.first().unwrap_or(&"");
 
@@ -333,40 +477,28 @@ Be concise and practical. Focus on quality requirements engineering practices."#
 
                 // Use workflow engine to check
                 let spec = self.load_spec()?;
-                match crate::workflow::WorkflowEngine::can_advance(&spec) {
+                let policy = &self.workflow_policy;
+                match crate::workflow::WorkflowEngine::can_advance_with(&spec, policy) {
                     Ok(next_stage) => {
                         println!("✓ Can advance to: {}", next_stage);
                         println!("\nAdvancing workflow stage...");
 
                         // Actually advance
-                        match crate::workflow::WorkflowEngine::advance_stage(
+                        match crate::workflow::WorkflowEngine::advance_stage_with(
                             &spec,
                             next_stage.clone(),
+                            policy,
                         ) {
                             Ok(transition) => {
-                                // Update spec
                                 let mut updated_spec = spec.clone();
-                                if !updated_spec.stages_completed.contains(&spec.stage) {
-                                    updated_spec.stages_completed.push(spec.stage.clone());
-                                }
-                                updated_spec.stage = transition.to.clone();
-                                updated_spec.history.updated_at = chrono::Utc::now().timestamp();
-
-                                // Log event
-                                self.db.log_workflow_event(
-                                    &updated_spec.spec_id,
-                                    &transition.to.to_string(),
-                                    &transition.event.as_string(),
-                                    "llm-session",
-                                    updated_spec.history.updated_at,
-                                    Some(&format!(
-                                        "Advanced from {} to {}",
-                                        transition.from, transition.to
-                                    )),
+                                crate::workflow::apply_transition(
+                                    &self.db,
+                                    &mut updated_spec,
+                                    &transition,
+                                    &self.actor,
+                                    policy,
                                 )?;
 
-                                self.db.update_spec(&updated_spec)?;
-
                                 println!("✓ Advanced to stage: {}", transition.to);
 
                                 // Update system prompt with new stage
@@ -384,29 +516,239 @@ Be concise and practical. Focus on quality requirements engineering practices."#
                 println!();
                 Ok(false)
             }
+            "/suggest" => {
+                if !self.llm_enabled {
+                    println!("LLM not enabled. Set OPENAI_API_KEY to use /suggest.");
+                    println!();
+                    return Ok(false);
+                }
+
+                println!("\n🔍 Reviewing spec...");
+                let suggestions = self.suggest().await?;
+                if suggestions.is_empty() {
+                    println!("✓ No suggestions");
+                } else {
+                    println!("\n💡 Suggestions ({}):", suggestions.len());
+                    for s in &suggestions {
+                        match &s.target {
+                            Some(target) => {
+                                println!("  [{}] {}: {}", s.category, target, s.suggestion)
+                            }
+                            None => println!("  [{}] {}", s.category, s.suggestion),
+                        }
+                    }
+                }
+                println!();
+                Ok(false)
+            }
+            "/set" => {
+                let (Some(key), Some(value)) = (parts.get(1), parts.get(2)) else {
+                    println!("Usage: /set <temp|max_tokens|top_p> <value>");
+                    println!(
+                        "Current: temp {}, max_tokens {}, top_p {}",
+                        self.llm_config.temperature,
+                        self.llm_config.max_tokens,
+                        self.llm_config.top_p
+                    );
+                    println!();
+                    return Ok(false);
+                };
+                self.llm_config.set(key, value)?;
+                println!("✓ {} set to {}", key, value);
+                println!();
+                Ok(false)
+            }
             "/exit" | "/quit" => {
                 println!("\n👋 Exiting LLM editing session...");
                 Ok(true)
             }
             _ => {
                 println!("Unknown command: {}", cmd);
-                println!("Available commands: /status, /show, /advance, /exit");
+                println!("Available commands: /status, /show, /advance, /suggest, /set, /exit");
                 println!();
                 Ok(false)
             }
         }
     }
 
+    /// Ask the LLM to critique the spec
+    ///
+    /// Sent as a one-off request grounded in the current lint warnings; the
+    /// spec and the chat history are left untouched.
+    pub async fn suggest(&self) -> Result<Vec<Suggestion>> {
+        let spec = self.load_spec()?;
+        let request_body = json!({
+            "model": self.llm_config.model,
+            "messages": suggest_messages(&spec),
+            "temperature": 0.2,
+            "max_tokens": self.llm_config.max_tokens,
+            "top_p": self.llm_config.top_p,
+        });
+
+        let reply = self.client.complete(&request_body).await?;
+        Ok(parse_suggestions(&reply.content))
+    }
+
     /// Load current spec from database
     fn load_spec(&self) -> Result<SpecData> {
-        let spec_row = self
-            .db
-            .get_spec(&self.spec_id)?
-            .ok_or_else(|| anyhow::anyhow!("Spec not found: {}", self.spec_id))?;
+        self.db
+            .get_spec_data(&self.spec_id)?
+            .ok_or_else(|| anyhow::anyhow!("Spec not found: {}", self.spec_id))
+    }
+}
 
-        let spec: SpecData =
-            serde_json::from_value(spec_row.data).context("Failed to parse spec data")?;
+/// Build the fixed critique prompt for `/suggest`
+fn suggest_messages(spec: &SpecData) -> Vec<ChatMessage> {
+    let spec_json = serde_json::to_string_pretty(spec).unwrap_or_default();
+
+    let warnings = crate::validation::lint_spec(spec);
+    let warnings = if warnings.is_empty() {
+        "(none)".to_string()
+    } else {
+        warnings
+            .iter()
+            .map(|w| format!("- {}", w))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    // Requirements no task traces back to
+    let orphans: Vec<&str> = spec
+        .requirements
+        .iter()
+        .filter(|r| !spec.tasks.iter().any(|t| t.requirement_ids.contains(&r.id)))
+        .map(|r| r.id.as_str())
+        .collect();
+    let orphans = if orphans.is_empty() {
+        "(none)".to_string()
+    } else {
+        orphans.join(", ")
+    };
+
+    let system = r#"You are an expert requirements engineer reviewing a Manifold specification.
+Critique it; do not rewrite it. Look for:
+1. missing_scenario: requirements without GIVEN/WHEN/THEN scenarios, or missing edge cases
+2. weak_shall: vague, untestable, or compound SHALL statements
+3. orphan_requirement: requirements that no task implements
+4. other: anything else that blocks review
+
+Treat the lint warnings you are given as confirmed findings and explain how to fix them.
+Respond with JSON only, in this form:
+{"suggestions": [{"category": "weak_shall", "target": "req-1", "suggestion": "..."}]}"#;
+
+    let user = format!(
+        "Lint warnings:\n{}\n\nRequirements without tasks: {}\n\nSpec:\n```json\n{}\n```",
+        warnings, orphans, spec_json
+    );
+
+    vec![
+        ChatMessage {
+            role: "system".to_string(),
+            content: system.to_string(),
+        },
+        ChatMessage {
+            role: "user".to_string(),
+            content: user,
+        },
+    ]
+}
 
-        Ok(spec)
+/// Parse the LLM reply, tolerating code fences or prose around the JSON
+fn parse_suggestions(content: &str) -> Vec<Suggestion> {
+    let json = match (content.find('{'), content.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &content[start..=end],
+        _ => content,
+    };
+
+    match serde_json::from_str::<SuggestionList>(json) {
+        Ok(list) => list.suggestions,
+        // Unstructured reply: still show it rather than dropping it
+        Err(_) => vec![Suggestion {
+            category: "other".to_string(),
+            target: None,
+            suggestion: content.trim().to_string(),
+        }],
     }
 }
+
+/// Ask the LLM for a merged value for a conflict
+///
+/// The reply must be a JSON value; anything else is an error, so a bad
+/// suggestion is never written to a spec. Model, `max_tokens` and `top_p`
+/// come from `config`, but temperature is pinned to 0 so the same conflict
+/// gets the same merge.
+pub async fn suggest_resolution(
+    client: &dyn ChatClient,
+    config: &LlmConfig,
+    conflict: &Conflict,
+) -> Result<Value> {
+    let request_body = json!({
+        "model": config.model,
+        "messages": resolution_messages(conflict),
+        "temperature": 0.0,
+        "max_tokens": config.max_tokens,
+        "top_p": config.top_p,
+    });
+
+    let reply = client.complete(&request_body).await?;
+    parse_suggested_value(&reply.content)
+}
+
+/// [`suggest_resolution`] over HTTP, for callers without an async runtime
+pub fn suggest_resolution_blocking(config: &LlmConfig, conflict: &Conflict) -> Result<Value> {
+    let config = config.clone();
+    let conflict = conflict.clone();
+
+    block_on_fresh_runtime(move || async move {
+        let client = HttpChatClient::new(config.clone());
+        suggest_resolution(&client, &config, &conflict).await
+    })
+}
+
+/// Build the three-way merge prompt for a conflict
+fn resolution_messages(conflict: &Conflict) -> Vec<ChatMessage> {
+    let pretty = |value: &Value| serde_json::to_string_pretty(value).unwrap_or_default();
+    let base = conflict
+        .base_value
+        .as_ref()
+        .map(pretty)
+        .unwrap_or_else(|| "(unknown)".to_string());
+
+    let system = r#"You merge conflicting edits to one field of a Manifold specification.
+You get the common base value and the local and remote edits. Produce a single
+merged value that keeps the intent of both edits; when they truly contradict,
+prefer the more specific or more recent-sounding one.
+Respond with the merged JSON value only: no prose, no code fences."#;
+
+    let user = format!(
+        "Field: {}\n\nBase:\n{}\n\nLocal:\n{}\n\nRemote:\n{}",
+        conflict.field_path,
+        base,
+        pretty(&conflict.local_value),
+        pretty(&conflict.remote_value)
+    );
+
+    vec![
+        ChatMessage {
+            role: "system".to_string(),
+            content: system.to_string(),
+        },
+        ChatMessage {
+            role: "user".to_string(),
+            content: user,
+        },
+    ]
+}
+
+/// Parse the suggested value, tolerating a code fence around it
+fn parse_suggested_value(content: &str) -> Result<Value> {
+    let trimmed = content.trim();
+    let json = trimmed
+        .strip_prefix("```")
+        .and_then(|rest| rest.strip_suffix("```"))
+        .map(|fenced| fenced.trim_start_matches("json").trim())
+        .unwrap_or(trimmed);
+
+    serde_json::from_str(json)
+        .with_context(|| format!("LLM suggestion is not valid JSON: {}", trimmed))
+}
@@ -2,19 +2,24 @@
 //!
 //! A local-first, MCP-native, JSON-canonical specification engine
 
+mod agent;
 mod collab;
 mod commands;
+mod completions;
 mod config;
 mod db;
 mod export;
 mod llm;
 mod mcp;
 mod models;
+mod search;
+mod templates;
 mod tui;
+mod util;
 mod validation;
 mod workflow;
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 
 #[derive(Parser)]
 #[command(name = "manifold")]
@@ -24,6 +29,14 @@ use clap::{Parser, Subcommand};
     about = "The Global Spec Manifold - A local-first, MCP-native specification engine"
 )]
 struct Cli {
+    /// Who changes are attributed to [default: $MANIFOLD_ACTOR, config user, or $USER]
+    #[arg(long, global = true)]
+    actor: Option<String>,
+
+    /// Operate on a named manifold from `manifold profile list`
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -31,20 +44,50 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Initialize manifold (first-time setup)
-    Init,
+    Init {
+        /// Also create a git sync repository under ~/.manifold/sync
+        #[arg(long)]
+        git: bool,
+
+        /// Remote URL for the sync repository (requires --git)
+        #[arg(long, requires = "git")]
+        remote: Option<String>,
+
+        /// Recreate missing directories, config, schema, and tables,
+        /// keeping existing specs
+        #[arg(long, conflicts_with = "git")]
+        repair: bool,
+    },
 
     /// Create a new spec
     New {
         /// Project identifier (e.g., "auric-raptor")
-        project_id: String,
+        #[arg(required_unless_present = "interactive")]
+        project_id: Option<String>,
 
         /// Human-readable name for the spec
         #[arg(short, long)]
         name: Option<String>,
 
-        /// Boundary: personal, work, or company
-        #[arg(short, long, default_value = "personal")]
-        boundary: String,
+        /// Boundary: personal, work, or company [default: config default_boundary]
+        #[arg(short, long)]
+        boundary: Option<String>,
+
+        /// Prompt for project id, name, boundary, and initial requirements
+        #[arg(short, long, conflicts_with_all = ["project_id", "name", "boundary"])]
+        interactive: bool,
+
+        /// Start from a saved template
+        #[arg(short, long)]
+        template: Option<String>,
+
+        /// Person accountable for the spec
+        #[arg(long)]
+        owner: Option<String>,
+
+        /// Team the spec belongs to
+        #[arg(long)]
+        team: Option<String>,
     },
 
     /// List all specs
@@ -56,16 +99,77 @@ enum Commands {
         /// Filter by workflow stage
         #[arg(short, long)]
         stage: Option<String>,
+
+        /// Only specs owned by this person
+        #[arg(long)]
+        owner: Option<String>,
+
+        /// Only specs belonging to this team
+        #[arg(long)]
+        team: Option<String>,
+
+        /// Sort order: updated, created, project, or stage
+        #[arg(long, default_value = "updated")]
+        sort: String,
+
+        /// Output format: summary, json, ndjson (one spec per line), or tree
+        /// (grouped by boundary and stage)
+        #[arg(long, default_value = "summary")]
+        format: String,
+
+        /// Maximum number of specs to show
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// Number of specs to skip
+        #[arg(long, default_value_t = 0)]
+        offset: usize,
     },
 
     /// Search specs using full-text search
     Search {
-        /// Search query
+        /// Search query (a regex pattern with --regex)
         query: String,
 
+        /// Match the query as a regex against spec JSON fields
+        #[arg(long, conflicts_with_all = ["limit", "offset"])]
+        regex: bool,
+
+        /// With --regex, only scan specs matching this full-text query
+        #[arg(long, requires = "regex")]
+        fts: Option<String>,
+
+        /// Only search specs in this boundary
+        #[arg(short, long)]
+        boundary: Option<String>,
+
+        /// Only search specs in this workflow stage
+        #[arg(short, long)]
+        stage: Option<String>,
+
+        /// Also match decisions and/or review comments (e.g. decisions,reviews)
+        #[arg(long, conflicts_with_all = ["regex", "highlight_json"])]
+        include: Option<String>,
+
         /// Output as JSON
-        #[arg(long)]
+        #[arg(long, conflicts_with = "format")]
         json: bool,
+
+        /// Output JSON with pointers to the fields each hit matched
+        #[arg(long, conflicts_with_all = ["regex", "format"])]
+        highlight_json: bool,
+
+        /// Output format: summary, json, or ndjson (one spec per line)
+        #[arg(long)]
+        format: Option<String>,
+
+        /// Maximum number of results to show
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// Number of results to skip
+        #[arg(long, default_value_t = 0)]
+        offset: usize,
     },
 
     /// Show a spec by ID
@@ -73,19 +177,120 @@ enum Commands {
         /// Spec ID
         id: String,
 
+        /// Output as JSON
+        #[arg(long, conflicts_with = "markdown")]
+        json: bool,
+
+        /// With --json, canonical minified JSON (sorted keys, one line)
+        #[arg(long, requires = "json")]
+        compact: bool,
+
+        /// Output as Markdown, as `export` writes it
+        #[arg(long)]
+        markdown: bool,
+
+        /// With --markdown, only this section: requirements, tasks,
+        /// decisions, or scenarios (repeatable)
+        #[arg(long = "section", value_name = "NAME", requires = "markdown")]
+        sections: Vec<String>,
+    },
+
+    /// Show a spec as it was at a past time (read-only)
+    Replay {
+        /// Spec ID
+        id: String,
+
+        /// Point in time: unix seconds, YYYY-MM-DD, or RFC 3339
+        #[arg(long)]
+        at: String,
+
         /// Output as JSON
         #[arg(long)]
         json: bool,
     },
 
+    /// Show a requirement within a spec
+    Requirements {
+        /// Spec ID
+        id: String,
+
+        /// Requirement ID (e.g., req-1)
+        req_id: String,
+
+        /// Show superseded wording
+        #[arg(long)]
+        history: bool,
+    },
+
+    /// Set a requirement's verification status
+    Verify {
+        /// Spec ID
+        id: String,
+
+        /// Requirement ID (e.g., req-1)
+        req_id: String,
+
+        /// not_started, in_progress, verified, or failed
+        status: String,
+    },
+
+    /// Set a spec's owner, and optionally its team
+    SetOwner {
+        /// Spec ID
+        id: String,
+
+        /// Person accountable for the spec
+        owner: String,
+
+        /// Team the spec belongs to
+        #[arg(long)]
+        team: Option<String>,
+    },
+
     /// Validate a spec against the schema
     Validate {
         /// Spec ID
-        id: String,
+        #[arg(required_unless_present = "all")]
+        id: Option<String>,
+
+        /// Validate every spec in parallel instead of one
+        #[arg(long, conflicts_with_all = ["id", "fix"])]
+        all: bool,
 
         /// Strict mode (fail on warnings)
         #[arg(long)]
         strict: bool,
+
+        /// Apply safe fixes first: renumber malformed ids (updating task
+        /// references) and add TODO acceptance placeholders
+        #[arg(long)]
+        fix: bool,
+
+        /// Output {spec_id, schema_valid, errors, warnings, strict} as JSON
+        ///
+        /// Exit codes: 0 pass, 1 schema failure, 2 warnings under --strict.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Merge two specs' requirements, tasks, and decisions by id
+    ///
+    /// Ids that collide with different content keep the first spec's version
+    /// and are recorded as conflicts. The second spec is left unchanged.
+    Merge {
+        /// Spec whose fields and overlapping items win
+        id_a: String,
+
+        /// Spec merged in
+        id_b: String,
+
+        /// Update the first spec instead of creating a new one
+        #[arg(long)]
+        in_place: bool,
+
+        /// Name for the new spec (defaults to the first spec's name)
+        #[arg(long, conflicts_with = "in_place")]
+        name: Option<String>,
     },
 
     /// Join (merge) a spec into another boundary
@@ -102,20 +307,117 @@ enum Commands {
     },
 
     /// Start the MCP server (JSON-RPC 2.0 over stdio)
-    Serve,
+    Serve {
+        /// Listen on this Unix domain socket instead of stdio
+        #[arg(long)]
+        socket: Option<String>,
+
+        /// Append every request and response to this JSONL file
+        #[arg(long)]
+        stdio_log: Option<String>,
+
+        /// Rotate the traffic log once it exceeds this many bytes
+        #[arg(long, requires = "stdio_log", default_value_t = mcp::traffic_log::DEFAULT_MAX_BYTES)]
+        stdio_log_max_bytes: u64,
+    },
 
     /// Workflow operations (advance stage, show history)
     Workflow {
         /// Spec ID
-        id: String,
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        id: Option<String>,
+
+        /// Advance every ready spec (advance operation only)
+        #[arg(long)]
+        all: bool,
+
+        /// Only advance specs in this boundary (with --all)
+        #[arg(long, requires = "all")]
+        boundary: Option<String>,
+
+        /// Report what would be advanced without changing anything (with --all)
+        #[arg(long, requires = "all")]
+        dry_run: bool,
+
+        /// Also advance specs through the manual approval gate (with --all)
+        #[arg(long, requires = "all")]
+        include_approval: bool,
 
         /// Operation: advance, history, or status
         #[arg(short, long, default_value = "status")]
         operation: String,
 
-        /// Target stage for advance operation (optional, auto-advances if not specified)
+        /// Target stage for advance operation (optional, auto-advances if not specified).
+        /// With --all, only specs whose next stage is this one are advanced
         #[arg(long)]
         stage: Option<String>,
+
+        /// Jump to --stage past intermediate stages; their checks are waived
+        /// and logged (approval can't be skipped)
+        #[arg(long, requires_all = ["stage", "reason"], conflicts_with = "all")]
+        skip: bool,
+
+        /// Justification for --skip, recorded in the workflow history
+        #[arg(long, requires = "skip")]
+        reason: Option<String>,
+
+        /// Maximum number of events to show (history operation)
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// Only show events since a date (YYYY-MM-DD, RFC 3339, or unix seconds)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Output history as JSON with a typed event_type (history operation)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Rebuild the full-text search index from stored specs
+    Reindex,
+
+    /// Check for stage/boundary columns that disagree with the spec JSON,
+    /// and completed stages that disagree with the current stage
+    Doctor {
+        /// Re-derive stale columns and completed stages
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Show spec coverage: scenarios, task traceability, acceptance criteria
+    Stats {
+        /// Spec ID (all specs when omitted)
+        id: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show a quality report: workflow progress, coverage, task completion,
+    /// traceability gaps, open conflicts and reviews
+    Report {
+        /// Spec ID
+        id: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show a spec and refresh it whenever it changes
+    Watch {
+        /// Spec ID
+        id: String,
+
+        /// Polling interval in milliseconds
+        #[arg(long, default_value = "500")]
+        interval: u64,
+
+        /// Wait this long for changes to settle before re-rendering
+        #[arg(long, default_value = "300")]
+        debounce: u64,
     },
 
     /// Interactive LLM editing session
@@ -125,20 +427,79 @@ enum Commands {
     },
 
     /// Launch TUI dashboard
-    Tui,
+    Tui {
+        /// Open with the spec list filtered to this boundary
+        #[arg(short, long)]
+        boundary: Option<String>,
 
-    /// Export spec(s) to Markdown
+        /// Open with this spec selected
+        #[arg(long)]
+        spec: Option<String>,
+    },
+
+    /// Export spec(s) to Markdown, HTML, or PDF
     Export {
         /// Spec ID (or 'all' for all specs)
-        id: String,
+        #[arg(required_unless_present = "diff")]
+        id: Option<String>,
+
+        /// Write a Markdown change report between two specs instead
+        #[arg(
+            long,
+            num_args = 2,
+            value_names = ["ID_A", "ID_B"],
+            conflicts_with_all = ["id", "tables", "front_matter", "toc", "include_resolved_conflicts"]
+        )]
+        diff: Option<Vec<String>>,
 
         /// Output file path
         #[arg(short, long)]
         output: String,
 
+        /// With 'all', only export specs in this boundary
+        #[arg(short, long, conflicts_with = "diff")]
+        boundary: Option<String>,
+
         /// Use table formatting
         #[arg(long)]
         tables: bool,
+
+        /// Output format: markdown, html, pdf (requires the `pdf` feature), dot (Graphviz), asciidoc, yaml (the full spec), or openapi-comments (annotations for `api`-tagged requirements)
+        #[arg(short, long, default_value = "markdown")]
+        format: String,
+
+        /// Strip assignees and patch actors before exporting
+        #[arg(long)]
+        anonymize: bool,
+
+        /// Prepend YAML front matter (single-spec Markdown only)
+        #[arg(long)]
+        front_matter: bool,
+
+        /// Add a linked table of contents ('all' Markdown exports only)
+        #[arg(long)]
+        toc: bool,
+
+        /// Append an audit table of resolved conflicts (single-spec Markdown only)
+        #[arg(long)]
+        include_resolved_conflicts: bool,
+
+        /// Only export this section: requirements, tasks, decisions, or
+        /// scenarios (repeatable; single-spec Markdown only)
+        #[arg(long = "section", value_name = "NAME", conflicts_with = "diff")]
+        sections: Vec<String>,
+
+        /// Keep running and re-export whenever the spec(s) change
+        #[arg(long, conflicts_with = "diff")]
+        watch: bool,
+
+        /// With --watch, polling interval in milliseconds
+        #[arg(long, default_value = "500", requires = "watch")]
+        interval: u64,
+
+        /// With --watch, wait this long for changes to settle before exporting
+        #[arg(long, default_value = "300", requires = "watch")]
+        debounce: u64,
     },
 
     /// Git-based sync operations
@@ -147,6 +508,30 @@ enum Commands {
         operation: SyncOperationCli,
     },
 
+    /// Inspect background agents
+    Agent {
+        #[command(subcommand)]
+        operation: AgentOperationCli,
+    },
+
+    /// Freeze, list, diff, and restore point-in-time copies of a spec
+    Snapshot {
+        #[command(subcommand)]
+        operation: SnapshotOperationCli,
+    },
+
+    /// Switch between named manifolds, each with its own home directory
+    Profile {
+        #[command(subcommand)]
+        operation: ProfileOperationCli,
+    },
+
+    /// Scenario operations
+    Scenario {
+        #[command(subcommand)]
+        operation: ScenarioOperationCli,
+    },
+
     /// Review and approval operations
     Review {
         #[command(subcommand)]
@@ -158,6 +543,68 @@ enum Commands {
         #[command(subcommand)]
         operation: ConflictOperationCli,
     },
+
+    /// Spec template operations
+    Template {
+        #[command(subcommand)]
+        operation: TemplateOperationCli,
+    },
+
+    /// Mark a spec public so it is included in published bundles
+    Publish {
+        /// Spec ID
+        id: String,
+
+        /// Make the spec private again
+        #[arg(long)]
+        private: bool,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Manifold manifest operations
+    Manifest {
+        #[command(subcommand)]
+        operation: ManifestOperationCli,
+    },
+
+    /// Import specs from elsewhere
+    Import {
+        #[command(subcommand)]
+        operation: ImportOperationCli,
+    },
+
+    /// Copy the database to a file (SQLite online backup)
+    Backup {
+        /// Destination file
+        path: String,
+    },
+
+    /// Replace the database with a backup file
+    Restore {
+        /// Backup file written by `manifold backup`
+        path: String,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell: bash, zsh, fish, powershell, or elvish
+        shell: clap_complete::Shell,
+    },
+
+    /// Print spec or project ids for shell completion scripts
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        /// What to list: specs or projects
+        #[arg(value_parser = ["specs", "projects"])]
+        kind: String,
+    },
 }
 
 // Clap-compatible wrapper enums for CLI parsing
@@ -170,10 +617,19 @@ enum SyncOperationCli {
         repo: String,
 
         /// Remote URL (optional)
-        #[arg(short, long)]
+        #[arg(long)]
         remote: Option<String>,
     },
 
+    /// Clone an existing spec repository and import its specs
+    Clone {
+        /// Remote URL to clone
+        url: String,
+
+        /// Where to clone to (defaults to the sync directory)
+        path: Option<String>,
+    },
+
     /// Push spec(s) to git repository
     Push {
         /// Spec ID (or 'all' for all specs)
@@ -206,6 +662,21 @@ enum SyncOperationCli {
         branch: String,
     },
 
+    /// Pull on an interval, saving conflicts without resolving them
+    Agent {
+        /// Seconds between pulls
+        #[arg(long, default_value_t = 60)]
+        interval: u64,
+
+        /// Remote name
+        #[arg(long, default_value = "origin")]
+        remote: String,
+
+        /// Branch name
+        #[arg(long, default_value = "main")]
+        branch: String,
+    },
+
     /// Show sync status
     Status,
 
@@ -261,8 +732,16 @@ enum ReviewOperationCli {
         spec_id: Option<String>,
 
         /// Filter by status
-        #[arg(long)]
+        #[arg(long, conflicts_with_all = ["mine", "assigned_to"])]
         status: Option<String>,
+
+        /// Only your pending reviews, oldest first (user from config or $USER)
+        #[arg(long, conflicts_with = "assigned_to")]
+        mine: bool,
+
+        /// Only pending reviews assigned to this reviewer, oldest first
+        #[arg(long)]
+        assigned_to: Option<String>,
     },
 }
 
@@ -272,6 +751,14 @@ enum ConflictOperationCli {
     List {
         /// Spec ID (optional)
         spec_id: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Hide resolved conflicts
+        #[arg(long)]
+        unresolved_only: bool,
     },
 
     /// Resolve a conflict
@@ -279,9 +766,176 @@ enum ConflictOperationCli {
         /// Conflict ID
         conflict_id: String,
 
-        /// Resolution strategy: ours, theirs, manual
-        #[arg(short, long, default_value = "manual")]
-        strategy: String,
+        /// Resolution strategy: ours, theirs, manual, merge, or ai (needs
+        /// OPENAI_API_KEY); prompts interactively when omitted
+        #[arg(short, long)]
+        strategy: Option<String>,
+
+        /// Show base/local/remote and prompt for a strategy or value
+        #[arg(short, long, conflicts_with_all = ["strategy", "json"])]
+        interactive: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum TemplateOperationCli {
+    /// Save a spec as a reusable template
+    Save {
+        /// Spec ID to use as the template source
+        spec_id: String,
+
+        /// Template name
+        name: String,
+    },
+
+    /// List saved templates
+    List,
+}
+
+#[derive(Subcommand)]
+enum ManifestOperationCli {
+    /// Show the manifest derived from stored specs
+    Show {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Write a bundle of public specs in public boundaries
+    Bundle {
+        /// Output file path
+        #[arg(short, long)]
+        output: String,
+
+        /// Gzip the bundle (use a .json.gz path); import detects it
+        #[arg(long)]
+        gzip: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum AgentOperationCli {
+    /// Show what background agents did, newest first
+    Logs {
+        /// Only this agent's events, e.g. sync:origin/main
+        #[arg(long)]
+        id: Option<String>,
+
+        /// Only events since this time (unix seconds, YYYY-MM-DD, or RFC 3339)
+        #[arg(long)]
+        since: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SnapshotOperationCli {
+    /// Freeze the current version of a spec
+    Create {
+        /// Spec ID
+        id: String,
+
+        /// Note shown in `snapshot list`, e.g. "v1.0 release"
+        #[arg(short, long)]
+        label: Option<String>,
+    },
+
+    /// List a spec's snapshots
+    List {
+        /// Spec ID
+        id: String,
+    },
+
+    /// Put a spec back to a snapshot (the current version is snapshotted first)
+    Restore {
+        /// Spec ID
+        id: String,
+
+        /// Snapshot ID from `snapshot list`
+        snapshot_id: i64,
+    },
+
+    /// Show what changed since a snapshot, as Markdown
+    Diff {
+        /// Spec ID
+        id: String,
+
+        /// Snapshot ID from `snapshot list`
+        snapshot_id: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProfileOperationCli {
+    /// Register a manifold home directory under a name
+    Add {
+        /// Profile name
+        name: String,
+
+        /// Manifold home directory for this profile
+        path: String,
+    },
+
+    /// List profiles; the active one is marked with *
+    List,
+
+    /// Use a profile for later commands ('default' for ~/.manifold)
+    Use {
+        /// Profile name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ScenarioOperationCli {
+    /// Move a scenario before or after another in the same requirement
+    Move {
+        /// Spec ID
+        spec_id: String,
+
+        /// Requirement ID
+        req_id: String,
+
+        /// Scenario to move
+        scenario_id: String,
+
+        /// Put it just before this scenario
+        #[arg(long, conflicts_with = "after", required_unless_present = "after")]
+        before: Option<String>,
+
+        /// Put it just after this scenario
+        #[arg(long)]
+        after: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ImportOperationCli {
+    /// Import a bundle written by `manifest bundle`
+    Bundle {
+        /// Path to the bundle JSON, plain or gzipped
+        path: String,
+
+        /// Merge into existing specs, recording conflicts instead of skipping
+        #[arg(long)]
+        merge: bool,
+    },
+
+    /// Create a spec from canonical JSON, e.g. `manifold import spec -`
+    Spec {
+        /// Path to the spec JSON (or YAML with --yaml), or "-" to read stdin
+        path: String,
+
+        /// Keep the spec_id from the input instead of generating a new one
+        #[arg(long)]
+        keep_id: bool,
+
+        /// Read YAML, as written by `export --format yaml`, instead of JSON
+        #[arg(long)]
+        yaml: bool,
     },
 }
 
@@ -292,6 +946,7 @@ impl From<SyncOperationCli> for commands::SyncOperation {
             SyncOperationCli::Init { repo, remote } => {
                 commands::SyncOperation::Init { repo, remote }
             }
+            SyncOperationCli::Clone { url, path } => commands::SyncOperation::Clone { url, path },
             SyncOperationCli::Push {
                 id,
                 message,
@@ -306,6 +961,15 @@ impl From<SyncOperationCli> for commands::SyncOperation {
             SyncOperationCli::Pull { id, remote, branch } => {
                 commands::SyncOperation::Pull { id, remote, branch }
             }
+            SyncOperationCli::Agent {
+                interval,
+                remote,
+                branch,
+            } => commands::SyncOperation::Agent {
+                interval,
+                remote,
+                branch,
+            },
             SyncOperationCli::Status => commands::SyncOperation::Status,
             SyncOperationCli::Diff { id, remote, branch } => {
                 commands::SyncOperation::Diff { id, remote, branch }
@@ -314,6 +978,66 @@ impl From<SyncOperationCli> for commands::SyncOperation {
     }
 }
 
+impl From<AgentOperationCli> for commands::AgentOperation {
+    fn from(op: AgentOperationCli) -> Self {
+        match op {
+            AgentOperationCli::Logs { id, since } => commands::AgentOperation::Logs { id, since },
+        }
+    }
+}
+
+impl From<SnapshotOperationCli> for commands::SnapshotOperation {
+    fn from(op: SnapshotOperationCli) -> Self {
+        match op {
+            SnapshotOperationCli::Create { id, label } => {
+                commands::SnapshotOperation::Create { id, label }
+            }
+            SnapshotOperationCli::List { id } => commands::SnapshotOperation::List { id },
+            SnapshotOperationCli::Restore { id, snapshot_id } => {
+                commands::SnapshotOperation::Restore { id, snapshot_id }
+            }
+            SnapshotOperationCli::Diff { id, snapshot_id } => {
+                commands::SnapshotOperation::Diff { id, snapshot_id }
+            }
+        }
+    }
+}
+
+impl From<ProfileOperationCli> for commands::ProfileOperation {
+    fn from(op: ProfileOperationCli) -> Self {
+        match op {
+            ProfileOperationCli::Add { name, path } => {
+                commands::ProfileOperation::Add { name, path }
+            }
+            ProfileOperationCli::List => commands::ProfileOperation::List,
+            ProfileOperationCli::Use { name } => commands::ProfileOperation::Use { name },
+        }
+    }
+}
+
+impl From<ScenarioOperationCli> for commands::ScenarioOperation {
+    fn from(op: ScenarioOperationCli) -> Self {
+        match op {
+            ScenarioOperationCli::Move {
+                spec_id,
+                req_id,
+                scenario_id,
+                before,
+                after,
+            } => commands::ScenarioOperation::Move {
+                spec_id,
+                req_id,
+                scenario_id,
+                position: match (before, after) {
+                    (Some(anchor), _) => models::ScenarioPosition::Before(anchor),
+                    (None, Some(anchor)) => models::ScenarioPosition::After(anchor),
+                    (None, None) => unreachable!("clap requires --before or --after"),
+                },
+            },
+        }
+    }
+}
+
 impl From<ReviewOperationCli> for commands::ReviewOperation {
     fn from(op: ReviewOperationCli) -> Self {
         match op {
@@ -326,9 +1050,17 @@ impl From<ReviewOperationCli> for commands::ReviewOperation {
             ReviewOperationCli::Reject { review_id, comment } => {
                 commands::ReviewOperation::Reject { review_id, comment }
             }
-            ReviewOperationCli::List { spec_id, status } => {
-                commands::ReviewOperation::List { spec_id, status }
-            }
+            ReviewOperationCli::List {
+                spec_id,
+                status,
+                mine,
+                assigned_to,
+            } => commands::ReviewOperation::List {
+                spec_id,
+                status,
+                mine,
+                assigned_to,
+            },
         }
     }
 }
@@ -336,54 +1068,253 @@ impl From<ReviewOperationCli> for commands::ReviewOperation {
 impl From<ConflictOperationCli> for commands::ConflictOperation {
     fn from(op: ConflictOperationCli) -> Self {
         match op {
-            ConflictOperationCli::List { spec_id } => commands::ConflictOperation::List { spec_id },
+            ConflictOperationCli::List {
+                spec_id,
+                json,
+                unresolved_only,
+            } => commands::ConflictOperation::List {
+                spec_id,
+                json,
+                unresolved_only,
+            },
             ConflictOperationCli::Resolve {
                 conflict_id,
                 strategy,
+                interactive: _,
+                json,
             } => commands::ConflictOperation::Resolve {
                 conflict_id,
                 strategy,
+                json,
             },
         }
     }
 }
 
+impl From<TemplateOperationCli> for commands::TemplateOperation {
+    fn from(op: TemplateOperationCli) -> Self {
+        match op {
+            TemplateOperationCli::Save { spec_id, name } => {
+                commands::TemplateOperation::Save { spec_id, name }
+            }
+            TemplateOperationCli::List => commands::TemplateOperation::List,
+        }
+    }
+}
+
+impl From<ImportOperationCli> for commands::ImportOperation {
+    fn from(op: ImportOperationCli) -> Self {
+        match op {
+            ImportOperationCli::Bundle { path, merge } => {
+                commands::ImportOperation::Bundle { path, merge }
+            }
+            ImportOperationCli::Spec {
+                path,
+                keep_id,
+                yaml,
+            } => commands::ImportOperation::Spec {
+                path,
+                keep_id,
+                yaml,
+            },
+        }
+    }
+}
+
+impl From<ManifestOperationCli> for commands::ManifestOperation {
+    fn from(op: ManifestOperationCli) -> Self {
+        match op {
+            ManifestOperationCli::Show { json } => commands::ManifestOperation::Show { json },
+            ManifestOperationCli::Bundle { output, gzip } => {
+                commands::ManifestOperation::Bundle { output, gzip }
+            }
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    if let Some(actor) = cli.actor {
+        commands::set_actor(actor);
+    }
+    if let Some(profile) = cli.profile {
+        config::set_profile(profile);
+    }
 
     match cli.command {
-        Commands::Init => {
-            commands::init()?;
+        Commands::Init {
+            git,
+            remote,
+            repair,
+        } => {
+            commands::init(git, remote.as_deref(), repair)?;
         }
         Commands::New {
             project_id,
             name,
             boundary,
+            interactive,
+            template,
+            owner,
+            team,
         } => {
-            commands::new_spec(&project_id, name.as_deref(), Some(&boundary))?;
+            if interactive {
+                commands::new_spec_interactive(
+                    template.as_deref(),
+                    owner.as_deref(),
+                    team.as_deref(),
+                )?;
+            } else {
+                commands::new_spec(
+                    project_id.as_deref().unwrap_or_default(),
+                    name.as_deref(),
+                    boundary.as_deref(),
+                    template.as_deref(),
+                    owner.as_deref(),
+                    team.as_deref(),
+                )?;
+            }
         }
-        Commands::List { boundary, stage } => {
-            commands::list(Some(&boundary), stage.as_deref())?;
+        Commands::List {
+            boundary,
+            stage,
+            owner,
+            team,
+            sort,
+            format,
+            limit,
+            offset,
+        } => {
+            let format = format
+                .parse::<commands::OutputFormat>()
+                .map_err(|e| anyhow::anyhow!(e))?;
+            commands::list(
+                Some(&boundary),
+                stage.as_deref(),
+                owner.as_deref(),
+                team.as_deref(),
+                Some(&sort),
+                format,
+                db::Page { limit, offset },
+            )?;
         }
-        Commands::Search { query, json } => {
-            let format = if json {
-                commands::OutputFormat::Json
-            } else {
-                commands::OutputFormat::Summary
+        Commands::Search {
+            query,
+            regex,
+            fts,
+            boundary,
+            stage,
+            include,
+            json,
+            highlight_json,
+            format,
+            limit,
+            offset,
+        } => {
+            if highlight_json {
+                return commands::search_highlight(
+                    &query,
+                    boundary.as_deref(),
+                    stage.as_deref(),
+                    limit,
+                    offset,
+                );
+            }
+            let format = match (json, format) {
+                (true, _) => commands::OutputFormat::Json,
+                (false, Some(f)) => f
+                    .parse::<commands::OutputFormat>()
+                    .map_err(|e| anyhow::anyhow!(e))?,
+                (false, None) => commands::OutputFormat::Summary,
             };
-            commands::search(&query, format)?;
+            if regex {
+                commands::search_regex(
+                    &query,
+                    fts.as_deref(),
+                    boundary.as_deref(),
+                    stage.as_deref(),
+                    format,
+                )?;
+            } else {
+                let sources = include
+                    .map(|i| i.parse::<db::SearchSources>())
+                    .transpose()
+                    .map_err(|e| anyhow::anyhow!(e))?
+                    .unwrap_or_default();
+                commands::search(
+                    &query,
+                    boundary.as_deref(),
+                    stage.as_deref(),
+                    sources,
+                    format,
+                    limit,
+                    offset,
+                )?;
+            }
+        }
+        Commands::Requirements {
+            id,
+            req_id,
+            history,
+        } => {
+            commands::requirement(&id, &req_id, history)?;
+        }
+        Commands::Replay { id, at, json } => {
+            commands::replay(&id, &at, json)?;
+        }
+        Commands::Verify { id, req_id, status } => {
+            commands::verify(&id, &req_id, &status)?;
+        }
+        Commands::SetOwner { id, owner, team } => {
+            commands::set_owner(&id, &owner, team.as_deref())?;
         }
-        Commands::Show { id, json } => {
-            let format = if json {
-                commands::OutputFormat::Json
+        Commands::Show {
+            id,
+            json,
+            compact,
+            markdown,
+            sections,
+        } => {
+            if markdown {
+                let sections = sections
+                    .iter()
+                    .map(|s| s.parse::<export::SpecSection>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| anyhow::anyhow!(e))?;
+                commands::show_markdown(&id, &sections)?;
             } else {
-                commands::OutputFormat::Summary
+                let format = if json {
+                    commands::OutputFormat::Json
+                } else {
+                    commands::OutputFormat::Summary
+                };
+                commands::show(&id, format, compact)?;
+            }
+        }
+        Commands::Validate {
+            id,
+            all,
+            strict,
+            fix,
+            json,
+        } => {
+            let outcome = match id {
+                Some(id) if !all => commands::validate(&id, strict, fix, json)?,
+                _ => commands::validate_all(strict, json)?,
             };
-            commands::show(&id, format)?;
+            if let Some(message) = outcome.failure() {
+                eprintln!("Error: {}", message);
+                std::process::exit(outcome.exit_code());
+            }
         }
-        Commands::Validate { id, strict } => {
-            commands::validate(&id, strict)?;
+        Commands::Merge {
+            id_a,
+            id_b,
+            in_place,
+            name,
+        } => {
+            commands::merge(&id_a, &id_b, commands::MergeOptions { in_place, name })?;
         }
         Commands::Join {
             source_id,
@@ -392,77 +1323,210 @@ async fn main() -> anyhow::Result<()> {
         } => {
             commands::join(&source_id, &target_boundary, !no_dedup)?;
         }
-        Commands::Serve => {
+        Commands::Serve {
+            socket,
+            stdio_log,
+            stdio_log_max_bytes,
+        } => {
             let mut server = mcp::McpServer::new()?;
-            server.run().await?;
+            if let Some(path) = stdio_log {
+                server = server.with_traffic_log(mcp::TrafficLog::new(path, stdio_log_max_bytes));
+            }
+            match socket {
+                #[cfg(unix)]
+                Some(path) => server.run_socket(std::path::Path::new(&path)).await?,
+                #[cfg(not(unix))]
+                Some(_) => anyhow::bail!("--socket is only supported on Unix"),
+                None => server.run().await?,
+            }
         }
         Commands::Workflow {
             id,
+            all,
+            boundary,
+            dry_run,
+            include_approval,
             operation,
             stage,
+            skip,
+            reason,
+            limit,
+            since,
+            json,
         } => {
-            let op = match operation.as_str() {
-                "advance" => commands::WorkflowOperation::Advance {
-                    target_stage: stage,
-                },
-                "history" => commands::WorkflowOperation::History,
-                "status" => commands::WorkflowOperation::Status,
-                _ => {
-                    eprintln!(
-                        "Invalid operation: {}. Use: advance, history, or status",
-                        operation
-                    );
+            if all {
+                if operation != "advance" {
+                    eprintln!("--all is only supported with --operation advance");
                     std::process::exit(1);
                 }
-            };
-            commands::workflow(&id, op)?;
+                let options = commands::BulkAdvanceOptions {
+                    boundary: boundary
+                        .map(|b| b.parse::<models::Boundary>())
+                        .transpose()
+                        .map_err(|e| anyhow::anyhow!(e))?,
+                    target_stage: stage
+                        .map(|s| s.parse::<models::WorkflowStage>())
+                        .transpose()
+                        .map_err(|e| anyhow::anyhow!(e))?,
+                    dry_run,
+                    include_approval,
+                    ..Default::default()
+                };
+                commands::workflow_advance_all(options)?;
+            } else {
+                let id = id.expect("clap requires an id unless --all is set");
+                let op = match operation.as_str() {
+                    "advance" => commands::WorkflowOperation::Advance {
+                        target_stage: stage,
+                        skip_reason: reason.filter(|_| skip),
+                    },
+                    "history" => commands::WorkflowOperation::History { limit, since, json },
+                    "status" => commands::WorkflowOperation::Status,
+                    _ => {
+                        eprintln!(
+                            "Invalid operation: {}. Use: advance, history, or status",
+                            operation
+                        );
+                        std::process::exit(1);
+                    }
+                };
+                commands::workflow(&id, op)?;
+            }
+        }
+        Commands::Reindex => {
+            commands::reindex()?;
+        }
+        Commands::Doctor { fix } => {
+            commands::doctor(fix)?;
+        }
+        Commands::Stats { id, json } => {
+            commands::stats(id.as_deref(), json)?;
+        }
+        Commands::Report { id, json } => {
+            commands::report(&id, json)?;
+        }
+        Commands::Watch {
+            id,
+            interval,
+            debounce,
+        } => {
+            commands::watch(&id, interval, debounce)?;
         }
         Commands::Edit { id } => {
             let paths = config::ManifoldPaths::new()?;
             let mut session = llm::LlmSession::new(id, &paths)?;
             session.run().await?;
         }
-        Commands::Tui => {
+        Commands::Tui { boundary, spec } => {
             let paths = config::ManifoldPaths::new()?;
-            let mut app = tui::TuiApp::new(&paths)?;
+            let boundary = boundary
+                .map(|b| b.parse::<models::Boundary>())
+                .transpose()
+                .map_err(|e| anyhow::anyhow!(e))?;
+            let mut app = tui::TuiApp::new(&paths, tui::TuiOptions { boundary, spec })?;
             app.run()?;
         }
-        Commands::Export { id, output, tables } => {
-            let paths = config::ManifoldPaths::new()?;
-            let db = db::Database::open(&paths)?;
-
-            let output_path = std::path::Path::new(&output);
-
-            if id == "all" {
-                // Export all specs
-                let spec_rows = db.list_specs(None, None)?;
-                let specs: Vec<models::SpecData> = spec_rows
-                    .into_iter()
-                    .filter_map(|row| serde_json::from_value(row.data).ok())
-                    .collect();
-
-                export::MarkdownRenderer::export_multi(&specs, output_path, tables)?;
-                println!("✓ Exported {} specs to {}", specs.len(), output);
+        Commands::Export {
+            id,
+            diff,
+            output,
+            boundary,
+            tables,
+            format,
+            anonymize,
+            front_matter,
+            toc,
+            include_resolved_conflicts,
+            sections,
+            watch,
+            interval,
+            debounce,
+        } => {
+            let format = format
+                .parse::<export::ExportFormat>()
+                .map_err(|e| anyhow::anyhow!(e))?;
+            if let Some(ids) = diff {
+                if format != export::ExportFormat::Markdown {
+                    anyhow::bail!("--diff only produces Markdown");
+                }
+                commands::export_diff(&ids[0], &ids[1], &output, anonymize)?;
             } else {
-                // Export single spec
-                let spec_row = db
-                    .get_spec(&id)?
-                    .ok_or_else(|| anyhow::anyhow!("Spec not found: {}", id))?;
-                let spec: models::SpecData = serde_json::from_value(spec_row.data)?;
-
-                export::MarkdownRenderer::export_to_file(&spec, output_path, tables)?;
-                println!("✓ Exported spec {} to {}", id, output);
+                let id = id.expect("clap requires id without --diff");
+                let boundary = boundary
+                    .map(|b| b.parse::<models::Boundary>())
+                    .transpose()
+                    .map_err(|e| anyhow::anyhow!(e))?;
+                let options = commands::ExportOptions {
+                    format,
+                    boundary,
+                    tables,
+                    anonymize,
+                    front_matter,
+                    toc,
+                    resolved_conflicts: include_resolved_conflicts,
+                    sections: sections
+                        .iter()
+                        .map(|s| s.parse::<export::SpecSection>())
+                        .collect::<Result<_, _>>()
+                        .map_err(|e| anyhow::anyhow!(e))?,
+                };
+                if watch {
+                    commands::export_watch(&id, &output, options, interval, debounce)?;
+                } else {
+                    commands::export(&id, &output, options)?;
+                }
             }
         }
         Commands::Sync { operation } => {
             commands::sync_command(operation.into()).await?;
         }
+        Commands::Agent { operation } => {
+            commands::agent_command(operation.into())?;
+        }
+        Commands::Snapshot { operation } => {
+            commands::snapshot_command(operation.into())?;
+        }
+        Commands::Profile { operation } => {
+            commands::profile_command(operation.into())?;
+        }
+        Commands::Scenario { operation } => {
+            commands::scenario_command(operation.into())?;
+        }
         Commands::Review { operation } => {
             commands::review_command(operation.into())?;
         }
         Commands::Conflicts { operation } => {
             commands::conflict_command(operation.into())?;
         }
+        Commands::Template { operation } => {
+            commands::template_command(operation.into())?;
+        }
+        Commands::Publish { id, private, yes } => {
+            commands::publish(&id, private, yes)?;
+        }
+        Commands::Manifest { operation } => {
+            commands::manifest_command(operation.into())?;
+        }
+        Commands::Import { operation } => {
+            commands::import_command(operation.into())?;
+        }
+        Commands::Backup { path } => {
+            commands::backup(&path)?;
+        }
+        Commands::Restore { path, yes } => {
+            commands::restore(&path, yes)?;
+        }
+        Commands::Completions { shell } => {
+            let mut cmd = Cli::command();
+            let name = cmd.get_name().to_string();
+            let mut script = Vec::new();
+            clap_complete::generate(shell, &mut cmd, name.clone(), &mut script);
+            let script = String::from_utf8(script)?;
+            print!("{}", completions::add_dynamic_ids(shell, &name, script));
+        }
+        Commands::Complete { kind } => {
+            commands::complete_ids(&kind)?;
+        }
     }
 
     Ok(())
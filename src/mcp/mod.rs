@@ -1,20 +1,31 @@
 //! MCP (Model Context Protocol) server for manifold
 //!
-//! Implements JSON-RPC 2.0 over stdio for LLM integration.
+//! Implements JSON-RPC 2.0 over stdio, or a Unix domain socket, for LLM
+//! integration.
 //! Tools exposed:
 //! - create_spec: Create new spec
 //! - apply_patch: Apply JSON patches to spec
+//! - apply_patch_batch: Apply patches to several specs in one call
+//! - add_requirement / add_task / add_decision: Add items with generated ids
 //! - advance_workflow: Move spec between workflow stages
 //! - query_manifold: Search/filter specs
 
 use crate::config;
 use crate::db::Database;
+use crate::validation::IdPatterns;
+use crate::workflow::WorkflowPolicy;
 use anyhow::{bail, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::io::{self, BufRead, Write};
+#[cfg(unix)]
+use std::path::Path;
 
 mod tools;
+pub mod traffic_log;
+
+use traffic_log::Direction;
+pub use traffic_log::TrafficLog;
 
 /// JSON-RPC 2.0 request
 #[derive(Debug, Deserialize)]
@@ -47,25 +58,64 @@ struct JsonRpcError {
 /// MCP Server
 pub struct McpServer {
     db: Database,
+    traffic_log: Option<TrafficLog>,
+    workflow_policy: WorkflowPolicy,
+    id_patterns: IdPatterns,
+    /// Who tool calls are attributed to
+    actor: String,
 }
 
 impl McpServer {
     pub fn new() -> Result<Self> {
         let paths = config::ManifoldPaths::new()?;
         let db = Database::open(&paths)?;
-        Ok(Self { db })
+        let config = config::load_config_from(&paths.config)?;
+        let id_patterns = IdPatterns::from_config(&config.validation)?;
+        let actor = crate::commands::channel_actor(&config, "mcp");
+        Ok(Self::with_database(db)
+            .with_workflow_policy(config.workflow)
+            .with_id_patterns(id_patterns)
+            .with_actor(actor))
+    }
+
+    /// Create a server over an already opened database
+    pub fn with_database(db: Database) -> Self {
+        Self {
+            db,
+            traffic_log: None,
+            workflow_policy: WorkflowPolicy::default(),
+            id_patterns: IdPatterns::default(),
+            actor: crate::commands::channel_actor(&config::Config::default(), "mcp"),
+        }
+    }
+
+    /// Apply configured workflow gates to advance_workflow
+    pub fn with_workflow_policy(mut self, policy: WorkflowPolicy) -> Self {
+        self.workflow_policy = policy;
+        self
+    }
+
+    /// Generate add_* ids from configured id schemes
+    pub fn with_id_patterns(mut self, id_patterns: IdPatterns) -> Self {
+        self.id_patterns = id_patterns;
+        self
+    }
+
+    /// Attribute changes made through tools to `actor`
+    pub fn with_actor(mut self, actor: String) -> Self {
+        self.actor = actor;
+        self
+    }
+
+    /// Record every request and response to a JSONL file
+    pub fn with_traffic_log(mut self, log: TrafficLog) -> Self {
+        self.traffic_log = Some(log);
+        self
     }
 
     /// Run the MCP server (stdio JSON-RPC 2.0)
     pub async fn run(&mut self) -> Result<()> {
-        eprintln!("Manifold MCP server starting...");
-        eprintln!("Protocol: JSON-RPC 2.0 over stdio");
-        eprintln!("Available tools:");
-        eprintln!("  - create_spec");
-        eprintln!("  - apply_patch");
-        eprintln!("  - advance_workflow");
-        eprintln!("  - query_manifold");
-        eprintln!();
+        self.print_banner("stdio");
 
         let stdin = io::stdin();
         let mut stdout = io::stdout();
@@ -76,8 +126,7 @@ impl McpServer {
                 continue;
             }
 
-            let response = self.handle_request(&line).await;
-            let response_json = serde_json::to_string(&response)?;
+            let response_json = self.handle_line(&line).await?;
             writeln!(stdout, "{}", response_json)?;
             stdout.flush()?;
         }
@@ -85,6 +134,104 @@ impl McpServer {
         Ok(())
     }
 
+    /// Run the MCP server on a Unix domain socket
+    ///
+    /// Connections are served one at a time, each as a stream of
+    /// newline-delimited requests like stdio. Runs until SIGINT or SIGTERM,
+    /// then removes the socket file.
+    #[cfg(unix)]
+    pub async fn run_socket(&mut self, path: &Path) -> Result<()> {
+        self.print_banner(&format!("Unix socket {}", path.display()));
+        let listener = bind_socket(path)?;
+
+        let result = self.accept_connections(&listener).await;
+        if let Err(e) = std::fs::remove_file(path) {
+            eprintln!("Warning: failed to remove {}: {}", path.display(), e);
+        }
+        result
+    }
+
+    #[cfg(unix)]
+    async fn accept_connections(&mut self, listener: &tokio::net::UnixListener) -> Result<()> {
+        let shutdown = shutdown_signal();
+        tokio::pin!(shutdown);
+
+        loop {
+            let stream = tokio::select! {
+                _ = &mut shutdown => return Ok(()),
+                accepted = listener.accept() => accepted?.0,
+            };
+            tokio::select! {
+                _ = &mut shutdown => return Ok(()),
+                served = self.serve_stream(stream) => {
+                    // One bad client shouldn't stop the server
+                    if let Err(e) = served {
+                        eprintln!("Warning: MCP connection ended with an error: {}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    async fn serve_stream(&mut self, stream: tokio::net::UnixStream) -> Result<()> {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let response_json = self.handle_line(&line).await?;
+            writer.write_all(response_json.as_bytes()).await?;
+            writer.write_all(b"\n").await?;
+            writer.flush().await?;
+        }
+        Ok(())
+    }
+
+    fn print_banner(&self, transport: &str) {
+        eprintln!("Manifold MCP server starting...");
+        eprintln!("Protocol: JSON-RPC 2.0 over {}", transport);
+        eprintln!("Available tools:");
+        eprintln!("  - create_spec");
+        eprintln!("  - apply_patch");
+        eprintln!("  - apply_patch_batch");
+        eprintln!("  - add_requirement");
+        eprintln!("  - add_task");
+        eprintln!("  - add_decision");
+        eprintln!("  - advance_workflow");
+        eprintln!("  - query_manifold");
+        if let Some(log) = &self.traffic_log {
+            eprintln!("Logging traffic to: {}", log.path().display());
+        }
+        eprintln!();
+    }
+
+    /// Handle one raw request line and return the serialized response
+    pub async fn handle_line(&mut self, line: &str) -> Result<String> {
+        self.log_traffic(Direction::Request, line);
+
+        let response = self.handle_request(line).await;
+        let response_json = serde_json::to_string(&response)?;
+
+        self.log_traffic(Direction::Response, &response_json);
+
+        Ok(response_json)
+    }
+
+    /// Write to the traffic log, if enabled
+    /// Logging problems are reported on stderr and never interrupt the protocol
+    fn log_traffic(&self, direction: Direction, message: &str) {
+        if let Some(log) = &self.traffic_log {
+            if let Err(e) = log.record(direction, message) {
+                eprintln!("Warning: failed to write MCP traffic log: {}", e);
+            }
+        }
+    }
+
     /// Handle a single JSON-RPC request
     async fn handle_request(&mut self, request_str: &str) -> JsonRpcResponse {
         // Parse request
@@ -225,7 +372,8 @@ impl McpServer {
                         "  \"description\": \"...\",       // Required: detailed description\n",
                         "  \"status\": \"pending\",        // Required: pending|in_progress|completed|blocked\n",
                         "  \"assignee\": \"@user\",        // Optional\n",
-                        "  \"acceptance\": []             // Optional: acceptance criteria\n",
+                        "  \"acceptance\": [],            // Optional: acceptance criteria\n",
+                        "  \"scenario_ids\": [\"req-1/sc-1\"]  // Optional: scenarios implemented\n",
                         "}\n\n",
                         "DECISION SCHEMA (for /decisions/- or /decisions/N):\n",
                         "{\n",
@@ -271,12 +419,96 @@ impl McpServer {
                         "required": ["spec_id", "patch", "summary"]
                     }
                 },
+                {
+                    "name": "apply_patch_batch",
+                    "description": concat!(
+                        "Apply JSON Patch operations to several specs in one call, with the same rules as apply_patch. ",
+                        "Each item succeeds or fails on its own and results are reported per item; ",
+                        "set atomic to roll every item back if any fails."
+                    ),
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "items": {
+                                "type": "array",
+                                "description": "Patches to apply, in order",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "spec_id": {"type": "string"},
+                                        "patch": {"type": "array", "items": {"type": "object"}},
+                                        "summary": {"type": "string"}
+                                    },
+                                    "required": ["spec_id", "patch", "summary"]
+                                }
+                            },
+                            "atomic": {
+                                "type": "boolean",
+                                "description": "Apply all items or none (optional, default false)"
+                            }
+                        },
+                        "required": ["items"]
+                    }
+                },
+                {
+                    "name": "add_requirement",
+                    "description": "Add a requirement to a spec. The id (req-N) is generated. Returns the created requirement.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "spec_id": {"type": "string", "description": "Spec ID to add to"},
+                            "capability": {"type": "string", "description": "Capability area (e.g., 'auth')"},
+                            "title": {"type": "string", "description": "Short title"},
+                            "shall": {"type": "string", "description": "SHALL statement (e.g., 'The system SHALL ...')"},
+                            "rationale": {"type": "string", "description": "Why this requirement exists (optional)"},
+                            "priority": {"type": "string", "enum": ["must", "should", "could", "wont"], "description": "Priority (optional, defaults to should)"},
+                            "tags": {"type": "array", "items": {"type": "string"}, "description": "Tags (optional)"}
+                        },
+                        "required": ["spec_id", "capability", "title", "shall"]
+                    }
+                },
+                {
+                    "name": "add_task",
+                    "description": "Add a task to a spec. The id (task-N) is generated and requirement_ids must exist. Returns the created task.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "spec_id": {"type": "string", "description": "Spec ID to add to"},
+                            "title": {"type": "string", "description": "Short title"},
+                            "description": {"type": "string", "description": "Detailed description"},
+                            "requirement_ids": {"type": "array", "items": {"type": "string"}, "description": "Requirements this task implements (optional)"},
+                            "status": {"type": "string", "enum": ["pending", "in_progress", "completed", "blocked"], "description": "Status (optional, defaults to pending)"},
+                            "assignee": {"type": "string", "description": "Assignee (optional)"},
+                            "acceptance": {"type": "array", "items": {"type": "string"}, "description": "Acceptance criteria (optional)"},
+                            "scenario_ids": {"type": "array", "items": {"type": "string"}, "description": "Scenarios this task implements, as req-x/sc-y (optional)"}
+                        },
+                        "required": ["spec_id", "title", "description"]
+                    }
+                },
+                {
+                    "name": "add_decision",
+                    "description": "Add a design decision to a spec. The id (dec-N) is generated and the date defaults to today. Returns the created decision.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "spec_id": {"type": "string", "description": "Spec ID to add to"},
+                            "title": {"type": "string", "description": "Short title"},
+                            "context": {"type": "string", "description": "Context or problem"},
+                            "decision": {"type": "string", "description": "What was decided"},
+                            "rationale": {"type": "string", "description": "Why"},
+                            "alternatives_rejected": {"type": "array", "items": {"type": "string"}, "description": "Other options considered (optional)"},
+                            "date": {"type": "string", "description": "ISO date, YYYY-MM-DD (optional)"}
+                        },
+                        "required": ["spec_id", "title", "context", "decision", "rationale"]
+                    }
+                },
                 {
                     "name": "advance_workflow",
                     "description": concat!(
                         "Move a spec to the next workflow stage. ",
                         "Stages must progress in order: requirements -> design -> tasks -> approval -> implemented. ",
-                        "Each stage has validation rules that must pass before advancing."
+                        "Each stage has validation rules that must pass before advancing; ",
+                        "failures list them as blockers."
                     ),
                     "inputSchema": {
                         "type": "object",
@@ -288,15 +520,15 @@ impl McpServer {
                             "target_stage": {
                                 "type": "string",
                                 "enum": ["requirements", "design", "tasks", "approval", "implemented"],
-                                "description": "Target workflow stage"
+                                "description": "Target workflow stage (optional, defaults to the next stage)"
                             }
                         },
-                        "required": ["spec_id", "target_stage"]
+                        "required": ["spec_id"]
                     }
                 },
                 {
                     "name": "query_manifold",
-                    "description": "Search and filter specs. Returns list of specs with id, project, name, boundary, stage, and updated_at.",
+                    "description": "Search and filter specs. Returns list of specs with id, project, name, boundary, stage, updated_at, requirement and task counts, and progress (completed tasks / tasks).",
                     "inputSchema": {
                         "type": "object",
                         "properties": {
@@ -313,6 +545,11 @@ impl McpServer {
                             "project": {
                                 "type": "string",
                                 "description": "Filter by project name - partial match (optional)"
+                            },
+                            "sort_by": {
+                                "type": "string",
+                                "enum": ["updated", "progress", "requirements"],
+                                "description": "Sort order, highest first (optional, default updated)"
                             }
                         }
                     }
@@ -330,11 +567,81 @@ impl McpServer {
         let arguments = params["arguments"].clone();
 
         match tool_name {
-            "create_spec" => tools::create_spec(&mut self.db, arguments).await,
-            "apply_patch" => tools::apply_patch(&mut self.db, arguments).await,
-            "advance_workflow" => tools::advance_workflow(&mut self.db, arguments).await,
+            "create_spec" => tools::create_spec(&mut self.db, &self.actor, arguments).await,
+            "apply_patch" => tools::apply_patch(&mut self.db, &self.actor, arguments).await,
+            "apply_patch_batch" => {
+                tools::apply_patch_batch(&mut self.db, &self.actor, arguments).await
+            }
+            "add_requirement" => {
+                tools::add_item(
+                    &mut self.db,
+                    &self.actor,
+                    &self.id_patterns,
+                    tools::ItemKind::Requirement,
+                    arguments,
+                )
+                .await
+            }
+            "add_task" => {
+                tools::add_item(
+                    &mut self.db,
+                    &self.actor,
+                    &self.id_patterns,
+                    tools::ItemKind::Task,
+                    arguments,
+                )
+                .await
+            }
+            "add_decision" => {
+                tools::add_item(
+                    &mut self.db,
+                    &self.actor,
+                    &self.id_patterns,
+                    tools::ItemKind::Decision,
+                    arguments,
+                )
+                .await
+            }
+            "advance_workflow" => {
+                tools::advance_workflow(&mut self.db, &self.actor, &self.workflow_policy, arguments)
+                    .await
+            }
             "query_manifold" => tools::query_manifold(&self.db, arguments).await,
             _ => bail!("Unknown tool: {}", tool_name),
         }
     }
 }
+
+/// Listen on `path`, replacing a stale socket left by a crashed server
+#[cfg(unix)]
+fn bind_socket(path: &Path) -> Result<tokio::net::UnixListener> {
+    use anyhow::Context;
+
+    if path.exists() {
+        if std::os::unix::net::UnixStream::connect(path).is_ok() {
+            bail!("{} is in use by another server", path.display());
+        }
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove stale socket {}", path.display()))?;
+    }
+    tokio::net::UnixListener::bind(path)
+        .with_context(|| format!("Failed to listen on {}", path.display()))
+}
+
+/// Resolves on SIGINT or SIGTERM
+#[cfg(unix)]
+async fn shutdown_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut terminate) => {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = terminate.recv() => {}
+            }
+        }
+        Err(_) => {
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
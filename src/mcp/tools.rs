@@ -1,8 +1,9 @@
 //! MCP tool implementations
 
-use crate::db::Database;
-use crate::models::{Boundary, PatchEntry, SpecData, WorkflowStage};
-use crate::workflow::WorkflowEngine;
+use crate::db::{Database, SpecOrder};
+use crate::models::{Boundary, PatchEntry, SpecData, Visibility, WorkflowStage};
+use crate::validation::{IdPatterns, IdScheme};
+use crate::workflow::{WorkflowEngine, WorkflowPolicy};
 use anyhow::{bail, Result};
 use serde_json::{json, Value};
 use std::collections::HashSet;
@@ -34,6 +35,7 @@ const VALID_TASK_FIELDS: &[&str] = &[
     "status",
     "assignee",
     "acceptance",
+    "scenario_ids",
 ];
 
 /// Valid fields for a Decision object  
@@ -120,7 +122,7 @@ fn validate_object_fields(path: &str, value: &Value) -> Result<()> {
 }
 
 /// Create a new spec
-pub async fn create_spec(db: &mut Database, args: Value) -> Result<Value> {
+pub async fn create_spec(db: &mut Database, actor: &str, args: Value) -> Result<Value> {
     let project = args["project"]
         .as_str()
         .ok_or_else(|| anyhow::anyhow!("Missing 'project' parameter"))?;
@@ -132,12 +134,9 @@ pub async fn create_spec(db: &mut Database, args: Value) -> Result<Value> {
         .ok_or_else(|| anyhow::anyhow!("Missing 'name' parameter"))?;
 
     // Parse boundary
-    let boundary = match boundary_str {
-        "personal" => Boundary::Personal,
-        "work" => Boundary::Work,
-        "company" => Boundary::Company,
-        _ => bail!("Invalid boundary: must be 'personal', 'work', or 'company'"),
-    };
+    let boundary = boundary_str
+        .parse::<Boundary>()
+        .map_err(|e| anyhow::anyhow!(e))?;
 
     // Generate spec ID
     let spec_id = crate::db::generate_spec_id(project);
@@ -150,6 +149,9 @@ pub async fn create_spec(db: &mut Database, args: Value) -> Result<Value> {
         project: project.to_string(),
         boundary,
         name: name.to_string(),
+        visibility: Visibility::Private,
+        owner: None,
+        team: None,
         stage: WorkflowStage::Requirements,
         stages_completed: vec![],
         requirements: vec![],
@@ -160,7 +162,7 @@ pub async fn create_spec(db: &mut Database, args: Value) -> Result<Value> {
             updated_at: now,
             patches: vec![PatchEntry {
                 timestamp: now,
-                actor: "mcp".to_string(),
+                actor: actor.to_string(),
                 op: "create".to_string(),
                 path: "/".to_string(),
                 summary: format!("Created via MCP: {}", name),
@@ -179,7 +181,19 @@ pub async fn create_spec(db: &mut Database, args: Value) -> Result<Value> {
 }
 
 /// Apply a JSON patch to a spec
-pub async fn apply_patch(db: &mut Database, args: Value) -> Result<Value> {
+pub async fn apply_patch(db: &mut Database, actor: &str, args: Value) -> Result<Value> {
+    let (spec_id, summary) = patch_spec(db, actor, &args)?;
+
+    Ok(json!({
+        "success": true,
+        "spec_id": spec_id,
+        "message": format!("Applied patch: {}", summary)
+    }))
+}
+
+/// Validate and apply one `{spec_id, patch, summary}`, returning its spec id
+/// and summary
+fn patch_spec<'a>(db: &Database, actor: &str, args: &'a Value) -> Result<(&'a str, &'a str)> {
     let spec_id = args["spec_id"]
         .as_str()
         .ok_or_else(|| anyhow::anyhow!("Missing 'spec_id' parameter"))?;
@@ -212,29 +226,21 @@ pub async fn apply_patch(db: &mut Database, args: Value) -> Result<Value> {
     }
 
     // Get current spec
-    let spec_row = db
-        .get_spec(spec_id)?
+    let mut spec = db
+        .get_spec_data(spec_id)?
         .ok_or_else(|| anyhow::anyhow!("Spec not found: {}", spec_id))?;
-    let mut spec: SpecData = serde_json::from_value(spec_row.data.clone())?;
-
-    // Convert to JSON for patching
-    let mut spec_json = serde_json::to_value(&spec)?;
 
     // Apply patch operations - convert Vec<Value> to Patch
     let patch_value = serde_json::Value::Array(patch_ops.clone());
     let patch: json_patch::Patch = serde_json::from_value(patch_value)?;
-    json_patch::patch(&mut spec_json, &patch)
-        .map_err(|e| anyhow::anyhow!("Failed to apply patch: {}", e))?;
-
-    // Convert back to SpecData
-    spec = serde_json::from_value(spec_json.clone())?;
+    spec.apply_json_patch_as(&patch, actor)?;
 
     // Update history
     let now = chrono::Utc::now().timestamp();
     spec.history.updated_at = now;
     spec.history.patches.push(PatchEntry {
         timestamp: now,
-        actor: "mcp".to_string(),
+        actor: actor.to_string(),
         op: "patch".to_string(),
         path: "/".to_string(),
         summary: summary.to_string(),
@@ -243,74 +249,239 @@ pub async fn apply_patch(db: &mut Database, args: Value) -> Result<Value> {
     // Update in database
     db.update_spec(&spec)?;
 
+    Ok((spec_id, summary))
+}
+
+/// Apply patches to several specs in one call
+///
+/// Each item commits on its own, so a bad patch only fails its own item.
+/// With `atomic`, the items share one transaction and any failure rolls
+/// all of them back.
+pub async fn apply_patch_batch(db: &mut Database, actor: &str, args: Value) -> Result<Value> {
+    let items = args["items"]
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("Missing or invalid 'items' parameter"))?;
+    let atomic = args["atomic"].as_bool().unwrap_or(false);
+    let item_spec_id = |item: &Value| item["spec_id"].clone();
+
+    let mut results = Vec::new();
+    if atomic {
+        let mut failure = None;
+        let outcome = db.in_transaction(|db| {
+            for (i, item) in items.iter().enumerate() {
+                if let Err(e) = patch_spec(db, actor, item) {
+                    failure = Some((i, e.to_string()));
+                    return Err(e);
+                }
+            }
+            Ok(())
+        });
+
+        for (i, item) in items.iter().enumerate() {
+            let mut result = json!({"index": i, "spec_id": item_spec_id(item)});
+            match (&outcome, &failure) {
+                (Ok(()), _) => result["success"] = json!(true),
+                (Err(_), Some((failed, error))) if *failed == i => {
+                    result["success"] = json!(false);
+                    result["error"] = json!(error);
+                }
+                (Err(_), Some((failed, _))) if i > *failed => {
+                    result["success"] = json!(false);
+                    result["error"] = json!("Not attempted: an earlier item failed");
+                }
+                (Err(e), _) => {
+                    result["success"] = json!(false);
+                    result["error"] = json!(format!("Rolled back: {}", e));
+                }
+            }
+            results.push(result);
+        }
+    } else {
+        for (i, item) in items.iter().enumerate() {
+            let mut result = json!({"index": i, "spec_id": item_spec_id(item)});
+            match db.in_transaction(|db| patch_spec(db, actor, item).map(|_| ())) {
+                Ok(()) => result["success"] = json!(true),
+                Err(e) => {
+                    result["success"] = json!(false);
+                    result["error"] = json!(e.to_string());
+                }
+            }
+            results.push(result);
+        }
+    }
+
+    let applied = results.iter().filter(|r| r["success"] == true).count();
+    Ok(json!({
+        "success": applied == results.len(),
+        "atomic": atomic,
+        "applied": applied,
+        "failed": results.len() - applied,
+        "results": results
+    }))
+}
+
+/// Structured item that the add_* tools create
+#[derive(Debug, Clone, Copy)]
+pub enum ItemKind {
+    Requirement,
+    Task,
+    Decision,
+}
+
+impl ItemKind {
+    /// Top-level array the item lives in
+    fn field(self) -> &'static str {
+        match self {
+            ItemKind::Requirement => "requirements",
+            ItemKind::Task => "tasks",
+            ItemKind::Decision => "decisions",
+        }
+    }
+
+    /// Configured id scheme for this kind
+    fn scheme(self, ids: &IdPatterns) -> &IdScheme {
+        match self {
+            ItemKind::Requirement => &ids.requirement,
+            ItemKind::Task => &ids.task,
+            ItemKind::Decision => &ids.decision,
+        }
+    }
+
+    /// Fields the caller must supply
+    fn required_fields(self) -> &'static [&'static str] {
+        match self {
+            ItemKind::Requirement => &["capability", "title", "shall"],
+            ItemKind::Task => &["title", "description"],
+            ItemKind::Decision => &["title", "context", "decision", "rationale"],
+        }
+    }
+}
+
+/// Add a requirement, task, or decision from typed fields
+///
+/// The id is generated, the item goes through the same patch path as
+/// apply_patch, and the created item is returned.
+pub async fn add_item(
+    db: &mut Database,
+    actor: &str,
+    ids: &IdPatterns,
+    kind: ItemKind,
+    args: Value,
+) -> Result<Value> {
+    let spec_id = args["spec_id"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing 'spec_id' parameter"))?;
+
+    let mut item = args
+        .as_object()
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Arguments must be an object"))?;
+    item.remove("spec_id");
+    if item.contains_key("id") {
+        bail!("'id' is generated and must not be supplied");
+    }
+    for field in kind.required_fields() {
+        match item.get(*field).and_then(|v| v.as_str()) {
+            Some(value) if !value.trim().is_empty() => {}
+            _ => bail!("Missing '{}' parameter", field),
+        }
+    }
+    if let ItemKind::Task = kind {
+        item.entry("requirement_ids").or_insert_with(|| json!([]));
+        item.entry("status").or_insert_with(|| json!("pending"));
+    }
+
+    let path = format!("/{}/-", kind.field());
+    validate_object_fields(&path, &Value::Object(item.clone()))?;
+
+    let mut spec = db
+        .get_spec_data(spec_id)?
+        .ok_or_else(|| anyhow::anyhow!("Spec not found: {}", spec_id))?;
+
+    if let ItemKind::Task = kind {
+        let ids = item["requirement_ids"]
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("'requirement_ids' must be an array"))?;
+        for id in ids {
+            let id = id.as_str().unwrap_or_default();
+            if spec.get_requirement(id).is_none() {
+                bail!("Unknown requirement '{}' in requirement_ids", id);
+            }
+        }
+    }
+
+    let scheme = kind.scheme(ids);
+    let id = scheme.next_id(&spec);
+    if !scheme.is_match(&id) {
+        bail!(
+            "Generated id '{}' doesn't match the configured pattern {}; check [validation.id_prefixes]",
+            id,
+            scheme.pattern.as_str()
+        );
+    }
+    item.insert("id".to_string(), json!(id));
+
+    let patch: json_patch::Patch =
+        serde_json::from_value(json!([{"op": "add", "path": path, "value": item}]))?;
+    spec.apply_json_patch_as(&patch, actor)?;
+
+    let created = serde_json::to_value(&spec)?[kind.field()]
+        .as_array()
+        .and_then(|items| items.last().cloned())
+        .unwrap_or(Value::Null);
+
+    let now = chrono::Utc::now().timestamp();
+    spec.history.updated_at = now;
+    spec.history.patches.push(PatchEntry {
+        timestamp: now,
+        actor: actor.to_string(),
+        op: "add".to_string(),
+        path: format!("/{}/{}", kind.field(), id),
+        summary: format!("Added {} via MCP", id),
+    });
+    db.update_spec(&spec)?;
+
     Ok(json!({
         "success": true,
         "spec_id": spec_id,
-        "message": format!("Applied patch: {}", summary)
+        "item": created,
     }))
 }
 
 /// Advance a spec to a new workflow stage
-pub async fn advance_workflow(db: &mut Database, args: Value) -> Result<Value> {
+pub async fn advance_workflow(
+    db: &mut Database,
+    actor: &str,
+    policy: &WorkflowPolicy,
+    args: Value,
+) -> Result<Value> {
     let spec_id = args["spec_id"]
         .as_str()
         .ok_or_else(|| anyhow::anyhow!("Missing 'spec_id' parameter"))?;
-    let target_stage_str = args["target_stage"]
-        .as_str()
-        .ok_or_else(|| anyhow::anyhow!("Missing 'target_stage' parameter"))?;
-
-    // Parse target stage
-    let target_stage = match target_stage_str {
-        "requirements" => WorkflowStage::Requirements,
-        "design" => WorkflowStage::Design,
-        "tasks" => WorkflowStage::Tasks,
-        "approval" => WorkflowStage::Approval,
-        "implemented" => WorkflowStage::Implemented,
-        _ => bail!("Invalid stage: {}", target_stage_str),
+    // Without a target, advance to the next stage
+    let target_stage = match args["target_stage"].as_str() {
+        Some(stage) => Some(
+            stage
+                .parse::<WorkflowStage>()
+                .map_err(|e| anyhow::anyhow!(e))?,
+        ),
+        None => None,
     };
 
     // Get current spec
-    let spec_row = db
-        .get_spec(spec_id)?
+    let mut spec = db
+        .get_spec_data(spec_id)?
         .ok_or_else(|| anyhow::anyhow!("Spec not found: {}", spec_id))?;
-    let mut spec: SpecData = serde_json::from_value(spec_row.data)?;
 
     // Validate and execute transition using workflow engine
-    match WorkflowEngine::advance_stage(&spec, target_stage) {
+    let result = match target_stage {
+        Some(target) => WorkflowEngine::advance_stage_with(&spec, target, policy),
+        None => WorkflowEngine::can_advance_with(&spec, policy)
+            .and_then(|next| WorkflowEngine::advance_stage_with(&spec, next, policy)),
+    };
+    match result {
         Ok(transition) => {
-            // Update spec
-            let old_stage = spec.stage.clone();
-            if !spec.stages_completed.contains(&old_stage) {
-                spec.stages_completed.push(old_stage.clone());
-            }
-            spec.stage = transition.to.clone();
-
-            // Update history
-            let now = chrono::Utc::now().timestamp();
-            spec.history.updated_at = now;
-            spec.history.patches.push(PatchEntry {
-                timestamp: now,
-                actor: "mcp".to_string(),
-                op: "advance".to_string(),
-                path: "/stage".to_string(),
-                summary: format!("Advanced from {} to {}", transition.from, transition.to),
-            });
-
-            // Log workflow event
-            db.log_workflow_event(
-                spec_id,
-                &transition.to.to_string(),
-                &transition.event.as_string(),
-                "mcp",
-                now,
-                Some(&format!(
-                    "Advanced from {} to {}",
-                    transition.from, transition.to
-                )),
-            )?;
-
-            // Update in database
-            db.update_spec(&spec)?;
+            crate::workflow::apply_transition(db, &mut spec, &transition, actor, policy)?;
 
             Ok(json!({
                 "success": true,
@@ -327,7 +498,7 @@ pub async fn advance_workflow(db: &mut Database, args: Value) -> Result<Value> {
                 spec_id,
                 &spec.stage.to_string(),
                 &format!("validation_failed:{}", e),
-                "mcp",
+                actor,
                 chrono::Utc::now().timestamp(),
                 Some(&e.to_string()),
             )?;
@@ -337,59 +508,108 @@ pub async fn advance_workflow(db: &mut Database, args: Value) -> Result<Value> {
                 "spec_id": spec_id,
                 "current_stage": spec.stage.to_string(),
                 "error": e.to_string(),
+                "blockers": [e.to_string()],
                 "message": format!("Cannot advance: {}", e)
             }))
         }
     }
 }
 
+/// Requirement and task counts read straight from stored spec JSON
+///
+/// Works on the raw value, so rows that no longer parse still get counts.
+fn spec_counts(data: &Value) -> (usize, usize, usize) {
+    let len = |key: &str| data.get(key).and_then(Value::as_array).map_or(0, Vec::len);
+    let completed = data
+        .get("tasks")
+        .and_then(Value::as_array)
+        .map_or(0, |tasks| {
+            tasks
+                .iter()
+                .filter(|t| t.get("status").and_then(Value::as_str) == Some("completed"))
+                .count()
+        });
+    (len("requirements"), len("tasks"), completed)
+}
+
 /// Query/search specs in manifold
 pub async fn query_manifold(db: &Database, args: Value) -> Result<Value> {
     let boundary_filter = args.get("boundary").and_then(|v| v.as_str());
     let stage_filter = args.get("stage").and_then(|v| v.as_str());
     let _project_filter = args.get("project").and_then(|v| v.as_str());
+    let sort_by = args
+        .get("sort_by")
+        .and_then(|v| v.as_str())
+        .unwrap_or("updated");
+    if !matches!(sort_by, "updated" | "progress" | "requirements") {
+        bail!(
+            "Invalid sort_by '{}'. Use updated, progress, or requirements",
+            sort_by
+        );
+    }
 
     // Parse filters
-    let boundary_enum = boundary_filter.and_then(|b| match b {
-        "personal" => Some(Boundary::Personal),
-        "work" => Some(Boundary::Work),
-        "company" => Some(Boundary::Company),
-        _ => None,
-    });
+    let boundary_enum = boundary_filter
+        .map(|b| b.parse::<Boundary>())
+        .transpose()
+        .map_err(|e| anyhow::anyhow!(e))?;
 
-    let stage_enum = stage_filter.and_then(|s| match s {
-        "requirements" => Some(WorkflowStage::Requirements),
-        "design" => Some(WorkflowStage::Design),
-        "tasks" => Some(WorkflowStage::Tasks),
-        "approval" => Some(WorkflowStage::Approval),
-        "implemented" => Some(WorkflowStage::Implemented),
-        _ => None,
-    });
+    let stage_enum = stage_filter
+        .map(|s| s.parse::<WorkflowStage>())
+        .transpose()
+        .map_err(|e| anyhow::anyhow!(e))?;
 
     // Get specs with filters (boundary and stage handled by DB query)
-    let filtered_specs = db.list_specs(boundary_enum.as_ref(), stage_enum.as_ref())?;
+    let filtered_specs = db.list_specs(
+        boundary_enum.as_ref(),
+        stage_enum.as_ref(),
+        SpecOrder::default(),
+    )?;
 
     // Convert to JSON
-    let results: Vec<Value> = filtered_specs
+    let mut results: Vec<Value> = filtered_specs
         .iter()
         .map(|spec| {
-            // Parse the data to get the name
-            let spec_data: Result<SpecData, _> = serde_json::from_value(spec.data.clone());
-            let name = spec_data
-                .map(|s| s.name)
-                .unwrap_or_else(|_| "Unknown".to_string());
-
-            json!({
+            let (requirement_count, task_count, completed_task_count) = spec_counts(&spec.data);
+            let progress = if task_count == 0 {
+                0.0
+            } else {
+                completed_task_count as f64 / task_count as f64
+            };
+            let mut summary = json!({
                 "spec_id": spec.id,
                 "project": spec.project,
-                "name": name,
+                "name": "Unknown",
                 "boundary": spec.boundary,
                 "stage": spec.stage,
-                "updated_at": spec.updated_at
-            })
+                "updated_at": spec.updated_at,
+                "requirement_count": requirement_count,
+                "task_count": task_count,
+                "completed_task_count": completed_task_count,
+                "progress": progress
+            });
+            // Parse the data to get the name, flagging rows that don't parse
+            match spec.parse() {
+                Ok(data) => summary["name"] = json!(data.name),
+                Err(e) => summary["parse_error"] = json!(e.to_string()),
+            }
+            summary
         })
         .collect();
 
+    // Rows arrive most recently updated first; stable sorts keep that as
+    // the tie-breaker
+    match sort_by {
+        "progress" => results.sort_by(|a, b| {
+            let progress = |v: &Value| v["progress"].as_f64().unwrap_or(0.0);
+            progress(b).total_cmp(&progress(a))
+        }),
+        "requirements" => {
+            results.sort_by_key(|v| std::cmp::Reverse(v["requirement_count"].as_u64().unwrap_or(0)))
+        }
+        _ => {}
+    }
+
     Ok(json!({
         "success": true,
         "count": results.len(),
@@ -0,0 +1,100 @@
+//! JSONL capture of MCP stdio traffic for debugging clients
+//!
+//! Each line records one inbound request or outbound response. The log is
+//! written to its own file, never to stdout, so the protocol stream stays clean.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Default size cap before the log is rotated (10 MiB)
+pub const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Direction of a logged message relative to the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Request,
+    Response,
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction::Request => write!(f, "request"),
+            Direction::Response => write!(f, "response"),
+        }
+    }
+}
+
+/// Append-only traffic log with single-file rotation
+///
+/// When appending would push the file past `max_bytes`, the current file is
+/// moved to `<path>.1` (replacing any previous rotation) and a fresh file is
+/// started.
+pub struct TrafficLog {
+    path: PathBuf,
+    max_bytes: u64,
+}
+
+impl TrafficLog {
+    pub fn new(path: impl Into<PathBuf>, max_bytes: u64) -> Self {
+        Self {
+            path: path.into(),
+            max_bytes,
+        }
+    }
+
+    /// Path of the active log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Path the log is rotated to once it exceeds the size cap
+    pub fn rotated_path(&self) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(".1");
+        PathBuf::from(name)
+    }
+
+    /// Append one message to the log
+    ///
+    /// Messages that are valid JSON are embedded as-is; anything else (such
+    /// as a request that failed to parse) is stored as a string.
+    pub fn record(&self, direction: Direction, message: &str) -> Result<()> {
+        let message: Value =
+            serde_json::from_str(message).unwrap_or_else(|_| Value::String(message.to_string()));
+        let entry = json!({
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "direction": direction.to_string(),
+            "message": message,
+        });
+        let line = format!("{}\n", serde_json::to_string(&entry)?);
+
+        self.rotate_if_needed(line.len() as u64)?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open MCP log {}", self.path.display()))?;
+        file.write_all(line.as_bytes())
+            .context("Failed to write MCP log entry")?;
+
+        Ok(())
+    }
+
+    fn rotate_if_needed(&self, incoming: u64) -> Result<()> {
+        let current = match fs::metadata(&self.path) {
+            Ok(meta) => meta.len(),
+            Err(_) => return Ok(()),
+        };
+
+        if current > 0 && current + incoming > self.max_bytes {
+            fs::rename(&self.path, self.rotated_path()).context("Failed to rotate MCP log")?;
+        }
+
+        Ok(())
+    }
+}
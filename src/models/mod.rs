@@ -4,9 +4,10 @@
 //! Optimized for LLM consumption and production
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Boundary type for spec isolation
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum Boundary {
     Personal,
@@ -28,7 +29,7 @@ impl std::str::FromStr for Boundary {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
+        match s.trim().to_lowercase().as_str() {
             "personal" => Ok(Boundary::Personal),
             "work" => Ok(Boundary::Work),
             "company" => Ok(Boundary::Company),
@@ -40,6 +41,156 @@ impl std::str::FromStr for Boundary {
     }
 }
 
+impl Boundary {
+    /// Visibility used when config.toml does not override it
+    pub fn default_visibility(&self) -> BoundaryVisibility {
+        match self {
+            Boundary::Personal => BoundaryVisibility::Private,
+            Boundary::Work => BoundaryVisibility::Team,
+            Boundary::Company => BoundaryVisibility::Public,
+        }
+    }
+}
+
+/// Who can see the specs in a boundary
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BoundaryVisibility {
+    /// Only the local user
+    Private,
+    /// Collaborators sharing the sync repository
+    Team,
+    /// Anyone the manifold is published to
+    Public,
+}
+
+impl std::fmt::Display for BoundaryVisibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoundaryVisibility::Private => write!(f, "private"),
+            BoundaryVisibility::Team => write!(f, "team"),
+            BoundaryVisibility::Public => write!(f, "public"),
+        }
+    }
+}
+
+impl std::str::FromStr for BoundaryVisibility {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "private" => Ok(BoundaryVisibility::Private),
+            "team" => Ok(BoundaryVisibility::Team),
+            "public" => Ok(BoundaryVisibility::Public),
+            _ => Err(format!(
+                "Invalid visibility: {}. Use: private, team, public",
+                s
+            )),
+        }
+    }
+}
+
+/// Publication state of an individual spec
+///
+/// Specs start private; only public specs leave the machine when their
+/// boundary is bundled for publishing.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    #[default]
+    Private,
+    Public,
+}
+
+impl std::fmt::Display for Visibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Visibility::Private => write!(f, "private"),
+            Visibility::Public => write!(f, "public"),
+        }
+    }
+}
+
+impl std::str::FromStr for Visibility {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "private" => Ok(Visibility::Private),
+            "public" => Ok(Visibility::Public),
+            _ => Err(format!("Invalid visibility: {}. Use: private, public", s)),
+        }
+    }
+}
+
+/// Per-boundary section of a manifold manifest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BoundaryConfig {
+    pub visibility: BoundaryVisibility,
+    /// Projects with at least one spec in this boundary, sorted
+    #[serde(default)]
+    pub projects: Vec<String>,
+    #[serde(default)]
+    pub spec_count: usize,
+}
+
+/// Manifest describing a whole manifold across its boundaries
+///
+/// Derived from stored specs rather than persisted, so it never drifts from
+/// the database.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifoldV2 {
+    pub manifold_id: String,
+    pub boundaries: BTreeMap<Boundary, BoundaryConfig>,
+}
+
+impl ManifoldV2 {
+    pub fn new(manifold_id: String) -> Self {
+        Self {
+            manifold_id,
+            boundaries: BTreeMap::new(),
+        }
+    }
+
+    /// Record `count` specs of `project` in `boundary`
+    ///
+    /// A boundary seen for the first time takes its visibility from
+    /// `overrides`, falling back to the boundary default.
+    pub fn add_specs(
+        &mut self,
+        boundary: Boundary,
+        project: &str,
+        count: usize,
+        overrides: &BTreeMap<Boundary, BoundaryVisibility>,
+    ) {
+        let visibility = overrides
+            .get(&boundary)
+            .copied()
+            .unwrap_or_else(|| boundary.default_visibility());
+        let config = self
+            .boundaries
+            .entry(boundary)
+            .or_insert_with(|| BoundaryConfig {
+                visibility,
+                projects: Vec::new(),
+                spec_count: 0,
+            });
+
+        if let Err(pos) = config
+            .projects
+            .binary_search_by(|p| p.as_str().cmp(project))
+        {
+            config.projects.insert(pos, project.to_string());
+        }
+        config.spec_count += count;
+    }
+
+    /// Total number of specs across all boundaries
+    pub fn spec_count(&self) -> usize {
+        self.boundaries.values().map(|b| b.spec_count).sum()
+    }
+}
+
 /// Workflow stages for a spec
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -67,12 +218,12 @@ impl std::str::FromStr for WorkflowStage {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "requirements" => Ok(WorkflowStage::Requirements),
+        match s.trim().to_lowercase().as_str() {
+            "requirements" | "requirement" | "reqs" | "req" => Ok(WorkflowStage::Requirements),
             "design" => Ok(WorkflowStage::Design),
-            "tasks" => Ok(WorkflowStage::Tasks),
+            "tasks" | "task" => Ok(WorkflowStage::Tasks),
             "approval" => Ok(WorkflowStage::Approval),
-            "implemented" => Ok(WorkflowStage::Implemented),
+            "implemented" | "implementation" | "impl" => Ok(WorkflowStage::Implemented),
             _ => Err(format!(
                 "Invalid stage: {}. Use: requirements, design, tasks, approval, implemented",
                 s
@@ -81,11 +232,48 @@ impl std::str::FromStr for WorkflowStage {
     }
 }
 
+impl WorkflowStage {
+    /// All stages in canonical workflow order
+    pub fn all() -> [WorkflowStage; 5] {
+        [
+            WorkflowStage::Requirements,
+            WorkflowStage::Design,
+            WorkflowStage::Tasks,
+            WorkflowStage::Approval,
+            WorkflowStage::Implemented,
+        ]
+    }
+
+    /// Zero-based position in the workflow
+    pub fn order(&self) -> usize {
+        match self {
+            WorkflowStage::Requirements => 0,
+            WorkflowStage::Design => 1,
+            WorkflowStage::Tasks => 2,
+            WorkflowStage::Approval => 3,
+            WorkflowStage::Implemented => 4,
+        }
+    }
+
+    /// Icon shown next to the stage in the TUI
+    pub fn icon(&self) -> &'static str {
+        match self {
+            WorkflowStage::Requirements => "📋",
+            WorkflowStage::Design => "📐",
+            WorkflowStage::Tasks => "📝",
+            WorkflowStage::Approval => "✅",
+            WorkflowStage::Implemented => "🎉",
+        }
+    }
+}
+
 /// Priority level (MoSCoW)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
+#[derive(Default)]
 pub enum Priority {
     Must,
+    #[default]
     Should,
     Could,
     Wont,
@@ -102,12 +290,6 @@ impl std::fmt::Display for Priority {
     }
 }
 
-impl Default for Priority {
-    fn default() -> Self {
-        Priority::Should
-    }
-}
-
 /// A scenario using GIVEN/WHEN/THEN pattern
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Scenario {
@@ -135,6 +317,109 @@ pub struct Requirement {
     pub tags: Vec<String>,
     #[serde(default)]
     pub scenarios: Vec<Scenario>,
+    /// Superseded wording, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<RequirementRevision>,
+    /// Whether the implementation has been checked against this requirement
+    #[serde(default, skip_serializing_if = "VerificationStatus::is_not_started")]
+    pub verification: VerificationStatus,
+}
+
+/// Where a scenario moves to, relative to another scenario of the same
+/// requirement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScenarioPosition {
+    Before(String),
+    After(String),
+}
+
+impl Requirement {
+    /// Move scenario `id` next to another one, returning its new index
+    pub fn move_scenario(
+        &mut self,
+        id: &str,
+        position: &ScenarioPosition,
+    ) -> anyhow::Result<usize> {
+        let anchor = match position {
+            ScenarioPosition::Before(anchor) | ScenarioPosition::After(anchor) => anchor,
+        };
+        if anchor == id {
+            anyhow::bail!("Cannot move {} relative to itself", id);
+        }
+        let find = |scenarios: &[Scenario], id: &str| {
+            scenarios
+                .iter()
+                .position(|s| s.id == id)
+                .ok_or_else(|| anyhow::anyhow!("Scenario {} not found in {}", id, self.id))
+        };
+
+        let from = find(&self.scenarios, id)?;
+        find(&self.scenarios, anchor)?;
+        let scenario = self.scenarios.remove(from);
+        let anchor_index = find(&self.scenarios, anchor)?;
+        let to = match position {
+            ScenarioPosition::Before(_) => anchor_index,
+            ScenarioPosition::After(_) => anchor_index + 1,
+        };
+        self.scenarios.insert(to, scenario);
+        Ok(to)
+    }
+}
+
+/// Verification progress of a requirement
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationStatus {
+    #[default]
+    NotStarted,
+    InProgress,
+    Verified,
+    Failed,
+}
+
+impl VerificationStatus {
+    pub fn is_not_started(&self) -> bool {
+        *self == VerificationStatus::NotStarted
+    }
+}
+
+impl std::fmt::Display for VerificationStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerificationStatus::NotStarted => write!(f, "not_started"),
+            VerificationStatus::InProgress => write!(f, "in_progress"),
+            VerificationStatus::Verified => write!(f, "verified"),
+            VerificationStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+impl std::str::FromStr for VerificationStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "not_started" => Ok(VerificationStatus::NotStarted),
+            "in_progress" => Ok(VerificationStatus::InProgress),
+            "verified" => Ok(VerificationStatus::Verified),
+            "failed" => Ok(VerificationStatus::Failed),
+            _ => Err(format!(
+                "Invalid verification status: {} (use not_started, in_progress, verified, or failed)",
+                s
+            )),
+        }
+    }
+}
+
+/// A previous version of a requirement's title and SHALL statement
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RequirementRevision {
+    pub title: String,
+    pub shall: String,
+    /// When this wording was superseded
+    pub timestamp: i64,
+    /// Who superseded it
+    pub actor: String,
 }
 
 /// Task status
@@ -170,6 +455,9 @@ pub struct Task {
     pub assignee: Option<String>,
     #[serde(default)]
     pub acceptance: Vec<String>,
+    /// Scenarios this task implements, as `req-x/sc-y`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scenario_ids: Vec<String>,
 }
 
 /// A design decision with rationale
@@ -182,6 +470,8 @@ pub struct Decision {
     pub rationale: String,
     #[serde(default)]
     pub alternatives_rejected: Vec<String>,
+    /// ISO-8601 date (YYYY-MM-DD); filled with today when left empty
+    #[serde(default)]
     pub date: String,
 }
 
@@ -215,6 +505,16 @@ pub struct SpecData {
     pub boundary: Boundary,
     pub name: String,
 
+    #[serde(default)]
+    pub visibility: Visibility,
+
+    /// Person accountable for the spec
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Team the spec belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team: Option<String>,
+
     pub stage: WorkflowStage,
     #[serde(default)]
     pub stages_completed: Vec<WorkflowStage>,
@@ -245,6 +545,9 @@ impl SpecData {
             project,
             name,
             boundary,
+            visibility: Visibility::Private,
+            owner: None,
+            team: None,
             stage: WorkflowStage::Requirements,
             stages_completed: Vec::new(),
             requirements: Vec::new(),
@@ -258,6 +561,114 @@ impl SpecData {
         }
     }
 
+    /// Completed stages as they should be shown or compared
+    ///
+    /// Manual JSON edits, imports, and backwards stage changes can leave
+    /// completed stages at or after the current one; those are dropped, and
+    /// the rest come back in workflow order. Stages missing before the
+    /// current one were skipped and stay missing. The stored list is not
+    /// changed.
+    pub fn completed_stages(&self) -> Vec<WorkflowStage> {
+        completed_before(&self.stage, &self.stages_completed)
+    }
+
+    /// Reconcile `stages_completed` to [`SpecData::completed_stages`]
+    ///
+    /// For explicit repairs (`doctor --fix`) and reconstructed views; loading
+    /// and saving keep what is stored. Returns whether anything changed.
+    pub fn normalize_stages(&mut self) -> bool {
+        let expected = self.completed_stages();
+        if self.stages_completed == expected {
+            return false;
+        }
+        self.stages_completed = expected;
+        true
+    }
+
+    /// Apply an RFC 6902 JSON patch, keeping the spec structurally valid
+    ///
+    /// The patch runs against a JSON copy, and the spec is only replaced if the
+    /// result still deserializes, keeps its spec_id, and has non-empty required
+    /// fields. A rejected patch leaves the spec untouched.
+    #[allow(dead_code)]
+    pub fn apply_json_patch(&mut self, patch: &json_patch::Patch) -> anyhow::Result<()> {
+        self.apply_json_patch_as(patch, "user")
+    }
+
+    /// Apply a JSON patch on behalf of `actor`
+    ///
+    /// Same as [`SpecData::apply_json_patch`], and any requirement whose title
+    /// or SHALL statement changes gets its previous wording recorded in its
+    /// history under `actor`.
+    pub fn apply_json_patch_as(
+        &mut self,
+        patch: &json_patch::Patch,
+        actor: &str,
+    ) -> anyhow::Result<()> {
+        let mut value = serde_json::to_value(&*self)?;
+        json_patch::patch(&mut value, patch)
+            .map_err(|e| anyhow::anyhow!("Failed to apply patch: {}", e))?;
+
+        let mut patched: SpecData = serde_json::from_value(value)
+            .map_err(|e| anyhow::anyhow!("Patch would leave the spec invalid: {}", e))?;
+
+        if patched.spec_id != self.spec_id {
+            anyhow::bail!("Patch cannot change spec_id");
+        }
+        for (field, value) in [("project", &patched.project), ("name", &patched.name)] {
+            if value.trim().is_empty() {
+                anyhow::bail!("Patch would leave required field '{}' empty", field);
+            }
+        }
+
+        patched.record_requirement_revisions(self, actor);
+        patched.fill_missing_decision_dates();
+        *self = patched;
+        Ok(())
+    }
+
+    /// Set today's UTC date on decisions that have none
+    ///
+    /// Returns how many decisions were filled.
+    pub fn fill_missing_decision_dates(&mut self) -> usize {
+        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        let mut filled = 0;
+        for decision in &mut self.decisions {
+            if decision.date.trim().is_empty() {
+                decision.date = today.clone();
+                filled += 1;
+            }
+        }
+        filled
+    }
+
+    /// Record superseded wording for requirements changed since `previous`
+    ///
+    /// Requirements are matched by id. History can't be dropped by replacing
+    /// a whole requirement object: the previous history is carried over.
+    pub fn record_requirement_revisions(&mut self, previous: &SpecData, actor: &str) {
+        let now = chrono::Utc::now().timestamp();
+
+        for req in &mut self.requirements {
+            let Some(old) = previous.requirements.iter().find(|r| r.id == req.id) else {
+                continue;
+            };
+
+            if req.history.len() < old.history.len() {
+                req.history = old.history.clone();
+            }
+
+            if req.title != old.title || req.shall != old.shall {
+                req.history.push(RequirementRevision {
+                    title: old.title.clone(),
+                    shall: old.shall.clone(),
+                    timestamp: now,
+                    actor: actor.to_string(),
+                });
+            }
+        }
+    }
+
     /// Get the current workflow stage
     /// Utility method for external consumers of the library
     #[allow(dead_code)]
@@ -278,6 +689,111 @@ impl SpecData {
     pub fn get_task(&self, id: &str) -> Option<&Task> {
         self.tasks.iter().find(|t| t.id == id)
     }
+
+    /// Next free id with `prefix`, e.g. `req-4` after `req-3`
+    ///
+    /// Looks at requirement, task, decision, and scenario ids so the result
+    /// never collides with an existing item of any type. The number is
+    /// zero-padded to `digits`.
+    pub fn next_id(&self, prefix: &str, digits: usize) -> String {
+        let scenario_ids = self
+            .requirements
+            .iter()
+            .flat_map(|r| r.scenarios.iter().map(|s| s.id.as_str()));
+        let max = self
+            .requirements
+            .iter()
+            .map(|r| r.id.as_str())
+            .chain(self.tasks.iter().map(|t| t.id.as_str()))
+            .chain(self.decisions.iter().map(|d| d.id.as_str()))
+            .chain(scenario_ids)
+            .filter_map(|id| id.strip_prefix(prefix)?.strip_prefix('-'))
+            .filter_map(|n| n.parse::<u64>().ok())
+            .max()
+            .unwrap_or(0);
+        format!("{}-{:0width$}", prefix, max + 1, width = digits)
+    }
+
+    /// Check that requirement, task, and decision ids share no values and that
+    /// scenario ids are unique across the whole spec
+    ///
+    /// Each duplicate lists every JSON pointer where the id appears.
+    pub fn validate_ids_unique_across_types(&self) -> Result<(), Vec<DuplicateId>> {
+        let mut item_ids: Vec<(&str, String)> = Vec::new();
+        for (i, req) in self.requirements.iter().enumerate() {
+            item_ids.push((&req.id, format!("/requirements/{}/id", i)));
+        }
+        for (i, task) in self.tasks.iter().enumerate() {
+            item_ids.push((&task.id, format!("/tasks/{}/id", i)));
+        }
+        for (i, decision) in self.decisions.iter().enumerate() {
+            item_ids.push((&decision.id, format!("/decisions/{}/id", i)));
+        }
+
+        let mut scenario_ids: Vec<(&str, String)> = Vec::new();
+        for (i, req) in self.requirements.iter().enumerate() {
+            for (j, scenario) in req.scenarios.iter().enumerate() {
+                scenario_ids.push((
+                    &scenario.id,
+                    format!("/requirements/{}/scenarios/{}/id", i, j),
+                ));
+            }
+        }
+
+        let mut duplicates = collect_duplicates(item_ids);
+        duplicates.extend(collect_duplicates(scenario_ids));
+
+        if duplicates.is_empty() {
+            Ok(())
+        } else {
+            Err(duplicates)
+        }
+    }
+}
+
+/// An id that appears at more than one location in a spec
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateId {
+    pub id: String,
+    /// JSON pointers to every occurrence, in document order
+    pub locations: Vec<String>,
+}
+
+impl std::fmt::Display for DuplicateId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Duplicate id '{}' at {}",
+            self.id,
+            self.locations.join(", ")
+        )
+    }
+}
+
+/// Group (id, location) pairs and keep the ids seen more than once
+fn collect_duplicates(entries: Vec<(&str, String)>) -> Vec<DuplicateId> {
+    let mut duplicates: Vec<DuplicateId> = Vec::new();
+    let mut seen: std::collections::HashMap<&str, String> = std::collections::HashMap::new();
+
+    for (id, location) in entries {
+        match seen.get(id) {
+            Some(first) => {
+                if let Some(dup) = duplicates.iter_mut().find(|d| d.id == id) {
+                    dup.locations.push(location);
+                } else {
+                    duplicates.push(DuplicateId {
+                        id: id.to_string(),
+                        locations: vec![first.clone(), location],
+                    });
+                }
+            }
+            None => {
+                seen.insert(id, location);
+            }
+        }
+    }
+
+    duplicates
 }
 
 /// Database row representation of a spec
@@ -291,3 +807,155 @@ pub struct SpecRow {
     pub updated_at: i64,
     pub created_at: i64,
 }
+
+impl SpecRow {
+    /// Deserialize the stored JSON into a SpecData
+    ///
+    /// Corrupt or old-schema rows yield an error naming the spec instead of
+    /// panicking, so callers can skip them visibly.
+    pub fn parse(&self) -> anyhow::Result<SpecData> {
+        serde_json::from_value(self.data.clone())
+            .map_err(|e| anyhow::anyhow!("Spec {} could not be parsed: {}", self.id, e))
+    }
+
+    /// Stored `stages_completed` that disagrees with the stored `stage`
+    ///
+    /// Unparseable stages are a parse problem, not a mismatch, and yield None.
+    pub fn stage_mismatch(&self) -> Option<StageMismatch> {
+        let stage: WorkflowStage = serde_json::from_value(self.data.get("stage")?.clone()).ok()?;
+        let completed: Vec<WorkflowStage> = match self.data.get("stages_completed") {
+            Some(value) => serde_json::from_value(value.clone()).ok()?,
+            None => Vec::new(),
+        };
+        let expected = completed_before(&stage, &completed);
+        (completed != expected).then(|| StageMismatch {
+            spec_id: self.id.clone(),
+            stage,
+            stored: completed,
+            expected,
+        })
+    }
+
+    /// Indexed columns that disagree with the JSON body
+    ///
+    /// `stage` and `boundary` are copied into columns for filtering, so an
+    /// edit that bypasses manifold can leave them stale. Fields missing from
+    /// the JSON are a parse problem, not a mismatch, and are skipped.
+    pub fn column_mismatches(&self) -> Vec<ColumnMismatch> {
+        [("stage", &self.stage), ("boundary", &self.boundary)]
+            .into_iter()
+            .filter_map(|(column, value)| {
+                let json_value = self.data.get(column)?.as_str()?;
+                (json_value != value).then(|| ColumnMismatch {
+                    spec_id: self.id.clone(),
+                    column: column.to_string(),
+                    column_value: value.clone(),
+                    json_value: json_value.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// A spec whose `stages_completed` doesn't match its stage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageMismatch {
+    pub spec_id: String,
+    pub stage: WorkflowStage,
+    pub stored: Vec<WorkflowStage>,
+    /// The stored stages that come before `stage`, which a repair writes
+    pub expected: Vec<WorkflowStage>,
+}
+
+impl std::fmt::Display for StageMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let list = |stages: &[WorkflowStage]| {
+            stages
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        write!(
+            f,
+            "{}: stage is '{}' but completed stages are [{}], expected [{}]",
+            self.spec_id,
+            self.stage,
+            list(&self.stored),
+            list(&self.expected)
+        )
+    }
+}
+
+/// The entries of `completed` that come before `stage`, once each, in
+/// workflow order
+fn completed_before(stage: &WorkflowStage, completed: &[WorkflowStage]) -> Vec<WorkflowStage> {
+    WorkflowStage::all()
+        .into_iter()
+        .take(stage.order())
+        .filter(|s| completed.contains(s))
+        .collect()
+}
+
+/// A spec whose indexed column differs from its JSON body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnMismatch {
+    pub spec_id: String,
+    /// `stage` or `boundary`
+    pub column: String,
+    pub column_value: String,
+    /// What the JSON says, and what a repair writes to the column
+    pub json_value: String,
+}
+
+impl std::fmt::Display for ColumnMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} column is '{}' but the spec JSON says '{}'",
+            self.spec_id, self.column, self.column_value, self.json_value
+        )
+    }
+}
+
+/// Canonical JSON text: object keys sorted, no whitespace
+///
+/// The same value always serializes to the same bytes, so the output is
+/// safe to hash or diff.
+pub fn canonical_json(value: &serde_json::Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &serde_json::Value, out: &mut String) {
+    use serde_json::Value;
+
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(&map[key], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
@@ -0,0 +1,266 @@
+//! Regex search over canonical spec JSON
+//!
+//! Complements FTS5 for patterns it cannot express, such as `req-\d{3}` or
+//! partial identifiers. Matches are reported per string field as JSON
+//! pointers into the spec. Full-text hits can be located the same way, so
+//! editors can jump to the field a query matched.
+
+use anyhow::{bail, Result};
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::db::{Database, Page, SpecOrder};
+use crate::models::{Boundary, WorkflowStage};
+
+/// Longest pattern accepted, in bytes
+pub const MAX_PATTERN_LEN: usize = 1024;
+
+/// Cap on the compiled program size, so huge repetitions fail fast
+const COMPILED_SIZE_LIMIT: usize = 1 << 20;
+
+/// Longest field value tested against the pattern, in bytes
+pub const MAX_FIELD_LEN: usize = 64 * 1024;
+
+/// A string field in a spec that matched the pattern
+#[derive(Debug, Clone, Serialize)]
+pub struct RegexMatch {
+    pub spec_id: String,
+    /// JSON pointer to the matching field, e.g. /requirements/0/id
+    pub path: String,
+    /// Text matched by the pattern
+    pub matched: String,
+    /// Full value of the field
+    pub value: String,
+}
+
+/// Compile a user-supplied pattern with size guards
+///
+/// The regex crate matches in linear time, so there is no catastrophic
+/// backtracking to time out; the remaining risk is a pattern that compiles
+/// to an enormous program, which the size limit rejects.
+pub fn compile_pattern(pattern: &str) -> Result<Regex> {
+    if pattern.len() > MAX_PATTERN_LEN {
+        bail!(
+            "Regex too long ({} bytes, max {})",
+            pattern.len(),
+            MAX_PATTERN_LEN
+        );
+    }
+
+    RegexBuilder::new(pattern)
+        .size_limit(COMPILED_SIZE_LIMIT)
+        .dfa_size_limit(COMPILED_SIZE_LIMIT)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Invalid regex: {}", e))
+}
+
+/// Find every string field in a spec's JSON that matches
+pub fn match_spec(re: &Regex, spec_id: &str, data: &Value) -> Vec<RegexMatch> {
+    let mut matches = Vec::new();
+    walk(re, spec_id, data, &mut String::new(), &mut matches);
+    matches
+}
+
+fn walk(re: &Regex, spec_id: &str, value: &Value, path: &mut String, out: &mut Vec<RegexMatch>) {
+    match value {
+        Value::String(s) => {
+            // Oversized fields are skipped rather than truncated, so a match
+            // is never reported against text the user can't see
+            if s.len() > MAX_FIELD_LEN {
+                return;
+            }
+            if let Some(m) = re.find(s) {
+                out.push(RegexMatch {
+                    spec_id: spec_id.to_string(),
+                    path: if path.is_empty() {
+                        "/".to_string()
+                    } else {
+                        path.clone()
+                    },
+                    matched: m.as_str().to_string(),
+                    value: s.clone(),
+                });
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                let len = path.len();
+                path.push_str(&format!("/{}", i));
+                walk(re, spec_id, item, path, out);
+                path.truncate(len);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                let len = path.len();
+                path.push('/');
+                // RFC 6901 escaping
+                path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                walk(re, spec_id, item, path, out);
+                path.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Run a regex over stored specs
+///
+/// With `fts`, candidates are first narrowed by a full-text query; otherwise
+/// every spec passing the boundary/stage filters is scanned one row at a time.
+pub fn regex_search(
+    db: &Database,
+    re: &Regex,
+    fts: Option<&str>,
+    boundary: Option<&Boundary>,
+    stage: Option<&WorkflowStage>,
+) -> Result<Vec<RegexMatch>> {
+    let mut matches = Vec::new();
+    let mut visit = |spec: crate::models::SpecRow| {
+        matches.extend(match_spec(re, &spec.id, &spec.data));
+        Ok(())
+    };
+
+    match fts {
+        Some(query) => {
+            db.each_search_result(query, boundary, stage, Page::default(), &mut visit)?
+        }
+        None => db.each_spec(
+            boundary,
+            stage,
+            SpecOrder::ProjectAsc,
+            Page::default(),
+            &mut visit,
+        )?,
+    }
+
+    Ok(matches)
+}
+
+/// Characters of context kept on each side of a match in a snippet
+const SNIPPET_CONTEXT: usize = 40;
+
+/// A full-text hit with the fields its terms matched
+#[derive(Debug, Clone, Serialize)]
+pub struct HighlightHit {
+    pub spec_id: String,
+    pub name: String,
+    pub matches: Vec<FieldHighlight>,
+}
+
+/// Where a query term matched inside one string field
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldHighlight {
+    /// JSON pointer to the field, e.g. /requirements/0/shall
+    pub path: String,
+    /// Character offset of the first match within the field
+    pub start: usize,
+    /// Character offset just past the first match
+    pub end: usize,
+    /// The matched text with surrounding context
+    pub snippet: String,
+}
+
+/// Regex matching the terms of an FTS query as whole words
+///
+/// Operators (`AND`, `OR`, `NOT`, `NEAR`), column filters, quotes, and
+/// parentheses are dropped; a trailing `*` matches as a prefix, as in FTS5.
+/// Returns `None` when the query has no terms left.
+pub fn fts_terms_pattern(query: &str) -> Option<Regex> {
+    let mut terms = Vec::new();
+    for raw in query.split_whitespace() {
+        if matches!(raw, "AND" | "OR" | "NOT" | "NEAR") {
+            continue;
+        }
+        let token = raw.rsplit_once(':').map_or(raw, |(_, term)| term);
+        let token = token.trim_matches(|c| matches!(c, '"' | '(' | ')' | '^' | '+'));
+        let (term, prefix) = match token.strip_suffix('*') {
+            Some(term) => (term, true),
+            None => (token, false),
+        };
+        if term.is_empty() {
+            continue;
+        }
+        let term = regex::escape(term);
+        terms.push(if prefix {
+            format!(r"\b{}\w*", term)
+        } else {
+            format!(r"\b{}\b", term)
+        });
+    }
+    if terms.is_empty() {
+        return None;
+    }
+
+    RegexBuilder::new(&terms.join("|"))
+        .case_insensitive(true)
+        .size_limit(COMPILED_SIZE_LIMIT)
+        .build()
+        .ok()
+}
+
+/// Locate `re` in every string field of a spec, with snippets
+pub fn highlight_spec(re: &Regex, spec_id: &str, data: &Value) -> Vec<FieldHighlight> {
+    match_spec(re, spec_id, data)
+        .into_iter()
+        .filter_map(|m| {
+            let found = re.find(&m.value)?;
+            let start = m.value[..found.start()].chars().count();
+            let end = start + found.as_str().chars().count();
+            Some(FieldHighlight {
+                path: m.path,
+                start,
+                end,
+                snippet: snippet(&m.value, start, end),
+            })
+        })
+        .collect()
+}
+
+/// `value` cut to the match plus context, with ellipses where trimmed
+fn snippet(value: &str, start: usize, end: usize) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    let from = start.saturating_sub(SNIPPET_CONTEXT);
+    let to = (end + SNIPPET_CONTEXT).min(chars.len());
+
+    let mut snippet = String::new();
+    if from > 0 {
+        snippet.push('…');
+    }
+    snippet.extend(&chars[from..to]);
+    if to < chars.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+/// Run a full-text search, reporting where in each spec the terms matched
+pub fn highlight_search(
+    db: &Database,
+    query: &str,
+    boundary: Option<&Boundary>,
+    stage: Option<&WorkflowStage>,
+    page: Page,
+) -> Result<Vec<HighlightHit>> {
+    let re = fts_terms_pattern(query);
+    let mut hits = Vec::new();
+    db.each_search_result(query, boundary, stage, page, |spec| {
+        let matches = re
+            .as_ref()
+            .map(|re| highlight_spec(re, &spec.id, &spec.data))
+            .unwrap_or_default();
+        hits.push(HighlightHit {
+            name: spec
+                .data
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            spec_id: spec.id,
+            matches,
+        });
+        Ok(())
+    })?;
+    Ok(hits)
+}
@@ -0,0 +1,184 @@
+//! Spec templates
+//!
+//! Reusable spec skeletons stored as JSON under ~/.manifold/templates/
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::{
+    Boundary, Decision, Requirement, SpecData, Task, TaskStatus, VerificationStatus,
+};
+use crate::validation::IdPatterns;
+
+/// A spec skeleton with no identity or history of its own
+///
+/// Ids are renumbered positionally with the configured id schemes, and
+/// timestamps, assignees, task progress, and verification are dropped, so a
+/// template carries no trace of its source spec.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub requirements: Vec<Requirement>,
+    #[serde(default)]
+    pub tasks: Vec<Task>,
+    #[serde(default)]
+    pub decisions: Vec<Decision>,
+}
+
+impl SpecTemplate {
+    /// Build a template from an existing spec, numbering ids with `ids`
+    pub fn from_spec(name: &str, spec: &SpecData, ids: &IdPatterns) -> Result<Self> {
+        validate_template_name(name)?;
+
+        let mut template = Self {
+            name: name.to_string(),
+            requirements: spec.requirements.clone(),
+            tasks: spec.tasks.clone(),
+            decisions: spec.decisions.clone(),
+        };
+
+        for req in &mut template.requirements {
+            req.history.clear();
+            req.verification = VerificationStatus::NotStarted;
+        }
+        for task in &mut template.tasks {
+            task.status = TaskStatus::Pending;
+            task.assignee = None;
+        }
+        for decision in &mut template.decisions {
+            decision.date = String::new();
+        }
+        template.renumber_ids(ids);
+
+        Ok(template)
+    }
+
+    /// Create a new spec from this template with ids freshly numbered by `ids`
+    pub fn instantiate(
+        &self,
+        spec_id: String,
+        project: String,
+        name: String,
+        boundary: Boundary,
+        ids: &IdPatterns,
+    ) -> SpecData {
+        let mut fresh = self.clone();
+        fresh.renumber_ids(ids);
+
+        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        for decision in &mut fresh.decisions {
+            decision.date = today.clone();
+        }
+
+        let mut spec = SpecData::new(spec_id, project, name, boundary);
+        spec.requirements = fresh.requirements;
+        spec.tasks = fresh.tasks;
+        spec.decisions = fresh.decisions;
+        spec
+    }
+
+    /// Renumber all ids sequentially, keeping task -> requirement and
+    /// task -> scenario links intact
+    fn renumber_ids(&mut self, ids: &IdPatterns) {
+        let mut req_ids = HashMap::new();
+        let mut scenario_refs = HashMap::new();
+        let mut scenario_counter = 0;
+
+        for (i, req) in self.requirements.iter_mut().enumerate() {
+            let new_id = ids.requirement.format(i as u64 + 1);
+            let old_id = std::mem::replace(&mut req.id, new_id.clone());
+
+            // Scenario ids are unique across the whole spec
+            for scenario in &mut req.scenarios {
+                scenario_counter += 1;
+                let new_sc_id = ids.scenario.format(scenario_counter);
+                let old_sc_id = std::mem::replace(&mut scenario.id, new_sc_id.clone());
+                scenario_refs.insert(
+                    format!("{}/{}", old_id, old_sc_id),
+                    format!("{}/{}", new_id, new_sc_id),
+                );
+            }
+            req_ids.insert(old_id, new_id);
+        }
+
+        for (i, task) in self.tasks.iter_mut().enumerate() {
+            task.id = ids.task.format(i as u64 + 1);
+            task.requirement_ids = task
+                .requirement_ids
+                .iter()
+                .filter_map(|id| req_ids.get(id).cloned())
+                .collect();
+            task.scenario_ids = task
+                .scenario_ids
+                .iter()
+                .filter_map(|id| scenario_refs.get(id).cloned())
+                .collect();
+        }
+
+        for (i, decision) in self.decisions.iter_mut().enumerate() {
+            decision.id = ids.decision.format(i as u64 + 1);
+        }
+    }
+}
+
+/// Template names double as file names, so keep them simple
+fn validate_template_name(name: &str) -> Result<()> {
+    let re = regex::Regex::new(r"^[a-z0-9][a-z0-9_-]*$").unwrap();
+    if !re.is_match(name) {
+        bail!(
+            "Invalid template name '{}'. Use lowercase letters, digits, '-' and '_'",
+            name
+        );
+    }
+    Ok(())
+}
+
+fn template_path(dir: &Path, name: &str) -> Result<PathBuf> {
+    validate_template_name(name)?;
+    Ok(dir.join(format!("{}.json", name)))
+}
+
+/// Save a template to the templates directory, overwriting any existing one
+pub fn save_template(dir: &Path, template: &SpecTemplate) -> Result<PathBuf> {
+    fs::create_dir_all(dir).context("Failed to create templates directory")?;
+    let path = template_path(dir, &template.name)?;
+    let content = serde_json::to_string_pretty(template)?;
+    fs::write(&path, content).context("Failed to write template")?;
+    Ok(path)
+}
+
+/// Load a template by name
+pub fn load_template(dir: &Path, name: &str) -> Result<SpecTemplate> {
+    let path = template_path(dir, name)?;
+    if !path.exists() {
+        bail!(
+            "Template not found: {}. Run `manifold template list` to see available templates.",
+            name
+        );
+    }
+    let content = fs::read_to_string(&path).context("Failed to read template")?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse template {}", name))
+}
+
+/// List template names, sorted alphabetically
+pub fn list_templates(dir: &Path) -> Result<Vec<String>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut names = Vec::new();
+    for entry in fs::read_dir(dir).context("Failed to read templates directory")? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) == Some("json") {
+            if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                names.push(stem.to_string());
+            }
+        }
+    }
+    names.sort();
+    Ok(names)
+}
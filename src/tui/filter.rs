@@ -0,0 +1,24 @@
+//! In-tab filtering for the Requirements and Tasks tabs
+//!
+//! Matches are case-insensitive substrings; an empty or blank query
+//! matches everything.
+
+use crate::models::{Requirement, Task};
+
+/// Whether a requirement's id, title, or SHALL statement contains `query`
+pub fn requirement_matches(req: &Requirement, query: &str) -> bool {
+    matches_any(&[&req.id, &req.title, &req.shall], query)
+}
+
+/// Whether a task's id, title, or description contains `query`
+pub fn task_matches(task: &Task, query: &str) -> bool {
+    matches_any(&[&task.id, &task.title, &task.description], query)
+}
+
+fn matches_any(fields: &[&str], query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    query.is_empty()
+        || fields
+            .iter()
+            .any(|field| field.to_lowercase().contains(&query))
+}
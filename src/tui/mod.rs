@@ -2,35 +2,72 @@
 //!
 //! Provides an interactive dashboard for browsing and managing specs
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::{Backend, CrosstermBackend},
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Tabs, Wrap},
     Frame, Terminal,
 };
+use std::collections::HashSet;
 use std::io;
+use std::time::{Duration, Instant};
 
 use crate::collab::conflicts::ConflictResolver;
 use crate::collab::{Conflict, ConflictStatus, ResolutionStrategy};
 use crate::config::ManifoldPaths;
-use crate::db::Database;
-use crate::models::{SpecData, SpecRow};
+use crate::db::{Database, SpecOrder};
+use crate::models::{Boundary, SpecData, SpecRow, WorkflowStage};
+
+pub mod filter;
+pub mod mouse;
+pub mod theme;
+
+use theme::Theme;
+
+/// Detail view tabs, in `selected_tab` order
+const TAB_TITLES: [&str; 6] = [
+    "Overview",
+    "Requirements",
+    "Tasks",
+    "Decisions",
+    "History",
+    "Conflicts",
+];
+
+/// Ids of rows that fail to deserialize, shown as warnings in the list
+fn unparseable_ids(specs: &[SpecRow]) -> HashSet<String> {
+    specs
+        .iter()
+        .filter(|row| row.parse().is_err())
+        .map(|row| row.id.clone())
+        .collect()
+}
 
 /// Main TUI application state
 pub struct TuiApp {
     db: Database,
     specs: Vec<SpecRow>,
+    /// Ids of loaded rows whose JSON doesn't deserialize
+    unparseable: HashSet<String>,
     list_state: ListState,
     selected_tab: usize,
     should_quit: bool,
     filter_boundary: Option<String>,
+    sort_order: SpecOrder,
+    /// Substring narrowing the Requirements and Tasks tabs
+    detail_filter: String,
+    /// Set while the detail filter is being typed
+    editing_filter: bool,
     conflicts: Vec<Conflict>,
     conflict_list_state: ListState,
     show_resolution_popup: bool,
@@ -39,10 +76,34 @@ pub struct TuiApp {
     // Manual editing state
     show_manual_edit_popup: bool,
     manual_edit_input: String,
+    /// Last AI suggestion put in the input, to attribute unedited values
+    ai_prefill: Option<String>,
+    /// Set when an API key is configured; enables AI suggestions
+    llm: Option<crate::llm::LlmConfig>,
     // Bulk operations
     show_bulk_popup: bool,
     // Conflict statistics
     conflict_stats: ConflictStats,
+    theme: Theme,
+    /// Who resolutions are attributed to
+    actor: String,
+    // Where the spec list and tabs were last drawn, for mouse hit-testing
+    list_area: Rect,
+    tabs_area: Rect,
+    /// How often to check for changes made elsewhere; None when disabled
+    refresh_interval: Option<Duration>,
+    last_refresh_check: Instant,
+    /// `Database::specs_stamp` as of the last load
+    specs_stamp: (i64, i64),
+}
+
+/// Where the TUI opens
+#[derive(Debug, Clone, Default)]
+pub struct TuiOptions {
+    /// Start with the list filtered to this boundary
+    pub boundary: Option<Boundary>,
+    /// Start with this spec selected, on its Overview tab
+    pub spec: Option<String>,
 }
 
 #[derive(Default, Clone)]
@@ -54,24 +115,51 @@ struct ConflictStats {
 
 impl TuiApp {
     /// Create a new TUI application
-    pub fn new(paths: &ManifoldPaths) -> Result<Self> {
+    pub fn new(paths: &ManifoldPaths, options: TuiOptions) -> Result<Self> {
+        let config = crate::config::load_config_from(&paths.config)?;
+        let theme = Theme::resolve(&config.tui.theme, Theme::no_color_requested())?;
+        let actor = crate::commands::current_actor(&config);
+        let llm =
+            Some(crate::llm::LlmConfig::from_settings(&config.llm)).filter(|c| c.is_enabled());
+
+        let refresh_interval =
+            Some(Duration::from_secs(config.tui.refresh_secs)).filter(|d| !d.is_zero());
+
         let db = Database::open(paths)?;
-        let specs = db.list_specs(None, None)?;
+        let specs_stamp = db.specs_stamp()?;
+        let specs = db.list_specs(options.boundary.as_ref(), None, SpecOrder::default())?;
 
         let mut list_state = ListState::default();
-        if !specs.is_empty() {
-            list_state.select(Some(0));
+        match &options.spec {
+            Some(id) => {
+                let index = specs.iter().position(|row| &row.id == id);
+                let Some(index) = index else {
+                    match (db.get_spec(id)?, &options.boundary) {
+                        (Some(_), Some(boundary)) => {
+                            bail!("Spec {} is not in the {} boundary", id, boundary)
+                        }
+                        _ => bail!("Spec not found: {}", id),
+                    }
+                };
+                list_state.select(Some(index));
+            }
+            None if !specs.is_empty() => list_state.select(Some(0)),
+            None => {}
         }
 
         let conflict_list_state = ListState::default();
 
         Ok(Self {
             db,
+            unparseable: unparseable_ids(&specs),
             specs,
             list_state,
             selected_tab: 0,
             should_quit: false,
-            filter_boundary: None,
+            filter_boundary: options.boundary.map(|b| b.to_string()),
+            sort_order: SpecOrder::default(),
+            detail_filter: String::new(),
+            editing_filter: false,
             conflicts: Vec::new(),
             conflict_list_state,
             show_resolution_popup: false,
@@ -79,11 +167,45 @@ impl TuiApp {
             status_message: None,
             show_manual_edit_popup: false,
             manual_edit_input: String::new(),
+            ai_prefill: None,
+            llm,
             show_bulk_popup: false,
             conflict_stats: ConflictStats::default(),
+            theme,
+            actor,
+            list_area: Rect::default(),
+            tabs_area: Rect::default(),
+            refresh_interval,
+            last_refresh_check: Instant::now(),
+            specs_stamp,
         })
     }
 
+    /// Boundary the spec list is filtered to, if any
+    #[allow(dead_code)]
+    pub fn filter_boundary(&self) -> Option<&str> {
+        self.filter_boundary.as_deref()
+    }
+
+    /// Id of the selected spec
+    #[allow(dead_code)]
+    pub fn selected_spec_id(&self) -> Option<&str> {
+        let row = self.specs.get(self.list_state.selected()?)?;
+        Some(&row.id)
+    }
+
+    /// Title of the detail tab being shown
+    #[allow(dead_code)]
+    pub fn selected_tab_title(&self) -> &'static str {
+        TAB_TITLES[self.selected_tab]
+    }
+
+    /// Ids in the spec list, in display order
+    #[allow(dead_code)]
+    pub fn spec_ids(&self) -> Vec<&str> {
+        self.specs.iter().map(|row| row.id.as_str()).collect()
+    }
+
     /// Run the TUI application
     pub fn run(&mut self) -> Result<()> {
         // Setup terminal
@@ -111,10 +233,23 @@ impl TuiApp {
     /// Main event loop
     fn run_app<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
         loop {
+            if let Some(interval) = self.refresh_interval {
+                if self.last_refresh_check.elapsed() >= interval {
+                    self.last_refresh_check = Instant::now();
+                    self.auto_refresh()?;
+                }
+            }
+
             terminal.draw(|f| self.ui(f))?;
 
             if event::poll(std::time::Duration::from_millis(100))? {
-                if let Event::Key(key) = event::read()? {
+                let event = event::read()?;
+                if let Event::Mouse(mouse) = event {
+                    self.handle_mouse(mouse);
+                    continue;
+                }
+
+                if let Event::Key(key) = event {
                     // Handle popup-specific keys first
                     if self.show_resolution_popup
                         || self.show_bulk_popup
@@ -132,6 +267,7 @@ impl TuiApp {
                                     self.show_resolution_popup = false;
                                     self.show_manual_edit_popup = true;
                                     self.manual_edit_input.clear();
+                                    self.ai_prefill = None;
                                 } else {
                                     self.apply_resolution()?;
                                 }
@@ -145,6 +281,10 @@ impl TuiApp {
                                 self.apply_manual_resolution()?;
                                 continue;
                             }
+                            KeyCode::Tab if self.show_manual_edit_popup => {
+                                self.prefill_ai_suggestion();
+                                continue;
+                            }
                             KeyCode::Char(c) if self.show_manual_edit_popup => {
                                 self.manual_edit_input.push(c);
                                 continue;
@@ -171,7 +311,31 @@ impl TuiApp {
                         }
                     }
 
+                    if self.editing_filter {
+                        match key.code {
+                            KeyCode::Esc => {
+                                self.editing_filter = false;
+                                self.detail_filter.clear();
+                            }
+                            KeyCode::Enter => self.editing_filter = false,
+                            KeyCode::Char(c) => self.detail_filter.push(c),
+                            KeyCode::Backspace => {
+                                self.detail_filter.pop();
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
                     match key.code {
+                        KeyCode::Esc
+                            if self.filters_detail_tab() && !self.detail_filter.is_empty() =>
+                        {
+                            self.detail_filter.clear();
+                        }
+                        KeyCode::Char('f') if self.filters_detail_tab() => {
+                            self.editing_filter = true;
+                        }
                         KeyCode::Char('q') | KeyCode::Esc => {
                             self.should_quit = true;
                         }
@@ -210,25 +374,30 @@ impl TuiApp {
                             self.filter_boundary = Some("company".to_string());
                             self.refresh_specs()?;
                         }
+                        KeyCode::Char('s') => {
+                            // Cycle sort order
+                            self.sort_order = self.sort_order.next();
+                            self.refresh_specs()?;
+                        }
                         KeyCode::Char('c') if self.selected_tab == 5 => {
                             // Load conflicts for selected spec
                             self.load_conflicts()?;
                         }
                         KeyCode::Char('o')
-                            if self.selected_tab == 5 && !self.show_resolution_popup =>
-                        {
+                            if self.selected_tab == 5 && !self.show_resolution_popup
                             // Open resolution popup
-                            if self.conflict_list_state.selected().is_some() {
-                                self.show_resolution_popup = true;
-                                self.selected_strategy = 0;
-                            }
+                            && self.conflict_list_state.selected().is_some() =>
+                        {
+                            self.show_resolution_popup = true;
+                            self.selected_strategy = 0;
                         }
-                        KeyCode::Char('b') if self.selected_tab == 5 && !self.show_bulk_popup => {
+                        KeyCode::Char('b')
+                            if self.selected_tab == 5 && !self.show_bulk_popup
                             // Open bulk resolution popup
-                            if !self.conflicts.is_empty() {
-                                self.show_bulk_popup = true;
-                                self.selected_strategy = 0;
-                            }
+                            && !self.conflicts.is_empty() =>
+                        {
+                            self.show_bulk_popup = true;
+                            self.selected_strategy = 0;
                         }
                         KeyCode::Char('a') if self.selected_tab == 5 => {
                             // Auto-merge all compatible conflicts
@@ -286,7 +455,7 @@ impl TuiApp {
         let title = Paragraph::new("╔═══════════════════════════════════════════════════════════════╗\n\
                                      ║  Manifold Dashboard - Specification Management System         ║\n\
                                      ╚═══════════════════════════════════════════════════════════════╝")
-            .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+            .style(self.theme.title);
         f.render_widget(title, area);
     }
 
@@ -301,14 +470,17 @@ impl TuiApp {
             .specs
             .iter()
             .map(|spec| {
-                let stage_icon = match spec.stage.as_str() {
-                    "requirements" => "📋",
-                    "design" => "📐",
-                    "tasks" => "📝",
-                    "approval" => "✅",
-                    "implemented" => "🎉",
-                    _ => "❓",
-                };
+                if self.unparseable.contains(&spec.id) {
+                    return ListItem::new(format!("⚠ unparseable: {}", spec.id))
+                        .style(self.theme.error);
+                }
+
+                // Only a corrupt stage column fails to parse
+                let stage_icon = spec
+                    .stage
+                    .parse::<WorkflowStage>()
+                    .map(|stage| stage.icon())
+                    .unwrap_or("❓");
 
                 let name = spec
                     .data
@@ -323,18 +495,16 @@ impl TuiApp {
 
         let list = List::new(items)
             .block(Block::default().borders(Borders::ALL).title(format!(
-                "Specs{} ({})",
+                "Specs{} ({}) sort: {}",
                 filter_text,
-                self.specs.len()
+                self.specs.len(),
+                self.sort_order
             )))
-            .highlight_style(
-                Style::default()
-                    .bg(Color::Blue)
-                    .add_modifier(Modifier::BOLD),
-            )
+            .highlight_style(self.theme.highlight)
             .highlight_symbol(">> ");
 
         f.render_stateful_widget(list, area, &mut self.list_state);
+        self.list_area = area;
     }
 
     /// Render detail view
@@ -345,45 +515,46 @@ impl TuiApp {
             .constraints([Constraint::Length(3), Constraint::Min(0)])
             .split(area);
 
-        let titles = vec![
-            "Overview",
-            "Requirements",
-            "Tasks",
-            "Decisions",
-            "History",
-            "Conflicts",
-        ];
-        let tabs = Tabs::new(titles)
+        let tabs = Tabs::new(TAB_TITLES)
             .block(Block::default().borders(Borders::ALL).title("Details"))
             .select(self.selected_tab)
-            .style(Style::default().fg(Color::White))
-            .highlight_style(
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            );
+            .style(self.theme.text)
+            .highlight_style(self.theme.tab_active);
 
         f.render_widget(tabs, tabs_area[0]);
+        self.tabs_area = tabs_area[0];
 
         // Tab content
         if let Some(selected) = self.list_state.selected() {
             if let Some(spec_row) = self.specs.get(selected) {
                 let content_area = tabs_area[1];
 
-                match self.selected_tab {
-                    0 => self.render_overview(f, content_area, spec_row),
-                    1 => self.render_requirements(f, content_area, spec_row),
-                    2 => self.render_tasks(f, content_area, spec_row),
-                    3 => self.render_decisions(f, content_area, spec_row),
-                    4 => self.render_history(f, content_area, spec_row),
-                    5 => self.render_conflicts(f, content_area),
-                    _ => {}
+                if self.selected_tab == 5 {
+                    self.render_conflicts(f, content_area);
+                } else {
+                    match spec_row.parse() {
+                        Ok(spec) => match self.selected_tab {
+                            0 => self.render_overview(f, content_area, &spec),
+                            1 => self.render_requirements(f, content_area, &spec),
+                            2 => self.render_tasks(f, content_area, &spec),
+                            3 => self.render_decisions(f, content_area, &spec),
+                            4 => self.render_history(f, content_area, &spec),
+                            _ => {}
+                        },
+                        Err(e) => {
+                            let error = Paragraph::new(format!("⚠ {}", e))
+                                .block(Block::default().borders(Borders::ALL))
+                                .style(self.theme.error)
+                                .wrap(Wrap { trim: true });
+                            f.render_widget(error, content_area);
+                        }
+                    }
                 }
             }
         } else {
             let empty = Paragraph::new("No spec selected")
                 .block(Block::default().borders(Borders::ALL))
-                .style(Style::default().fg(Color::DarkGray));
+                .style(self.theme.muted);
             f.render_widget(empty, tabs_area[1]);
         }
 
@@ -409,29 +580,26 @@ impl TuiApp {
     }
 
     /// Render overview tab
-    fn render_overview(&self, f: &mut Frame, area: Rect, spec_row: &SpecRow) {
-        let spec: SpecData = serde_json::from_value(spec_row.data.clone()).unwrap();
-
-        let workflow_stages = ["requirements", "design", "tasks", "approval", "implemented"];
-        let current_stage_idx = workflow_stages
-            .iter()
-            .position(|&s| s == spec.stage.to_string())
-            .unwrap_or(0);
+    fn render_overview(&self, f: &mut Frame, area: Rect, spec: &SpecData) {
+        let workflow_stages = WorkflowStage::all();
+        let current_stage_idx = spec.stage.order();
 
         let mut workflow_viz = String::new();
         for (i, stage) in workflow_stages.iter().enumerate() {
             if i == current_stage_idx {
-                workflow_viz.push_str(&format!(" [{}] ", stage.to_uppercase()));
+                workflow_viz.push_str(&format!(" [{}] ", stage.to_string().to_uppercase()));
             } else if i < current_stage_idx {
                 workflow_viz.push_str(&format!(" ✓ {} ", stage));
             } else {
                 workflow_viz.push_str(&format!(" · {} ", stage));
             }
             if i < workflow_stages.len() - 1 {
-                workflow_viz.push_str("→");
+                workflow_viz.push('→');
             }
         }
 
+        let coverage = crate::validation::coverage(spec);
+
         let text = format!(
             "Spec ID:      {}\n\
              Project:      {}\n\
@@ -445,6 +613,8 @@ impl TuiApp {
              Requirements: {}\n\
              Tasks:        {}\n\
              Decisions:    {}\n\
+             Coverage:     {:.0}% ({}/{} reqs with scenarios, {}/{} traced, {}/{} tasks with acceptance)\n\
+             Verified:     {}/{} requirements\n\
              \n\
              Created:      {}\n\
              Updated:      {}",
@@ -456,6 +626,15 @@ impl TuiApp {
             spec.requirements.len(),
             spec.tasks.len(),
             spec.decisions.len(),
+            coverage.score,
+            coverage.requirements_with_scenarios,
+            coverage.requirements,
+            coverage.requirements_traced,
+            coverage.requirements,
+            coverage.tasks_with_acceptance,
+            coverage.tasks,
+            coverage.requirements_verified,
+            coverage.requirements,
             chrono::DateTime::from_timestamp(spec.history.created_at, 0)
                 .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
                 .unwrap_or_else(|| "unknown".to_string()),
@@ -471,15 +650,38 @@ impl TuiApp {
         f.render_widget(paragraph, area);
     }
 
+    /// Whether the selected tab is one the detail filter applies to
+    fn filters_detail_tab(&self) -> bool {
+        matches!(self.selected_tab, 1 | 2)
+    }
+
+    /// Tab title, with the active detail filter if there is one
+    fn filtered_title(&self, title: &str, shown: usize, total: usize) -> String {
+        if self.detail_filter.is_empty() && !self.editing_filter {
+            return title.to_string();
+        }
+        let cursor = if self.editing_filter { "_" } else { "" };
+        format!(
+            "{} (filter: {}{}, {}/{})",
+            title, self.detail_filter, cursor, shown, total
+        )
+    }
+
     /// Render requirements tab
-    fn render_requirements(&self, f: &mut Frame, area: Rect, spec_row: &SpecRow) {
-        let spec: SpecData = serde_json::from_value(spec_row.data.clone()).unwrap();
+    fn render_requirements(&self, f: &mut Frame, area: Rect, spec: &SpecData) {
+        let requirements: Vec<_> = spec
+            .requirements
+            .iter()
+            .filter(|req| filter::requirement_matches(req, &self.detail_filter))
+            .collect();
 
         let mut text = String::new();
         if spec.requirements.is_empty() {
             text.push_str("No requirements defined yet.\n");
+        } else if requirements.is_empty() {
+            text.push_str("No requirements match the filter.\n");
         } else {
-            for req in &spec.requirements {
+            for req in requirements.iter().copied() {
                 text.push_str(&format!(
                     "\n{} - {} [{}]\n",
                     req.id, req.title, req.priority
@@ -496,22 +698,30 @@ impl TuiApp {
             }
         }
 
+        let title =
+            self.filtered_title("Requirements", requirements.len(), spec.requirements.len());
         let paragraph = Paragraph::new(text)
-            .block(Block::default().borders(Borders::ALL).title("Requirements"))
+            .block(Block::default().borders(Borders::ALL).title(title))
             .wrap(Wrap { trim: true });
 
         f.render_widget(paragraph, area);
     }
 
     /// Render tasks tab
-    fn render_tasks(&self, f: &mut Frame, area: Rect, spec_row: &SpecRow) {
-        let spec: SpecData = serde_json::from_value(spec_row.data.clone()).unwrap();
+    fn render_tasks(&self, f: &mut Frame, area: Rect, spec: &SpecData) {
+        let tasks: Vec<_> = spec
+            .tasks
+            .iter()
+            .filter(|task| filter::task_matches(task, &self.detail_filter))
+            .collect();
 
         let mut text = String::new();
         if spec.tasks.is_empty() {
             text.push_str("No tasks defined yet.\n");
+        } else if tasks.is_empty() {
+            text.push_str("No tasks match the filter.\n");
         } else {
-            for task in &spec.tasks {
+            for task in tasks.iter().copied() {
                 text.push_str(&format!(
                     "\n{} - {} [{}]\n",
                     task.id, task.title, task.status
@@ -523,17 +733,16 @@ impl TuiApp {
             }
         }
 
+        let title = self.filtered_title("Tasks", tasks.len(), spec.tasks.len());
         let paragraph = Paragraph::new(text)
-            .block(Block::default().borders(Borders::ALL).title("Tasks"))
+            .block(Block::default().borders(Borders::ALL).title(title))
             .wrap(Wrap { trim: true });
 
         f.render_widget(paragraph, area);
     }
 
     /// Render decisions tab
-    fn render_decisions(&self, f: &mut Frame, area: Rect, spec_row: &SpecRow) {
-        let spec: SpecData = serde_json::from_value(spec_row.data.clone()).unwrap();
-
+    fn render_decisions(&self, f: &mut Frame, area: Rect, spec: &SpecData) {
         let mut text = String::new();
         if spec.decisions.is_empty() {
             text.push_str("No design decisions documented yet.\n");
@@ -560,9 +769,7 @@ impl TuiApp {
     }
 
     /// Render history tab
-    fn render_history(&self, f: &mut Frame, area: Rect, spec_row: &SpecRow) {
-        let spec: SpecData = serde_json::from_value(spec_row.data.clone()).unwrap();
-
+    fn render_history(&self, f: &mut Frame, area: Rect, spec: &SpecData) {
         let mut text = String::new();
         if spec.history.patches.is_empty() {
             text.push_str("No history recorded.\n");
@@ -598,13 +805,18 @@ impl TuiApp {
             } else {
                 "  ↑/↓: Navigate  c: Load Conflicts  o: Resolve  b: Bulk  a: Auto-merge  r: Refresh  q/Esc: Quit".to_string()
             }
+        } else if self.editing_filter {
+            "  Type to filter  Enter: Apply  Esc: Clear".to_string()
+        } else if self.filters_detail_tab() {
+            "  ↑/↓: Navigate  Tab: Switch Tab  f: Filter  Esc: Clear Filter  1-4: Filter Boundary  s: Sort  q: Quit"
+                .to_string()
         } else {
-            "  ↑/↓: Navigate  Tab: Switch Tab  1-4: Filter Boundary  r: Refresh  q/Esc: Quit"
+            "  ↑/↓: Navigate  Tab: Switch Tab  1-4: Filter Boundary  s: Sort  r: Refresh  q/Esc: Quit"
                 .to_string()
         };
 
         let footer = Paragraph::new(footer_text)
-            .style(Style::default().fg(Color::White).bg(Color::DarkGray))
+            .style(self.theme.footer)
             .block(Block::default().borders(Borders::ALL));
 
         f.render_widget(footer, area);
@@ -646,6 +858,33 @@ impl TuiApp {
         self.list_state.select(Some(i));
     }
 
+    /// Select a spec row or tab under a left click
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left)
+            || self.show_resolution_popup
+            || self.show_bulk_popup
+            || self.show_manual_edit_popup
+        {
+            return;
+        }
+
+        let position = Position::new(mouse.column, mouse.row);
+        if self.list_area.contains(position) {
+            let offset = self.list_state.offset();
+            if let Some(index) =
+                mouse::list_row_at(self.list_area, offset, mouse.row, self.specs.len())
+            {
+                self.list_state.select(Some(index));
+            }
+            return;
+        }
+
+        if let Some(tab) = mouse::tab_at(self.tabs_area, &TAB_TITLES, mouse.column, mouse.row) {
+            self.selected_tab = tab;
+            self.status_message = None;
+        }
+    }
+
     /// Navigate to next tab
     fn next_tab(&mut self) {
         self.selected_tab = (self.selected_tab + 1) % 6;
@@ -664,11 +903,7 @@ impl TuiApp {
 
     /// Refresh spec list from database
     fn refresh_specs(&mut self) -> Result<()> {
-        // Invalidate cache to see changes from other processes (e.g., MCP server)
-        self.db.invalidate_cache()?;
-
-        let boundary = self.filter_boundary.as_ref().and_then(|b| b.parse().ok());
-        self.specs = self.db.list_specs(boundary.as_ref(), None)?;
+        self.reload_specs()?;
 
         if !self.specs.is_empty() {
             self.list_state.select(Some(0));
@@ -679,13 +914,56 @@ impl TuiApp {
         Ok(())
     }
 
+    /// Reload the spec list if specs changed since it was last loaded
+    ///
+    /// The selected spec stays selected wherever it moved to; if it is gone,
+    /// the selection stays at the same row, clamped to the new list.
+    /// Called on the `[tui] refresh_secs` interval.
+    /// Returns whether the list was reloaded.
+    pub fn auto_refresh(&mut self) -> Result<bool> {
+        // Invalidate cache to see changes from other processes (e.g., MCP server)
+        self.db.invalidate_cache()?;
+        if self.db.specs_stamp()? == self.specs_stamp {
+            return Ok(false);
+        }
+
+        let previous = self.list_state.selected();
+        let selected_id = self.selected_spec_id().map(String::from);
+        self.reload_specs()?;
+
+        let index = selected_id
+            .and_then(|id| self.specs.iter().position(|row| row.id == id))
+            .or_else(|| {
+                let last = self.specs.len().checked_sub(1)?;
+                Some(previous.unwrap_or(0).min(last))
+            });
+        self.list_state.select(index);
+
+        Ok(true)
+    }
+
+    /// Re-query the spec list with the current filter and sort order
+    fn reload_specs(&mut self) -> Result<()> {
+        // Invalidate cache to see changes from other processes (e.g., MCP server)
+        self.db.invalidate_cache()?;
+
+        let boundary = self.filter_boundary.as_ref().and_then(|b| b.parse().ok());
+        self.specs_stamp = self.db.specs_stamp()?;
+        self.specs = self
+            .db
+            .list_specs(boundary.as_ref(), None, self.sort_order)?;
+        self.unparseable = unparseable_ids(&self.specs);
+
+        Ok(())
+    }
+
     /// Render conflicts tab
     fn render_conflicts(&mut self, f: &mut Frame, area: Rect) {
         if self.conflicts.is_empty() {
             let text = "No conflicts for this spec.\n\nPress 'c' to load conflicts from database.";
             let paragraph = Paragraph::new(text)
                 .block(Block::default().borders(Borders::ALL).title("Conflicts"))
-                .style(Style::default().fg(Color::Green))
+                .style(self.theme.success)
                 .wrap(Wrap { trim: true });
             f.render_widget(paragraph, area);
             return;
@@ -714,8 +992,8 @@ impl TuiApp {
                 };
                 let content = format!("{} {} - {}", status_icon, i + 1, conflict.field_path);
                 let style = match conflict.status {
-                    ConflictStatus::Unresolved => Style::default().fg(Color::Red),
-                    _ => Style::default().fg(Color::Green),
+                    ConflictStatus::Unresolved => self.theme.error,
+                    _ => self.theme.success,
                 };
                 ListItem::new(content).style(style)
             })
@@ -727,11 +1005,7 @@ impl TuiApp {
                     .borders(Borders::ALL)
                     .title(format!("Conflicts ({})", self.conflicts.len())),
             )
-            .highlight_style(
-                Style::default()
-                    .bg(Color::Blue)
-                    .add_modifier(Modifier::BOLD),
-            )
+            .highlight_style(self.theme.highlight)
             .highlight_symbol(">> ");
 
         f.render_stateful_widget(list, chunks[0], &mut self.conflict_list_state);
@@ -745,7 +1019,7 @@ impl TuiApp {
                 let base_val = conflict
                     .base_value
                     .as_ref()
-                    .map(|v| format_conflict_value(v))
+                    .map(format_conflict_value)
                     .unwrap_or_else(|| "(no base)".to_string());
 
                 // Create diff markers
@@ -793,7 +1067,7 @@ impl TuiApp {
                             .title("Conflict Details"),
                     )
                     .wrap(Wrap { trim: true })
-                    .style(Style::default().fg(Color::Yellow));
+                    .style(self.theme.warning);
 
                 f.render_widget(paragraph, chunks[1]);
             }
@@ -805,7 +1079,7 @@ impl TuiApp {
         let area = centered_rect(60, 40, f.area());
 
         // Clear background
-        let clear = Block::default().style(Style::default().bg(Color::Black));
+        let clear = Block::default().style(self.theme.background);
         f.render_widget(clear, area);
 
         // Split into title and content
@@ -821,15 +1095,11 @@ impl TuiApp {
         // Title
         let title = Paragraph::new("Select Resolution Strategy")
             .block(Block::default().borders(Borders::ALL))
-            .style(
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            );
+            .style(self.theme.title);
         f.render_widget(title, chunks[0]);
 
         // Strategies
-        let strategies = vec![
+        let strategies = [
             "Ours (Keep Local)",
             "Theirs (Accept Remote)",
             "Merge (Auto)",
@@ -840,9 +1110,7 @@ impl TuiApp {
             .enumerate()
             .map(|(i, s)| {
                 let style = if i == self.selected_strategy {
-                    Style::default()
-                        .bg(Color::Blue)
-                        .add_modifier(Modifier::BOLD)
+                    self.theme.highlight
                 } else {
                     Style::default()
                 };
@@ -857,7 +1125,7 @@ impl TuiApp {
         // Instructions
         let instructions = Paragraph::new("←/→: Select  Enter: Apply  Esc: Cancel")
             .block(Block::default().borders(Borders::ALL))
-            .style(Style::default().fg(Color::White));
+            .style(self.theme.text);
         f.render_widget(instructions, chunks[2]);
     }
 
@@ -867,7 +1135,7 @@ impl TuiApp {
 
         let paragraph = Paragraph::new(message)
             .block(Block::default().borders(Borders::ALL).title("Status"))
-            .style(Style::default().fg(Color::Green))
+            .style(self.theme.success)
             .wrap(Wrap { trim: true });
 
         f.render_widget(paragraph, area);
@@ -904,6 +1172,22 @@ impl TuiApp {
         Ok(())
     }
 
+    /// Patch specs with resolved values and record the resolutions for audits
+    ///
+    /// Goes through the same transaction as `conflicts resolve`, so a failed
+    /// patch leaves every conflict in the batch unresolved.
+    fn save_resolutions(
+        &self,
+        resolutions: &[(
+            Conflict,
+            ResolutionStrategy,
+            serde_json::Value,
+            ConflictStatus,
+        )],
+    ) -> Result<()> {
+        crate::commands::apply_conflict_resolutions(&self.db, &self.actor, resolutions)
+    }
+
     /// Apply selected resolution strategy
     fn apply_resolution(&mut self) -> Result<()> {
         if let Some(conflict_idx) = self.conflict_list_state.selected() {
@@ -916,24 +1200,18 @@ impl TuiApp {
                     _ => ResolutionStrategy::Ours,
                 };
 
-                match ConflictResolver::resolve_conflict(conflict, strategy, None) {
-                    Ok((resolved_value, status)) => {
-                        // Update conflict status in database
-                        self.db.update_conflict_status(&conflict.id, &status)?;
-
-                        // Apply resolution to spec
-                        if let Some(selected) = self.list_state.selected() {
-                            if let Some(spec_row) = self.specs.get(selected) {
-                                let mut spec: SpecData =
-                                    serde_json::from_value(spec_row.data.clone())?;
-                                ConflictResolver::apply_resolutions(
-                                    &mut spec,
-                                    &[(conflict.field_path.clone(), resolved_value)],
-                                )?;
-                                self.db.update_spec(&spec)?;
-                            }
-                        }
-
+                let saved = ConflictResolver::resolve_conflict(conflict, strategy, None).and_then(
+                    |(resolved_value, status)| {
+                        self.save_resolutions(&[(
+                            conflict.clone(),
+                            strategy,
+                            resolved_value,
+                            status,
+                        )])
+                    },
+                );
+                match saved {
+                    Ok(()) => {
                         self.show_resolution_popup = false;
                         self.status_message = Some(format!(
                             "✓ Conflict resolved with strategy: {}",
@@ -942,6 +1220,7 @@ impl TuiApp {
                                 ResolutionStrategy::Theirs => "theirs",
                                 ResolutionStrategy::Merge => "merge",
                                 ResolutionStrategy::Manual => "manual",
+                                ResolutionStrategy::Ai => "ai",
                             }
                         ));
 
@@ -963,7 +1242,7 @@ impl TuiApp {
         let area = centered_rect(60, 40, f.area());
 
         // Clear background
-        let clear = Block::default().style(Style::default().bg(Color::Black));
+        let clear = Block::default().style(self.theme.background);
         f.render_widget(clear, area);
 
         // Split into title and content
@@ -979,11 +1258,11 @@ impl TuiApp {
         // Title
         let title = Paragraph::new("Bulk Resolution - Resolve All Unresolved Conflicts")
             .block(Block::default().borders(Borders::ALL))
-            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+            .style(self.theme.error.add_modifier(Modifier::BOLD));
         f.render_widget(title, chunks[0]);
 
         // Strategies
-        let strategies = vec![
+        let strategies = [
             "Ours (Keep Local)",
             "Theirs (Accept Remote)",
             "Merge (Auto)",
@@ -994,9 +1273,7 @@ impl TuiApp {
             .enumerate()
             .map(|(i, s)| {
                 let style = if i == self.selected_strategy {
-                    Style::default()
-                        .bg(Color::Blue)
-                        .add_modifier(Modifier::BOLD)
+                    self.theme.highlight
                 } else {
                     Style::default()
                 };
@@ -1019,7 +1296,7 @@ impl TuiApp {
         // Instructions
         let instructions = Paragraph::new("←/→: Select  Enter: Apply to All  Esc: Cancel")
             .block(Block::default().borders(Borders::ALL))
-            .style(Style::default().fg(Color::White));
+            .style(self.theme.text);
         f.render_widget(instructions, chunks[2]);
     }
 
@@ -1028,7 +1305,7 @@ impl TuiApp {
         let area = centered_rect(70, 50, f.area());
 
         // Clear background
-        let clear = Block::default().style(Style::default().bg(Color::Black));
+        let clear = Block::default().style(self.theme.background);
         f.render_widget(clear, area);
 
         // Split into sections
@@ -1045,11 +1322,7 @@ impl TuiApp {
         // Title
         let title = Paragraph::new("Manual Value Entry")
             .block(Block::default().borders(Borders::ALL))
-            .style(
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            );
+            .style(self.theme.title);
         f.render_widget(title, chunks[0]);
 
         // Show current values for context
@@ -1075,16 +1348,49 @@ impl TuiApp {
         // Input field
         let input = Paragraph::new(self.manual_edit_input.as_str())
             .block(Block::default().borders(Borders::ALL).title("Custom Value"))
-            .style(Style::default().fg(Color::Yellow));
+            .style(self.theme.warning);
         f.render_widget(input, chunks[2]);
 
         // Instructions
-        let instructions = Paragraph::new("Type: Enter value  Enter: Apply  Esc: Cancel")
+        let instructions = if self.llm.is_some() {
+            "Type: Enter value  Tab: AI suggestion  Enter: Apply  Esc: Cancel"
+        } else {
+            "Type: Enter value  Enter: Apply  Esc: Cancel"
+        };
+        let instructions = Paragraph::new(instructions)
             .block(Block::default().borders(Borders::ALL))
-            .style(Style::default().fg(Color::White));
+            .style(self.theme.text);
         f.render_widget(instructions, chunks[3]);
     }
 
+    /// Replace the manual input with the LLM's suggested merge
+    fn prefill_ai_suggestion(&mut self) {
+        let Some(llm) = &self.llm else {
+            self.status_message = Some("✗ AI suggestions need OPENAI_API_KEY".to_string());
+            return;
+        };
+        let Some(conflict) = self
+            .conflict_list_state
+            .selected()
+            .and_then(|i| self.conflicts.get(i))
+        else {
+            return;
+        };
+
+        match crate::llm::suggest_resolution_blocking(llm, conflict) {
+            Ok(value) => {
+                let text = value.to_string();
+                self.manual_edit_input = text.clone();
+                self.ai_prefill = Some(text);
+                self.status_message =
+                    Some("AI suggestion loaded; edit it or press Enter to apply".to_string());
+            }
+            Err(e) => {
+                self.status_message = Some(format!("✗ AI suggestion failed: {:#}", e));
+            }
+        }
+    }
+
     /// Apply bulk resolution to all unresolved conflicts
     fn apply_bulk_resolution(&mut self) -> Result<()> {
         let strategy = match self.selected_strategy {
@@ -1102,7 +1408,6 @@ impl TuiApp {
             return Ok(());
         }
 
-        let mut resolved_count = 0;
         let mut failed_count = 0;
         let mut resolutions = Vec::new();
 
@@ -1117,14 +1422,7 @@ impl TuiApp {
         for conflict in &unresolved_conflicts {
             match ConflictResolver::resolve_conflict(conflict, strategy, None) {
                 Ok((resolved_value, status)) => {
-                    // Update conflict status in database
-                    if let Err(e) = self.db.update_conflict_status(&conflict.id, &status) {
-                        failed_count += 1;
-                        eprintln!("Failed to update conflict {}: {}", conflict.id, e);
-                        continue;
-                    }
-                    resolutions.push((conflict.field_path.clone(), resolved_value));
-                    resolved_count += 1;
+                    resolutions.push((conflict.clone(), strategy, resolved_value, status));
                 }
                 Err(_) => {
                     failed_count += 1;
@@ -1132,25 +1430,15 @@ impl TuiApp {
             }
         }
 
-        // Apply all resolutions to spec
-        if !resolutions.is_empty() {
-            if let Some(selected) = self.list_state.selected() {
-                if let Some(spec_row) = self.specs.get(selected) {
-                    let mut spec: SpecData = serde_json::from_value(spec_row.data.clone())?;
-                    if let Err(e) = ConflictResolver::apply_resolutions(&mut spec, &resolutions) {
-                        self.show_bulk_popup = false;
-                        self.status_message = Some(format!("✗ Failed to apply resolutions: {}", e));
-                        return Ok(());
-                    }
-                    self.db.update_spec(&spec)?;
-                }
-            }
-        }
-
         self.show_bulk_popup = false;
+        if let Err(e) = self.save_resolutions(&resolutions) {
+            self.status_message = Some(format!("✗ Failed to apply resolutions: {}", e));
+            return Ok(());
+        }
         self.status_message = Some(format!(
             "✓ Bulk resolution complete: {} resolved, {} failed",
-            resolved_count, failed_count
+            resolutions.len(),
+            failed_count
         ));
 
         // Reload conflicts
@@ -1163,38 +1451,27 @@ impl TuiApp {
     fn apply_manual_resolution(&mut self) -> Result<()> {
         if let Some(conflict_idx) = self.conflict_list_state.selected() {
             if let Some(conflict) = self.conflicts.get(conflict_idx) {
-                // Parse input as JSON value
-                let manual_value: serde_json::Value = if self.manual_edit_input.trim().is_empty() {
-                    serde_json::Value::Null
+                let manual_value = ConflictResolver::parse_manual_value(&self.manual_edit_input);
+
+                // An unedited suggestion is attributed to the LLM
+                let strategy = if self.ai_prefill.as_ref() == Some(&self.manual_edit_input) {
+                    ResolutionStrategy::Ai
                 } else {
-                    // Try parsing as JSON, otherwise treat as string
-                    serde_json::from_str(&self.manual_edit_input).unwrap_or_else(|_| {
-                        serde_json::Value::String(self.manual_edit_input.clone())
-                    })
+                    ResolutionStrategy::Manual
                 };
 
-                match ConflictResolver::resolve_conflict(
-                    conflict,
-                    ResolutionStrategy::Manual,
-                    Some(manual_value.clone()),
-                ) {
-                    Ok((resolved_value, status)) => {
-                        // Update conflict status in database
-                        self.db.update_conflict_status(&conflict.id, &status)?;
-
-                        // Apply resolution to spec
-                        if let Some(selected) = self.list_state.selected() {
-                            if let Some(spec_row) = self.specs.get(selected) {
-                                let mut spec: SpecData =
-                                    serde_json::from_value(spec_row.data.clone())?;
-                                ConflictResolver::apply_resolutions(
-                                    &mut spec,
-                                    &[(conflict.field_path.clone(), resolved_value)],
-                                )?;
-                                self.db.update_spec(&spec)?;
-                            }
-                        }
-
+                let saved =
+                    ConflictResolver::resolve_conflict(conflict, strategy, Some(manual_value))
+                        .and_then(|(resolved_value, status)| {
+                            self.save_resolutions(&[(
+                                conflict.clone(),
+                                strategy,
+                                resolved_value,
+                                status,
+                            )])
+                        });
+                match saved {
+                    Ok(()) => {
                         self.show_manual_edit_popup = false;
                         self.status_message =
                             Some("✓ Manual value applied successfully".to_string());
@@ -1215,8 +1492,6 @@ impl TuiApp {
 
     /// Auto-merge all compatible conflicts
     fn auto_merge_conflicts(&mut self) -> Result<()> {
-        let mut merged_count = 0;
-        let mut failed_count = 0;
         let mut skipped_count = 0;
         let mut resolutions = Vec::new();
 
@@ -1231,14 +1506,12 @@ impl TuiApp {
         for conflict in &unresolved_conflicts {
             match ConflictResolver::resolve_conflict(conflict, ResolutionStrategy::Merge, None) {
                 Ok((resolved_value, status)) => {
-                    // Update conflict status in database
-                    if let Err(e) = self.db.update_conflict_status(&conflict.id, &status) {
-                        failed_count += 1;
-                        eprintln!("Failed to update conflict {}: {}", conflict.id, e);
-                        continue;
-                    }
-                    resolutions.push((conflict.field_path.clone(), resolved_value));
-                    merged_count += 1;
+                    resolutions.push((
+                        conflict.clone(),
+                        ResolutionStrategy::Merge,
+                        resolved_value,
+                        status,
+                    ));
                 }
                 Err(_) => {
                     // Cannot auto-merge, requires manual resolution
@@ -1247,23 +1520,14 @@ impl TuiApp {
             }
         }
 
-        // Apply all resolutions to spec
-        if !resolutions.is_empty() {
-            if let Some(selected) = self.list_state.selected() {
-                if let Some(spec_row) = self.specs.get(selected) {
-                    let mut spec: SpecData = serde_json::from_value(spec_row.data.clone())?;
-                    if let Err(e) = ConflictResolver::apply_resolutions(&mut spec, &resolutions) {
-                        self.status_message = Some(format!("✗ Failed to apply auto-merge: {}", e));
-                        return Ok(());
-                    }
-                    self.db.update_spec(&spec)?;
-                }
-            }
+        if let Err(e) = self.save_resolutions(&resolutions) {
+            self.status_message = Some(format!("✗ Failed to apply auto-merge: {}", e));
+            return Ok(());
         }
-
         self.status_message = Some(format!(
-            "✓ Auto-merge: {} merged, {} skipped (need manual), {} failed",
-            merged_count, skipped_count, failed_count
+            "✓ Auto-merge: {} merged, {} skipped (need manual)",
+            resolutions.len(),
+            skipped_count
         ));
 
         // Reload conflicts
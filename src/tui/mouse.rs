@@ -0,0 +1,48 @@
+//! Mouse hit-testing for the TUI
+//!
+//! Maps click positions to list rows and tabs using the rects recorded
+//! during the last draw. Both assume the widget has a full border.
+
+use ratatui::layout::{Position, Rect};
+
+/// Index of the list item under row `y`, given the bordered list area,
+/// the list's scroll offset, and its length
+pub fn list_row_at(area: Rect, offset: usize, y: u16, len: usize) -> Option<usize> {
+    let inner = inner(area);
+    if y < inner.top() || y >= inner.bottom() {
+        return None;
+    }
+
+    let index = offset + usize::from(y - inner.top());
+    (index < len).then_some(index)
+}
+
+/// Index of the tab under `(x, y)`, laid out the way `Tabs` draws them:
+/// one space of padding on each side of a title, then a one-column divider
+pub fn tab_at(area: Rect, titles: &[&str], x: u16, y: u16) -> Option<usize> {
+    let inner = inner(area);
+    if !inner.contains(Position { x, y }) || y != inner.top() {
+        return None;
+    }
+
+    let mut left = inner.left();
+    for (i, title) in titles.iter().enumerate() {
+        let width = title.chars().count() as u16 + 2;
+        let right = left.saturating_add(width);
+        if x >= left && x < right {
+            return Some(i);
+        }
+        // Skip the divider
+        left = right.saturating_add(1);
+    }
+    None
+}
+
+fn inner(area: Rect) -> Rect {
+    Rect {
+        x: area.x.saturating_add(1),
+        y: area.y.saturating_add(1),
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(2),
+    }
+}
@@ -0,0 +1,191 @@
+//! Color themes for the TUI
+//!
+//! Colors are configured per semantic role under `[tui.theme]` in
+//! config.toml, on top of a preset, and resolved into `Style`s once at
+//! startup. Setting `NO_COLOR` drops all colors but keeps emphasis.
+
+use anyhow::{anyhow, Result};
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Built-in palettes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemePreset {
+    #[default]
+    Default,
+    /// Bright colors on black, for low-contrast terminals
+    HighContrast,
+    /// Okabe-Ito palette, which avoids red/green pairs
+    Colorblind,
+}
+
+/// What a color is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Header and popup titles
+    Title,
+    /// Background of the selected list item
+    Highlight,
+    /// Text of the selected list item
+    HighlightText,
+    /// Body text, tab labels, and instructions
+    Text,
+    /// The active tab
+    TabActive,
+    /// Errors, unparseable specs, and unresolved conflicts
+    Error,
+    /// Resolved conflicts and status messages
+    Success,
+    /// Conflict details and text input
+    Warning,
+    /// Placeholder text
+    Muted,
+    /// Footer background
+    Footer,
+    /// Popup background
+    Background,
+}
+
+/// The `[tui.theme]` config table
+///
+/// ```toml
+/// [tui.theme]
+/// preset = "colorblind"
+/// highlight = "#005f87"
+/// error = "lightred"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ThemeConfig {
+    #[serde(default)]
+    pub preset: ThemePreset,
+    /// Per-role overrides; values are color names, `#rrggbb`, or 0-255
+    #[serde(flatten)]
+    pub colors: BTreeMap<Role, String>,
+}
+
+/// Styles for each role, ready to render
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub title: Style,
+    pub highlight: Style,
+    pub text: Style,
+    pub tab_active: Style,
+    pub error: Style,
+    pub success: Style,
+    pub warning: Style,
+    pub muted: Style,
+    pub footer: Style,
+    pub background: Style,
+}
+
+impl ThemePreset {
+    fn palette(self) -> BTreeMap<Role, Color> {
+        use Role::*;
+        let colors = match self {
+            ThemePreset::Default => [
+                (Title, Color::Cyan),
+                (Highlight, Color::Blue),
+                (HighlightText, Color::Reset),
+                (Text, Color::White),
+                (TabActive, Color::Yellow),
+                (Error, Color::Red),
+                (Success, Color::Green),
+                (Warning, Color::Yellow),
+                (Muted, Color::DarkGray),
+                (Footer, Color::DarkGray),
+                (Background, Color::Black),
+            ],
+            ThemePreset::HighContrast => [
+                (Title, Color::White),
+                (Highlight, Color::Yellow),
+                (HighlightText, Color::Black),
+                (Text, Color::White),
+                (TabActive, Color::LightYellow),
+                (Error, Color::LightRed),
+                (Success, Color::LightGreen),
+                (Warning, Color::LightYellow),
+                (Muted, Color::Gray),
+                (Footer, Color::Black),
+                (Background, Color::Black),
+            ],
+            ThemePreset::Colorblind => [
+                (Title, Color::Rgb(86, 180, 233)),
+                (Highlight, Color::Rgb(0, 114, 178)),
+                (HighlightText, Color::White),
+                (Text, Color::White),
+                (TabActive, Color::Rgb(240, 228, 66)),
+                (Error, Color::Rgb(213, 94, 0)),
+                (Success, Color::Rgb(86, 180, 233)),
+                (Warning, Color::Rgb(230, 159, 0)),
+                (Muted, Color::Gray),
+                (Footer, Color::DarkGray),
+                (Background, Color::Black),
+            ],
+        };
+        colors.into_iter().collect()
+    }
+}
+
+impl Theme {
+    /// Resolve a theme config, or plain styles when `no_color` is set
+    pub fn resolve(config: &ThemeConfig, no_color: bool) -> Result<Self> {
+        if no_color {
+            return Ok(Self::plain());
+        }
+
+        let mut palette = config.preset.palette();
+        for (role, value) in &config.colors {
+            let color = value
+                .parse::<Color>()
+                .map_err(|_| anyhow!("Invalid color '{}' for tui.theme role {:?}", value, role))?;
+            palette.insert(*role, color);
+        }
+        Ok(Self::from_palette(&palette))
+    }
+
+    /// Whether the `NO_COLOR` convention asks for no colors
+    pub fn no_color_requested() -> bool {
+        std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+    }
+
+    fn from_palette(palette: &BTreeMap<Role, Color>) -> Self {
+        let fg = |role| Style::default().fg(palette[&role]);
+        let bold = Modifier::BOLD;
+
+        Self {
+            title: fg(Role::Title).add_modifier(bold),
+            highlight: Style::default()
+                .bg(palette[&Role::Highlight])
+                .fg(palette[&Role::HighlightText])
+                .add_modifier(bold),
+            text: fg(Role::Text),
+            tab_active: fg(Role::TabActive).add_modifier(bold),
+            error: fg(Role::Error),
+            success: fg(Role::Success),
+            warning: fg(Role::Warning),
+            muted: fg(Role::Muted),
+            footer: fg(Role::Text).bg(palette[&Role::Footer]),
+            background: Style::default().bg(palette[&Role::Background]),
+        }
+    }
+
+    /// No colors; emphasis carries the meaning instead
+    fn plain() -> Self {
+        let plain = Style::default();
+        Self {
+            title: plain.add_modifier(Modifier::BOLD),
+            highlight: plain.add_modifier(Modifier::REVERSED | Modifier::BOLD),
+            text: plain,
+            tab_active: plain.add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            error: plain.add_modifier(Modifier::BOLD),
+            success: plain,
+            warning: plain,
+            muted: plain.add_modifier(Modifier::DIM),
+            footer: plain.add_modifier(Modifier::REVERSED),
+            background: plain,
+        }
+    }
+}
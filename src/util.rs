@@ -0,0 +1,25 @@
+//! Small helpers shared across modules
+
+use anyhow::Result;
+use std::future::Future;
+
+/// Run an async operation to completion from synchronous code
+///
+/// Callers may already be inside a tokio runtime, where blocking on another
+/// would panic, so the future is built and driven on a separate thread with
+/// its own current-thread runtime.
+pub fn block_on_fresh_runtime<F, Fut, T>(make_future: F) -> Result<T>
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T>>,
+    T: Send + 'static,
+{
+    std::thread::spawn(move || -> Result<T> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        runtime.block_on(make_future())
+    })
+    .join()
+    .map_err(|_| anyhow::anyhow!("background runtime thread panicked"))?
+}
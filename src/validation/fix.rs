@@ -0,0 +1,199 @@
+//! Mechanical fixes for `validate --fix`
+//!
+//! Only corrections with one obvious answer are applied: ids that don't
+//! match their configured pattern are re-cased or renumbered (and task
+//! references to them rewritten), and tasks without acceptance criteria get
+//! a TODO placeholder. Everything else is left for lint to report.
+
+use super::{IdPatterns, IdScheme};
+use crate::models::SpecData;
+use std::collections::{HashMap, HashSet};
+
+/// Acceptance criterion added to tasks that have none
+pub const ACCEPTANCE_PLACEHOLDER: &str = "TODO: define acceptance criteria";
+
+/// One correction applied to a spec
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// Patch operation, as recorded in the spec history
+    pub op: String,
+    /// JSON pointer to the changed field
+    pub path: String,
+    pub summary: String,
+}
+
+impl Fix {
+    fn new(op: &str, path: String, summary: String) -> Self {
+        Self {
+            op: op.to_string(),
+            path,
+            summary,
+        }
+    }
+}
+
+/// Apply every safe fix to `spec`, returning what changed
+pub fn fix_spec(spec: &mut SpecData, ids: &IdPatterns) -> Vec<Fix> {
+    let mut fixes = Vec::new();
+
+    // Requirements, remembering renames so task references can follow
+    let req_ids: Vec<String> = spec.requirements.iter().map(|r| r.id.clone()).collect();
+    let mut req_renames = HashMap::new();
+    for (i, new_id) in renumber(&req_ids, &ids.requirement).into_iter().enumerate() {
+        if let Some(new_id) = new_id {
+            let req = &mut spec.requirements[i];
+            fixes.push(Fix::new(
+                "replace",
+                format!("/requirements/{}/id", i),
+                format!("Renamed requirement '{}' to {}", req.id, new_id),
+            ));
+            req_renames.insert(req.id.clone(), new_id.clone());
+            req.id = new_id;
+        }
+    }
+
+    // Scenario ids are unique across the whole spec, so number them globally
+    let scenario_ids: Vec<String> = spec
+        .requirements
+        .iter()
+        .flat_map(|r| r.scenarios.iter().map(|s| s.id.clone()))
+        .collect();
+    let mut scenario_renames = renumber(&scenario_ids, &ids.scenario).into_iter();
+    let mut scenario_ref_renames = HashMap::new();
+    for (i, req) in spec.requirements.iter_mut().enumerate() {
+        let old_req_id = req_ids[i].clone();
+        for (j, scenario) in req.scenarios.iter_mut().enumerate() {
+            let new_id = scenario_renames.next().flatten();
+            if let Some(new_id) = &new_id {
+                fixes.push(Fix::new(
+                    "replace",
+                    format!("/requirements/{}/scenarios/{}/id", i, j),
+                    format!("Renamed scenario '{}' to {}", scenario.id, new_id),
+                ));
+            }
+            let new_sc_id = new_id.unwrap_or_else(|| scenario.id.clone());
+            let old_ref = format!("{}/{}", old_req_id, scenario.id);
+            let new_ref = format!("{}/{}", req.id, new_sc_id);
+            if old_ref != new_ref {
+                scenario_ref_renames.insert(old_ref, new_ref);
+            }
+            scenario.id = new_sc_id;
+        }
+    }
+
+    let task_ids: Vec<String> = spec.tasks.iter().map(|t| t.id.clone()).collect();
+    for (i, new_id) in renumber(&task_ids, &ids.task).into_iter().enumerate() {
+        if let Some(new_id) = new_id {
+            let task = &mut spec.tasks[i];
+            fixes.push(Fix::new(
+                "replace",
+                format!("/tasks/{}/id", i),
+                format!("Renamed task '{}' to {}", task.id, new_id),
+            ));
+            task.id = new_id;
+        }
+    }
+
+    for (i, task) in spec.tasks.iter_mut().enumerate() {
+        let mut renamed = false;
+        for req_id in &mut task.requirement_ids {
+            if let Some(new_id) = req_renames.get(req_id) {
+                *req_id = new_id.clone();
+                renamed = true;
+            }
+        }
+        for scenario_id in &mut task.scenario_ids {
+            if let Some(new_ref) = scenario_ref_renames.get(scenario_id) {
+                *scenario_id = new_ref.clone();
+                renamed = true;
+            }
+        }
+        if renamed {
+            fixes.push(Fix::new(
+                "replace",
+                format!("/tasks/{}", i),
+                format!("Updated {} references to renamed ids", task.id),
+            ));
+        }
+
+        if task.acceptance.is_empty() {
+            task.acceptance.push(ACCEPTANCE_PLACEHOLDER.to_string());
+            fixes.push(Fix::new(
+                "add",
+                format!("/tasks/{}/acceptance/0", i),
+                format!("Added acceptance placeholder to {}", task.id),
+            ));
+        }
+    }
+
+    let decision_ids: Vec<String> = spec.decisions.iter().map(|d| d.id.clone()).collect();
+    for (i, new_id) in renumber(&decision_ids, &ids.decision)
+        .into_iter()
+        .enumerate()
+    {
+        if let Some(new_id) = new_id {
+            let decision = &mut spec.decisions[i];
+            fixes.push(Fix::new(
+                "replace",
+                format!("/decisions/{}/id", i),
+                format!("Renamed decision '{}' to {}", decision.id, new_id),
+            ));
+            decision.id = new_id;
+        }
+    }
+
+    fixes
+}
+
+/// New ids for entries of `ids` that don't match the scheme's pattern
+///
+/// An id that only differs by case or surrounding whitespace keeps its
+/// number when that is still free; anything else gets the next unused
+/// number. Valid ids, including duplicates, are left alone, as are invalid
+/// ones when the scheme's prefix can't produce a matching id.
+fn renumber(ids: &[String], scheme: &IdScheme) -> Vec<Option<String>> {
+    let mut taken: HashSet<String> = ids
+        .iter()
+        .filter(|id| scheme.is_match(id))
+        .cloned()
+        .collect();
+    let mut next = taken
+        .iter()
+        .filter_map(|id| scheme.number(id))
+        .max()
+        .unwrap_or(0)
+        + 1;
+
+    ids.iter()
+        .map(|id| {
+            if scheme.is_match(id) {
+                return None;
+            }
+            let trimmed = id.trim();
+            let recased = [
+                trimmed.to_string(),
+                trimmed.to_lowercase(),
+                trimmed.to_uppercase(),
+            ]
+            .into_iter()
+            .find(|candidate| scheme.is_match(candidate) && !taken.contains(candidate));
+            let new_id = match recased {
+                Some(candidate) => candidate,
+                None => {
+                    let mut id = scheme.format(next);
+                    while taken.contains(&id) {
+                        next += 1;
+                        id = scheme.format(next);
+                    }
+                    if !scheme.is_match(&id) {
+                        return None;
+                    }
+                    next += 1;
+                    id
+                }
+            };
+            taken.insert(new_id.clone());
+            Some(new_id)
+        })
+        .collect()
+}
@@ -1,16 +1,254 @@
 //! Schema validation for manifold specs
 
+pub mod fix;
+pub mod report;
+
+use crate::config::{LintConfig, ValidationConfig};
 use crate::models::SpecData;
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use jsonschema::JSONSchema;
+use regex::Regex;
+use serde::Serialize;
 use serde_json::Value;
+use std::collections::HashSet;
+
+/// One kind of item id: the pattern it must match and how new ones are made
+#[derive(Debug, Clone)]
+pub struct IdScheme {
+    pub pattern: Regex,
+    /// Generated ids are `<prefix>-<n>`
+    pub prefix: String,
+    /// Minimum digits in generated numbers, zero-padded
+    pub digits: usize,
+}
+
+impl IdScheme {
+    pub fn new(pattern: &str, prefix: &str, digits: usize) -> Result<Self> {
+        let pattern = Regex::new(pattern)
+            .with_context(|| format!("Invalid id pattern '{}' in config", pattern))?;
+        Ok(Self {
+            pattern,
+            prefix: prefix.to_string(),
+            digits,
+        })
+    }
+
+    pub fn is_match(&self, id: &str) -> bool {
+        self.pattern.is_match(id)
+    }
+
+    /// Number of an id written `<prefix>-<n>`
+    pub fn number(&self, id: &str) -> Option<u64> {
+        id.strip_prefix(self.prefix.as_str())?
+            .strip_prefix('-')?
+            .parse()
+            .ok()
+    }
+
+    /// Id with number `n`
+    pub fn format(&self, n: u64) -> String {
+        format!("{}-{:0width$}", self.prefix, n, width = self.digits)
+    }
+
+    /// Next free id of this kind in `spec`
+    pub fn next_id(&self, spec: &SpecData) -> String {
+        spec.next_id(&self.prefix, self.digits)
+    }
+}
+
+/// Compiled id schemes for every kind of item, from `[validation]` config
+#[derive(Debug, Clone)]
+pub struct IdPatterns {
+    pub requirement: IdScheme,
+    pub scenario: IdScheme,
+    pub task: IdScheme,
+    pub decision: IdScheme,
+}
+
+impl IdPatterns {
+    pub fn from_config(config: &ValidationConfig) -> Result<Self> {
+        let (patterns, prefixes) = (&config.id_patterns, &config.id_prefixes);
+        let scheme = |pattern: &str, prefix: &str| IdScheme::new(pattern, prefix, config.id_digits);
+        Ok(Self {
+            requirement: scheme(&patterns.requirement, &prefixes.requirement)?,
+            scenario: scheme(&patterns.scenario, &prefixes.scenario)?,
+            task: scheme(&patterns.task, &prefixes.task)?,
+            decision: scheme(&patterns.decision, &prefixes.decision)?,
+        })
+    }
+
+    /// Point the id patterns in a JSON schema at these schemes
+    fn apply_to_schema(&self, schema: &mut Value) {
+        // A task's scenario refs are `<requirement>/<scenario>`
+        let unanchored = |re: &Regex| {
+            re.as_str()
+                .trim_start_matches('^')
+                .trim_end_matches('$')
+                .to_string()
+        };
+        let scenario_ref = format!(
+            "^(?:{})/(?:{})$",
+            unanchored(&self.requirement.pattern),
+            unanchored(&self.scenario.pattern)
+        );
+        let overrides = [
+            (
+                "/$defs/requirement/properties/id",
+                self.requirement.pattern.as_str(),
+            ),
+            (
+                "/$defs/scenario/properties/id",
+                self.scenario.pattern.as_str(),
+            ),
+            ("/$defs/task/properties/id", self.task.pattern.as_str()),
+            (
+                "/$defs/task/properties/requirement_ids/items",
+                self.requirement.pattern.as_str(),
+            ),
+            ("/$defs/task/properties/scenario_ids/items", &scenario_ref),
+            (
+                "/$defs/decision/properties/id",
+                self.decision.pattern.as_str(),
+            ),
+        ];
+        for (pointer, pattern) in overrides {
+            if let Some(Value::Object(field)) = schema.pointer_mut(pointer) {
+                field.insert("pattern".to_string(), Value::String(pattern.to_string()));
+            }
+        }
+    }
+}
+
+impl Default for IdPatterns {
+    fn default() -> Self {
+        Self::from_config(&ValidationConfig::default()).expect("default id patterns compile")
+    }
+}
+
+/// Validate a spec against the JSON schema, with ids checked against `ids`
+pub fn validate_spec(spec: &SpecData, ids: &IdPatterns) -> Result<()> {
+    SpecValidator::load(ids)?.validate(spec)
+}
 
-/// Validate a spec against the JSON schema
-pub fn validate_spec(spec: &SpecData) -> Result<()> {
-    // First validate against JSON schema
-    validate_against_schema(spec)?;
+/// The core schema compiled for one set of id patterns
+///
+/// Compiling dominates the cost of validating a single spec, so anything
+/// checking many specs builds one validator and reuses it. It is `Sync`,
+/// letting `validate --all` share it across threads.
+pub struct SpecValidator {
+    schema: JSONSchema,
+    ids: IdPatterns,
+}
+
+// `validate --all` shares one validator across rayon workers
+const _: () = {
+    const fn assert_sync<T: Sync>() {}
+    assert_sync::<SpecValidator>();
+};
+
+impl SpecValidator {
+    /// Compile `schema` with its id patterns pointed at `ids`
+    pub fn new(schema: &Value, ids: &IdPatterns) -> Result<Self> {
+        let mut schema = schema.clone();
+        ids.apply_to_schema(&mut schema);
+        let compiled = JSONSchema::compile(&schema)
+            .map_err(|e| anyhow::anyhow!("Failed to compile JSON schema: {}", e))?;
+        Ok(Self {
+            schema: compiled,
+            ids: ids.clone(),
+        })
+    }
+
+    /// Compile the installed core schema (~/.manifold/schemas/core.json)
+    pub fn load(ids: &IdPatterns) -> Result<Self> {
+        let schema_path = crate::config::manifold_home()?.join("schemas/core.json");
+        let schema_content = std::fs::read_to_string(&schema_path)
+            .map_err(|e| anyhow::anyhow!("Failed to read schema from {:?}: {}", schema_path, e))?;
+        Self::new(&serde_json::from_str(&schema_content)?, ids)
+    }
+
+    /// Check a spec against the schema, then the structural rules
+    pub fn validate(&self, spec: &SpecData) -> Result<()> {
+        self.validate_against_schema(spec)?;
+        validate_structure(spec, &self.ids)
+    }
+
+    fn validate_against_schema(&self, spec: &SpecData) -> Result<()> {
+        let spec_json = serde_json::to_value(spec)?;
+        if let Err(errors) = self.schema.validate(&spec_json) {
+            let error_messages: Vec<String> = errors
+                .map(|e| match e.instance_path.to_string() {
+                    path if path.is_empty() => format!("{}", e),
+                    path => format!("{}: {}", path, e),
+                })
+                .collect();
+            bail!("Schema validation failed:\n{}", error_messages.join("\n"));
+        }
+        Ok(())
+    }
+}
+
+/// Individual messages from a validation error
+pub fn error_messages(error: &anyhow::Error) -> Vec<String> {
+    let text = error.to_string();
+    match text.strip_prefix("Schema validation failed:\n") {
+        Some(rest) => rest.lines().map(str::to_string).collect(),
+        None => vec![text],
+    }
+}
 
-    // Then run basic structural checks
+/// One spec's result in `validate --all`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpecValidation {
+    pub spec_id: String,
+    pub schema_valid: bool,
+    pub errors: Vec<String>,
+    /// Lint warnings; only collected when the schema passes
+    pub warnings: Vec<String>,
+}
+
+impl SpecValidation {
+    /// Validate and lint one spec, with the size and similarity limits from `lint`
+    pub fn check(spec: &SpecData, validator: &SpecValidator, lint: &LintConfig) -> Self {
+        let errors = match validator.validate(spec) {
+            Ok(()) => Vec::new(),
+            Err(e) => error_messages(&e),
+        };
+        let warnings = if errors.is_empty() {
+            let mut warnings = lint_spec(spec);
+            warnings.extend(lint_spec_size(spec, lint.max_spec_bytes()));
+            warnings.extend(lint_similar_requirements(spec, lint.similarity_threshold));
+            warnings
+        } else {
+            Vec::new()
+        };
+        Self {
+            spec_id: spec.spec_id.clone(),
+            schema_valid: errors.is_empty(),
+            errors,
+            warnings,
+        }
+    }
+}
+
+/// Validate and lint every spec in parallel, sorted by spec id
+pub fn validate_all(
+    specs: &[SpecData],
+    validator: &SpecValidator,
+    lint: &LintConfig,
+) -> Vec<SpecValidation> {
+    use rayon::prelude::*;
+
+    let mut results: Vec<SpecValidation> = specs
+        .par_iter()
+        .map(|spec| SpecValidation::check(spec, validator, lint))
+        .collect();
+    results.sort_by(|a, b| a.spec_id.cmp(&b.spec_id));
+    results
+}
+
+/// Structural checks the schema can't express
+fn validate_structure(spec: &SpecData, ids: &IdPatterns) -> Result<()> {
     // Check required fields
     if spec.spec_id.is_empty() {
         bail!("spec_id is required");
@@ -28,7 +266,7 @@ pub fn validate_spec(spec: &SpecData) -> Result<()> {
 
     // Validate requirements
     for req in &spec.requirements {
-        validate_id_pattern(&req.id, "requirement id", r"^req-[0-9]+$")?;
+        validate_id(&req.id, "requirement id", &ids.requirement)?;
         if req.title.is_empty() {
             bail!("Requirement {} has empty title", req.id);
         }
@@ -38,7 +276,7 @@ pub fn validate_spec(spec: &SpecData) -> Result<()> {
 
         // Validate scenarios
         for scenario in &req.scenarios {
-            validate_id_pattern(&scenario.id, "scenario id", r"^sc-[0-9]+$")?;
+            validate_id(&scenario.id, "scenario id", &ids.scenario)?;
             if scenario.name.is_empty() {
                 bail!("Scenario {} has empty name", scenario.id);
             }
@@ -47,20 +285,39 @@ pub fn validate_spec(spec: &SpecData) -> Result<()> {
 
     // Validate tasks
     for task in &spec.tasks {
-        validate_id_pattern(&task.id, "task id", r"^task-[0-9]+$")?;
+        validate_id(&task.id, "task id", &ids.task)?;
         if task.title.is_empty() {
             bail!("Task {} has empty title", task.id);
         }
     }
+    validate_scenario_links(spec)?;
 
     // Validate decisions
     for decision in &spec.decisions {
-        validate_id_pattern(&decision.id, "decision id", r"^dec-[0-9]+$")?;
+        validate_id(&decision.id, "decision id", &ids.decision)?;
         if decision.title.is_empty() {
             bail!("Decision {} has empty title", decision.id);
         }
     }
 
+    // Ids must not collide across requirements, tasks, decisions, or scenarios
+    if let Err(duplicates) = spec.validate_ids_unique_across_types() {
+        let messages: Vec<String> = duplicates.iter().map(|d| d.to_string()).collect();
+        bail!("Duplicate ids found:\n{}", messages.join("\n"));
+    }
+
+    Ok(())
+}
+
+fn validate_id(id: &str, name: &str, scheme: &IdScheme) -> Result<()> {
+    if !scheme.is_match(id) {
+        bail!(
+            "{} '{}' doesn't match required pattern {}",
+            name,
+            id,
+            scheme.pattern.as_str()
+        );
+    }
     Ok(())
 }
 
@@ -77,30 +334,237 @@ fn validate_id_pattern(id: &str, name: &str, pattern: &str) -> Result<()> {
     Ok(())
 }
 
-/// Validate spec against JSON schema
-fn validate_against_schema(spec: &SpecData) -> Result<()> {
-    // Load the schema
-    let schema_path = crate::config::manifold_home()?.join("schemas/core.json");
-    let schema_content = std::fs::read_to_string(&schema_path)
-        .map_err(|e| anyhow::anyhow!("Failed to read schema from {:?}: {}", schema_path, e))?;
-    let schema_json: Value = serde_json::from_str(&schema_content)?;
+/// How completely a spec's requirements and tasks are specified
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CoverageReport {
+    pub requirements: usize,
+    /// Requirements with at least one scenario
+    pub requirements_with_scenarios: usize,
+    /// Requirements referenced by at least one task
+    pub requirements_traced: usize,
+    pub tasks: usize,
+    /// Tasks with at least one acceptance criterion
+    pub tasks_with_acceptance: usize,
+    /// Requirements whose implementation is verified; not part of the score
+    pub requirements_verified: usize,
+    /// Mean of the applicable ratios, as a percentage (0-100)
+    pub score: f64,
+}
 
-    // Compile the schema
-    let compiled = JSONSchema::compile(&schema_json)
-        .map_err(|e| anyhow::anyhow!("Failed to compile JSON schema: {}", e))?;
+/// Compute the coverage score for a spec
+///
+/// Ratios with nothing to measure (no requirements, no tasks) are left out
+/// of the mean; a spec with neither scores 0.
+pub fn coverage(spec: &SpecData) -> CoverageReport {
+    let traced: HashSet<&str> = spec
+        .tasks
+        .iter()
+        .flat_map(|t| t.requirement_ids.iter().map(String::as_str))
+        .collect();
 
-    // Convert spec to JSON
-    let spec_json = serde_json::to_value(spec)?;
+    let requirements = spec.requirements.len();
+    let requirements_with_scenarios = spec
+        .requirements
+        .iter()
+        .filter(|r| !r.scenarios.is_empty())
+        .count();
+    let requirements_traced = spec
+        .requirements
+        .iter()
+        .filter(|r| traced.contains(r.id.as_str()))
+        .count();
+    let requirements_verified = spec
+        .requirements
+        .iter()
+        .filter(|r| r.verification == crate::models::VerificationStatus::Verified)
+        .count();
+    let tasks = spec.tasks.len();
+    let tasks_with_acceptance = spec
+        .tasks
+        .iter()
+        .filter(|t| !t.acceptance.is_empty())
+        .count();
 
-    // Validate
-    if let Err(errors) = compiled.validate(&spec_json) {
-        let error_messages: Vec<String> = errors.map(|e| format!("{}", e)).collect();
-        bail!("Schema validation failed:\n{}", error_messages.join("\n"));
+    let mut ratios = Vec::new();
+    if requirements > 0 {
+        ratios.push(requirements_with_scenarios as f64 / requirements as f64);
+        ratios.push(requirements_traced as f64 / requirements as f64);
+    }
+    if tasks > 0 {
+        ratios.push(tasks_with_acceptance as f64 / tasks as f64);
+    }
+    let score = if ratios.is_empty() {
+        0.0
+    } else {
+        ratios.iter().sum::<f64>() / ratios.len() as f64 * 100.0
+    };
+
+    CoverageReport {
+        requirements,
+        requirements_with_scenarios,
+        requirements_traced,
+        tasks,
+        tasks_with_acceptance,
+        requirements_verified,
+        score,
+    }
+}
+
+/// Default cap on a spec's stored JSON before it's flagged (512 KiB)
+pub const DEFAULT_MAX_SPEC_BYTES: usize = 512 * 1024;
+
+/// Size of a spec's canonical JSON and the section contributing most to it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SizeReport {
+    pub bytes: usize,
+    /// Top-level field with the largest serialized value, e.g. "history"
+    pub largest_section: String,
+    pub largest_section_bytes: usize,
+}
+
+/// Measure a spec as it is stored: compact JSON, one top-level field per section
+pub fn spec_size(spec: &SpecData) -> SizeReport {
+    let value = serde_json::to_value(spec).unwrap_or(Value::Null);
+    let bytes = value.to_string().len();
+
+    let (largest_section, largest_section_bytes) = value
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(key, field)| (key.clone(), field.to_string().len()))
+        .max_by_key(|(_, size)| *size)
+        .unwrap_or_default();
+
+    SizeReport {
+        bytes,
+        largest_section,
+        largest_section_bytes,
+    }
+}
+
+/// Warn when a spec's stored JSON exceeds `max_bytes`
+pub fn lint_spec_size(spec: &SpecData, max_bytes: usize) -> Option<String> {
+    let size = spec_size(spec);
+    if size.bytes <= max_bytes {
+        return None;
+    }
+
+    Some(format!(
+        "Spec JSON is {} KiB, over the {} KiB limit; largest section is '{}' ({} KiB, {:.0}%)",
+        size.bytes / 1024,
+        max_bytes / 1024,
+        size.largest_section,
+        size.largest_section_bytes / 1024,
+        size.largest_section_bytes as f64 * 100.0 / size.bytes as f64
+    ))
+}
+
+/// Two requirements whose SHALL statements are worded nearly the same
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimilarRequirements {
+    pub first: String,
+    pub second: String,
+    /// Token overlap of the two SHALL statements, 0.0 to 1.0
+    pub similarity: f64,
+}
+
+/// Pairs of requirements whose SHALL statements are at least `threshold` similar
+///
+/// Similarity is the Jaccard index of the statements' lowercase word sets,
+/// so reordered or lightly reworded statements score high while ones that
+/// only share boilerplate such as "the system shall" score low. Pairs are
+/// in spec order.
+pub fn find_similar_requirements(spec: &SpecData, threshold: f64) -> Vec<SimilarRequirements> {
+    let words: Vec<HashSet<String>> = spec
+        .requirements
+        .iter()
+        .map(|req| {
+            req.shall
+                .to_lowercase()
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .collect();
+
+    let mut pairs = Vec::new();
+    for (i, first) in spec.requirements.iter().enumerate() {
+        for (j, second) in spec.requirements.iter().enumerate().skip(i + 1) {
+            let union = words[i].union(&words[j]).count();
+            if union == 0 {
+                continue;
+            }
+            let similarity = words[i].intersection(&words[j]).count() as f64 / union as f64;
+            if similarity >= threshold {
+                pairs.push(SimilarRequirements {
+                    first: first.id.clone(),
+                    second: second.id.clone(),
+                    similarity,
+                });
+            }
+        }
     }
+    pairs
+}
+
+/// Warn about likely duplicate requirements, see [`find_similar_requirements`]
+pub fn lint_similar_requirements(spec: &SpecData, threshold: f64) -> Vec<String> {
+    find_similar_requirements(spec, threshold)
+        .into_iter()
+        .map(|pair| {
+            format!(
+                "{}: SHALL statement is {:.0}% similar to {}; possible duplicate",
+                pair.second,
+                pair.similarity * 100.0,
+                pair.first
+            )
+        })
+        .collect()
+}
+
+/// Every scenario in the spec as `req-x/sc-y`, in spec order
+pub fn scenario_refs(spec: &SpecData) -> Vec<String> {
+    spec.requirements
+        .iter()
+        .flat_map(|req| {
+            req.scenarios
+                .iter()
+                .map(move |scenario| format!("{}/{}", req.id, scenario.id))
+        })
+        .collect()
+}
 
+/// Check that every task's `scenario_ids` names a scenario in the spec
+pub fn validate_scenario_links(spec: &SpecData) -> Result<()> {
+    let scenarios = scenario_refs(spec);
+    for task in &spec.tasks {
+        for scenario_id in &task.scenario_ids {
+            if !scenarios.contains(scenario_id) {
+                bail!(
+                    "Task {} references unknown scenario {}",
+                    task.id,
+                    scenario_id
+                );
+            }
+        }
+    }
     Ok(())
 }
 
+/// Scenarios that no task lists in its `scenario_ids`
+pub fn uncovered_scenarios(spec: &SpecData) -> Vec<String> {
+    let covered: HashSet<&String> = spec
+        .tasks
+        .iter()
+        .flat_map(|task| &task.scenario_ids)
+        .collect();
+    scenario_refs(spec)
+        .into_iter()
+        .filter(|scenario| !covered.contains(scenario))
+        .collect()
+}
+
 /// Check for common spec issues (lint-like checks)
 pub fn lint_spec(spec: &SpecData) -> Vec<String> {
     let mut warnings = Vec::new();
@@ -138,6 +602,7 @@ pub fn lint_spec(spec: &SpecData) -> Vec<String> {
     }
 
     // Check tasks
+    let scenarios = scenario_refs(spec);
     for task in &spec.tasks {
         // Tasks should reference at least one requirement
         if task.requirement_ids.is_empty() {
@@ -157,12 +622,38 @@ pub fn lint_spec(spec: &SpecData) -> Vec<String> {
             }
         }
 
+        for scenario_id in &task.scenario_ids {
+            if !scenarios.contains(scenario_id) {
+                warnings.push(format!(
+                    "{}: References non-existent scenario {}",
+                    task.id, scenario_id
+                ));
+            }
+        }
+
         // Tasks should have acceptance criteria
         if task.acceptance.is_empty() {
             warnings.push(format!("{}: No acceptance criteria defined", task.id));
         }
     }
 
+    // Once tasks link scenarios, every scenario should be covered by one
+    if spec.tasks.iter().any(|task| !task.scenario_ids.is_empty()) {
+        for scenario in uncovered_scenarios(spec) {
+            warnings.push(format!("{}: Scenario not covered by any task", scenario));
+        }
+    }
+
+    // Decision dates should be machine-readable
+    for decision in &spec.decisions {
+        if !is_iso8601_date(&decision.date) {
+            warnings.push(format!(
+                "{}: Date '{}' is not ISO-8601 (YYYY-MM-DD)",
+                decision.id, decision.date
+            ));
+        }
+    }
+
     // Check for duplicate IDs
     let mut req_ids = std::collections::HashSet::new();
     for req in &spec.requirements {
@@ -180,3 +671,9 @@ pub fn lint_spec(spec: &SpecData) -> Vec<String> {
 
     warnings
 }
+
+/// Whether a string is an ISO-8601 calendar date or RFC 3339 timestamp
+pub fn is_iso8601_date(date: &str) -> bool {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok()
+        || chrono::DateTime::parse_from_rfc3339(date).is_ok()
+}
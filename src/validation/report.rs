@@ -0,0 +1,213 @@
+//! Quality report for `manifold report`
+//!
+//! Composes the pieces other commands show separately (workflow status,
+//! coverage, task progress, traceability gaps, open conflicts and reviews)
+//! into one structure that renders as text or JSON.
+
+use super::{coverage, scenario_refs, uncovered_scenarios, CoverageReport};
+use crate::collab::reviews::{ReviewManager, ReviewStats};
+use crate::collab::{Conflict, ConflictStatus, Review, ReviewStatus};
+use crate::models::{SpecData, TaskStatus, WorkflowStage};
+use crate::workflow::{WorkflowEngine, WorkflowPolicy};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// Where a spec is in the workflow
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WorkflowProgress {
+    pub stage: WorkflowStage,
+    pub next_stage: Option<WorkflowStage>,
+    /// Why the spec can't advance yet, if it can't
+    pub blocker: Option<String>,
+    /// Position of the stage in the workflow, as a percentage (0-100)
+    pub percent: f64,
+}
+
+/// Task counts by status
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TaskProgress {
+    pub total: usize,
+    pub completed: usize,
+    pub in_progress: usize,
+    pub pending: usize,
+    pub blocked: usize,
+    /// Completed tasks as a percentage (0-100); 0 without tasks
+    pub percent: f64,
+}
+
+/// Scenarios implemented by at least one task
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScenarioCoverage {
+    pub scenarios: usize,
+    pub covered: usize,
+    /// Covered scenarios as a percentage (0-100); 0 without scenarios
+    pub percent: f64,
+}
+
+/// A broken or missing link between requirements, scenarios, and tasks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceabilityGap {
+    /// `untraced_requirement`, `untraced_task`, `unknown_requirement`,
+    /// `uncovered_scenario`, or `unknown_scenario`
+    pub kind: &'static str,
+    /// Requirement, task, or `req-x/sc-y` scenario id the gap is about
+    pub id: String,
+    pub message: String,
+}
+
+/// Everything `manifold report` shows for one spec
+#[derive(Debug, Clone, Serialize)]
+pub struct SpecReport {
+    pub spec_id: String,
+    pub name: String,
+    pub workflow: WorkflowProgress,
+    pub coverage: CoverageReport,
+    pub scenarios: ScenarioCoverage,
+    pub tasks: TaskProgress,
+    pub gaps: Vec<TraceabilityGap>,
+    /// Unresolved conflicts, by field path
+    pub open_conflicts: Vec<String>,
+    pub reviews: ReviewStats,
+    pub pending_reviews: Vec<Review>,
+}
+
+impl SpecReport {
+    /// Build the report from a spec and its stored conflicts and reviews
+    pub fn build(
+        spec: &SpecData,
+        policy: &WorkflowPolicy,
+        conflicts: &[Conflict],
+        reviews: &[Review],
+    ) -> Self {
+        let next_stage = WorkflowEngine::next_stage(&spec.stage);
+        let blocker = match next_stage {
+            Some(_) => WorkflowEngine::can_advance_with(spec, policy)
+                .err()
+                .map(|e| e.to_string()),
+            None => None,
+        };
+        let workflow = WorkflowProgress {
+            stage: spec.stage.clone(),
+            next_stage,
+            blocker,
+            percent: percent(spec.stage.order(), WorkflowStage::all().len() - 1),
+        };
+
+        let scenario_total = scenario_refs(spec).len();
+        let uncovered = uncovered_scenarios(spec);
+        let scenarios = ScenarioCoverage {
+            scenarios: scenario_total,
+            covered: scenario_total - uncovered.len(),
+            percent: percent(scenario_total - uncovered.len(), scenario_total),
+        };
+
+        let mut tasks = TaskProgress {
+            total: spec.tasks.len(),
+            ..Default::default()
+        };
+        for task in &spec.tasks {
+            match task.status {
+                TaskStatus::Completed => tasks.completed += 1,
+                TaskStatus::InProgress => tasks.in_progress += 1,
+                TaskStatus::Pending => tasks.pending += 1,
+                TaskStatus::Blocked => tasks.blocked += 1,
+            }
+        }
+        tasks.percent = percent(tasks.completed, tasks.total);
+
+        Self {
+            spec_id: spec.spec_id.clone(),
+            name: spec.name.clone(),
+            workflow,
+            coverage: coverage(spec),
+            scenarios,
+            tasks,
+            gaps: traceability_gaps(spec, &uncovered),
+            open_conflicts: conflicts
+                .iter()
+                .filter(|c| c.status == ConflictStatus::Unresolved)
+                .map(|c| c.field_path.clone())
+                .collect(),
+            reviews: ReviewManager::get_stats(reviews),
+            pending_reviews: reviews
+                .iter()
+                .filter(|r| r.status == ReviewStatus::Pending)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+fn percent(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / whole as f64
+    }
+}
+
+/// Every traceability gap, in spec order: requirements first, then tasks,
+/// then scenarios
+fn traceability_gaps(spec: &SpecData, uncovered: &[String]) -> Vec<TraceabilityGap> {
+    let traced: HashSet<&str> = spec
+        .tasks
+        .iter()
+        .flat_map(|t| t.requirement_ids.iter().map(String::as_str))
+        .collect();
+    let scenarios = scenario_refs(spec);
+    let mut gaps = Vec::new();
+
+    for req in &spec.requirements {
+        if !traced.contains(req.id.as_str()) {
+            gaps.push(TraceabilityGap {
+                kind: "untraced_requirement",
+                id: req.id.clone(),
+                message: format!("{}: No task implements this requirement", req.id),
+            });
+        }
+    }
+
+    for task in &spec.tasks {
+        if task.requirement_ids.is_empty() {
+            gaps.push(TraceabilityGap {
+                kind: "untraced_task",
+                id: task.id.clone(),
+                message: format!("{}: Task doesn't reference any requirements", task.id),
+            });
+        }
+        for req_id in &task.requirement_ids {
+            if !spec.requirements.iter().any(|r| &r.id == req_id) {
+                gaps.push(TraceabilityGap {
+                    kind: "unknown_requirement",
+                    id: task.id.clone(),
+                    message: format!(
+                        "{}: References non-existent requirement {}",
+                        task.id, req_id
+                    ),
+                });
+            }
+        }
+        for scenario_id in &task.scenario_ids {
+            if !scenarios.contains(scenario_id) {
+                gaps.push(TraceabilityGap {
+                    kind: "unknown_scenario",
+                    id: task.id.clone(),
+                    message: format!(
+                        "{}: References non-existent scenario {}",
+                        task.id, scenario_id
+                    ),
+                });
+            }
+        }
+    }
+
+    for scenario in uncovered {
+        gaps.push(TraceabilityGap {
+            kind: "uncovered_scenario",
+            id: scenario.clone(),
+            message: format!("{}: No task implements this scenario", scenario),
+        });
+    }
+
+    gaps
+}
@@ -0,0 +1,134 @@
+//! Transition hooks
+//!
+//! Shell commands and webhooks run after a spec changes stage, configured
+//! under `[workflow.hooks]`. Keys are `from->to` transitions, where either
+//! side may be `*`:
+//!
+//! ```toml
+//! [workflow.hooks]
+//! "tasks->approval" = { command = "./scripts/request-review.sh" }
+//! "*->implemented" = { webhook = "https://hooks.example.com/manifold" }
+//! ```
+//!
+//! Commands get the spec id, from stage, and to stage as arguments (and as
+//! `MANIFOLD_*` environment variables), with their stdout sent to stderr;
+//! webhooks get a JSON POST. Hooks never
+//! block a transition: failures are reported on stderr and returned.
+
+use super::WorkflowTransition;
+use crate::util::block_on_fresh_runtime;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::io;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// How long a webhook may take before it counts as failed
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What to run for one transition pattern
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TransitionHook {
+    /// Shell command, run with `sh -c`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// URL to POST the transition to as JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+}
+
+/// Whether a `from->to` key matches a transition
+pub fn matches(key: &str, from: &str, to: &str) -> bool {
+    let Some((key_from, key_to)) = key.split_once("->") else {
+        return false;
+    };
+    let side = |pattern: &str, stage: &str| {
+        let pattern = pattern.trim();
+        pattern == "*" || pattern == stage
+    };
+    side(key_from, from) && side(key_to, to)
+}
+
+/// Run every hook matching `transition`, returning one message per failure
+pub fn run_hooks(
+    hooks: &BTreeMap<String, TransitionHook>,
+    spec_id: &str,
+    transition: &WorkflowTransition,
+    actor: &str,
+) -> Vec<String> {
+    let from = transition.from.to_string();
+    let to = transition.to.to_string();
+    let mut failures = Vec::new();
+
+    for (key, hook) in hooks {
+        if !matches(key, &from, &to) {
+            continue;
+        }
+        if let Some(command) = &hook.command {
+            if let Err(e) = run_command(command, spec_id, &from, &to, actor) {
+                failures.push(format!("Hook '{}' command failed: {:#}", key, e));
+            }
+        }
+        if let Some(url) = &hook.webhook {
+            if let Err(e) = post_webhook(url, spec_id, &from, &to, actor) {
+                failures.push(format!("Hook '{}' webhook failed: {:#}", key, e));
+            }
+        }
+    }
+
+    for failure in &failures {
+        eprintln!("⚠ {}", failure);
+    }
+    failures
+}
+
+fn run_command(command: &str, spec_id: &str, from: &str, to: &str, actor: &str) -> Result<()> {
+    // "$@" hands the arguments to the configured command, not to sh. Output
+    // goes to stderr: under `manifold serve`, stdout is the JSON-RPC stream.
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$@\"", command))
+        .arg("manifold-hook")
+        .args([spec_id, from, to])
+        .env("MANIFOLD_SPEC_ID", spec_id)
+        .env("MANIFOLD_FROM_STAGE", from)
+        .env("MANIFOLD_TO_STAGE", to)
+        .env("MANIFOLD_ACTOR", actor)
+        .stdout(Stdio::from(io::stderr()))
+        .status()
+        .with_context(|| format!("Failed to run '{}'", command))?;
+
+    if !status.success() {
+        bail!("'{}' exited with {}", command, status);
+    }
+    Ok(())
+}
+
+fn post_webhook(url: &str, spec_id: &str, from: &str, to: &str, actor: &str) -> Result<()> {
+    let payload = json!({
+        "event": "workflow.transition",
+        "spec_id": spec_id,
+        "from": from,
+        "to": to,
+        "actor": actor,
+        "timestamp": chrono::Utc::now().timestamp(),
+    });
+    let url = url.to_string();
+
+    block_on_fresh_runtime(move || async move {
+        let response = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()?
+            .post(&url)
+            .json(&payload)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach {}", url))?;
+        if !response.status().is_success() {
+            bail!("{} responded with {}", url, response.status());
+        }
+        Ok(())
+    })
+}
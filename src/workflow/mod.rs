@@ -3,14 +3,45 @@
 //! Implements state machine with validation rules:
 //! - requirements -> design: Must have at least one requirement
 //! - design -> tasks: Must have at least one decision
-//! - tasks -> approval: Must have at least one task
-//! - approval -> implemented: Manual approval only
+//! - tasks -> approval: Must have at least one task, and meet the
+//!   configured minimum coverage if one is set
+//! - approval -> implemented: Manual approval, and every `must` requirement
+//!   verified if the policy requires it
 //!
-//! All transitions are logged to workflow_events table
-
-use crate::models::{SpecData, WorkflowStage};
+//! Stages may be skipped with a justification. The current stage's exit
+//! rules still apply; the skipped stages' rules are waived and recorded.
+//! Approval can never be skipped.
+//!
+//! All transitions are logged to workflow_events table, and may trigger
+//! configured [`hooks`]
+
+use crate::db::Database;
+use crate::models::{PatchEntry, Priority, SpecData, VerificationStatus, WorkflowStage};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+pub mod hooks;
+
+use hooks::TransitionHook;
+
+/// Configurable gates on top of the built-in transition rules
+///
+/// Read from the `[workflow]` table in config.toml.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowPolicy {
+    /// Minimum coverage score (0-100) required to enter approval
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_coverage: Option<f64>,
+    /// Require every `must` requirement to be verified before implemented
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub require_verified: bool,
+    /// Commands and webhooks to run after transitions, keyed `from->to`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hooks: BTreeMap<String, TransitionHook>,
+}
+
 /// Workflow event types
 /// Core types for workflow event tracking in database
 #[derive(Debug, Clone)]
@@ -26,6 +57,8 @@ pub enum WorkflowEvent {
     Approved(String),
     /// Manual rejection
     Rejected(String),
+    /// Forward jump past one or more stages (from, to, justification)
+    Skipped(WorkflowStage, WorkflowStage, String),
 }
 
 impl WorkflowEvent {
@@ -46,6 +79,57 @@ impl WorkflowEvent {
             WorkflowEvent::Rejected(reason) => {
                 format!("rejected:{}", reason)
             }
+            WorkflowEvent::Skipped(from, to, _) => {
+                format!("skipped:{}:{}", from, to)
+            }
+        }
+    }
+
+    /// Event type as stored before the first `:`, e.g. `validation_failed`
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            WorkflowEvent::Transition(..) => "transition",
+            WorkflowEvent::StageCompleted(_) => "completed",
+            WorkflowEvent::ValidationFailed(_) => "validation_failed",
+            WorkflowEvent::Approved(_) => "approved",
+            WorkflowEvent::Rejected(_) => "rejected",
+            WorkflowEvent::Skipped(..) => "skipped",
+        }
+    }
+
+    /// Everything after the event type: a message, a name, or `from:to`
+    pub fn payload(&self) -> String {
+        let stored = self.as_string();
+        stored
+            .split_once(':')
+            .map(|(_, rest)| rest.to_string())
+            .unwrap_or_default()
+    }
+}
+
+impl std::str::FromStr for WorkflowEvent {
+    type Err = String;
+
+    /// Parse an event as stored by [`WorkflowEvent::as_string`]
+    ///
+    /// The stored form of a skip has no justification (it lives in the
+    /// event's details), so it comes back empty.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, rest) = s.split_once(':').unwrap_or((s, ""));
+        let stages = || -> Result<(WorkflowStage, WorkflowStage), String> {
+            let (from, to) = rest
+                .split_once(':')
+                .ok_or_else(|| format!("Invalid {} event: {}", kind, s))?;
+            Ok((from.parse()?, to.parse()?))
+        };
+        match kind {
+            "transition" => stages().map(|(from, to)| WorkflowEvent::Transition(from, to)),
+            "completed" => Ok(WorkflowEvent::StageCompleted(rest.parse()?)),
+            "validation_failed" => Ok(WorkflowEvent::ValidationFailed(rest.to_string())),
+            "approved" => Ok(WorkflowEvent::Approved(rest.to_string())),
+            "rejected" => Ok(WorkflowEvent::Rejected(rest.to_string())),
+            "skipped" => stages().map(|(from, to)| WorkflowEvent::Skipped(from, to, String::new())),
+            _ => Err(format!("Unknown workflow event: {}", s)),
         }
     }
 }
@@ -75,9 +159,19 @@ pub struct WorkflowEngine;
 
 impl WorkflowEngine {
     /// Validate and execute a stage transition
+    #[allow(dead_code)]
     pub fn advance_stage(
         spec: &SpecData,
         target_stage: WorkflowStage,
+    ) -> Result<WorkflowTransition, WorkflowError> {
+        Self::advance_stage_with(spec, target_stage, &WorkflowPolicy::default())
+    }
+
+    /// Validate and execute a stage transition under `policy`
+    pub fn advance_stage_with(
+        spec: &SpecData,
+        target_stage: WorkflowStage,
+        policy: &WorkflowPolicy,
     ) -> Result<WorkflowTransition, WorkflowError> {
         let current = &spec.stage;
 
@@ -87,7 +181,7 @@ impl WorkflowEngine {
         }
 
         // Check if trying to go backwards
-        if Self::stage_index(&target_stage) <= Self::stage_index(current) {
+        if target_stage.order() <= current.order() {
             return Err(WorkflowError::BackwardTransition {
                 from: current.to_string(),
                 to: target_stage.to_string(),
@@ -95,7 +189,7 @@ impl WorkflowEngine {
         }
 
         // Validate the transition
-        Self::validate_transition(spec, current, &target_stage)?;
+        Self::validate_transition(spec, current, &target_stage, policy)?;
 
         // Create transition event
         let event = WorkflowEvent::Transition(current.clone(), target_stage.clone());
@@ -104,6 +198,70 @@ impl WorkflowEngine {
             from: current.clone(),
             to: target_stage,
             event,
+            waived: Vec::new(),
+        })
+    }
+
+    /// Jump forward past one or more stages, with a justification
+    ///
+    /// The rules for leaving the current stage must pass. Each skipped
+    /// stage's own exit rules are checked too; failures are waived and
+    /// returned in [`WorkflowTransition::waived`] rather than blocking.
+    pub fn skip_to_with(
+        spec: &SpecData,
+        target_stage: WorkflowStage,
+        justification: &str,
+        policy: &WorkflowPolicy,
+    ) -> Result<WorkflowTransition, WorkflowError> {
+        let current = &spec.stage;
+        let justification = justification.trim();
+
+        if justification.is_empty() {
+            return Err(WorkflowError::ValidationFailed(
+                "A justification is required to skip stages".to_string(),
+            ));
+        }
+        if current == &target_stage {
+            return Err(WorkflowError::AlreadyAtStage(target_stage.to_string()));
+        }
+        if target_stage.order() <= current.order() {
+            return Err(WorkflowError::BackwardTransition {
+                from: current.to_string(),
+                to: target_stage.to_string(),
+            });
+        }
+
+        let path = &WorkflowStage::all()[current.order()..=target_stage.order()];
+        let skipped = &path[1..path.len() - 1];
+        if skipped.is_empty() {
+            return Err(WorkflowError::InvalidTransition {
+                from: current.to_string(),
+                to: target_stage.to_string(),
+                reason: "No stages to skip; advance without skipping".to_string(),
+            });
+        }
+        if skipped.contains(&WorkflowStage::Approval) {
+            return Err(WorkflowError::InvalidTransition {
+                from: current.to_string(),
+                to: target_stage.to_string(),
+                reason: "Approval is a manual gate and cannot be skipped".to_string(),
+            });
+        }
+
+        // Prerequisites: leaving the current stage
+        Self::validate_transition(spec, &path[0], &path[1], policy)?;
+
+        let waived = path[1..]
+            .windows(2)
+            .filter_map(|step| Self::validate_transition(spec, &step[0], &step[1], policy).err())
+            .map(|e| e.to_string())
+            .collect();
+
+        Ok(WorkflowTransition {
+            from: current.clone(),
+            to: target_stage.clone(),
+            event: WorkflowEvent::Skipped(current.clone(), target_stage, justification.to_string()),
+            waived,
         })
     }
 
@@ -112,6 +270,7 @@ impl WorkflowEngine {
         spec: &SpecData,
         from: &WorkflowStage,
         to: &WorkflowStage,
+        policy: &WorkflowPolicy,
     ) -> Result<(), WorkflowError> {
         match (from, to) {
             // requirements -> design: Must have at least one requirement
@@ -161,11 +320,41 @@ impl WorkflowEngine {
                     }
                 }
 
+                if let Some(min) = policy.min_coverage {
+                    let score = crate::validation::coverage(spec).score;
+                    if score < min {
+                        return Err(WorkflowError::ValidationFailed(format!(
+                            "Cannot advance to approval: coverage {:.0}% is below the required {:.0}%",
+                            score, min
+                        )));
+                    }
+                }
+
                 Ok(())
             }
 
-            // approval -> implemented: No automatic validation (manual approval required)
-            (WorkflowStage::Approval, WorkflowStage::Implemented) => Ok(()),
+            // approval -> implemented: Manual approval, plus verification if configured
+            (WorkflowStage::Approval, WorkflowStage::Implemented) => {
+                if policy.require_verified {
+                    let unverified: Vec<&str> = spec
+                        .requirements
+                        .iter()
+                        .filter(|r| {
+                            r.priority == Priority::Must
+                                && r.verification != VerificationStatus::Verified
+                        })
+                        .map(|r| r.id.as_str())
+                        .collect();
+                    if !unverified.is_empty() {
+                        return Err(WorkflowError::ValidationFailed(format!(
+                            "Cannot advance to implemented: must requirements not verified: {}",
+                            unverified.join(", ")
+                        )));
+                    }
+                }
+
+                Ok(())
+            }
 
             // All other transitions
             _ => Err(WorkflowError::InvalidTransition {
@@ -176,35 +365,27 @@ impl WorkflowEngine {
         }
     }
 
-    /// Get numeric index for stage (for comparison)
-    fn stage_index(stage: &WorkflowStage) -> usize {
-        match stage {
-            WorkflowStage::Requirements => 0,
-            WorkflowStage::Design => 1,
-            WorkflowStage::Tasks => 2,
-            WorkflowStage::Approval => 3,
-            WorkflowStage::Implemented => 4,
-        }
-    }
-
     /// Get the next stage in the workflow
     pub fn next_stage(current: &WorkflowStage) -> Option<WorkflowStage> {
-        match current {
-            WorkflowStage::Requirements => Some(WorkflowStage::Design),
-            WorkflowStage::Design => Some(WorkflowStage::Tasks),
-            WorkflowStage::Tasks => Some(WorkflowStage::Approval),
-            WorkflowStage::Approval => Some(WorkflowStage::Implemented),
-            WorkflowStage::Implemented => None,
-        }
+        WorkflowStage::all().get(current.order() + 1).cloned()
     }
 
     /// Check if a stage can be advanced
+    #[allow(dead_code)]
     pub fn can_advance(spec: &SpecData) -> Result<WorkflowStage, WorkflowError> {
+        Self::can_advance_with(spec, &WorkflowPolicy::default())
+    }
+
+    /// Check if a stage can be advanced under `policy`
+    pub fn can_advance_with(
+        spec: &SpecData,
+        policy: &WorkflowPolicy,
+    ) -> Result<WorkflowStage, WorkflowError> {
         let current = &spec.stage;
 
         match Self::next_stage(current) {
             Some(next) => {
-                Self::validate_transition(spec, current, &next)?;
+                Self::validate_transition(spec, current, &next, policy)?;
                 Ok(next)
             }
             None => Err(WorkflowError::ValidationFailed(
@@ -220,6 +401,22 @@ pub struct WorkflowTransition {
     pub from: WorkflowStage,
     pub to: WorkflowStage,
     pub event: WorkflowEvent,
+    /// Rules of skipped stages that didn't pass and were waived
+    pub waived: Vec<String>,
+}
+
+impl WorkflowTransition {
+    /// Human-readable description for the workflow event log
+    pub fn details(&self) -> String {
+        let mut details = format!("Advanced from {} to {}", self.from, self.to);
+        if let WorkflowEvent::Skipped(_, _, justification) = &self.event {
+            details.push_str(&format!(" (skipped: {})", justification));
+        }
+        if !self.waived.is_empty() {
+            details.push_str(&format!("; waived: {}", self.waived.join("; ")));
+        }
+        details
+    }
 }
 
 impl WorkflowTransition {
@@ -234,6 +431,52 @@ impl WorkflowTransition {
     }
 }
 
+/// Move a spec along a validated transition and record it
+///
+/// Marks the old stage completed, moves the spec to the target stage and
+/// adds an `advance` entry to its patch history, then in one transaction
+/// logs the event and saves the spec. The policy's hooks
+/// run only once that has committed, so they never fire for a transition
+/// that wasn't recorded. Every entry point (CLI, MCP, LLM session) goes
+/// through here.
+pub fn apply_transition(
+    db: &Database,
+    spec: &mut SpecData,
+    transition: &WorkflowTransition,
+    actor: &str,
+    policy: &WorkflowPolicy,
+) -> Result<()> {
+    let old_stage = spec.stage.clone();
+    if !spec.stages_completed.contains(&old_stage) {
+        spec.stages_completed.push(old_stage);
+    }
+    spec.stage = transition.to.clone();
+    let now = chrono::Utc::now().timestamp();
+    spec.history.updated_at = now;
+    spec.history.patches.push(PatchEntry {
+        timestamp: now,
+        actor: actor.to_string(),
+        op: "advance".to_string(),
+        path: "/stage".to_string(),
+        summary: transition.details(),
+    });
+
+    db.in_transaction(|db| {
+        db.log_workflow_event(
+            &spec.spec_id,
+            &transition.to.to_string(),
+            &transition.event.as_string(),
+            actor,
+            now,
+            Some(&transition.details()),
+        )?;
+        db.update_spec(spec)
+    })?;
+
+    hooks::run_hooks(&policy.hooks, &spec.spec_id, transition, actor);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -270,6 +513,8 @@ mod tests {
             priority: Priority::Must,
             tags: vec![],
             scenarios: vec![],
+            history: vec![],
+            verification: Default::default(),
         });
 
         let result = WorkflowEngine::advance_stage(&spec, WorkflowStage::Design);
@@ -288,6 +533,8 @@ mod tests {
             priority: Priority::Must,
             tags: vec![],
             scenarios: vec![],
+            history: vec![],
+            verification: Default::default(),
         });
 
         let result = WorkflowEngine::advance_stage(&spec, WorkflowStage::Tasks);
@@ -0,0 +1,1592 @@
+// Integration tests for the manifold binary
+// Tests run with HOME pointed at a temp dir so ~/.manifold is never touched
+
+mod common;
+
+use manifold::db::Database;
+use manifold::models::{Boundary, SpecData, WorkflowStage};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+#[test]
+fn test_bash_completions_list_subcommands() {
+    let output = Command::new(env!("CARGO_BIN_EXE_manifold"))
+        .args(["completions", "bash"])
+        .output()
+        .expect("run manifold");
+
+    assert!(output.status.success());
+    let script = String::from_utf8(output.stdout).expect("utf-8 output");
+
+    assert!(script.contains("_manifold"));
+    for subcommand in [
+        "init",
+        "new",
+        "list",
+        "export",
+        "sync",
+        "workflow",
+        "conflicts",
+    ] {
+        assert!(
+            script.contains(subcommand),
+            "missing subcommand {}",
+            subcommand
+        );
+    }
+}
+
+/// Create an initialized ~/.manifold under a temp HOME
+fn setup_home() -> (TempDir, Database) {
+    let home = TempDir::new().unwrap();
+    let paths = common::paths_under(&home.path().join(".manifold"));
+    fs::create_dir_all(&paths.db).unwrap();
+    fs::write(&paths.config, "").unwrap();
+    let db = Database::init(&paths).unwrap();
+    (home, db)
+}
+
+/// Run manifold against the temp HOME and return stdout
+fn run_in(home: &TempDir, args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_manifold"))
+        .args(args)
+        .env("HOME", home.path())
+        .output()
+        .expect("run manifold");
+    assert!(
+        output.status.success(),
+        "manifold {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).expect("utf-8 output")
+}
+
+#[test]
+fn test_complete_helper_lists_spec_and_project_ids() {
+    let (home, db) = setup_home();
+
+    for (id, project) in [
+        ("spec-b", "proj-1"),
+        ("spec-a", "proj-2"),
+        ("spec-c", "proj-1"),
+    ] {
+        let spec = SpecData::new(
+            id.to_string(),
+            project.to_string(),
+            format!("Spec {}", id),
+            Boundary::Personal,
+        );
+        db.insert_spec(&spec).unwrap();
+    }
+
+    let complete = |kind: &str| run_in(&home, &["__complete", kind]);
+
+    assert_eq!(complete("specs"), "spec-a\nspec-b\nspec-c\n");
+    assert_eq!(complete("projects"), "proj-1\nproj-2\n");
+}
+
+#[test]
+fn test_completion_scripts_call_complete_helper() {
+    for shell in ["bash", "zsh", "fish"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_manifold"))
+            .args(["completions", shell])
+            .output()
+            .expect("run manifold");
+
+        assert!(output.status.success());
+        let script = String::from_utf8(output.stdout).expect("utf-8 output");
+        assert!(
+            script.contains("manifold __complete"),
+            "{} script does not complete spec ids",
+            shell
+        );
+    }
+}
+
+#[test]
+fn test_ndjson_output_is_one_spec_per_line() {
+    let (home, db) = setup_home();
+
+    for i in 0..5 {
+        let spec = SpecData::new(
+            format!("spec-{}", i),
+            "stream-project".to_string(),
+            format!("Streaming spec {}", i),
+            Boundary::Work,
+        );
+        db.insert_spec(&spec).unwrap();
+    }
+
+    let listed = run_in(&home, &["list", "--format", "ndjson", "--sort", "project"]);
+    let lines: Vec<&str> = listed.lines().collect();
+    assert_eq!(lines.len(), 5);
+    for line in &lines {
+        let spec: serde_json::Value = serde_json::from_str(line).expect("line parses as JSON");
+        assert_eq!(spec["project"], "stream-project");
+    }
+
+    let paged = run_in(
+        &home,
+        &[
+            "list", "--format", "ndjson", "--limit", "2", "--offset", "4",
+        ],
+    );
+    assert_eq!(paged.lines().count(), 1);
+
+    let searched = run_in(
+        &home,
+        &["search", "streaming", "--format", "ndjson", "--limit", "3"],
+    );
+    let lines: Vec<&str> = searched.lines().collect();
+    assert_eq!(lines.len(), 3);
+    for line in lines {
+        let spec: serde_json::Value = serde_json::from_str(line).expect("line parses as JSON");
+        assert!(spec["spec_id"].as_str().unwrap().starts_with("spec-"));
+    }
+}
+
+#[test]
+fn test_list_tree_groups_by_boundary_then_stage() {
+    let (home, db) = setup_home();
+
+    let specs = [
+        (
+            "personal-a",
+            Boundary::Personal,
+            WorkflowStage::Requirements,
+        ),
+        (
+            "personal-b",
+            Boundary::Personal,
+            WorkflowStage::Requirements,
+        ),
+        ("personal-c", Boundary::Personal, WorkflowStage::Design),
+        ("work-a", Boundary::Work, WorkflowStage::Tasks),
+    ];
+    for (id, boundary, stage) in specs {
+        let mut spec = SpecData::new(
+            id.to_string(),
+            "tree-project".to_string(),
+            format!("Tree spec {}", id),
+            boundary,
+        );
+        spec.stage = stage;
+        db.insert_spec(&spec).unwrap();
+    }
+
+    let tree = run_in(&home, &["list", "--format", "tree", "--sort", "project"]);
+    let lines: Vec<&str> = tree.lines().collect();
+
+    let personal = lines.iter().position(|l| *l == "personal (3)").unwrap();
+    let work = lines.iter().position(|l| *l == "work (1)").unwrap();
+    assert!(personal < work, "boundaries out of order:\n{}", tree);
+    assert_eq!(lines[personal + 1], "├── requirements (2)");
+    assert!(tree.contains("└── design (1)"));
+    assert!(tree.contains("└── tasks (1)"));
+    assert!(tree.contains("work-a  Tree spec work-a"));
+    assert!(!tree.contains("company"));
+}
+
+#[test]
+fn test_list_filters_accept_mixed_case_and_aliases() {
+    let (home, db) = setup_home();
+    let mut spec = SpecData::new(
+        "spec-alias".to_string(),
+        "alias-project".to_string(),
+        "Alias spec".to_string(),
+        Boundary::Work,
+    );
+    spec.stage = WorkflowStage::Implemented;
+    db.insert_spec(&spec).unwrap();
+
+    for (boundary, stage) in [
+        ("work", "implemented"),
+        ("WORK", "impl"),
+        (" Work ", "Implementation"),
+    ] {
+        let listed = run_in(
+            &home,
+            &[
+                "list",
+                "--format",
+                "ndjson",
+                "--boundary",
+                boundary,
+                "--stage",
+                stage,
+            ],
+        );
+        assert_eq!(listed.lines().count(), 1, "{} / {}", boundary, stage);
+    }
+
+    let listed = run_in(&home, &["list", "--format", "ndjson", "--stage", "req"]);
+    assert_eq!(listed.lines().count(), 0);
+
+    for args in [["--boundary", "team"], ["--stage", "done"]] {
+        let output = Command::new(env!("CARGO_BIN_EXE_manifold"))
+            .args(["list"])
+            .args(args)
+            .env("HOME", home.path())
+            .output()
+            .expect("run manifold");
+        assert!(!output.status.success(), "{:?} was accepted", args);
+    }
+}
+
+#[test]
+fn test_malformed_spec_row_is_skipped_not_fatal() {
+    let (home, db) = setup_home();
+
+    for id in ["spec-good", "spec-old-schema"] {
+        let spec = SpecData::new(
+            id.to_string(),
+            "corrupt-project".to_string(),
+            format!("Spec {}", id),
+            Boundary::Work,
+        );
+        db.insert_spec(&spec).unwrap();
+    }
+    drop(db);
+
+    // Corrupt a row behind manifold's back
+    let conn = rusqlite::Connection::open(home.path().join(".manifold/db/manifold.db")).unwrap();
+    conn.execute(
+        "UPDATE specs SET data = '{\"spec_id\": 42}' WHERE id = 'spec-old-schema'",
+        [],
+    )
+    .unwrap();
+    drop(conn);
+
+    // Rows still load through the library, and parse errors name the spec
+    let paths = common::paths_under(&home.path().join(".manifold"));
+    let db = Database::open(&paths).unwrap();
+    let rows = db
+        .list_specs(None, None, manifold::db::SpecOrder::default())
+        .unwrap();
+    assert_eq!(rows.len(), 2);
+    for row in &rows {
+        match row.id.as_str() {
+            "spec-good" => assert!(row.parse().is_ok()),
+            id => assert!(row.parse().unwrap_err().to_string().contains(id)),
+        }
+    }
+
+    // export all keeps going and reports the skipped id
+    let out_dir = home.path().join("out");
+    let output = Command::new(env!("CARGO_BIN_EXE_manifold"))
+        .args(["export", "all", "--output"])
+        .arg(&out_dir)
+        .env("HOME", home.path())
+        .output()
+        .expect("run manifold");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "export failed: {}", stderr);
+    assert!(stderr.contains("spec-old-schema"));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Exported 1 specs"));
+
+    // show still prints what it can, with a warning
+    let shown = run_in(&home, &["show", "spec-old-schema"]);
+    assert!(shown.contains("⚠ Spec spec-old-schema could not be parsed"));
+}
+
+#[test]
+fn test_corrupt_data_column_is_a_contextual_error() {
+    let (home, db) = setup_home();
+    let spec = SpecData::new(
+        "spec-garbage".to_string(),
+        "corrupt-project".to_string(),
+        "Spec spec-garbage".to_string(),
+        Boundary::Work,
+    );
+    db.insert_spec(&spec).unwrap();
+    drop(db);
+
+    let conn = rusqlite::Connection::open(home.path().join(".manifold/db/manifold.db")).unwrap();
+    conn.execute(
+        "UPDATE specs SET data = 'not json' WHERE id = 'spec-garbage'",
+        [],
+    )
+    .unwrap();
+    drop(conn);
+
+    let paths = common::paths_under(&home.path().join(".manifold"));
+    let db = Database::open(&paths).unwrap();
+    let err = db.get_spec("spec-garbage").unwrap_err().to_string();
+    assert!(
+        err.contains("corrupt column data for spec spec-garbage"),
+        "unexpected error: {}",
+        err
+    );
+    let err = db
+        .list_specs(None, None, manifold::db::SpecOrder::default())
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("spec-garbage"), "unexpected error: {}", err);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_manifold"))
+        .args(["show", "spec-garbage"])
+        .env("HOME", home.path())
+        .output()
+        .expect("run manifold");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("corrupt column data for spec spec-garbage"));
+}
+
+#[test]
+fn test_show_json_compact_is_canonical() {
+    let (home, db) = setup_home();
+    let mut spec = SpecData::new(
+        "spec-compact".to_string(),
+        "compact-project".to_string(),
+        "Compact\nOutput".to_string(),
+        Boundary::Work,
+    );
+    spec.owner = Some("alice".to_string());
+    db.insert_spec(&spec).unwrap();
+
+    let compact = run_in(&home, &["show", "spec-compact", "--json", "--compact"]);
+    let line = compact.strip_suffix('\n').expect("trailing newline");
+    assert!(
+        !line.contains('\n'),
+        "compact output spans lines: {}",
+        compact
+    );
+    assert!(
+        !line.contains(": "),
+        "compact output has whitespace: {}",
+        compact
+    );
+
+    // Same spec as the pretty output, in canonical form
+    let pretty = run_in(&home, &["show", "spec-compact", "--json"]);
+    let value: serde_json::Value = serde_json::from_str(&pretty).unwrap();
+    assert_eq!(line, manifold::models::canonical_json(&value));
+    assert!(line.find("\"boundary\"").unwrap() < line.find("\"name\"").unwrap());
+}
+
+#[test]
+fn test_export_all_filters_by_boundary() {
+    let (home, db) = setup_home();
+    for (id, boundary) in [
+        ("spec-work", Boundary::Work),
+        ("spec-home", Boundary::Personal),
+    ] {
+        db.insert_spec(&SpecData::new(
+            id.to_string(),
+            format!("{}-project", id),
+            format!("Export {}", id),
+            boundary,
+        ))
+        .unwrap();
+    }
+
+    let out = home.path().join("work.md");
+    let stdout = run_in(
+        &home,
+        &[
+            "export",
+            "all",
+            "--boundary",
+            "work",
+            "--output",
+            out.to_str().unwrap(),
+        ],
+    );
+    assert!(stdout.contains("Exported 1 specs"));
+
+    let exported = fs::read_to_string(&out).unwrap();
+    assert!(exported.contains("Export spec-work"));
+    assert!(!exported.contains("Export spec-home"));
+
+    // A single-spec export has nothing to filter
+    let output = Command::new(env!("CARGO_BIN_EXE_manifold"))
+        .args(["export", "spec-work", "--boundary", "work", "--output"])
+        .arg(home.path().join("x.md"))
+        .env("HOME", home.path())
+        .output()
+        .expect("run manifold");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_profiles_resolve_to_isolated_manifolds() {
+    let home = TempDir::new().unwrap();
+    let acme = home.path().join("clients/acme");
+    let globex = home.path().join("clients/globex");
+
+    run_in(&home, &["profile", "add", "acme", acme.to_str().unwrap()]);
+    run_in(
+        &home,
+        &["profile", "add", "globex", globex.to_str().unwrap()],
+    );
+    assert!(acme != globex);
+
+    run_in(&home, &["--profile", "acme", "init"]);
+    run_in(&home, &["--profile", "globex", "init"]);
+    assert!(acme.join("db/manifold.db").exists());
+    assert!(globex.join("db/manifold.db").exists());
+    assert!(!home.path().join(".manifold/db").exists());
+
+    run_in(
+        &home,
+        &[
+            "--profile",
+            "acme",
+            "new",
+            "acme-billing",
+            "--name",
+            "Billing",
+        ],
+    );
+
+    let list = |args: &[&str]| {
+        let mut full = args.to_vec();
+        full.extend(["list", "--format", "json"]);
+        let specs: Vec<serde_json::Value> = serde_json::from_str(&run_in(&home, &full)).unwrap();
+        specs
+    };
+    assert_eq!(list(&["--profile", "acme"]).len(), 1);
+    assert!(list(&["--profile", "globex"]).is_empty());
+
+    // The active profile applies without --profile, and the flag still wins
+    run_in(&home, &["profile", "use", "acme"]);
+    assert_eq!(list(&[]).len(), 1);
+    assert!(list(&["--profile", "globex"]).is_empty());
+    assert!(run_in(&home, &["profile", "list"]).contains("* acme"));
+
+    run_in(&home, &["profile", "use", "default"]);
+    let output = Command::new(env!("CARGO_BIN_EXE_manifold"))
+        .args(["list"])
+        .env("HOME", home.path())
+        .output()
+        .expect("run manifold");
+    assert!(
+        !output.status.success(),
+        "default manifold is not initialized"
+    );
+
+    // Two profiles may not share a home
+    let output = Command::new(env!("CARGO_BIN_EXE_manifold"))
+        .args(["profile", "add", "copy"])
+        .arg(&acme)
+        .env("HOME", home.path())
+        .output()
+        .expect("run manifold");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_new_uses_configured_default_boundary() {
+    let (home, db) = setup_home();
+    fs::write(
+        home.path().join(".manifold/config.toml"),
+        "default_boundary = \"work\"\n",
+    )
+    .unwrap();
+
+    run_in(&home, &["new", "defaulted"]);
+    run_in(&home, &["new", "explicit", "--boundary", "company"]);
+
+    let boundary_of = |project: &str| {
+        db.list_specs(None, None, manifold::db::SpecOrder::default())
+            .unwrap()
+            .into_iter()
+            .find(|row| row.project == project)
+            .unwrap()
+            .boundary
+    };
+    assert_eq!(boundary_of("defaulted"), "work");
+    assert_eq!(boundary_of("explicit"), "company");
+}
+
+/// Run `manifold import spec -` with `input` on stdin
+fn import_spec_stdin(home: &TempDir, input: &str, extra: &[&str]) -> std::process::Output {
+    let mut args = vec!["import", "spec", "-"];
+    args.extend(extra);
+    run_with_stdin(home, &args, input)
+}
+
+/// Run manifold with `input` piped to stdin
+fn run_with_stdin(home: &TempDir, args: &[&str], input: &str) -> std::process::Output {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_manifold"))
+        .args(args)
+        .env("HOME", home.path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("run manifold");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().expect("wait for manifold")
+}
+
+#[test]
+fn test_import_spec_from_stdin_is_stored_and_searchable() {
+    let (home, db) = setup_home();
+    let schemas = home.path().join(".manifold/schemas");
+    fs::create_dir_all(&schemas).unwrap();
+    fs::write(
+        schemas.join("core.json"),
+        include_str!("../schemas/core.json"),
+    )
+    .unwrap();
+
+    let spec = SpecData::new(
+        "spec-piped".to_string(),
+        "piped-project".to_string(),
+        "Telemetry Ingest".to_string(),
+        Boundary::Work,
+    );
+    let json = serde_json::to_string_pretty(&spec).unwrap();
+
+    let output = import_spec_stdin(&home, &json, &[]);
+    assert!(
+        output.status.success(),
+        "import failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // A fresh id is generated from the project
+    let rows = db
+        .list_specs(None, None, manifold::db::SpecOrder::default())
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    let id = rows[0].id.clone();
+    assert_ne!(id, "spec-piped");
+    assert_eq!(rows[0].project, "piped-project");
+    assert!(String::from_utf8_lossy(&output.stdout).contains(&id));
+
+    let found = run_in(&home, &["search", "Telemetry"]);
+    assert!(found.contains(&id), "search output: {}", found);
+
+    // --keep-id stores the id as given, once
+    let output = import_spec_stdin(&home, &json, &["--keep-id"]);
+    assert!(output.status.success());
+    assert!(db.get_spec("spec-piped").unwrap().is_some());
+    let output = import_spec_stdin(&home, &json, &["--keep-id"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already exists"));
+
+    // Malformed JSON and schema violations are rejected with their location
+    let output = import_spec_stdin(&home, "{\"spec_id\": ", &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid JSON"));
+
+    let output = import_spec_stdin(&home, &json.replace("\"work\"", "\"galaxy\""), &[]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not a valid manifold spec"), "{}", stderr);
+    assert!(stderr.contains("line"), "{}", stderr);
+}
+
+#[test]
+fn test_yaml_export_then_import_yields_identical_spec() {
+    use manifold::models::{PatchEntry, Priority, Requirement, Task, TaskStatus};
+
+    let (source, source_db) = setup_home();
+    let mut spec = SpecData::new(
+        "spec-yaml".to_string(),
+        "yaml-project".to_string(),
+        "Edited: outside the tool".to_string(),
+        Boundary::Work,
+    );
+    spec.owner = Some("alice".to_string());
+    spec.requirements.push(Requirement {
+        id: "req-1".to_string(),
+        capability: "auth".to_string(),
+        title: "User Login".to_string(),
+        shall: "The system SHALL authenticate users".to_string(),
+        rationale: Some("Multi-line\nrationale: with colons".to_string()),
+        priority: Priority::Must,
+        tags: vec!["security".to_string(), "yes".to_string()],
+        scenarios: vec![],
+        history: vec![],
+        verification: Default::default(),
+    });
+    spec.tasks.push(Task {
+        id: "task-1".to_string(),
+        requirement_ids: vec!["req-1".to_string()],
+        title: "Build login form".to_string(),
+        description: "Form".to_string(),
+        status: TaskStatus::InProgress,
+        assignee: Some("bob".to_string()),
+        acceptance: vec!["Submits credentials".to_string()],
+        scenario_ids: vec![],
+    });
+    spec.history.patches.push(PatchEntry {
+        timestamp: 1_700_000_100,
+        actor: "alice".to_string(),
+        op: "replace".to_string(),
+        path: "/name".to_string(),
+        summary: "Renamed".to_string(),
+    });
+    source_db.insert_spec(&spec).unwrap();
+
+    let yaml_path = source.path().join("spec.yaml");
+    run_in(
+        &source,
+        &[
+            "export",
+            "spec-yaml",
+            "-o",
+            yaml_path.to_str().unwrap(),
+            "--format",
+            "yaml",
+        ],
+    );
+
+    let (target, target_db) = setup_home();
+    let schemas = target.path().join(".manifold/schemas");
+    fs::create_dir_all(&schemas).unwrap();
+    fs::write(
+        schemas.join("core.json"),
+        include_str!("../schemas/core.json"),
+    )
+    .unwrap();
+    run_in(
+        &target,
+        &[
+            "import",
+            "spec",
+            "--yaml",
+            yaml_path.to_str().unwrap(),
+            "--keep-id",
+        ],
+    );
+
+    let exported = source_db.get_spec_data("spec-yaml").unwrap().unwrap();
+    let imported = target_db.get_spec_data("spec-yaml").unwrap().unwrap();
+    assert_eq!(
+        serde_json::to_value(&imported).unwrap(),
+        serde_json::to_value(&exported).unwrap()
+    );
+}
+
+#[test]
+fn test_actor_flag_and_env_are_recorded_in_patches() {
+    let (home, db) = setup_home();
+    fs::write(
+        home.path().join(".manifold/config.toml"),
+        "default_boundary = \"personal\"\n",
+    )
+    .unwrap();
+    for id in ["spec-flag", "spec-env"] {
+        let spec = SpecData::new(
+            id.to_string(),
+            "actor-project".to_string(),
+            format!("Spec {}", id),
+            Boundary::Company,
+        );
+        db.insert_spec(&spec).unwrap();
+    }
+
+    run_in(
+        &home,
+        &["--actor", "alice", "publish", "spec-flag", "--yes"],
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_manifold"))
+        .args(["publish", "spec-env", "--yes"])
+        .env("HOME", home.path())
+        .env("MANIFOLD_ACTOR", "ci-bot")
+        .output()
+        .expect("run manifold");
+    assert!(output.status.success());
+
+    let last_actor = |id: &str| {
+        let spec = db.get_spec(id).unwrap().unwrap().parse().unwrap();
+        spec.history.patches.last().unwrap().actor.clone()
+    };
+    assert_eq!(last_actor("spec-flag"), "alice");
+    assert_eq!(last_actor("spec-env"), "ci-bot");
+}
+
+#[test]
+fn test_mcp_changes_are_attributed_to_the_actor_and_channel() {
+    let (home, db) = setup_home();
+    let mut spec = SpecData::new(
+        "spec-mcp".to_string(),
+        "actor-project".to_string(),
+        "Via MCP".to_string(),
+        Boundary::Work,
+    );
+    spec.requirements.push(manifold::models::Requirement {
+        id: "req-1".to_string(),
+        capability: "workflow".to_string(),
+        title: "Advance".to_string(),
+        shall: "The system SHALL advance specs".to_string(),
+        rationale: None,
+        priority: manifold::models::Priority::Must,
+        tags: vec![],
+        scenarios: vec![],
+        history: vec![],
+        verification: Default::default(),
+    });
+    db.insert_spec(&spec).unwrap();
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {"name": "advance_workflow", "arguments": {"spec_id": "spec-mcp"}}
+    });
+    let output = run_with_stdin(
+        &home,
+        &["--actor", "alice", "serve"],
+        &format!("{}\n", request),
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let events = db
+        .get_workflow_events("spec-mcp", None, None, None)
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].actor, "alice (mcp)");
+    let spec = db.get_spec_data("spec-mcp").unwrap().unwrap();
+    assert_eq!(spec.history.patches.last().unwrap().actor, "alice (mcp)");
+}
+
+#[test]
+fn test_serve_keeps_hook_output_out_of_the_response_stream() {
+    let (home, db) = setup_home();
+    fs::write(
+        home.path().join(".manifold/config.toml"),
+        "[workflow.hooks]\n\"requirements->design\" = { command = \"echo hook says hi\" }\n",
+    )
+    .unwrap();
+    let mut spec = SpecData::new(
+        "spec-hooked".to_string(),
+        "hook-project".to_string(),
+        "Hooked".to_string(),
+        Boundary::Work,
+    );
+    spec.requirements.push(manifold::models::Requirement {
+        id: "req-1".to_string(),
+        capability: "workflow".to_string(),
+        title: "Advance".to_string(),
+        shall: "The system SHALL advance specs".to_string(),
+        rationale: None,
+        priority: manifold::models::Priority::Must,
+        tags: vec![],
+        scenarios: vec![],
+        history: vec![],
+        verification: Default::default(),
+    });
+    db.insert_spec(&spec).unwrap();
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {"name": "advance_workflow", "arguments": {"spec_id": "spec-hooked"}}
+    });
+    let output = run_with_stdin(&home, &["serve"], &format!("{}\n", request));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert!(
+        stderr.contains("hook says hi"),
+        "hook did not run: {}",
+        stderr
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1, "stdout: {}", stdout);
+    let response: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+    assert_eq!(response["result"]["success"], true);
+}
+
+#[test]
+fn test_init_repair_restores_schema_and_keeps_specs() {
+    let home = TempDir::new().unwrap();
+    run_in(&home, &["init"]);
+
+    let root = home.path().join(".manifold");
+    let paths = common::paths_under(&root);
+    let db = Database::open(&paths).unwrap();
+    db.insert_spec(&SpecData::new(
+        "spec-kept".to_string(),
+        "repair".to_string(),
+        "Survives repair".to_string(),
+        Boundary::Personal,
+    ))
+    .unwrap();
+    drop(db);
+
+    fs::remove_file(paths.schemas.join("core.json")).unwrap();
+    fs::remove_dir_all(&paths.exports).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_manifold"))
+        .args(["validate", "spec-kept"])
+        .env("HOME", home.path())
+        .output()
+        .expect("run manifold");
+    assert!(!output.status.success());
+
+    // Plain init still refuses to touch an initialized manifold
+    let stdout = run_in(&home, &["init"]);
+    assert!(stdout.contains("already initialized"), "{}", stdout);
+    assert!(!paths.schemas.join("core.json").exists());
+
+    let stdout = run_in(&home, &["init", "--repair"]);
+    assert!(stdout.contains("1 spec(s) kept"), "{}", stdout);
+    assert!(paths.exports.is_dir());
+
+    let stdout = run_in(&home, &["validate", "spec-kept"]);
+    assert!(
+        stdout.contains("Schema validation... ✓ passed"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_init_repair_adds_tables_missing_from_older_databases() {
+    let home = TempDir::new().unwrap();
+    run_in(&home, &["init"]);
+    let root = home.path().join(".manifold");
+    let paths = common::paths_under(&root);
+    let newer_tables = [
+        "import_bases",
+        "conflict_resolutions",
+        "spec_snapshots",
+        "agent_events",
+        "spec_notes_fts",
+    ];
+    let table_count = |conn: &rusqlite::Connection| -> i64 {
+        let names: Vec<String> = newer_tables.iter().map(|t| format!("'{}'", t)).collect();
+        conn.query_row(
+            &format!(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name IN ({})",
+                names.join(", ")
+            ),
+            [],
+            |row| row.get(0),
+        )
+        .unwrap()
+    };
+
+    let conn = rusqlite::Connection::open(&paths.db_file).unwrap();
+    assert_eq!(table_count(&conn), newer_tables.len() as i64);
+    for table in newer_tables {
+        conn.execute(&format!("DROP TABLE {}", table), []).unwrap();
+    }
+
+    // Reads on the older schema come back empty without creating anything
+    let db = Database::open_readonly(&paths).unwrap();
+    assert!(db.get_snapshots("spec-any").unwrap().is_empty());
+    assert!(db.get_agent_events(None, None).unwrap().is_empty());
+    assert!(db.get_import_base("spec-any", "bundle").unwrap().is_none());
+    drop(db);
+    assert_eq!(table_count(&conn), 0);
+
+    run_in(&home, &["init", "--repair"]);
+    assert_eq!(table_count(&conn), newer_tables.len() as i64);
+}
+
+#[test]
+fn test_validate_fix_persists_mechanical_fixes() {
+    use manifold::models::{Priority, Requirement, Task, TaskStatus};
+
+    let home = TempDir::new().unwrap();
+    run_in(&home, &["init"]);
+    let root = home.path().join(".manifold");
+    let paths = common::paths_under(&root);
+    let db = Database::open(&paths).unwrap();
+
+    let mut spec = SpecData::new(
+        "spec-fixable".to_string(),
+        "fixes".to_string(),
+        "Fixable".to_string(),
+        Boundary::Personal,
+    );
+    spec.requirements.push(Requirement {
+        id: "REQ-1".to_string(),
+        capability: "auth".to_string(),
+        title: "Login".to_string(),
+        shall: "The system SHALL let users log in".to_string(),
+        rationale: None,
+        priority: Priority::Must,
+        tags: vec![],
+        scenarios: vec![],
+        history: vec![],
+        verification: Default::default(),
+    });
+    spec.tasks.push(Task {
+        id: "task-1".to_string(),
+        requirement_ids: vec!["REQ-1".to_string()],
+        title: "Build login".to_string(),
+        description: "Login form and session handling".to_string(),
+        status: TaskStatus::Pending,
+        assignee: None,
+        acceptance: vec![],
+        scenario_ids: vec![],
+    });
+    db.insert_spec(&spec).unwrap();
+
+    let warnings = manifold::validation::lint_spec(&spec);
+    assert!(warnings.contains(&"task-1: No acceptance criteria defined".to_string()));
+
+    let stdout = run_in(&home, &["validate", "spec-fixable", "--fix"]);
+    assert!(stdout.contains("applied 3 fix(es)"), "{}", stdout);
+    assert!(
+        stdout.contains("Renamed requirement 'REQ-1' to req-1"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Schema validation... ✓ passed"),
+        "{}",
+        stdout
+    );
+    assert!(
+        !stdout.contains("No acceptance criteria defined"),
+        "{}",
+        stdout
+    );
+
+    let fixed = db.get_spec_data("spec-fixable").unwrap().unwrap();
+    assert_eq!(fixed.requirements[0].id, "req-1");
+    assert_eq!(fixed.tasks[0].requirement_ids, vec!["req-1"]);
+    assert_eq!(
+        fixed.tasks[0].acceptance,
+        vec![manifold::validation::fix::ACCEPTANCE_PLACEHOLDER]
+    );
+    assert_eq!(fixed.history.patches.len(), 3);
+
+    let again = run_in(&home, &["validate", "spec-fixable", "--fix"]);
+    assert!(again.contains("nothing to fix"), "{}", again);
+}
+
+#[test]
+fn test_validate_uses_configured_id_patterns() {
+    use manifold::models::{Priority, Requirement};
+    use manifold::validation::IdPatterns;
+
+    let home = TempDir::new().unwrap();
+    run_in(&home, &["init"]);
+    let root = home.path().join(".manifold");
+    let config = "default_boundary = \"personal\"\n\n\
+                  [validation]\nid_digits = 3\n\n\
+                  [validation.id_patterns]\nrequirement = \"^REQ-[0-9]{3}$\"\n\n\
+                  [validation.id_prefixes]\nrequirement = \"REQ\"\n";
+    fs::write(root.join("config.toml"), config).unwrap();
+    let paths = common::paths_under(&root);
+    let db = Database::open(&paths).unwrap();
+
+    let spec_with = |spec_id: &str, req_id: &str| {
+        let mut spec = SpecData::new(
+            spec_id.to_string(),
+            "patterns".to_string(),
+            "Patterns".to_string(),
+            Boundary::Personal,
+        );
+        spec.requirements.push(Requirement {
+            id: req_id.to_string(),
+            capability: "auth".to_string(),
+            title: "Login".to_string(),
+            shall: "The system SHALL let users log in".to_string(),
+            rationale: None,
+            priority: Priority::Must,
+            tags: vec![],
+            scenarios: vec![],
+            history: vec![],
+            verification: Default::default(),
+        });
+        spec
+    };
+    db.insert_spec(&spec_with("spec-upper", "REQ-001")).unwrap();
+    db.insert_spec(&spec_with("spec-lower", "req-1")).unwrap();
+
+    let stdout = run_in(&home, &["validate", "spec-upper"]);
+    assert!(
+        stdout.contains("Schema validation... ✓ passed"),
+        "{}",
+        stdout
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_manifold"))
+        .args(["validate", "spec-lower"])
+        .env("HOME", home.path())
+        .output()
+        .expect("run manifold");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Schema validation... ✗ failed"),
+        "{}",
+        stdout
+    );
+
+    // Generated ids follow the configured prefix and padding
+    let loaded = manifold::config::load_config_from(&paths.config).unwrap();
+    let ids = IdPatterns::from_config(&loaded.validation).unwrap();
+    let next = ids.requirement.next_id(&spec_with("spec-next", "REQ-001"));
+    assert_eq!(next, "REQ-002");
+    assert!(ids.requirement.is_match(&next));
+}
+
+#[test]
+fn test_doctor_detects_and_repairs_stale_columns() {
+    let (home, db) = setup_home();
+
+    let mut spec = SpecData::new(
+        "spec-drifted".to_string(),
+        "doctor-project".to_string(),
+        "Drifted".to_string(),
+        Boundary::Work,
+    );
+    spec.stage = WorkflowStage::Design;
+    db.insert_spec(&spec).unwrap();
+    drop(db);
+
+    // An external edit moves the JSON on without touching the columns
+    let db_file = home.path().join(".manifold/db/manifold.db");
+    let conn = rusqlite::Connection::open(&db_file).unwrap();
+    conn.execute(
+        "UPDATE specs SET data = json_set(data, '$.stage', 'tasks', '$.boundary', 'company') WHERE id = 'spec-drifted'",
+        [],
+    )
+    .unwrap();
+    drop(conn);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_manifold"))
+        .args(["doctor"])
+        .env("HOME", home.path())
+        .output()
+        .expect("run manifold");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("spec-drifted: stage column is 'design' but the spec JSON says 'tasks'"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("spec-drifted: boundary column is 'work' but the spec JSON says 'company'")
+    );
+
+    let stdout = run_in(&home, &["doctor", "--fix"]);
+    assert!(stdout.contains("Re-derived 2 column(s)"), "{}", stdout);
+
+    let stdout = run_in(&home, &["doctor"]);
+    assert!(stdout.contains("✓ ok"), "{}", stdout);
+    let listed = run_in(
+        &home,
+        &["list", "--stage", "tasks", "--boundary", "company"],
+    );
+    assert!(listed.contains("spec-drifted"), "{}", listed);
+}
+
+#[test]
+fn test_snapshot_create_edit_and_restore() {
+    let (home, db) = setup_home();
+    fs::write(
+        home.path().join(".manifold/config.toml"),
+        "default_boundary = \"personal\"\n",
+    )
+    .unwrap();
+
+    let spec = SpecData::new(
+        "spec-frozen".to_string(),
+        "snapshot-project".to_string(),
+        "Release candidate".to_string(),
+        Boundary::Personal,
+    );
+    db.insert_spec(&spec).unwrap();
+
+    let stdout = run_in(
+        &home,
+        &["snapshot", "create", "spec-frozen", "--label", "v1.0"],
+    );
+    assert!(
+        stdout.contains("Created snapshot 1 of spec-frozen"),
+        "{}",
+        stdout
+    );
+
+    let mut edited = spec.clone();
+    edited.name = "Rewritten after release".to_string();
+    edited.stage = WorkflowStage::Design;
+    db.update_spec(&edited).unwrap();
+
+    let diff = run_in(&home, &["snapshot", "diff", "spec-frozen", "1"]);
+    assert!(diff.contains("Release candidate"), "{}", diff);
+    assert!(diff.contains("Rewritten after release"), "{}", diff);
+
+    let stdout = run_in(&home, &["snapshot", "restore", "spec-frozen", "1"]);
+    assert!(
+        stdout.contains("Restored spec-frozen to snapshot 1"),
+        "{}",
+        stdout
+    );
+
+    let restored = db.get_spec_data("spec-frozen").unwrap().unwrap();
+    assert_eq!(restored.name, "Release candidate");
+    assert_eq!(restored.stage, WorkflowStage::Requirements);
+    let last = restored.history.patches.last().unwrap();
+    assert_eq!(last.summary, "Restored snapshot 1 (v1.0)");
+
+    // The edited version was kept, so the restore can be undone
+    let listed = run_in(&home, &["snapshot", "list", "spec-frozen"]);
+    assert!(listed.contains("v1.0"), "{}", listed);
+    assert!(listed.contains("before restoring snapshot 1"), "{}", listed);
+    let backup = db.get_snapshot("spec-frozen", 2).unwrap().unwrap();
+    assert_eq!(backup.data.name, "Rewritten after release");
+}
+
+#[test]
+fn test_report_shows_coverage_and_traceability_gaps() {
+    use manifold::models::{Priority, Requirement, Scenario, Task, TaskStatus};
+
+    let (home, db) = setup_home();
+    let requirement = |id: &str, scenarios: Vec<Scenario>| Requirement {
+        id: id.to_string(),
+        capability: "auth".to_string(),
+        title: format!("Requirement {}", id),
+        shall: "The system SHALL authenticate users".to_string(),
+        rationale: None,
+        priority: Priority::Must,
+        tags: vec![],
+        scenarios,
+        history: vec![],
+        verification: Default::default(),
+    };
+    let mut spec = SpecData::new(
+        "spec-report".to_string(),
+        "reports".to_string(),
+        "Reported".to_string(),
+        Boundary::Personal,
+    );
+    spec.requirements.push(requirement(
+        "req-1",
+        vec![Scenario {
+            id: "sc-1".to_string(),
+            name: "Valid login".to_string(),
+            given: vec!["a registered user".to_string()],
+            when: "they log in".to_string(),
+            then: vec!["a session starts".to_string()],
+            edge_cases: vec![],
+        }],
+    ));
+    spec.requirements.push(requirement("req-2", vec![]));
+    spec.tasks.push(Task {
+        id: "task-1".to_string(),
+        requirement_ids: vec!["req-1".to_string()],
+        title: "Build login".to_string(),
+        description: "Login form".to_string(),
+        status: TaskStatus::Completed,
+        assignee: None,
+        acceptance: vec!["Users can log in".to_string()],
+        scenario_ids: vec![],
+    });
+    db.insert_spec(&spec).unwrap();
+
+    // Scenarios 1/2, traced 1/2, acceptance 1/1
+    let stdout = run_in(&home, &["report", "spec-report"]);
+    assert!(stdout.contains("Coverage: 67%"), "{}", stdout);
+    assert!(stdout.contains("Tasks: 1/1 completed (100%)"), "{}", stdout);
+    assert!(stdout.contains("Traceability: 2 gap(s)"), "{}", stdout);
+    assert!(
+        stdout.contains("req-2: No task implements this requirement"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("req-1/sc-1: No task implements this scenario"),
+        "{}",
+        stdout
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&run_in(&home, &["report", "spec-report", "--json"])).unwrap();
+    let score = json["coverage"]["score"].as_f64().unwrap();
+    assert!((score - 200.0 / 3.0).abs() < 1e-9, "{}", score);
+    let gaps: Vec<(&str, &str)> = json["gaps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|g| (g["kind"].as_str().unwrap(), g["id"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        gaps,
+        vec![
+            ("untraced_requirement", "req-2"),
+            ("uncovered_scenario", "req-1/sc-1")
+        ]
+    );
+    assert_eq!(json["workflow"]["stage"], "requirements");
+    assert_eq!(json["open_conflicts"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn test_validate_json_and_exit_codes() {
+    use manifold::models::{Priority, Requirement};
+
+    // Schema validation needs the schemas written by a real init
+    let home = TempDir::new().unwrap();
+    run_in(&home, &["init"]);
+    let root = home.path().join(".manifold");
+    let db = Database::open(&common::paths_under(&root)).unwrap();
+    let spec_with = |spec_id: &str, req_id: &str| {
+        let mut spec = SpecData::new(
+            spec_id.to_string(),
+            "ci".to_string(),
+            "CI".to_string(),
+            Boundary::Personal,
+        );
+        // No scenarios, so lint warns
+        spec.requirements.push(Requirement {
+            id: req_id.to_string(),
+            capability: "auth".to_string(),
+            title: "Login".to_string(),
+            shall: "The system SHALL let users log in".to_string(),
+            rationale: None,
+            priority: Priority::Must,
+            tags: vec![],
+            scenarios: vec![],
+            history: vec![],
+            verification: Default::default(),
+        });
+        spec
+    };
+    db.insert_spec(&spec_with("spec-warned", "req-1")).unwrap();
+    db.insert_spec(&spec_with("spec-invalid", "REQ-1")).unwrap();
+    let validate = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_manifold"))
+            .arg("validate")
+            .args(args)
+            .env("HOME", home.path())
+            .output()
+            .expect("run manifold")
+    };
+
+    // Warnings alone pass outside strict mode
+    let output = validate(&["spec-warned", "--json"]);
+    assert_eq!(output.status.code(), Some(0));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["spec_id"], "spec-warned");
+    assert_eq!(json["schema_valid"], true);
+    assert_eq!(json["strict"], false);
+    assert_eq!(json["errors"], serde_json::json!([]));
+    assert!(json["warnings"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!("req-1: No scenarios defined")));
+
+    let output = validate(&["spec-warned", "--strict", "--json"]);
+    assert_eq!(output.status.code(), Some(2));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["strict"], true);
+    assert_eq!(json["schema_valid"], true);
+
+    let output = validate(&["spec-warned", "--strict"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("⚠ 1 warning(s)"));
+
+    let output = validate(&["spec-invalid", "--strict", "--json"]);
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["schema_valid"], false);
+    assert!(!json["errors"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_scenario_move_reorders_and_records_patch() {
+    use manifold::models::{Priority, Requirement, Scenario};
+
+    let (home, db) = setup_home();
+    let scenario = |id: &str| Scenario {
+        id: id.to_string(),
+        name: format!("Scenario {}", id),
+        given: vec!["a user".to_string()],
+        when: "they act".to_string(),
+        then: vec!["it works".to_string()],
+        edge_cases: vec![],
+    };
+    let mut spec = SpecData::new(
+        "spec-ordered".to_string(),
+        "ordering".to_string(),
+        "Ordered".to_string(),
+        Boundary::Personal,
+    );
+    spec.requirements.push(Requirement {
+        id: "req-1".to_string(),
+        capability: "auth".to_string(),
+        title: "Login".to_string(),
+        shall: "The system SHALL let users log in".to_string(),
+        rationale: None,
+        priority: Priority::Must,
+        tags: vec![],
+        scenarios: vec![scenario("sc-1"), scenario("sc-2"), scenario("sc-3")],
+        history: vec![],
+        verification: Default::default(),
+    });
+    db.insert_spec(&spec).unwrap();
+    let order = || -> Vec<String> {
+        db.get_spec_data("spec-ordered")
+            .unwrap()
+            .unwrap()
+            .requirements[0]
+            .scenarios
+            .iter()
+            .map(|s| s.id.clone())
+            .collect()
+    };
+
+    let stdout = run_in(
+        &home,
+        &[
+            "scenario",
+            "move",
+            "spec-ordered",
+            "req-1",
+            "sc-3",
+            "--before",
+            "sc-1",
+        ],
+    );
+    assert!(stdout.contains("sc-3, sc-1, sc-2"), "{}", stdout);
+    assert_eq!(order(), vec!["sc-3", "sc-1", "sc-2"]);
+
+    run_in(
+        &home,
+        &[
+            "scenario",
+            "move",
+            "spec-ordered",
+            "req-1",
+            "sc-3",
+            "--after",
+            "sc-2",
+        ],
+    );
+    assert_eq!(order(), vec!["sc-1", "sc-2", "sc-3"]);
+
+    let spec = db.get_spec_data("spec-ordered").unwrap().unwrap();
+    let patch = spec.history.patches.last().unwrap();
+    assert_eq!(patch.op, "move");
+    assert_eq!(patch.path, "/requirements/0/scenarios/2");
+    assert_eq!(patch.summary, "Moved req-1/sc-3 after sc-2");
+
+    // Unknown anchors are rejected without touching the spec
+    let output = Command::new(env!("CARGO_BIN_EXE_manifold"))
+        .args([
+            "scenario",
+            "move",
+            "spec-ordered",
+            "req-1",
+            "sc-1",
+            "--after",
+            "sc-9",
+        ])
+        .env("HOME", home.path())
+        .output()
+        .expect("run manifold");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Scenario sc-9 not found in req-1"));
+    assert_eq!(order(), vec!["sc-1", "sc-2", "sc-3"]);
+}
+
+#[test]
+fn test_completed_stages_are_normalized_and_repaired() {
+    let (home, db) = setup_home();
+
+    // Normalizing drops stages at or after the current one; a gap before it
+    // is a skipped stage and stays
+    let mut spec = SpecData::new(
+        "spec-staged".to_string(),
+        "stages".to_string(),
+        "Staged".to_string(),
+        Boundary::Work,
+    );
+    spec.stage = WorkflowStage::Tasks;
+    spec.stages_completed = vec![WorkflowStage::Requirements, WorkflowStage::Approval];
+    let mut normalized = spec.clone();
+    assert!(normalized.normalize_stages());
+    assert_eq!(
+        normalized.stages_completed,
+        vec![WorkflowStage::Requirements]
+    );
+    assert!(!normalized.normalize_stages());
+
+    // Saving and loading keep what is stored; only the view is normalized
+    db.insert_spec(&spec).unwrap();
+    let stored = db.get_spec("spec-staged").unwrap().unwrap();
+    assert_eq!(
+        stored.data["stages_completed"],
+        serde_json::json!(["requirements", "approval"])
+    );
+    let loaded = db.get_spec_data("spec-staged").unwrap().unwrap();
+    assert_eq!(loaded.stages_completed, spec.stages_completed);
+    assert_eq!(loaded.completed_stages(), vec![WorkflowStage::Requirements]);
+    drop(db);
+
+    // An external edit (e.g. moving the stage back by hand) goes stale
+    let db_file = home.path().join(".manifold/db/manifold.db");
+    let conn = rusqlite::Connection::open(&db_file).unwrap();
+    conn.execute(
+        "UPDATE specs SET data = json_set(data, '$.stage', 'design', '$.stages_completed', json('[\"requirements\",\"design\",\"tasks\"]')), stage = 'design' WHERE id = 'spec-staged'",
+        [],
+    )
+    .unwrap();
+    drop(conn);
+
+    let db = Database::open(&common::paths_under(&home.path().join(".manifold"))).unwrap();
+    let loaded = db.get_spec_data("spec-staged").unwrap().unwrap();
+    assert_eq!(loaded.completed_stages(), vec![WorkflowStage::Requirements]);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_manifold"))
+        .args(["doctor"])
+        .env("HOME", home.path())
+        .output()
+        .expect("run manifold");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(
+            "spec-staged: stage is 'design' but completed stages are [requirements, design, tasks], expected [requirements]"
+        ),
+        "{}",
+        stdout
+    );
+
+    let stdout = run_in(&home, &["doctor", "--fix"]);
+    assert!(
+        stdout.contains("Dropped stale completed stages from 1 spec(s)"),
+        "{}",
+        stdout
+    );
+    let stored = db.get_spec("spec-staged").unwrap().unwrap();
+    assert_eq!(
+        stored.data["stages_completed"],
+        serde_json::json!(["requirements"])
+    );
+    let stdout = run_in(&home, &["doctor"]);
+    assert!(!stdout.contains("⚠"), "{}", stdout);
+}
+
+#[test]
+fn test_skipped_stage_stays_out_of_completed_stages() {
+    let (home, db) = setup_home();
+    let mut spec = SpecData::new(
+        "spec-skip".to_string(),
+        "stages".to_string(),
+        "Skipper".to_string(),
+        Boundary::Work,
+    );
+    spec.requirements.push(manifold::models::Requirement {
+        id: "req-1".to_string(),
+        capability: "workflow".to_string(),
+        title: "Skip".to_string(),
+        shall: "The system SHALL allow skipping design".to_string(),
+        rationale: None,
+        priority: manifold::models::Priority::Must,
+        tags: vec![],
+        scenarios: vec![],
+        history: vec![],
+        verification: Default::default(),
+    });
+    db.insert_spec(&spec).unwrap();
+
+    run_in(
+        &home,
+        &[
+            "workflow",
+            "spec-skip",
+            "--operation",
+            "advance",
+            "--stage",
+            "tasks",
+            "--skip",
+            "--reason",
+            "Trivial spec",
+        ],
+    );
+
+    // Design was skipped, not completed, and that is not an inconsistency
+    let stored = db.get_spec("spec-skip").unwrap().unwrap();
+    assert_eq!(
+        stored.data["stages_completed"],
+        serde_json::json!(["requirements"])
+    );
+    let loaded = db.get_spec_data("spec-skip").unwrap().unwrap();
+    assert_eq!(loaded.stages_completed, vec![WorkflowStage::Requirements]);
+    let stdout = run_in(&home, &["doctor"]);
+    assert!(!stdout.contains("⚠"), "{}", stdout);
+
+    let history = run_in(&home, &["workflow", "spec-skip", "--operation", "history"]);
+    assert!(history.contains("Trivial spec"), "{}", history);
+}
+
+#[test]
+fn test_new_interactive_reprompts_until_answers_are_valid() {
+    let (home, db) = setup_home();
+
+    // Blank and spaced project ids, an unknown boundary, and a requirement
+    // without SHALL are each rejected and asked again
+    let script = [
+        "",
+        "my proj",
+        "guided",
+        "Guided Spec",
+        "workz",
+        "WORK",
+        "Login",
+        "Users log in",
+        "The system SHALL let users log in",
+        "",
+    ]
+    .join("\n");
+    let output = run_with_stdin(
+        &home,
+        &["new", "--interactive", "--owner", "alice"],
+        &script,
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("✗ Project id is required"));
+    assert!(stdout.contains("✗ Project id can't contain spaces"));
+    assert!(stdout.contains("✗ Invalid boundary: workz"));
+    assert!(stdout.contains("✗ Requirement must use SHALL or MUST"));
+
+    let rows = db.list_specs(None, None, Default::default()).unwrap();
+    assert_eq!(rows.len(), 1);
+    let spec = rows[0].parse().unwrap();
+    assert_eq!(spec.project, "guided");
+    assert_eq!(spec.name, "Guided Spec");
+    assert_eq!(spec.boundary, Boundary::Work);
+    assert_eq!(spec.owner.as_deref(), Some("alice"));
+    assert_eq!(spec.requirements.len(), 1);
+    assert_eq!(spec.requirements[0].title, "Login");
+    assert_eq!(
+        spec.requirements[0].shall,
+        "The system SHALL let users log in"
+    );
+
+    // Ending input before the boundary is answered creates nothing
+    let output = run_with_stdin(&home, &["new", "--interactive"], "cancelled\n");
+    assert!(!output.status.success());
+    assert_eq!(
+        db.list_specs(None, None, Default::default()).unwrap().len(),
+        1
+    );
+}
@@ -1,36 +1,20 @@
 // Integration tests for collaboration features
 // Tests git sync, conflict detection, and resolution
 
+mod common;
+
 use anyhow::Result;
+use common::setup;
 use manifold::collab::conflicts::ConflictResolver;
+use manifold::collab::notify::{Notifier, SmtpConfig, SmtpNotifier, SmtpSecurity};
+use manifold::collab::replay::replay;
 use manifold::collab::reviews::ReviewManager;
 use manifold::collab::{Conflict, ConflictStatus, ResolutionStrategy, ReviewStatus};
-use manifold::config::ManifoldPaths;
-use manifold::db::Database;
-use manifold::models::{Boundary, SpecData};
+use manifold::db::{SpecSnapshot, WorkflowEventRow};
+use manifold::models::{Boundary, PatchEntry, SpecData, WorkflowStage};
 use std::fs;
-use tempfile::TempDir;
-
-/// Setup test environment
-fn setup() -> anyhow::Result<(TempDir, ManifoldPaths, Database)> {
-    let temp_dir = TempDir::new()?;
-
-    // Create a custom ManifoldPaths for testing
-    let paths = ManifoldPaths {
-        root: temp_dir.path().to_path_buf(),
-        config: temp_dir.path().join("config.toml"),
-        db: temp_dir.path().join("db"),
-        db_file: temp_dir.path().join("db/manifold.db"),
-        schemas: temp_dir.path().join("schemas"),
-        exports: temp_dir.path().join("exports"),
-        cache: temp_dir.path().join("cache"),
-    };
-
-    fs::create_dir_all(&paths.db)?;
-    let db = Database::init(&paths)?;
-
-    Ok((temp_dir, paths, db))
-}
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 fn create_test_spec(spec_id: &str, project: &str, name: &str) -> SpecData {
     SpecData::new(
@@ -64,6 +48,35 @@ fn test_conflict_detection_simple() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_conflict_detection_boundary_and_project() -> Result<()> {
+    let base = create_test_spec("test-spec", "test-project", "Name");
+
+    let mut local = base.clone();
+    local.boundary = Boundary::Work;
+    local.project = "local-project".to_string();
+
+    let mut remote = base.clone();
+    remote.boundary = Boundary::Company;
+    remote.project = "remote-project".to_string();
+
+    let conflicts = ConflictResolver::detect_conflicts(&local, &remote, Some(&base))?;
+    let paths: Vec<&str> = conflicts.iter().map(|c| c.field_path.as_str()).collect();
+    assert_eq!(paths, vec!["boundary", "project"]);
+    assert_eq!(conflicts[0].local_value, serde_json::json!("work"));
+    assert_eq!(conflicts[0].remote_value, serde_json::json!("company"));
+    assert_eq!(conflicts[0].base_value, Some(serde_json::json!("personal")));
+
+    // A one-sided change is not a conflict and carries over in a merge
+    let conflicts = ConflictResolver::detect_conflicts(&base, &remote, Some(&base))?;
+    assert!(conflicts.is_empty());
+    let merged = ConflictResolver::merge_with_base(&base, &remote, &base)?;
+    assert_eq!(merged.boundary, Boundary::Company);
+    assert_eq!(merged.project, "remote-project");
+
+    Ok(())
+}
+
 #[test]
 fn test_conflict_resolution_ours() -> Result<()> {
     let conflict = Conflict {
@@ -132,6 +145,84 @@ fn test_3way_merge() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_scenario_edits_conflict_per_scenario() -> Result<()> {
+    use manifold::models::{Priority, Requirement, Scenario};
+
+    let scenario = |id: &str, when: &str| Scenario {
+        id: id.to_string(),
+        name: format!("Scenario {}", id),
+        given: vec!["a user".to_string()],
+        when: when.to_string(),
+        then: vec!["it works".to_string()],
+        edge_cases: vec![],
+    };
+    let mut base = create_test_spec("test-spec", "test-project", "Original");
+    base.requirements.push(Requirement {
+        id: "req-1".to_string(),
+        capability: "auth".to_string(),
+        title: "Login".to_string(),
+        shall: "The system SHALL log users in".to_string(),
+        rationale: None,
+        priority: Priority::Must,
+        tags: vec![],
+        scenarios: vec![
+            scenario("sc-1", "they log in"),
+            scenario("sc-2", "they log out"),
+        ],
+        history: vec![],
+        verification: Default::default(),
+    });
+
+    let mut local = base.clone();
+    local.requirements[0].scenarios[0].when = "they log in with SSO".to_string();
+    let mut remote = base.clone();
+    remote.requirements[0].scenarios[1].when = "they log out everywhere".to_string();
+
+    // Without a base each differing scenario is its own conflict
+    let mut conflicts = ConflictResolver::detect_conflicts(&local, &remote, None)?;
+    conflicts.sort_by(|a, b| a.field_path.cmp(&b.field_path));
+    let paths: Vec<_> = conflicts.iter().map(|c| c.field_path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "requirements/req-1/scenarios/sc-1",
+            "requirements/req-1/scenarios/sc-2"
+        ]
+    );
+
+    // A resolution applies to just that scenario
+    let mut resolved = local.clone();
+    let theirs = conflicts[1].remote_value.clone();
+    ConflictResolver::apply_resolutions(
+        &mut resolved,
+        &[(conflicts[1].field_path.clone(), theirs)],
+    )?;
+    assert_eq!(
+        resolved.requirements[0].scenarios[0].when,
+        "they log in with SSO"
+    );
+    assert_eq!(
+        resolved.requirements[0].scenarios[1].when,
+        "they log out everywhere"
+    );
+
+    // With a base, edits to different scenarios merge cleanly
+    let conflicts = ConflictResolver::detect_conflicts(&local, &remote, Some(&base))?;
+    assert!(conflicts.is_empty());
+    let merged = ConflictResolver::merge_with_base(&local, &remote, &base)?;
+    assert_eq!(
+        merged.requirements[0].scenarios[0].when,
+        "they log in with SSO"
+    );
+    assert_eq!(
+        merged.requirements[0].scenarios[1].when,
+        "they log out everywhere"
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_merge_strategy() -> Result<()> {
     let base = create_test_spec("test-spec", "test-project", "Original");
@@ -255,6 +346,195 @@ fn test_review_persistence() -> Result<()> {
     Ok(())
 }
 
+/// Notifier that remembers which reviews it was told about
+#[derive(Default)]
+struct RecordingNotifier {
+    requested: std::sync::Mutex<Vec<String>>,
+}
+
+impl Notifier for RecordingNotifier {
+    fn review_requested(&self, review: &manifold::collab::Review) -> Result<()> {
+        self.requested.lock().unwrap().push(review.id.clone());
+        Ok(())
+    }
+}
+
+/// Notifier whose endpoint is always down
+struct FailingNotifier;
+
+impl Notifier for FailingNotifier {
+    fn review_requested(&self, _review: &manifold::collab::Review) -> Result<()> {
+        anyhow::bail!("relay unreachable")
+    }
+}
+
+#[test]
+fn test_review_request_notifies_reviewer() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+    db.insert_spec(&create_test_spec("spec-notify", "test-project", "Notify"))?;
+
+    let notifier = Arc::new(RecordingNotifier::default());
+    let notifiers: Vec<Arc<dyn Notifier>> = vec![notifier.clone()];
+    let (review, notifications) = manifold::commands::request_review(
+        &db,
+        &notifiers,
+        "spec-notify",
+        "alice@example.com",
+        "bob@example.com",
+    )?;
+    notifications.wait();
+    assert_eq!(*notifier.requested.lock().unwrap(), vec![review.id.clone()]);
+    assert!(db.get_review(&review.id)?.is_some());
+
+    // A failed notification doesn't undo the request
+    let failing: Vec<Arc<dyn Notifier>> = vec![Arc::new(FailingNotifier)];
+    let (review, notifications) = manifold::commands::request_review(
+        &db,
+        &failing,
+        "spec-notify",
+        "alice@example.com",
+        "bob@example.com",
+    )?;
+    notifications.wait();
+    assert!(db.get_review(&review.id)?.is_some());
+
+    // Unknown specs are rejected before anyone is notified
+    let notifier = Arc::new(RecordingNotifier::default());
+    let notifiers: Vec<Arc<dyn Notifier>> = vec![notifier.clone()];
+    assert!(manifold::commands::request_review(
+        &db,
+        &notifiers,
+        "spec-missing",
+        "alice@example.com",
+        "bob@example.com",
+    )
+    .is_err());
+    assert!(notifier.requested.lock().unwrap().is_empty());
+
+    Ok(())
+}
+
+/// Notifier that takes a while before it succeeds
+struct SlowNotifier(RecordingNotifier);
+
+impl Notifier for SlowNotifier {
+    fn review_requested(&self, review: &manifold::collab::Review) -> Result<()> {
+        std::thread::sleep(Duration::from_millis(500));
+        self.0.review_requested(review)
+    }
+}
+
+#[test]
+fn test_review_request_does_not_wait_for_notifiers() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+    db.insert_spec(&create_test_spec("spec-slow", "test-project", "Slow"))?;
+
+    let slow = Arc::new(SlowNotifier(RecordingNotifier::default()));
+    let notifiers: Vec<Arc<dyn Notifier>> = vec![slow.clone(), Arc::new(FailingNotifier)];
+    let started = Instant::now();
+    let (review, notifications) = manifold::commands::request_review(
+        &db,
+        &notifiers,
+        "spec-slow",
+        "alice@example.com",
+        "bob@example.com",
+    )?;
+    assert!(started.elapsed() < Duration::from_millis(500));
+    assert!(db.get_review(&review.id)?.is_some());
+
+    notifications.wait();
+    assert_eq!(*slow.0.requested.lock().unwrap(), vec![review.id]);
+
+    Ok(())
+}
+
+#[test]
+fn test_smtp_notifier_mails_reviewer() -> Result<()> {
+    use std::io::{BufRead, BufReader, Write};
+
+    // A minimal relay that accepts one message and hands back what it got
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let port = listener.local_addr()?.port();
+    let relay = std::thread::spawn(move || -> std::io::Result<String> {
+        let (stream, _) = listener.accept()?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut writer = stream;
+        writer.write_all(b"220 test relay\r\n")?;
+        let mut received = String::new();
+        let mut in_data = false;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            received.push_str(&line);
+            if in_data {
+                if line == ".\r\n" {
+                    in_data = false;
+                    writer.write_all(b"250 queued\r\n")?;
+                }
+                continue;
+            }
+            let reply: &[u8] = match line.get(..4).unwrap_or("").to_ascii_uppercase().as_str() {
+                "DATA" => {
+                    in_data = true;
+                    b"354 go ahead\r\n"
+                }
+                "QUIT" => {
+                    writer.write_all(b"221 bye\r\n")?;
+                    break;
+                }
+                _ => b"250 ok\r\n",
+            };
+            writer.write_all(reply)?;
+        }
+        Ok(received)
+    });
+
+    let notifier = SmtpNotifier {
+        config: SmtpConfig {
+            server: format!("127.0.0.1:{}", port),
+            from: "manifold@example.com".to_string(),
+            security: SmtpSecurity::None,
+            username: None,
+            password: None,
+        },
+    };
+    let review = ReviewManager::create_review(
+        "spec-mail".to_string(),
+        "alice@example.com".to_string(),
+        "bob@example.com".to_string(),
+    );
+    notifier.review_requested(&review)?;
+
+    let received = relay.join().unwrap()?;
+    assert!(
+        received.contains("RCPT TO:<bob@example.com>"),
+        "{}",
+        received
+    );
+    assert!(
+        received.contains("Subject: Review requested: spec-mail"),
+        "{}",
+        received
+    );
+    assert!(
+        received.contains(&format!("manifold review approve {}", review.id)),
+        "{}",
+        received
+    );
+
+    // Reviewers without an address are reported, not mailed
+    let review = ReviewManager::create_review(
+        "spec-mail".to_string(),
+        "alice".to_string(),
+        "bob".to_string(),
+    );
+    assert!(notifier.review_requested(&review).is_err());
+
+    Ok(())
+}
+
 #[test]
 fn test_multiple_conflicts() -> Result<()> {
     let base = create_test_spec("test-spec", "test-project", "Original");
@@ -270,7 +550,7 @@ fn test_multiple_conflicts() -> Result<()> {
     let conflicts = ConflictResolver::detect_conflicts(&local, &remote, Some(&base))?;
 
     // Only name field differs
-    assert!(conflicts.len() >= 1);
+    assert!(!conflicts.is_empty());
 
     Ok(())
 }
@@ -380,3 +660,969 @@ fn test_conflict_with_resolutions() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_sync_config_persisted_and_reloaded() -> Result<()> {
+    let (_temp, paths, _db) = setup()?;
+
+    let repo_path = paths.sync_dir();
+    let sync_config = manifold::commands::init_sync(
+        &paths,
+        repo_path.clone(),
+        Some("https://example.com/specs.git".to_string()),
+    )?;
+
+    assert!(repo_path.join(".git").exists());
+    assert_eq!(sync_config.repo_path, repo_path);
+
+    // Persisted into config.toml
+    let config = manifold::config::load_config_from(&paths.config)?;
+    let persisted = config.sync.expect("sync config should be persisted");
+    assert_eq!(persisted.repo_path, repo_path);
+    assert_eq!(
+        persisted.remote_url.as_deref(),
+        Some("https://example.com/specs.git")
+    );
+
+    // Reloaded by sync commands
+    let loaded = manifold::commands::load_sync_config(&paths)?;
+    assert_eq!(loaded.repo_path, repo_path);
+    assert_eq!(loaded.commit_author, sync_config.commit_author);
+
+    Ok(())
+}
+
+#[test]
+fn test_sync_status_uses_persisted_remote() -> Result<()> {
+    let (temp, paths, _db) = setup()?;
+
+    // `sync init` with a custom repo path and remote
+    let repo_path = temp.path().join("team-specs");
+    manifold::commands::init_sync(
+        &paths,
+        repo_path.clone(),
+        Some("git@example.com:team/specs.git".to_string()),
+    )?;
+
+    // `sync status` loads the persisted settings rather than the default dir
+    let loaded = manifold::commands::load_sync_config(&paths)?;
+    assert_eq!(loaded.repo_path, repo_path);
+    assert_eq!(
+        loaded.remote_url.as_deref(),
+        Some("git@example.com:team/specs.git")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_sync_config_missing_errors() -> Result<()> {
+    let (_temp, paths, _db) = setup()?;
+
+    let err = manifold::commands::load_sync_config(&paths).unwrap_err();
+    assert!(err.to_string().contains("Sync is not initialized"));
+
+    Ok(())
+}
+
+#[test]
+fn test_conflict_list_json_output() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    let spec = create_test_spec("spec-json", "test-project", "Test Spec");
+    db.insert_spec(&spec)?;
+
+    let conflict = Conflict {
+        id: "conflict-json".to_string(),
+        spec_id: "spec-json".to_string(),
+        field_path: "name".to_string(),
+        local_value: serde_json::Value::String("Local".to_string()),
+        remote_value: serde_json::Value::String("Remote".to_string()),
+        base_value: None,
+        detected_at: chrono::Utc::now().timestamp(),
+        status: ConflictStatus::Unresolved,
+    };
+    db.save_conflict(&conflict)?;
+
+    // Same serialization as `conflicts list --json`
+    let loaded = db.get_conflicts("spec-json")?;
+    let json: serde_json::Value = serde_json::from_str(&serde_json::to_string_pretty(&loaded)?)?;
+
+    assert_eq!(json[0]["field_path"], "name");
+    assert_eq!(json[0]["status"], "unresolved");
+
+    // Same shape as `conflicts resolve --json`
+    let (value, status) =
+        ConflictResolver::resolve_conflict(&conflict, ResolutionStrategy::Theirs, None)?;
+    let resolved = ConflictResolver::resolution_json(&conflict, "theirs", &value, &status);
+
+    assert_eq!(resolved["spec_id"], "spec-json");
+    assert_eq!(resolved["resolved_value"], "Remote");
+    assert_eq!(resolved["status"], "resolved_remote");
+
+    Ok(())
+}
+
+#[test]
+fn test_failed_resolution_leaves_conflict_unresolved() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    let spec = create_test_spec("spec-atomic", "test-project", "Test Spec");
+    db.insert_spec(&spec)?;
+    let conflict = Conflict {
+        id: "conflict-atomic".to_string(),
+        spec_id: "spec-atomic".to_string(),
+        field_path: "name".to_string(),
+        local_value: serde_json::Value::String("Local".to_string()),
+        remote_value: serde_json::Value::String("Remote".to_string()),
+        base_value: None,
+        detected_at: chrono::Utc::now().timestamp(),
+        status: ConflictStatus::Unresolved,
+    };
+    db.save_conflict(&conflict)?;
+
+    // A name must be a string, so the patched spec fails to deserialize
+    let result = manifold::commands::apply_conflict_resolution(
+        &db,
+        &manifold::config::Config::default(),
+        &conflict,
+        ResolutionStrategy::Manual,
+        &serde_json::json!(42),
+        &ConflictStatus::ResolvedManual,
+    );
+    assert!(result.is_err());
+
+    let loaded = db.get_conflict_by_id("conflict-atomic")?.unwrap();
+    assert_eq!(loaded.status, ConflictStatus::Unresolved);
+    assert!(db.get_resolved_conflicts("spec-atomic")?.is_empty());
+    assert_eq!(db.get_spec_data("spec-atomic")?.unwrap().name, "Test Spec");
+
+    // The same conflict can then be resolved properly
+    manifold::commands::apply_conflict_resolution(
+        &db,
+        &manifold::config::Config::default(),
+        &conflict,
+        ResolutionStrategy::Theirs,
+        &serde_json::json!("Remote"),
+        &ConflictStatus::ResolvedRemote,
+    )?;
+    assert_eq!(db.get_spec_data("spec-atomic")?.unwrap().name, "Remote");
+    assert_eq!(db.get_resolved_conflicts("spec-atomic")?.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_failed_batch_resolution_rolls_back_every_conflict() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    db.insert_spec(&create_test_spec("spec-batch", "test-project", "Test Spec"))?;
+    let conflict = |id: &str, field: &str| Conflict {
+        id: id.to_string(),
+        spec_id: "spec-batch".to_string(),
+        field_path: field.to_string(),
+        local_value: serde_json::Value::String("Local".to_string()),
+        remote_value: serde_json::Value::String("Remote".to_string()),
+        base_value: None,
+        detected_at: chrono::Utc::now().timestamp(),
+        status: ConflictStatus::Unresolved,
+    };
+    let project = conflict("conflict-project", "project");
+    let name = conflict("conflict-name", "name");
+    db.save_conflict(&project)?;
+    db.save_conflict(&name)?;
+
+    // The second value cannot be applied, so the first must not stick either
+    let result = manifold::commands::apply_conflict_resolutions(
+        &db,
+        "alice",
+        &[
+            (
+                project.clone(),
+                ResolutionStrategy::Theirs,
+                serde_json::json!("Remote"),
+                ConflictStatus::ResolvedRemote,
+            ),
+            (
+                name.clone(),
+                ResolutionStrategy::Manual,
+                serde_json::json!(42),
+                ConflictStatus::ResolvedManual,
+            ),
+        ],
+    );
+    assert!(result.is_err());
+
+    for id in ["conflict-project", "conflict-name"] {
+        let loaded = db.get_conflict_by_id(id)?.unwrap();
+        assert_eq!(loaded.status, ConflictStatus::Unresolved);
+    }
+    assert!(db.get_resolved_conflicts("spec-batch")?.is_empty());
+    assert_eq!(
+        db.get_spec_data("spec-batch")?.unwrap().project,
+        "test-project"
+    );
+
+    manifold::commands::apply_conflict_resolutions(
+        &db,
+        "alice",
+        &[
+            (
+                project,
+                ResolutionStrategy::Theirs,
+                serde_json::json!("Remote"),
+                ConflictStatus::ResolvedRemote,
+            ),
+            (
+                name,
+                ResolutionStrategy::Theirs,
+                serde_json::json!("Remote"),
+                ConflictStatus::ResolvedRemote,
+            ),
+        ],
+    )?;
+    let spec = db.get_spec_data("spec-batch")?.unwrap();
+    assert_eq!(
+        (spec.project.as_str(), spec.name.as_str()),
+        ("Remote", "Remote")
+    );
+    let resolved = db.get_resolved_conflicts("spec-batch")?;
+    assert_eq!(resolved.len(), 2);
+    assert!(resolved
+        .iter()
+        .all(|(_, r)| r.as_ref().unwrap().resolved_by == "alice"));
+
+    Ok(())
+}
+
+#[test]
+fn test_resolved_conflict_cannot_be_resolved_again() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    db.insert_spec(&create_test_spec("spec-twice", "test-project", "Test Spec"))?;
+    let conflict = Conflict {
+        id: "conflict-twice".to_string(),
+        spec_id: "spec-twice".to_string(),
+        field_path: "name".to_string(),
+        local_value: serde_json::Value::String("Local".to_string()),
+        remote_value: serde_json::Value::String("Remote".to_string()),
+        base_value: None,
+        detected_at: chrono::Utc::now().timestamp(),
+        status: ConflictStatus::Unresolved,
+    };
+    db.save_conflict(&conflict)?;
+
+    let config = manifold::config::Config::default();
+    manifold::commands::apply_conflict_resolution(
+        &db,
+        &config,
+        &conflict,
+        ResolutionStrategy::Theirs,
+        &serde_json::json!("Remote"),
+        &ConflictStatus::ResolvedRemote,
+    )?;
+    let patches = db
+        .get_spec_data("spec-twice")?
+        .unwrap()
+        .history
+        .patches
+        .len();
+
+    // Even with the stale unresolved copy in hand, the second attempt is
+    // rejected and neither the spec nor the audit record changes
+    let err = manifold::commands::apply_conflict_resolution(
+        &db,
+        &config,
+        &conflict,
+        ResolutionStrategy::Ours,
+        &serde_json::json!("Local"),
+        &ConflictStatus::ResolvedLocal,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("already resolved"), "{}", err);
+
+    let spec = db.get_spec_data("spec-twice")?.unwrap();
+    assert_eq!(spec.name, "Remote");
+    assert_eq!(spec.history.patches.len(), patches);
+    let resolved = db.get_resolved_conflicts("spec-twice")?;
+    assert_eq!(resolved.len(), 1);
+    assert_eq!(resolved[0].0.status, ConflictStatus::ResolvedRemote);
+    assert_eq!(resolved[0].1.as_ref().unwrap().strategy, "theirs");
+
+    Ok(())
+}
+
+#[test]
+fn test_get_conflict_by_id() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    let spec = create_test_spec("spec-lookup", "test-project", "Test Spec");
+    db.insert_spec(&spec)?;
+
+    for (id, field) in [("conflict-a", "name"), ("conflict-b", "project")] {
+        db.save_conflict(&Conflict {
+            id: id.to_string(),
+            spec_id: "spec-lookup".to_string(),
+            field_path: field.to_string(),
+            local_value: serde_json::Value::String("Local".to_string()),
+            remote_value: serde_json::Value::String("Remote".to_string()),
+            base_value: None,
+            detected_at: chrono::Utc::now().timestamp(),
+            status: ConflictStatus::Unresolved,
+        })?;
+    }
+
+    let found = db
+        .get_conflict_by_id("conflict-b")?
+        .expect("conflict exists");
+    assert_eq!(found.id, "conflict-b");
+    assert_eq!(found.field_path, "project");
+
+    // Resolved conflicts are still reachable by id
+    db.update_conflict_status("conflict-b", &ConflictStatus::ResolvedLocal)?;
+    let resolved = db
+        .get_conflict_by_id("conflict-b")?
+        .expect("conflict exists");
+    assert_eq!(resolved.status, ConflictStatus::ResolvedLocal);
+
+    assert!(db.get_conflict_by_id("conflict-missing")?.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_malformed_conflict_row_is_a_contextual_error() -> Result<()> {
+    use manifold::collab::ConflictResolution;
+
+    let (_temp, paths, db) = setup()?;
+    db.insert_spec(&create_test_spec(
+        "spec-corrupt",
+        "test-project",
+        "Test Spec",
+    ))?;
+
+    for id in ["c-local", "c-status", "c-resolved"] {
+        db.save_conflict(&Conflict {
+            id: id.to_string(),
+            spec_id: "spec-corrupt".to_string(),
+            field_path: "name".to_string(),
+            local_value: serde_json::Value::String("Local".to_string()),
+            remote_value: serde_json::Value::String("Remote".to_string()),
+            base_value: None,
+            detected_at: 1_700_000_000,
+            status: ConflictStatus::Unresolved,
+        })?;
+    }
+    db.record_conflict_resolution(
+        &ConflictStatus::ResolvedRemote,
+        &ConflictResolution {
+            conflict_id: "c-resolved".to_string(),
+            strategy: ResolutionStrategy::Theirs.to_string(),
+            value: serde_json::Value::String("Remote".to_string()),
+            resolved_by: "alice".to_string(),
+            resolved_at: 1_700_000_000,
+        },
+    )?;
+
+    // Corrupt the rows behind manifold's back
+    let conn = rusqlite::Connection::open(&paths.db_file)?;
+    conn.execute(
+        "UPDATE conflicts SET local_value = 'not json' WHERE id = 'c-local'",
+        [],
+    )?;
+    conn.execute(
+        "UPDATE conflicts SET status = 'resolved_sideways' WHERE id = 'c-status'",
+        [],
+    )?;
+    conn.execute(
+        "UPDATE conflict_resolutions SET value = 'not json' WHERE conflict_id = 'c-resolved'",
+        [],
+    )?;
+    drop(conn);
+
+    let err = db.get_conflict_by_id("c-local").unwrap_err().to_string();
+    assert!(
+        err.contains("corrupt column local_value for conflict c-local"),
+        "unexpected error: {}",
+        err
+    );
+
+    // An unknown status is an error, not a silently reopened conflict
+    let err = db.get_conflict_by_id("c-status").unwrap_err().to_string();
+    assert!(
+        err.contains("corrupt column status for conflict c-status"),
+        "unexpected error: {}",
+        err
+    );
+    assert!(db.get_all_conflicts().is_err());
+
+    // With the status repaired, the corrupt resolution value still surfaces
+    rusqlite::Connection::open(&paths.db_file)?.execute(
+        "UPDATE conflicts SET status = 'unresolved' WHERE id = 'c-status'",
+        [],
+    )?;
+    let err = db
+        .get_resolved_conflicts("spec-corrupt")
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("corrupt column value for conflict resolution c-resolved"),
+        "unexpected error: {}",
+        err
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_review_inbox_shows_pending_reviews_for_reviewer() -> Result<()> {
+    use manifold::commands::review_inbox;
+
+    let (_temp, _paths, db) = setup()?;
+    for id in ["spec-a", "spec-b"] {
+        db.insert_spec(&create_test_spec(id, "test-project", "Test Spec"))?;
+    }
+
+    let request = |spec_id: &str, reviewer: &str, requested_at: i64| {
+        let mut review = ReviewManager::create_review(
+            spec_id.to_string(),
+            "alice@example.com".to_string(),
+            reviewer.to_string(),
+        );
+        review.requested_at = requested_at;
+        review
+    };
+
+    let newer = request("spec-a", "bob", 200);
+    let older = request("spec-b", "bob", 100);
+    let mut done = request("spec-a", "bob", 50);
+    ReviewManager::approve(&mut done, "bob", None)?;
+    let someone_else = request("spec-b", "carol", 10);
+    for review in [&newer, &older, &done, &someone_else] {
+        db.save_review(review)?;
+    }
+
+    assert_eq!(db.get_all_reviews()?.len(), 4);
+
+    let inbox: Vec<String> = review_inbox(&db, "bob")?
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+    assert_eq!(inbox, vec![older.id, newer.id]);
+
+    assert_eq!(review_inbox(&db, "carol")?.len(), 1);
+    assert!(review_inbox(&db, "dave")?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_conflicts_grouped_by_spec() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    for id in ["spec-one", "spec-two"] {
+        db.insert_spec(&create_test_spec(id, "test-project", "Test Spec"))?;
+    }
+    let conflicts = [
+        ("c-1", "spec-two", ConflictStatus::Unresolved),
+        ("c-2", "spec-one", ConflictStatus::Unresolved),
+        ("c-3", "spec-one", ConflictStatus::ResolvedLocal),
+        ("c-4", "spec-two", ConflictStatus::ResolvedRemote),
+        ("c-5", "spec-two", ConflictStatus::Unresolved),
+    ];
+    for (i, (id, spec_id, status)) in conflicts.into_iter().enumerate() {
+        db.save_conflict(&Conflict {
+            id: id.to_string(),
+            spec_id: spec_id.to_string(),
+            field_path: "name".to_string(),
+            local_value: serde_json::Value::String("Local".to_string()),
+            remote_value: serde_json::Value::String("Remote".to_string()),
+            base_value: None,
+            detected_at: i as i64,
+            status,
+        })?;
+    }
+
+    let groups = ConflictResolver::group_by_spec(db.get_all_conflicts()?, false);
+    let summary: Vec<(&str, usize, usize, Vec<&str>)> = groups
+        .iter()
+        .map(|g| {
+            let ids = g.conflicts.iter().map(|c| c.id.as_str()).collect();
+            (g.spec_id.as_str(), g.unresolved, g.resolved, ids)
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("spec-one", 1, 1, vec!["c-2", "c-3"]),
+            ("spec-two", 2, 1, vec!["c-1", "c-4", "c-5"]),
+        ]
+    );
+
+    // Counts survive the filter; resolved conflicts don't
+    let groups = ConflictResolver::group_by_spec(db.get_all_conflicts()?, true);
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[1].resolved, 1);
+    let ids: Vec<&str> = groups[1].conflicts.iter().map(|c| c.id.as_str()).collect();
+    assert_eq!(ids, vec!["c-1", "c-5"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_resolved_conflicts_export_section() -> Result<()> {
+    use manifold::collab::ConflictResolution;
+    use manifold::export::MarkdownRenderer;
+
+    let (_temp, _paths, db) = setup()?;
+    db.insert_spec(&create_test_spec("spec-audit", "test-project", "Test Spec"))?;
+
+    for (id, field) in [("c-name", "name"), ("c-project", "project")] {
+        db.save_conflict(&Conflict {
+            id: id.to_string(),
+            spec_id: "spec-audit".to_string(),
+            field_path: field.to_string(),
+            local_value: serde_json::Value::String("Local".to_string()),
+            remote_value: serde_json::Value::String("Remote".to_string()),
+            base_value: None,
+            detected_at: 1_700_000_000,
+            status: ConflictStatus::Unresolved,
+        })?;
+    }
+
+    let conflict = db.get_conflict_by_id("c-name")?.unwrap();
+    let (value, status) =
+        ConflictResolver::resolve_conflict(&conflict, ResolutionStrategy::Theirs, None)?;
+    db.record_conflict_resolution(
+        &status,
+        &ConflictResolution {
+            conflict_id: conflict.id.clone(),
+            strategy: ResolutionStrategy::Theirs.to_string(),
+            value,
+            resolved_by: "alice".to_string(),
+            resolved_at: 1_700_000_000,
+        },
+    )?;
+
+    // Only the resolved conflict is listed
+    let resolved = db.get_resolved_conflicts("spec-audit")?;
+    assert_eq!(resolved.len(), 1);
+
+    let md = MarkdownRenderer::render_resolved_conflicts(&resolved);
+    assert!(md.contains("## Resolved Conflicts"));
+    assert!(
+        md.contains("| `name` | \"Remote\" | theirs | 2023-11-14 22:13 UTC by alice |"),
+        "{}",
+        md
+    );
+    assert!(!md.contains("`project`"));
+
+    Ok(())
+}
+
+#[test]
+fn test_sync_agent_tick_imports_new_remote_spec() -> Result<()> {
+    use manifold::agent::SyncAgent;
+    use std::path::Path;
+    use std::process::Command;
+
+    let (temp, paths, db) = setup()?;
+    let git = |dir: &Path, args: &[&str]| {
+        let output = Command::new("git")
+            .args([
+                "-c",
+                "user.name=Teammate",
+                "-c",
+                "user.email=teammate@example.com",
+            ])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("run git");
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    };
+
+    // A teammate pushes a spec we don't have yet
+    let remote = temp.path().join("remote.git");
+    let teammate = temp.path().join("teammate");
+    fs::create_dir_all(&remote)?;
+    fs::create_dir_all(&teammate)?;
+    git(&remote, &["init", "--bare"]);
+    git(&teammate, &["init", "-b", "main"]);
+    let spec = create_test_spec("spec-remote", "team-project", "From a teammate");
+    fs::write(
+        teammate.join("spec-remote.json"),
+        serde_json::to_string_pretty(&spec)?,
+    )?;
+    git(&teammate, &["add", "spec-remote.json"]);
+    git(&teammate, &["commit", "-m", "Add spec-remote"]);
+    git(&teammate, &["push", remote.to_str().unwrap(), "main"]);
+
+    manifold::commands::init_sync(
+        &paths,
+        temp.path().join("sync"),
+        Some(remote.to_str().unwrap().to_string()),
+    )?;
+
+    let agent = SyncAgent::new(paths.clone(), "origin", "main");
+    let report = agent.tick()?;
+
+    assert_eq!(report.imported, vec!["spec-remote".to_string()]);
+    assert!(report.conflicted.is_empty());
+    let imported = db.get_spec_data("spec-remote")?.expect("spec imported");
+    assert_eq!(imported.name, "From a teammate");
+
+    Ok(())
+}
+
+#[test]
+fn test_sync_agent_tick_writes_agent_event() -> Result<()> {
+    use manifold::agent::SyncAgent;
+
+    let (temp, paths, db) = setup()?;
+    // A local-only sync repo: the pull fails, which is logged too
+    manifold::commands::init_sync(&paths, temp.path().join("sync"), None)?;
+
+    let agent = SyncAgent::new(paths.clone(), "origin", "main");
+    assert_eq!(agent.id(), "sync:origin/main");
+    assert!(agent.tick().is_err());
+
+    let events = db.get_agent_events(Some("sync:origin/main"), None)?;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, "error");
+    assert!(events[0].detail.contains("Git pull failed"));
+    assert!(db
+        .get_agent_events(Some("sync:other/main"), None)?
+        .is_empty());
+    assert!(db
+        .get_agent_events(None, Some(events[0].timestamp + 1))?
+        .is_empty());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_agents_share_pooled_connections_concurrently() -> Result<()> {
+    use manifold::agent::AgentManager;
+    use std::sync::{Arc, Mutex};
+
+    let (_temp, paths, db) = setup()?;
+    let errors = Arc::new(Mutex::new(Vec::new()));
+
+    let mut agents = AgentManager::new(&paths, 2);
+    for id in ["agent-a", "agent-b"] {
+        let errors = Arc::clone(&errors);
+        agents.spawn(id, Duration::from_millis(5), move |db| {
+            // Slow ticks so the two agents' writes interleave
+            let now = chrono::Utc::now().timestamp();
+            let result = db.log_agent_event(id, "tick", "start", now).and_then(|()| {
+                std::thread::sleep(Duration::from_millis(2));
+                db.log_agent_event(id, "tick", "end", now)
+            });
+            if let Err(e) = &result {
+                errors.lock().unwrap().push(format!("{}: {:#}", id, e));
+            }
+            result
+        });
+    }
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let pool = Arc::clone(agents.pool());
+    drop(agents);
+
+    assert!(
+        errors.lock().unwrap().is_empty(),
+        "{:?}",
+        errors.lock().unwrap()
+    );
+    assert!(pool.size() <= 2);
+    for id in ["agent-a", "agent-b"] {
+        assert!(
+            db.get_agent_events(Some(id), None)?.len() >= 3,
+            "{} barely ran",
+            id
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_sync_clone_imports_specs_from_bare_repo() -> Result<()> {
+    use std::path::Path;
+    use std::process::Command;
+
+    let (temp, paths, db) = setup()?;
+    let git = |dir: &Path, args: &[&str]| {
+        let output = Command::new("git")
+            .args([
+                "-c",
+                "user.name=Teammate",
+                "-c",
+                "user.email=teammate@example.com",
+            ])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("run git");
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    };
+
+    // An existing team repository with one spec
+    let remote = temp.path().join("remote.git");
+    let teammate = temp.path().join("teammate");
+    fs::create_dir_all(&remote)?;
+    fs::create_dir_all(&teammate)?;
+    git(&remote, &["init", "--bare", "-b", "main"]);
+    git(&teammate, &["init", "-b", "main"]);
+    let spec = create_test_spec("spec-team", "team-project", "Shared spec");
+    fs::write(
+        teammate.join("spec-team.json"),
+        serde_json::to_string_pretty(&spec)?,
+    )?;
+    git(&teammate, &["add", "spec-team.json"]);
+    git(&teammate, &["commit", "-m", "Add spec-team"]);
+    git(&teammate, &["push", remote.to_str().unwrap(), "main"]);
+
+    let repo_path = temp.path().join("clone");
+    let url = remote.to_str().unwrap();
+    let (sync_config, report) = manifold::commands::clone_sync(&paths, url, repo_path.clone())?;
+
+    assert!(repo_path.join("spec-team.json").exists());
+    assert_eq!(report.imported, vec!["spec-team".to_string()]);
+    assert!(report.conflicted.is_empty());
+    let imported = db.get_spec_data("spec-team")?.expect("spec imported");
+    assert_eq!(imported.name, "Shared spec");
+
+    let loaded = manifold::commands::load_sync_config(&paths)?;
+    assert_eq!(loaded.repo_path, sync_config.repo_path);
+    assert_eq!(loaded.remote_url.as_deref(), Some(url));
+
+    Ok(())
+}
+
+#[test]
+fn test_merge_specs_records_overlapping_conflicts() -> Result<()> {
+    use manifold::commands::{merge_specs, MergeOptions};
+    use manifold::models::{Priority, Requirement};
+
+    let (_temp, _paths, db) = setup()?;
+    let requirement = |id: &str, shall: &str, tags: &[&str]| Requirement {
+        id: id.to_string(),
+        capability: "auth".to_string(),
+        title: format!("Requirement {}", id),
+        shall: shall.to_string(),
+        rationale: None,
+        priority: Priority::Must,
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        scenarios: vec![],
+        history: vec![],
+        verification: Default::default(),
+    };
+
+    let mut a = create_test_spec("spec-a", "test-project", "Draft A");
+    a.requirements.push(requirement(
+        "req-1",
+        "The system SHALL log in with passwords",
+        &[],
+    ));
+    a.requirements.push(requirement(
+        "req-2",
+        "The system SHALL log out",
+        &["session"],
+    ));
+    let mut b = create_test_spec("spec-b", "test-project", "Draft B");
+    b.requirements.push(requirement(
+        "req-1",
+        "The system SHALL log in with passkeys",
+        &[],
+    ));
+    b.requirements.push(requirement(
+        "req-2",
+        "The system SHALL log out",
+        &["security"],
+    ));
+    b.requirements
+        .push(requirement("req-3", "The system SHALL lock accounts", &[]));
+    db.insert_spec(&a)?;
+    db.insert_spec(&b)?;
+
+    let options = MergeOptions {
+        name: Some("Merged".to_string()),
+        ..Default::default()
+    };
+    let (merged, conflicts) = merge_specs(&db, "tester", "spec-a", "spec-b", &options)?;
+
+    assert_ne!(merged.spec_id, "spec-a");
+    assert_eq!(merged.name, "Merged");
+    let ids: Vec<&str> = merged.requirements.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(ids, vec!["req-1", "req-2", "req-3"]);
+    // The overlap keeps the first spec's wording until resolved
+    assert_eq!(
+        merged.requirements[0].shall,
+        "The system SHALL log in with passwords"
+    );
+    // Tags alone never conflict
+    assert_eq!(merged.requirements[1].tags, vec!["session", "security"]);
+
+    assert_eq!(conflicts.len(), 1);
+    let stored = db.get_conflicts(&merged.spec_id)?;
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].field_path, "requirements/req-1");
+    assert_eq!(
+        stored[0].remote_value["shall"],
+        "The system SHALL log in with passkeys"
+    );
+
+    let saved = db.get_spec_data(&merged.spec_id)?.unwrap();
+    assert_eq!(saved.requirements.len(), 3);
+    assert_eq!(db.get_spec_data("spec-b")?.unwrap().requirements.len(), 3);
+
+    // In place updates the first spec
+    let in_place = MergeOptions {
+        in_place: true,
+        ..Default::default()
+    };
+    let (merged, _) = merge_specs(&db, "tester", "spec-a", "spec-b", &in_place)?;
+    assert_eq!(merged.spec_id, "spec-a");
+    assert_eq!(db.get_spec_data("spec-a")?.unwrap().requirements.len(), 3);
+    assert_eq!(db.get_conflicts("spec-a")?.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_manual_value_parses_json_or_falls_back_to_string() -> Result<()> {
+    use serde_json::{json, Value};
+
+    // Valid JSON keeps its type
+    assert_eq!(ConflictResolver::parse_manual_value("42"), json!(42));
+    assert_eq!(ConflictResolver::parse_manual_value("true"), json!(true));
+    assert_eq!(
+        ConflictResolver::parse_manual_value(r#"{"id": "req-1", "title": "Login"}"#),
+        json!({"id": "req-1", "title": "Login"})
+    );
+    assert_eq!(
+        ConflictResolver::parse_manual_value(r#""quoted""#),
+        json!("quoted")
+    );
+
+    // Anything else is taken verbatim as a string
+    assert_eq!(
+        ConflictResolver::parse_manual_value("Merged Name"),
+        json!("Merged Name")
+    );
+    assert_eq!(
+        ConflictResolver::parse_manual_value("{not json"),
+        json!("{not json")
+    );
+
+    // Blank input deletes
+    assert_eq!(ConflictResolver::parse_manual_value("   "), Value::Null);
+
+    // The parsed value feeds a manual resolution unchanged
+    let conflict = Conflict {
+        id: "test-conflict".to_string(),
+        spec_id: "test-spec".to_string(),
+        field_path: "name".to_string(),
+        local_value: json!("Local"),
+        remote_value: json!("Remote"),
+        base_value: Some(json!("Base")),
+        detected_at: 0,
+        status: ConflictStatus::Unresolved,
+    };
+    let (value, status) = ConflictResolver::resolve_conflict(
+        &conflict,
+        ResolutionStrategy::Manual,
+        Some(ConflictResolver::parse_manual_value("Merged Name")),
+    )?;
+    assert_eq!(value, json!("Merged Name"));
+    assert_eq!(status, ConflictStatus::ResolvedManual);
+
+    Ok(())
+}
+
+fn patch(timestamp: i64, path: &str, summary: &str) -> PatchEntry {
+    PatchEntry {
+        timestamp,
+        actor: "alice".to_string(),
+        op: "replace".to_string(),
+        path: path.to_string(),
+        summary: summary.to_string(),
+    }
+}
+
+#[test]
+fn test_replay_reconstructs_prior_name_from_snapshot() -> Result<()> {
+    let mut spec = SpecData::new(
+        "spec-replay".to_string(),
+        "replay".to_string(),
+        "Ledger Service".to_string(),
+        Boundary::Work,
+    );
+    spec.history.created_at = 1_000;
+    spec.history.updated_at = 1_000;
+    let snapshot = SpecSnapshot {
+        id: 1,
+        spec_id: spec.spec_id.clone(),
+        label: Some("before rename".to_string()),
+        created_by: "alice".to_string(),
+        created_at: 1_500,
+        data: spec.clone(),
+    };
+
+    // Renamed at 2000, then advanced to design at 3000
+    spec.name = "Accounts Service".to_string();
+    spec.history
+        .patches
+        .push(patch(2_000, "/name", "Renamed to Accounts Service"));
+    spec.stage = WorkflowStage::Design;
+    spec.stages_completed = vec![WorkflowStage::Requirements];
+    spec.history.updated_at = 3_000;
+    let events = vec![WorkflowEventRow {
+        id: 1,
+        spec_id: spec.spec_id.clone(),
+        stage: "design".to_string(),
+        event: "transition:requirements:design".to_string(),
+        actor: "alice".to_string(),
+        timestamp: 3_000,
+        details: None,
+    }];
+    let snapshots = [snapshot];
+
+    // Before the rename, the snapshot has the old name and nothing is missing
+    let before = replay(&spec, &snapshots, &events, 1_800)?;
+    assert_eq!(before.snapshot_id, Some(1));
+    assert_eq!(before.spec.name, "Ledger Service");
+    assert_eq!(before.spec.stage, WorkflowStage::Requirements);
+    assert!(before.spec.history.patches.is_empty());
+    assert!(before.notes.is_empty());
+
+    // Between rename and advance, only the stage changed since, so the
+    // current spec is rewound exactly
+    let between = replay(&spec, &snapshots, &events, 2_500)?;
+    assert_eq!(between.snapshot_id, None);
+    assert_eq!(between.spec.name, "Accounts Service");
+    assert_eq!(between.spec.stage, WorkflowStage::Requirements);
+    assert!(between.spec.stages_completed.is_empty());
+    assert_eq!(between.spec.history.updated_at, 2_000);
+    assert!(between.notes.is_empty());
+
+    // Without the snapshot, the rename can't be undone and says so
+    let unsnapshotted = replay(&spec, &[], &events, 1_800)?;
+    assert_eq!(unsnapshotted.spec.name, "Accounts Service");
+    assert!(unsnapshotted.notes[0].contains("1 change(s)"));
+    assert!(unsnapshotted.notes[1].contains("/name"));
+
+    assert!(replay(&spec, &snapshots, &events, 500).is_err());
+    Ok(())
+}
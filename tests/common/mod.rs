@@ -0,0 +1,35 @@
+// Fixtures shared by the integration test suites
+
+use anyhow::Result;
+use manifold::config::ManifoldPaths;
+use manifold::db::Database;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// The standard manifold layout under `root`
+#[allow(dead_code)]
+pub fn paths_under(root: &Path) -> ManifoldPaths {
+    ManifoldPaths {
+        root: root.to_path_buf(),
+        config: root.join("config.toml"),
+        db: root.join("db"),
+        db_file: root.join("db/manifold.db"),
+        schemas: root.join("schemas"),
+        exports: root.join("exports"),
+        cache: root.join("cache"),
+    }
+}
+
+/// Setup test environment
+#[allow(dead_code)]
+pub fn setup() -> Result<(TempDir, ManifoldPaths, Database)> {
+    let temp_dir = TempDir::new()?;
+
+    let paths = paths_under(temp_dir.path());
+
+    fs::create_dir_all(&paths.db)?;
+    let db = Database::init(&paths)?;
+
+    Ok((temp_dir, paths, db))
+}
@@ -0,0 +1,440 @@
+// Integration tests for spec export
+// Tests HTML rendering and the optional PDF backend
+
+use anyhow::Result;
+use manifold::export::{
+    self, AsciiDocRenderer, DotRenderer, HtmlRenderer, MarkdownRenderer, OpenApiCommentsRenderer,
+    SpecSection,
+};
+use manifold::models::{
+    Boundary, PatchEntry, Priority, Requirement, Scenario, SpecData, Task, TaskStatus,
+};
+use serde::Deserialize;
+
+fn create_test_spec() -> SpecData {
+    let mut spec = SpecData::new(
+        "test-spec".to_string(),
+        "test-project".to_string(),
+        "Auth <Service>".to_string(),
+        Boundary::Personal,
+    );
+    spec.requirements.push(Requirement {
+        id: "req-1".to_string(),
+        capability: "auth".to_string(),
+        title: "User Login".to_string(),
+        shall: "The system SHALL authenticate users & sessions".to_string(),
+        rationale: None,
+        priority: Priority::Must,
+        tags: vec![],
+        scenarios: vec![],
+        history: vec![],
+        verification: Default::default(),
+    });
+    spec
+}
+
+#[test]
+fn test_html_export_escapes_content() {
+    let spec = create_test_spec();
+    let html = HtmlRenderer::render_spec(&spec, false);
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<h1>Auth &lt;Service&gt;</h1>"));
+    assert!(html.contains("authenticate users &amp; sessions"));
+    assert!(html.contains("<h2>Requirements</h2>"));
+}
+
+#[test]
+fn test_html_export_to_file() -> Result<()> {
+    let temp = tempfile::TempDir::new()?;
+    let output = temp.path().join("nested/spec.html");
+
+    HtmlRenderer::export_to_file(&create_test_spec(), &output, true)?;
+
+    let html = std::fs::read_to_string(&output)?;
+    assert!(html.contains("<table>"));
+    Ok(())
+}
+
+#[cfg(feature = "pdf")]
+#[test]
+fn test_pdf_export_writes_pdf_header() -> Result<()> {
+    use manifold::export::PdfRenderer;
+
+    let temp = tempfile::TempDir::new()?;
+    let output = temp.path().join("spec.pdf");
+
+    PdfRenderer::export_to_file(&create_test_spec(), &output, false)?;
+
+    let bytes = std::fs::read(&output)?;
+    assert!(!bytes.is_empty());
+    assert!(bytes.starts_with(b"%PDF"));
+    Ok(())
+}
+
+#[test]
+fn test_anonymized_export_strips_assignees() {
+    let mut spec = create_test_spec();
+    spec.tasks.push(Task {
+        id: "task-1".to_string(),
+        requirement_ids: vec!["req-1".to_string()],
+        title: "Build login".to_string(),
+        description: "Login form".to_string(),
+        status: TaskStatus::InProgress,
+        assignee: Some("alice@example.com".to_string()),
+        acceptance: vec![],
+        scenario_ids: vec![],
+    });
+    spec.history.patches.push(PatchEntry {
+        timestamp: 1_700_000_000,
+        actor: "bob@example.com".to_string(),
+        op: "add".to_string(),
+        path: "/tasks/0".to_string(),
+        summary: "Bob assigned the task to Alice".to_string(),
+    });
+
+    let anonymized = export::anonymize(&spec);
+
+    assert!(anonymized.tasks[0].assignee.is_none());
+    assert_eq!(anonymized.history.patches[0].actor, export::ANONYMOUS_ACTOR);
+
+    for output in [
+        MarkdownRenderer::render_spec(&anonymized, false),
+        MarkdownRenderer::render_spec(&anonymized, true),
+        HtmlRenderer::render_spec(&anonymized, true),
+    ] {
+        assert!(!output.contains("alice@example.com"));
+        assert!(!output.contains("bob@example.com"));
+        assert!(!output.contains("Alice"));
+    }
+
+    // The original spec is untouched
+    assert_eq!(spec.tasks[0].assignee.as_deref(), Some("alice@example.com"));
+}
+
+#[test]
+fn test_front_matter_parses_and_contains_stage() -> Result<()> {
+    let mut spec = create_test_spec();
+    spec.name = "Auth: \"v2\" # rollout".to_string();
+    spec.requirements[0].tags = vec!["security".to_string(), "auth".to_string()];
+
+    let temp_dir = tempfile::TempDir::new()?;
+    let path = temp_dir.path().join("spec.md");
+    MarkdownRenderer::export_to_file(&spec, &path, false, true)?;
+    let content = std::fs::read_to_string(&path)?;
+
+    let body = content
+        .strip_prefix("---\n")
+        .expect("front matter opens the file");
+    let (block, rest) = body.split_once("---\n").expect("front matter is closed");
+    assert!(rest.trim_start().starts_with("# Auth"));
+
+    // Every value is a YAML flow scalar/sequence that is also valid JSON
+    let mut fields = std::collections::HashMap::new();
+    for line in block.lines() {
+        let (key, value) = line.split_once(": ").expect("key: value line");
+        let value: serde_json::Value = serde_json::from_str(value)?;
+        fields.insert(key.to_string(), value);
+    }
+
+    assert_eq!(fields["stage"], "requirements");
+    assert_eq!(fields["name"], "Auth: \"v2\" # rollout");
+    assert_eq!(fields["spec_id"], "test-spec");
+    assert_eq!(fields["tags"], serde_json::json!(["auth", "security"]));
+    assert!(fields["created"].as_str().unwrap().ends_with('Z'));
+
+    Ok(())
+}
+
+#[test]
+fn test_multi_export_toc_links_each_spec_heading() {
+    let mut first = create_test_spec();
+    first.name = "Auth Service".to_string();
+    let mut second = create_test_spec();
+    second.spec_id = "auth-v2".to_string();
+    second.name = "Auth Service".to_string();
+    // Collides with the per-spec "Requirements" section slug
+    let mut third = create_test_spec();
+    third.spec_id = "reqs".to_string();
+    third.name = "Requirements!".to_string();
+
+    let md = MarkdownRenderer::render_multi(&[first, second, third], false, true);
+
+    // Map every heading's GitHub anchor to its text, in document order
+    let mut slugger = export::Slugger::default();
+    let mut headings = std::collections::HashMap::new();
+    for line in md.lines().filter(|l| l.starts_with('#')) {
+        let text = line.trim_start_matches('#').trim().to_string();
+        headings.insert(slugger.next(&text), text);
+    }
+
+    let entries: Vec<(String, String)> = md
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once(". [")?;
+            let (text, rest) = rest.split_once("](#")?;
+            let (anchor, _) = rest.split_once(')')?;
+            Some((text.to_string(), anchor.to_string()))
+        })
+        .filter(|(_, anchor)| !anchor.is_empty())
+        .filter(|(text, _)| text.starts_with("Auth") || text.starts_with("Requirements"))
+        .collect();
+
+    let spec_entries: Vec<_> = entries
+        .iter()
+        .filter(|(text, _)| text != "Requirements")
+        .collect();
+    assert_eq!(spec_entries.len(), 3);
+    assert_eq!(spec_entries[0].0, "Auth Service (test-spec)");
+    assert_eq!(spec_entries[1].0, "Auth Service (auth-v2)");
+    for (text, anchor) in spec_entries {
+        assert_eq!(headings.get(anchor), Some(text), "anchor #{}", anchor);
+    }
+
+    // Without --toc the collection has no table of contents
+    let plain = MarkdownRenderer::render_multi(&[create_test_spec()], false, false);
+    assert!(!plain.contains("## Specifications"));
+}
+
+#[test]
+fn test_traceability_matrix_shows_scenario_coverage() {
+    use manifold::models::Scenario;
+
+    let mut spec = create_test_spec();
+    for id in ["sc-1", "sc-2"] {
+        spec.requirements[0].scenarios.push(Scenario {
+            id: id.to_string(),
+            name: format!("Login {}", id),
+            given: vec!["a user".to_string()],
+            when: "they sign in".to_string(),
+            then: vec!["a session starts".to_string()],
+            edge_cases: vec![],
+        });
+    }
+    let task = |scenario_ids: Vec<String>| Task {
+        id: "task-1".to_string(),
+        requirement_ids: vec!["req-1".to_string()],
+        title: "Build login".to_string(),
+        description: "Login form".to_string(),
+        status: TaskStatus::Pending,
+        assignee: None,
+        acceptance: vec![],
+        scenario_ids,
+    };
+
+    // No links, no matrix
+    spec.tasks.push(task(vec![]));
+    assert!(!MarkdownRenderer::render_spec(&spec, false).contains("Traceability Matrix"));
+
+    spec.tasks[0] = task(vec!["req-1/sc-1".to_string()]);
+    let md = MarkdownRenderer::render_spec(&spec, false);
+    assert!(md.contains("### Traceability Matrix"));
+    assert!(md.contains("| req-1/sc-1 | Login sc-1 | task-1 |"));
+    assert!(md.contains("| req-1/sc-2 | Login sc-2 | ⚠️ uncovered |"));
+    assert!(md.contains("**Scenario coverage:** 1/2 scenarios have a task"));
+}
+
+#[test]
+fn test_diff_report_shows_old_and_new_shall() -> Result<()> {
+    use manifold::collab::diff::SpecDiff;
+
+    let before = create_test_spec();
+    let mut after = before.clone();
+    after.spec_id = "test-spec-v2".to_string();
+    after.requirements[0].shall = "The system SHALL authenticate users with MFA".to_string();
+    after.requirements.push(Requirement {
+        id: "req-2".to_string(),
+        title: "Logout".to_string(),
+        shall: "The system SHALL end sessions on logout".to_string(),
+        ..before.requirements[0].clone()
+    });
+    after.tasks.push(Task {
+        id: "task-1".to_string(),
+        requirement_ids: vec!["req-1".to_string()],
+        title: "Build login".to_string(),
+        description: "Login form".to_string(),
+        status: TaskStatus::Pending,
+        assignee: None,
+        acceptance: vec![],
+        scenario_ids: vec![],
+    });
+    // History alone is not a change
+    after.history.updated_at += 60;
+
+    let diff = SpecDiff::between(&before, &after)?;
+    assert_eq!(diff.fields.len(), 1, "{:?}", diff.fields);
+    assert_eq!(diff.fields[0].path, "spec_id");
+    let requirements = &diff.sections[0];
+    assert_eq!(requirements.added.len(), 1);
+    assert_eq!(requirements.changed.len(), 1);
+    assert_eq!(requirements.changed[0].changes[0].path, "shall");
+
+    let md = MarkdownRenderer::render_diff(&diff);
+    assert!(md.contains("# Changes: test-spec → test-spec-v2"));
+    assert!(md.contains("| Requirements | 1 | 0 | 1 |"));
+    assert!(md.contains("| Tasks | 1 | 0 | 0 |"));
+    assert!(md.contains("#### req-1 - User Login"));
+    assert!(md.contains("  - Before: > The system SHALL authenticate users & sessions\n"));
+    assert!(md.contains("  - After: > The system SHALL authenticate users with MFA\n"));
+    assert!(md.contains("- **req-2** Logout"));
+
+    assert!(SpecDiff::between(&before, &before)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_dot_export_is_valid_graph_with_requirement_task_edges() {
+    let mut spec = create_test_spec();
+    spec.requirements[0].title = "User \"Login\"".to_string();
+    spec.tasks.push(Task {
+        id: "task-1".to_string(),
+        requirement_ids: vec!["req-1".to_string(), "req-9".to_string()],
+        title: "Build login form".to_string(),
+        description: "Form".to_string(),
+        status: TaskStatus::Pending,
+        assignee: None,
+        acceptance: vec![],
+        scenario_ids: vec![],
+    });
+
+    let dot = DotRenderer::render_spec(&spec);
+    assert!(dot.starts_with("digraph \"test-spec\" {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert!(dot.contains("\"req-1\" -> \"task-1\";"));
+    assert!(dot.contains("\"req-9\" -> \"task-1\";"));
+    assert!(dot.contains("label=\"req-1\\nUser \\\"Login\\\"\""));
+    assert!(dot.contains("\"req-9\" [label=\"req-9\\n(missing)\""));
+
+    // Every statement line is a quoted node, edge, or graph attribute
+    let statement = regex::Regex::new(
+        r#"^\s*("([^"\\]|\\.)*"( -> "([^"\\]|\\.)*")?( \[.*\])?|[a-z]+=.*|node \[.*\]);$"#,
+    )
+    .unwrap();
+    for line in dot.lines().skip(1) {
+        if line == "}" {
+            continue;
+        }
+        assert!(statement.is_match(line), "not a DOT statement: {}", line);
+    }
+
+    let multi = DotRenderer::render_multi(&[spec]);
+    assert!(multi.contains("subgraph \"cluster_test-spec\" {"));
+    assert!(multi.contains("\"test-spec/req-1\" -> \"test-spec/task-1\";"));
+    assert_eq!(multi.matches('{').count(), multi.matches('}').count());
+}
+
+#[test]
+fn test_asciidoc_export_uses_asciidoc_sections() {
+    let mut spec = create_test_spec();
+    spec.tasks.push(Task {
+        id: "task-1".to_string(),
+        requirement_ids: vec!["req-1".to_string()],
+        title: "Build login form".to_string(),
+        description: "Form".to_string(),
+        status: TaskStatus::Pending,
+        assignee: None,
+        acceptance: vec!["Submits credentials".to_string()],
+        scenario_ids: vec![],
+    });
+
+    let doc = AsciiDocRenderer::render_spec(&spec);
+    assert!(doc.starts_with("= Auth <Service>\n"));
+    assert!(doc.contains("\n== Requirements\n"));
+    assert!(doc.contains("\n=== req-1 - User Login\n"));
+    assert!(doc.contains("IMPORTANT: Priority: must"));
+    assert!(doc.contains("\n== Tasks\n"));
+    assert!(doc.contains("Traces to:: req-1"));
+    assert!(!doc.contains("\n## "), "no Markdown headings");
+
+    // In a collection each spec is a level-1 section
+    let multi = AsciiDocRenderer::render_multi(&[spec]);
+    assert!(multi.contains("\n== Auth <Service>\n"));
+    assert!(multi.contains("\n=== Requirements\n"));
+}
+
+#[test]
+fn test_section_export_renders_only_requested_sections() {
+    let mut spec = create_test_spec();
+    spec.tasks.push(Task {
+        id: "task-1".to_string(),
+        requirement_ids: vec!["req-1".to_string()],
+        title: "Build login form".to_string(),
+        description: "Form".to_string(),
+        status: TaskStatus::Pending,
+        assignee: None,
+        acceptance: vec![],
+        scenario_ids: vec![],
+    });
+
+    let md = MarkdownRenderer::render_sections(&spec, &[SpecSection::Requirements], false);
+    assert!(md.starts_with("# Auth <Service>\n"));
+    assert!(md.contains("## Requirements"));
+    assert!(md.contains("### req-1 - User Login"));
+    assert!(!md.contains("## Tasks"));
+    assert!(!md.contains("Build login form"));
+    assert!(!md.contains("## Change History"));
+
+    // Repeated sections render once each, in the order given
+    let md = MarkdownRenderer::render_sections(
+        &spec,
+        &[
+            SpecSection::Tasks,
+            SpecSection::Decisions,
+            SpecSection::Tasks,
+        ],
+        false,
+    );
+    assert_eq!(md.matches("## Tasks").count(), 1);
+    assert!(md.find("## Tasks") < md.find("## Design Decisions"));
+    assert!(!md.contains("## Requirements"));
+
+    assert!("scenarios".parse::<SpecSection>().is_ok());
+    assert!("history".parse::<SpecSection>().is_err());
+}
+
+#[test]
+fn test_openapi_comments_cover_only_api_requirements() -> Result<()> {
+    let mut spec = create_test_spec();
+    let mut api = spec.requirements[0].clone();
+    api.id = "req-2".to_string();
+    api.title = "Token endpoint".to_string();
+    api.shall = "The API SHALL issue a token: valid for 1h".to_string();
+    api.tags = vec!["API".to_string()];
+    api.scenarios.push(Scenario {
+        id: "sc-1".to_string(),
+        name: "Valid credentials".to_string(),
+        given: vec!["a registered user".to_string()],
+        when: "they POST /token".to_string(),
+        then: vec!["a token is returned".to_string()],
+        edge_cases: vec![],
+    });
+    spec.requirements.push(api);
+
+    let rendered = OpenApiCommentsRenderer::render_spec(&spec)?;
+    assert!(rendered.contains("# test-spec / req-2: Token endpoint"));
+    assert!(
+        !rendered.contains("req-1"),
+        "untagged requirement leaked:\n{}",
+        rendered
+    );
+
+    let docs: Vec<serde_yaml::Value> = serde_yaml::Deserializer::from_str(&rendered)
+        .map(serde_yaml::Value::deserialize)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(docs.len(), 1);
+    let annotation = &docs[0];
+    assert_eq!(
+        annotation["description"].as_str(),
+        Some("The API SHALL issue a token: valid for 1h")
+    );
+    assert_eq!(annotation["x-requirement"].as_str(), Some("req-2"));
+    assert_eq!(
+        annotation["x-acceptance"][0]["when"].as_str(),
+        Some("they POST /token")
+    );
+    assert_eq!(
+        annotation["x-acceptance"][0]["then"][0].as_str(),
+        Some("a token is returned")
+    );
+    Ok(())
+}
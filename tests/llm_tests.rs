@@ -0,0 +1,254 @@
+// Integration tests for the LLM editing session
+// Uses a capturing chat client so no network access is needed
+
+mod common;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use common::setup;
+use manifold::db::Database;
+use manifold::llm::{ChatClient, ChatMessage, LlmSession};
+use manifold::models::{Boundary, Priority, Requirement, SpecData};
+use serde_json::Value;
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+/// Records each request and replies with a canned message
+struct CapturingClient {
+    requests: Arc<Mutex<Vec<Value>>>,
+    reply: String,
+}
+
+#[async_trait]
+impl ChatClient for CapturingClient {
+    async fn complete(&self, request: &Value) -> Result<ChatMessage> {
+        self.requests.lock().unwrap().push(request.clone());
+        Ok(ChatMessage {
+            role: "assistant".to_string(),
+            content: self.reply.clone(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_suggest_sends_lint_warnings() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    let mut spec = SpecData::new(
+        "spec-suggest".to_string(),
+        "llm-project".to_string(),
+        "Suggest".to_string(),
+        Boundary::Personal,
+    );
+    spec.requirements.push(Requirement {
+        id: "req-1".to_string(),
+        capability: "auth".to_string(),
+        title: "Login".to_string(),
+        shall: "Users can log in".to_string(),
+        rationale: None,
+        priority: Priority::Must,
+        tags: vec![],
+        scenarios: vec![],
+        history: vec![],
+        verification: Default::default(),
+    });
+    db.insert_spec(&spec)?;
+
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let client = CapturingClient {
+        requests: requests.clone(),
+        reply: "```json\n{\"suggestions\": [{\"category\": \"weak_shall\", \"target\": \"req-1\", \"suggestion\": \"Use SHALL\"}]}\n```".to_string(),
+    };
+    let session = LlmSession::with_client("spec-suggest".to_string(), db, Box::new(client));
+
+    let suggestions = session.suggest().await?;
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].category, "weak_shall");
+    assert_eq!(suggestions[0].target.as_deref(), Some("req-1"));
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    let prompt = requests[0]["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|m| m["content"].as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    assert!(prompt.contains("req-1: No scenarios defined"));
+    assert!(prompt.contains("req-1: Requirement doesn't use SHALL or MUST"));
+    assert!(prompt.contains("Requirements without tasks: req-1"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_chat_request_uses_configured_sampling() -> Result<()> {
+    use manifold::llm::LlmConfig;
+
+    let (_temp, _paths, db) = setup()?;
+    let spec = SpecData::new(
+        "spec-sampling".to_string(),
+        "llm-project".to_string(),
+        "Sampling".to_string(),
+        Boundary::Personal,
+    );
+    db.insert_spec(&spec)?;
+
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let client = CapturingClient {
+        requests: requests.clone(),
+        reply: "ok".to_string(),
+    };
+    let llm_config = LlmConfig {
+        temperature: 0.1,
+        max_tokens: 800,
+        top_p: 0.9,
+        ..LlmConfig::default()
+    };
+    let mut session = LlmSession::with_client("spec-sampling".to_string(), db, Box::new(client))
+        .with_llm_config(llm_config);
+
+    session.chat("Tighten req-1").await?;
+    session.handle_command("/set temp 0.5").await?;
+    session.chat("Again").await?;
+    assert!(session.handle_command("/set temp 3").await.is_err());
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0]["temperature"], 0.1);
+    assert_eq!(requests[0]["max_tokens"], 800);
+    assert_eq!(requests[0]["top_p"], 0.9);
+    assert_eq!(requests[1]["temperature"], 0.5);
+    assert_eq!(requests[1]["max_tokens"], 800);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_suggest_resolution_prompts_with_all_three_values() -> Result<()> {
+    use manifold::collab::{Conflict, ConflictStatus};
+    use manifold::llm::suggest_resolution;
+    use serde_json::json;
+
+    let conflict = Conflict {
+        id: "conflict-1".to_string(),
+        spec_id: "spec-merge".to_string(),
+        field_path: "requirements.req-1.shall".to_string(),
+        local_value: json!("The system SHALL lock accounts after 3 failures"),
+        remote_value: json!("The system SHALL lock accounts for 15 minutes"),
+        base_value: Some(json!("The system SHALL lock accounts")),
+        detected_at: 0,
+        status: ConflictStatus::Unresolved,
+    };
+
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let client = CapturingClient {
+        requests: requests.clone(),
+        reply: "```json\n\"The system SHALL lock accounts for 15 minutes after 3 failures\"\n```"
+            .to_string(),
+    };
+    let config = manifold::llm::LlmConfig {
+        model: "test-model".to_string(),
+        max_tokens: 700,
+        top_p: 0.8,
+        temperature: 0.9,
+        ..Default::default()
+    };
+    let value = suggest_resolution(&client, &config, &conflict).await?;
+    assert_eq!(
+        value,
+        json!("The system SHALL lock accounts for 15 minutes after 3 failures")
+    );
+
+    let request = requests.lock().unwrap()[0].clone();
+    assert_eq!(request["model"], "test-model");
+    assert_eq!(request["max_tokens"], 700);
+    assert_eq!(request["top_p"], 0.8);
+    // Merges stay deterministic whatever the configured temperature
+    assert_eq!(request["temperature"], 0.0);
+    let prompt = request["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|m| m["content"].as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(prompt.contains("requirements.req-1.shall"));
+    assert!(prompt.contains("\"The system SHALL lock accounts\""));
+    assert!(prompt.contains("after 3 failures"));
+    assert!(prompt.contains("for 15 minutes"));
+
+    // Prose instead of JSON is rejected
+    let client = CapturingClient {
+        requests: Arc::new(Mutex::new(Vec::new())),
+        reply: "Keep both edits".to_string(),
+    };
+    let err = suggest_resolution(&client, &config, &conflict)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not valid JSON"), "{}", err);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_advance_command_runs_transition_hooks() -> Result<()> {
+    use manifold::workflow::hooks::TransitionHook;
+    use manifold::workflow::WorkflowPolicy;
+
+    let (temp, paths, db) = setup()?;
+    let mut spec = SpecData::new(
+        "spec-hooked".to_string(),
+        "llm-project".to_string(),
+        "Hooked".to_string(),
+        Boundary::Personal,
+    );
+    spec.requirements.push(Requirement {
+        id: "req-1".to_string(),
+        capability: "auth".to_string(),
+        title: "Login".to_string(),
+        shall: "The system SHALL let users log in".to_string(),
+        rationale: None,
+        priority: Priority::Must,
+        tags: vec![],
+        scenarios: vec![],
+        history: vec![],
+        verification: Default::default(),
+    });
+    db.insert_spec(&spec)?;
+
+    let log = temp.path().join("hook.log");
+    let mut policy = WorkflowPolicy::default();
+    policy.hooks.insert(
+        "requirements->design".to_string(),
+        TransitionHook {
+            command: Some(format!("echo >> {}", log.display())),
+            webhook: None,
+        },
+    );
+    let client = CapturingClient {
+        requests: Arc::new(Mutex::new(Vec::new())),
+        reply: "ok".to_string(),
+    };
+    let mut session = LlmSession::with_client("spec-hooked".to_string(), db, Box::new(client))
+        .with_workflow_policy(policy);
+
+    session.handle_command("/advance").await?;
+    assert_eq!(
+        fs::read_to_string(&log)?,
+        "spec-hooked requirements design\n"
+    );
+
+    // Logged the same way as a CLI advance
+    let db = Database::open(&paths)?;
+    let events = db.get_workflow_events("spec-hooked", None, None, None)?;
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events[0].details.as_deref(),
+        Some("Advanced from requirements to design")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,299 @@
+// Integration tests for the manifold manifest
+// Tests deriving ManifoldV2 from stored specs and config overrides, and bundle import
+
+mod common;
+
+use anyhow::Result;
+use common::setup;
+use manifold::commands::load_manifest;
+use manifold::config::{load_config_from, save_config_to, Config};
+use manifold::db::Database;
+use manifold::export::bundle::build_public_bundle;
+use manifold::models::{Boundary, BoundaryVisibility, SpecData, Visibility};
+use std::collections::BTreeMap;
+use std::fs;
+use tempfile::TempDir;
+
+fn insert(db: &Database, id: &str, project: &str, boundary: Boundary) -> Result<()> {
+    let spec = SpecData::new(
+        id.to_string(),
+        project.to_string(),
+        format!("Spec {}", id),
+        boundary,
+    );
+    db.insert_spec(&spec)?;
+    Ok(())
+}
+
+#[test]
+fn test_manifest_from_specs_with_mixed_visibility() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    insert(&db, "p-1", "notes", Boundary::Personal)?;
+    insert(&db, "w-1", "billing", Boundary::Work)?;
+    insert(&db, "w-2", "auth", Boundary::Work)?;
+    insert(&db, "w-3", "billing", Boundary::Work)?;
+
+    // Work is published beyond the team; personal keeps its default
+    let mut overrides = BTreeMap::new();
+    overrides.insert(Boundary::Work, BoundaryVisibility::Public);
+
+    let manifest = db.manifold_v2("mf-test", &overrides)?;
+
+    assert_eq!(manifest.manifold_id, "mf-test");
+    assert_eq!(manifest.spec_count(), 4);
+    assert_eq!(
+        manifest.boundaries.keys().cloned().collect::<Vec<_>>(),
+        vec![Boundary::Personal, Boundary::Work]
+    );
+
+    let personal = &manifest.boundaries[&Boundary::Personal];
+    assert_eq!(personal.visibility, BoundaryVisibility::Private);
+    assert_eq!(personal.projects, vec!["notes".to_string()]);
+    assert_eq!(personal.spec_count, 1);
+
+    let work = &manifest.boundaries[&Boundary::Work];
+    assert_eq!(work.visibility, BoundaryVisibility::Public);
+    assert_eq!(
+        work.projects,
+        vec!["auth".to_string(), "billing".to_string()]
+    );
+    assert_eq!(work.spec_count, 3);
+
+    let json = serde_json::to_value(&manifest)?;
+    assert_eq!(json["boundaries"]["work"]["visibility"], "public");
+
+    Ok(())
+}
+
+#[test]
+fn test_manifest_id_and_visibility_from_config() -> Result<()> {
+    let (_temp, paths, db) = setup()?;
+    insert(&db, "c-1", "handbook", Boundary::Company)?;
+
+    let mut config = Config::default();
+    config
+        .visibility
+        .insert(Boundary::Company, BoundaryVisibility::Team);
+    save_config_to(&config, &paths.config)?;
+
+    // The id is assigned once and then stays stable
+    let first = load_manifest(&paths, &db)?;
+    let second = load_manifest(&paths, &db)?;
+    assert!(!first.manifold_id.is_empty());
+    assert_eq!(first.manifold_id, second.manifold_id);
+
+    let saved = load_config_from(&paths.config)?;
+    assert_eq!(
+        saved.manifold_id.as_deref(),
+        Some(first.manifold_id.as_str())
+    );
+    assert_eq!(
+        first.boundaries[&Boundary::Company].visibility,
+        BoundaryVisibility::Team
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_public_bundle_excludes_private_specs() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    let mut released = SpecData::new(
+        "w-pub".to_string(),
+        "api".to_string(),
+        "Released API".to_string(),
+        Boundary::Work,
+    );
+    released.visibility = Visibility::Public;
+    let draft = SpecData::new(
+        "w-draft".to_string(),
+        "drafts".to_string(),
+        "Draft".to_string(),
+        Boundary::Work,
+    );
+    let mut personal = SpecData::new(
+        "p-pub".to_string(),
+        "notes".to_string(),
+        "Notes".to_string(),
+        Boundary::Personal,
+    );
+    personal.visibility = Visibility::Public;
+    for spec in [&released, &draft, &personal] {
+        db.insert_spec(spec)?;
+    }
+
+    let mut overrides = BTreeMap::new();
+    overrides.insert(Boundary::Work, BoundaryVisibility::Public);
+    let manifest = db.manifold_v2("mf-bundle", &overrides)?;
+
+    let build = build_public_bundle(&manifest, vec![released, draft, personal]);
+
+    let ids: Vec<_> = build
+        .bundle
+        .specs
+        .iter()
+        .map(|s| s.spec_id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["w-pub"]);
+    assert_eq!(build.skipped, vec!["w-draft".to_string()]);
+
+    // The bundled manifest must not leak the draft's project or count
+    let work = &build.bundle.manifest.boundaries[&Boundary::Work];
+    assert_eq!(work.projects, vec!["api".to_string()]);
+    assert_eq!(work.spec_count, 1);
+    assert!(!build
+        .bundle
+        .manifest
+        .boundaries
+        .contains_key(&Boundary::Personal));
+
+    Ok(())
+}
+
+#[test]
+fn test_spec_visibility_defaults_to_private() -> Result<()> {
+    let mut json = serde_json::to_value(SpecData::new(
+        "legacy".to_string(),
+        "old".to_string(),
+        "Legacy".to_string(),
+        Boundary::Work,
+    ))?;
+    json.as_object_mut().unwrap().remove("visibility");
+
+    let spec: SpecData = serde_json::from_value(json)?;
+    assert_eq!(spec.visibility, Visibility::Private);
+
+    Ok(())
+}
+
+#[test]
+fn test_import_bundle_merge_records_conflicts() -> Result<()> {
+    use manifold::commands::import_bundle;
+    use manifold::export::bundle::Bundle;
+    use manifold::models::ManifoldV2;
+
+    let (_temp, _paths, db) = setup()?;
+
+    let mut shared = SpecData::new(
+        "w-shared".to_string(),
+        "api".to_string(),
+        "Shared API".to_string(),
+        Boundary::Work,
+    );
+    shared.visibility = Visibility::Public;
+    let bundle = |specs: Vec<SpecData>| Bundle {
+        manifest: ManifoldV2::new("mf-teammate".to_string()),
+        specs,
+    };
+
+    // First import inserts and remembers the bundle version as the base
+    let report = import_bundle(&db, &bundle(vec![shared.clone()]), true)?;
+    assert_eq!(report.imported, vec!["w-shared".to_string()]);
+
+    // Both sides rename the spec differently
+    let mut local: SpecData = db.get_spec("w-shared")?.unwrap().parse()?;
+    local.name = "Shared API (local)".to_string();
+    db.update_spec(&local)?;
+    let mut theirs = shared.clone();
+    theirs.name = "Shared API (theirs)".to_string();
+
+    // Without --merge the existing spec is left alone
+    let report = import_bundle(&db, &bundle(vec![theirs.clone()]), false)?;
+    assert_eq!(report.skipped, vec!["w-shared".to_string()]);
+    assert!(db.get_conflicts("w-shared")?.is_empty());
+
+    let report = import_bundle(&db, &bundle(vec![theirs]), true)?;
+    assert_eq!(report.conflicted, vec![("w-shared".to_string(), 1)]);
+
+    let conflicts = db.get_conflicts("w-shared")?;
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].field_path, "name");
+    assert_eq!(
+        conflicts[0].base_value,
+        Some(serde_json::json!("Shared API"))
+    );
+
+    // The local edit was not overwritten
+    let stored: SpecData = db.get_spec("w-shared")?.unwrap().parse()?;
+    assert_eq!(stored.name, "Shared API (local)");
+
+    Ok(())
+}
+
+#[test]
+fn test_import_bundle_merge_takes_disjoint_changes() -> Result<()> {
+    use manifold::commands::import_bundle;
+    use manifold::export::bundle::Bundle;
+    use manifold::models::{ManifoldV2, WorkflowStage};
+
+    let (_temp, _paths, db) = setup()?;
+
+    let shared = SpecData::new(
+        "w-disjoint".to_string(),
+        "api".to_string(),
+        "Disjoint".to_string(),
+        Boundary::Work,
+    );
+    let bundle = |spec: SpecData| Bundle {
+        manifest: ManifoldV2::new("mf-teammate".to_string()),
+        specs: vec![spec],
+    };
+    import_bundle(&db, &bundle(shared.clone()), true)?;
+
+    // Local renames; the teammate advances the stage
+    let mut local: SpecData = db.get_spec("w-disjoint")?.unwrap().parse()?;
+    local.name = "Disjoint (renamed)".to_string();
+    db.update_spec(&local)?;
+    let mut theirs = shared;
+    theirs.stage = WorkflowStage::Design;
+
+    let report = import_bundle(&db, &bundle(theirs), true)?;
+    assert_eq!(report.merged, vec!["w-disjoint".to_string()]);
+    assert!(db.get_conflicts("w-disjoint")?.is_empty());
+
+    let stored: SpecData = db.get_spec("w-disjoint")?.unwrap().parse()?;
+    assert_eq!(stored.name, "Disjoint (renamed)");
+    assert_eq!(stored.stage, WorkflowStage::Design);
+
+    Ok(())
+}
+
+#[test]
+fn test_gzip_bundle_round_trips_and_is_detected() -> Result<()> {
+    use manifold::export::bundle::{read_bundle, write_bundle, write_bundle_gzip, Bundle};
+    use manifold::models::ManifoldV2;
+
+    let temp = TempDir::new()?;
+    let mut spec = SpecData::new(
+        "w-zipped".to_string(),
+        "api".to_string(),
+        "Zipped ✓ API".to_string(),
+        Boundary::Work,
+    );
+    spec.visibility = Visibility::Public;
+    let bundle = Bundle {
+        manifest: ManifoldV2::new("mf-gzip".to_string()),
+        specs: vec![spec; 50],
+    };
+
+    let plain = temp.path().join("bundle.json");
+    let zipped = temp.path().join("bundle.json.gz");
+    // Detection looks at content, not the extension
+    let disguised = temp.path().join("bundle-gz.json");
+    write_bundle(&bundle, &plain)?;
+    write_bundle_gzip(&bundle, &zipped)?;
+    write_bundle_gzip(&bundle, &disguised)?;
+
+    let bytes = fs::read(&zipped)?;
+    assert_eq!(&bytes[..2], &[0x1f, 0x8b]);
+    assert!(bytes.len() < fs::metadata(&plain)?.len() as usize);
+
+    let expected = serde_json::to_value(&bundle)?;
+    for path in [&plain, &zipped, &disguised] {
+        assert_eq!(serde_json::to_value(read_bundle(path)?)?, expected);
+    }
+
+    Ok(())
+}
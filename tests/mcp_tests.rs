@@ -0,0 +1,608 @@
+// Integration tests for the MCP server
+// Tests stdio traffic logging and the structured add_* tools
+
+mod common;
+
+use anyhow::Result;
+use common::setup;
+use manifold::db::Database;
+use manifold::mcp::{McpServer, TrafficLog};
+use std::fs;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_traffic_log_records_request_response_pair() -> Result<()> {
+    let (temp, _paths, db) = setup()?;
+    let log_path = temp.path().join("mcp.jsonl");
+
+    let mut server =
+        McpServer::with_database(db).with_traffic_log(TrafficLog::new(&log_path, 1024 * 1024));
+
+    let request = r#"{"jsonrpc":"2.0","id":1,"method":"initialize"}"#;
+    let response = server.handle_line(request).await?;
+
+    // Stdout payload is the bare response, untouched by logging
+    let response_json: serde_json::Value = serde_json::from_str(&response)?;
+    assert_eq!(response_json["id"], 1);
+
+    let content = fs::read_to_string(&log_path)?;
+    let entries: Vec<serde_json::Value> = content
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["direction"], "request");
+    assert_eq!(entries[0]["message"]["method"], "initialize");
+    assert_eq!(entries[1]["direction"], "response");
+    assert_eq!(entries[1]["message"], response_json);
+    assert!(entries[0]["timestamp"].is_string());
+
+    Ok(())
+}
+
+#[test]
+fn test_traffic_log_rotates_at_size_cap() -> Result<()> {
+    let temp = TempDir::new()?;
+    let log = TrafficLog::new(temp.path().join("mcp.jsonl"), 200);
+
+    for i in 0..5 {
+        log.record(
+            manifold::mcp::traffic_log::Direction::Request,
+            &format!(r#"{{"jsonrpc":"2.0","id":{},"method":"tools/list"}}"#, i),
+        )?;
+    }
+
+    assert!(log.rotated_path().exists());
+    assert!(fs::metadata(log.path())?.len() <= 200);
+
+    Ok(())
+}
+
+/// Send a tools/call request and return the JSON-RPC response
+async fn call_tool(
+    server: &mut McpServer,
+    name: &str,
+    arguments: serde_json::Value,
+) -> Result<serde_json::Value> {
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {"name": name, "arguments": arguments}
+    });
+    let response = server.handle_line(&request.to_string()).await?;
+    Ok(serde_json::from_str(&response)?)
+}
+
+#[tokio::test]
+async fn test_add_requirement_generates_id_and_updates_spec() -> Result<()> {
+    let (_temp, paths, db) = setup()?;
+    let spec = manifold::models::SpecData::new(
+        "add-items".to_string(),
+        "agents".to_string(),
+        "Agent Items".to_string(),
+        manifold::models::Boundary::Work,
+    );
+    db.insert_spec(&spec)?;
+
+    let mut server = McpServer::with_database(db);
+
+    let listed = server
+        .handle_line(r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#)
+        .await?;
+    for tool in ["add_requirement", "add_task", "add_decision"] {
+        assert!(listed.contains(tool), "{} not listed", tool);
+    }
+
+    let mut ids = Vec::new();
+    for title in ["Login", "Logout"] {
+        let response = call_tool(
+            &mut server,
+            "add_requirement",
+            serde_json::json!({
+                "spec_id": "add-items",
+                "capability": "auth",
+                "title": title,
+                "shall": "The system SHALL authenticate users"
+            }),
+        )
+        .await?;
+        let item = &response["result"]["item"];
+        assert_eq!(item["title"], title);
+        assert_eq!(item["priority"], "should");
+        ids.push(item["id"].as_str().unwrap().to_string());
+    }
+    assert_eq!(ids, vec!["req-1", "req-2"]);
+
+    // Tasks must link to requirements that exist
+    let response = call_tool(
+        &mut server,
+        "add_task",
+        serde_json::json!({
+            "spec_id": "add-items",
+            "title": "Build login",
+            "description": "Login endpoint",
+            "requirement_ids": ["req-9"]
+        }),
+    )
+    .await?;
+    assert!(response["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("req-9"));
+
+    let db = Database::open(&paths)?;
+    let stored = db.get_spec("add-items")?.unwrap().parse()?;
+    assert_eq!(stored.requirements.len(), 2);
+    assert_eq!(stored.requirements[1].id, "req-2");
+    assert!(stored.tasks.is_empty());
+    assert!(stored
+        .history
+        .patches
+        .iter()
+        .any(|p| p.summary == "Added req-1 via MCP"));
+
+    Ok(())
+}
+
+/// Insert `ids` as empty work specs and serve them
+fn batch_server(db: Database, ids: &[&str]) -> Result<McpServer> {
+    for id in ids {
+        db.insert_spec(&manifold::models::SpecData::new(
+            id.to_string(),
+            "batch".to_string(),
+            format!("Batch {}", id),
+            manifold::models::Boundary::Work,
+        ))?;
+    }
+    Ok(McpServer::with_database(db))
+}
+
+fn rename_item(spec_id: &str, name: &str) -> serde_json::Value {
+    serde_json::json!({
+        "spec_id": spec_id,
+        "patch": [{"op": "replace", "path": "/name", "value": name}],
+        "summary": format!("Renamed to {}", name)
+    })
+}
+
+#[tokio::test]
+async fn test_apply_patch_batch_reports_each_item() -> Result<()> {
+    let (_temp, paths, db) = setup()?;
+    let mut server = batch_server(db, &["batch-a", "batch-b"])?;
+
+    let response = call_tool(
+        &mut server,
+        "apply_patch_batch",
+        serde_json::json!({"items": [
+            rename_item("batch-a", "Renamed A"),
+            rename_item("batch-missing", "Nowhere"),
+            rename_item("batch-b", "Renamed B"),
+        ]}),
+    )
+    .await?;
+    let result = &response["result"];
+    assert_eq!(result["success"], false);
+    assert_eq!(result["applied"], 2);
+    assert_eq!(result["failed"], 1);
+    assert_eq!(result["results"][0]["success"], true);
+    assert_eq!(result["results"][1]["success"], false);
+    assert!(result["results"][1]["error"]
+        .as_str()
+        .unwrap()
+        .contains("batch-missing"));
+    assert_eq!(result["results"][2]["success"], true);
+
+    let db = Database::open(&paths)?;
+    assert_eq!(db.get_spec_data("batch-a")?.unwrap().name, "Renamed A");
+    assert_eq!(db.get_spec_data("batch-b")?.unwrap().name, "Renamed B");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_apply_patch_batch_atomic_rolls_back() -> Result<()> {
+    let (_temp, paths, db) = setup()?;
+    let mut server = batch_server(db, &["atomic-a", "atomic-b"])?;
+
+    let response = call_tool(
+        &mut server,
+        "apply_patch_batch",
+        serde_json::json!({
+            "atomic": true,
+            "items": [
+                rename_item("atomic-a", "Renamed A"),
+                {
+                    "spec_id": "atomic-b",
+                    "patch": [{"op": "replace", "path": "/owner", "value": "x"}],
+                    "summary": "Unknown field"
+                },
+                rename_item("atomic-b", "Renamed B"),
+            ]
+        }),
+    )
+    .await?;
+    let result = &response["result"];
+    assert_eq!(result["success"], false);
+    assert_eq!(result["applied"], 0);
+    assert!(result["results"][0]["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Rolled back"));
+    assert!(result["results"][2]["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Not attempted"));
+
+    // The first item's write was undone with the rest
+    let db = Database::open(&paths)?;
+    assert_eq!(
+        db.get_spec_data("atomic-a")?.unwrap().name,
+        "Batch atomic-a"
+    );
+    assert_eq!(
+        db.get_spec_data("atomic-b")?.unwrap().name,
+        "Batch atomic-b"
+    );
+    assert!(!db
+        .get_spec_data("atomic-a")?
+        .unwrap()
+        .history
+        .patches
+        .iter()
+        .any(|p| p.summary == "Renamed to Renamed A"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_advance_workflow_without_target_moves_to_next_stage() -> Result<()> {
+    let (_temp, paths, db) = setup()?;
+    let spec = manifold::models::SpecData::new(
+        "auto-advance".to_string(),
+        "agents".to_string(),
+        "Auto Advance".to_string(),
+        manifold::models::Boundary::Work,
+    );
+    db.insert_spec(&spec)?;
+    let mut server = McpServer::with_database(db);
+
+    // No requirements yet, so the blockers come back instead
+    let response = call_tool(
+        &mut server,
+        "advance_workflow",
+        serde_json::json!({"spec_id": "auto-advance"}),
+    )
+    .await?;
+    assert_eq!(response["result"]["success"], false);
+    assert!(response["result"]["blockers"][0]
+        .as_str()
+        .unwrap()
+        .contains("no requirements"));
+
+    call_tool(
+        &mut server,
+        "add_requirement",
+        serde_json::json!({
+            "spec_id": "auto-advance",
+            "capability": "workflow",
+            "title": "Advance",
+            "shall": "The system SHALL advance specs"
+        }),
+    )
+    .await?;
+
+    let response = call_tool(
+        &mut server,
+        "advance_workflow",
+        serde_json::json!({"spec_id": "auto-advance"}),
+    )
+    .await?;
+    assert_eq!(response["result"]["success"], true);
+    assert_eq!(response["result"]["old_stage"], "requirements");
+    assert_eq!(response["result"]["new_stage"], "design");
+
+    let db = Database::open(&paths)?;
+    let stored = db.get_spec_data("auto-advance")?.unwrap();
+    assert_eq!(stored.stage, manifold::models::WorkflowStage::Design);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_query_manifold_reports_counts_and_sorts() -> Result<()> {
+    use manifold::models::{Boundary, Priority, Requirement, SpecData, Task, TaskStatus};
+
+    let (_temp, _paths, db) = setup()?;
+    let requirement = |id: &str| Requirement {
+        id: id.to_string(),
+        capability: "query".to_string(),
+        title: id.to_string(),
+        shall: "The system SHALL answer".to_string(),
+        rationale: None,
+        priority: Priority::Must,
+        tags: vec![],
+        scenarios: vec![],
+        history: vec![],
+        verification: Default::default(),
+    };
+    let task = |id: &str, status: TaskStatus| Task {
+        id: id.to_string(),
+        requirement_ids: vec!["req-1".to_string()],
+        title: id.to_string(),
+        description: "Do it".to_string(),
+        status,
+        assignee: None,
+        acceptance: vec![],
+        scenario_ids: vec![],
+    };
+
+    // Updated earlier, so the default (updated) order lists it last
+    let mut done = SpecData::new(
+        "spec-done".to_string(),
+        "query".to_string(),
+        "Done".to_string(),
+        Boundary::Work,
+    );
+    done.requirements.push(requirement("req-1"));
+    done.tasks.push(task("task-1", TaskStatus::Completed));
+    done.history.updated_at -= 60;
+    db.insert_spec(&done)?;
+
+    let mut wide = SpecData::new(
+        "spec-wide".to_string(),
+        "query".to_string(),
+        "Wide".to_string(),
+        Boundary::Work,
+    );
+    for id in ["req-1", "req-2", "req-3"] {
+        wide.requirements.push(requirement(id));
+    }
+    wide.tasks.push(task("task-1", TaskStatus::Completed));
+    wide.tasks.push(task("task-2", TaskStatus::Pending));
+    wide.tasks.push(task("task-3", TaskStatus::InProgress));
+    wide.tasks.push(task("task-4", TaskStatus::Blocked));
+    db.insert_spec(&wide)?;
+
+    let mut server = McpServer::with_database(db);
+    let ids = |response: &serde_json::Value| -> Vec<String> {
+        response["result"]["specs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["spec_id"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let response = call_tool(&mut server, "query_manifold", serde_json::json!({})).await?;
+    assert_eq!(ids(&response), vec!["spec-wide", "spec-done"]);
+    let summary = &response["result"]["specs"][0];
+    assert_eq!(summary["requirement_count"], 3);
+    assert_eq!(summary["task_count"], 4);
+    assert_eq!(summary["completed_task_count"], 1);
+    assert_eq!(summary["progress"], 0.25);
+
+    let response = call_tool(
+        &mut server,
+        "query_manifold",
+        serde_json::json!({"sort_by": "progress"}),
+    )
+    .await?;
+    assert_eq!(ids(&response), vec!["spec-done", "spec-wide"]);
+    assert_eq!(response["result"]["specs"][0]["progress"], 1.0);
+
+    let response = call_tool(
+        &mut server,
+        "query_manifold",
+        serde_json::json!({"sort_by": "requirements"}),
+    )
+    .await?;
+    assert_eq!(ids(&response), vec!["spec-wide", "spec-done"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_boundary_and_stage_aliases_parse_like_cli() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+    let mut server = McpServer::with_database(db);
+
+    let response = call_tool(
+        &mut server,
+        "create_spec",
+        serde_json::json!({"project": "alias", "boundary": "Personal", "name": "Alias"}),
+    )
+    .await?;
+    assert!(response["error"].is_null(), "{}", response);
+
+    for (boundary, stage) in [("PERSONAL", "req"), (" personal ", "Requirements")] {
+        let response = call_tool(
+            &mut server,
+            "query_manifold",
+            serde_json::json!({"boundary": boundary, "stage": stage}),
+        )
+        .await?;
+        assert_eq!(response["result"]["specs"].as_array().unwrap().len(), 1);
+    }
+
+    let response = call_tool(
+        &mut server,
+        "query_manifold",
+        serde_json::json!({"boundary": "personal", "stage": "impl"}),
+    )
+    .await?;
+    assert!(response["result"]["specs"].as_array().unwrap().is_empty());
+
+    // Unknown filters are rejected rather than silently ignored
+    let response = call_tool(
+        &mut server,
+        "query_manifold",
+        serde_json::json!({"stage": "shipping"}),
+    )
+    .await?;
+    assert!(response["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("Invalid stage"));
+
+    // Names that read like another boundary or a state are not aliases
+    for boundary in ["private", "team", "org", "corp"] {
+        let response = call_tool(
+            &mut server,
+            "query_manifold",
+            serde_json::json!({"boundary": boundary}),
+        )
+        .await?;
+        assert!(
+            response["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("Invalid boundary"),
+            "{}",
+            boundary
+        );
+    }
+    for stage in ["approve", "approved", "done"] {
+        let response = call_tool(
+            &mut server,
+            "query_manifold",
+            serde_json::json!({"stage": stage}),
+        )
+        .await?;
+        assert!(
+            response["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("Invalid stage"),
+            "{}",
+            stage
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_serve_over_unix_socket() -> Result<()> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+    use std::process::{Command, Stdio};
+    use std::time::{Duration, Instant};
+
+    let home = TempDir::new()?;
+    let root = home.path().join(".manifold");
+    fs::create_dir_all(root.join("db"))?;
+    fs::write(
+        root.join("config.toml"),
+        "default_boundary = \"personal\"\n",
+    )?;
+    drop(Database::init(&common::paths_under(&root))?);
+
+    let socket = home.path().join("mcp.sock");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_manifold"))
+        .args(["serve", "--socket"])
+        .arg(&socket)
+        .env("HOME", home.path())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    let started = Instant::now();
+    while !socket.exists() {
+        assert!(
+            started.elapsed() < Duration::from_secs(10),
+            "socket never appeared"
+        );
+        std::thread::sleep(Duration::from_millis(20));
+    }
+
+    // Connections are served one after another
+    for id in 1..=2 {
+        let mut stream = UnixStream::connect(&socket)?;
+        stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+        writeln!(
+            stream,
+            r#"{{"jsonrpc":"2.0","id":{},"method":"initialize"}}"#,
+            id
+        )?;
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line)?;
+        let response: serde_json::Value = serde_json::from_str(&line)?;
+        assert_eq!(response["id"], id);
+        assert_eq!(response["result"]["serverInfo"]["name"], "manifold");
+    }
+
+    let status = Command::new("kill")
+        .args(["-TERM", &child.id().to_string()])
+        .status()?;
+    assert!(status.success());
+    child.wait()?;
+    assert!(
+        !socket.exists(),
+        "socket file should be removed on shutdown"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_advance_workflow_runs_transition_hooks() -> Result<()> {
+    use manifold::workflow::hooks::TransitionHook;
+    use manifold::workflow::WorkflowPolicy;
+
+    let (temp, paths, db) = setup()?;
+    let mut spec = manifold::models::SpecData::new(
+        "hooked-mcp".to_string(),
+        "agents".to_string(),
+        "Hooked".to_string(),
+        manifold::models::Boundary::Work,
+    );
+    spec.requirements.push(manifold::models::Requirement {
+        id: "req-1".to_string(),
+        capability: "workflow".to_string(),
+        title: "Advance".to_string(),
+        shall: "The system SHALL advance specs".to_string(),
+        rationale: None,
+        priority: manifold::models::Priority::Must,
+        tags: vec![],
+        scenarios: vec![],
+        history: vec![],
+        verification: Default::default(),
+    });
+    db.insert_spec(&spec)?;
+
+    let log = temp.path().join("hook.log");
+    let mut policy = WorkflowPolicy::default();
+    policy.hooks.insert(
+        "requirements->design".to_string(),
+        TransitionHook {
+            command: Some(format!("echo >> {}", log.display())),
+            webhook: None,
+        },
+    );
+    let mut server = McpServer::with_database(db).with_workflow_policy(policy);
+
+    let response = call_tool(
+        &mut server,
+        "advance_workflow",
+        serde_json::json!({"spec_id": "hooked-mcp"}),
+    )
+    .await?;
+    assert_eq!(response["result"]["success"], true);
+    assert_eq!(
+        fs::read_to_string(&log)?,
+        "hooked-mcp requirements design\n"
+    );
+
+    // Logged the same way as a CLI advance
+    let db = Database::open(&paths)?;
+    let events = db.get_workflow_events("hooked-mcp", None, None, None)?;
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events[0].details.as_deref(),
+        Some("Advanced from requirements to design")
+    );
+
+    Ok(())
+}
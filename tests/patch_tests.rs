@@ -0,0 +1,164 @@
+// Integration tests for JSON patch application
+// Tests SpecData::apply_json_patch operations, rejection, and requirement history
+
+use anyhow::Result;
+use manifold::models::{Boundary, SpecData};
+use serde_json::json;
+
+fn create_test_spec() -> SpecData {
+    SpecData::new(
+        "test-spec".to_string(),
+        "test-project".to_string(),
+        "Test Spec".to_string(),
+        Boundary::Personal,
+    )
+}
+
+fn patch(ops: serde_json::Value) -> json_patch::Patch {
+    serde_json::from_value(ops).expect("valid patch document")
+}
+
+fn requirement(id: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "capability": "core",
+        "title": format!("Requirement {}", id),
+        "shall": "The system SHALL work",
+        "priority": "must"
+    })
+}
+
+#[test]
+fn test_apply_patch_add() -> Result<()> {
+    let mut spec = create_test_spec();
+
+    spec.apply_json_patch(&patch(json!([
+        {"op": "add", "path": "/requirements/-", "value": requirement("req-1")}
+    ])))?;
+
+    assert_eq!(spec.requirements.len(), 1);
+    assert_eq!(spec.requirements[0].id, "req-1");
+
+    Ok(())
+}
+
+#[test]
+fn test_apply_patch_replace_and_remove() -> Result<()> {
+    let mut spec = create_test_spec();
+    spec.apply_json_patch(&patch(json!([
+        {"op": "add", "path": "/requirements/-", "value": requirement("req-1")},
+        {"op": "add", "path": "/requirements/-", "value": requirement("req-2")}
+    ])))?;
+
+    spec.apply_json_patch(&patch(json!([
+        {"op": "replace", "path": "/name", "value": "Renamed Spec"},
+        {"op": "replace", "path": "/requirements/1/title", "value": "Second"},
+        {"op": "remove", "path": "/requirements/0"}
+    ])))?;
+
+    assert_eq!(spec.name, "Renamed Spec");
+    assert_eq!(spec.requirements.len(), 1);
+    assert_eq!(spec.requirements[0].id, "req-2");
+    assert_eq!(spec.requirements[0].title, "Second");
+
+    Ok(())
+}
+
+#[test]
+fn test_apply_patch_rejects_breaking_required_fields() {
+    let mut spec = create_test_spec();
+
+    // Removing a required field fails to deserialize
+    let result = spec.apply_json_patch(&patch(json!([
+        {"op": "remove", "path": "/name"}
+    ])));
+    assert!(result.is_err());
+
+    // Blanking a required field is rejected too
+    let result = spec.apply_json_patch(&patch(json!([
+        {"op": "replace", "path": "/project", "value": ""}
+    ])));
+    assert!(result.is_err());
+
+    // As is rewriting the spec's identity
+    let result = spec.apply_json_patch(&patch(json!([
+        {"op": "replace", "path": "/spec_id", "value": "other-spec"}
+    ])));
+    assert!(result.is_err());
+
+    // A failed patch leaves the spec unchanged, even for partially applied ops
+    let result = spec.apply_json_patch(&patch(json!([
+        {"op": "replace", "path": "/name", "value": "Half Applied"},
+        {"op": "remove", "path": "/requirements/5"}
+    ])));
+    assert!(result.is_err());
+    assert_eq!(spec.name, "Test Spec");
+    assert_eq!(spec.project, "test-project");
+    assert_eq!(spec.spec_id, "test-spec");
+}
+
+#[test]
+fn test_editing_requirement_records_previous_text() -> Result<()> {
+    let mut spec = create_test_spec();
+    spec.apply_json_patch(&patch(json!([
+        {"op": "add", "path": "/requirements/-", "value": requirement("req-1")}
+    ])))?;
+
+    spec.apply_json_patch_as(
+        &patch(json!([
+            {"op": "replace", "path": "/requirements/0/shall", "value": "The system SHALL work offline"}
+        ])),
+        "mcp",
+    )?;
+
+    let req = &spec.requirements[0];
+    assert_eq!(req.shall, "The system SHALL work offline");
+    assert_eq!(req.history.len(), 1);
+    assert_eq!(req.history[0].shall, "The system SHALL work");
+    assert_eq!(req.history[0].title, "Requirement req-1");
+    assert_eq!(req.history[0].actor, "mcp");
+
+    // Replacing the whole requirement keeps the earlier history
+    spec.apply_json_patch(&patch(json!([
+        {"op": "replace", "path": "/requirements/0", "value": requirement("req-1")}
+    ])))?;
+    let req = &spec.requirements[0];
+    assert_eq!(req.history.len(), 2);
+    assert_eq!(req.history[1].shall, "The system SHALL work offline");
+
+    // Edits that leave title and SHALL alone add nothing
+    spec.apply_json_patch(&patch(json!([
+        {"op": "replace", "path": "/requirements/0/capability", "value": "sync"}
+    ])))?;
+    assert_eq!(spec.requirements[0].history.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_decision_without_date_gets_today() -> Result<()> {
+    let mut spec = create_test_spec();
+    spec.apply_json_patch_as(
+        &patch(json!([
+            {"op": "add", "path": "/decisions/-", "value": {
+                "id": "dec-1",
+                "title": "Use SQLite",
+                "context": "Need local storage",
+                "decision": "SQLite",
+                "rationale": "Embedded"
+            }}
+        ])),
+        "mcp",
+    )?;
+
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    assert_eq!(spec.decisions[0].date, today);
+
+    // An explicit date is kept as given
+    spec.apply_json_patch(&patch(json!([
+        {"op": "replace", "path": "/decisions/0/date", "value": "2023-06-01"}
+    ])))?;
+    assert_eq!(spec.decisions[0].date, "2023-06-01");
+
+    Ok(())
+}
@@ -0,0 +1,516 @@
+// Integration tests for spec queries
+// Tests SQL-side filtering and ordering, and indexed content
+
+mod common;
+
+use anyhow::Result;
+use common::setup;
+use manifold::collab::reviews::ReviewManager;
+use manifold::db::{Database, DbError, Page, SearchSources, SpecFilter, SpecOrder};
+use manifold::models::{
+    Boundary, Decision, Priority, Requirement, SpecData, Task, TaskStatus, WorkflowStage,
+};
+use manifold::search::{compile_pattern, highlight_search, regex_search, MAX_PATTERN_LEN};
+use std::fs;
+
+#[test]
+fn test_boundary_scoped_search_filters_in_sql() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    let boundaries = [Boundary::Personal, Boundary::Work, Boundary::Company];
+    for i in 0..1000 {
+        let boundary = boundaries[i % boundaries.len()].clone();
+        let mut spec = SpecData::new(
+            format!("spec-{}", i),
+            "search-project".to_string(),
+            format!("Telemetry pipeline {}", i),
+            boundary,
+        );
+        if i % 2 == 0 {
+            spec.stage = WorkflowStage::Design;
+        }
+        db.insert_spec(&spec)?;
+    }
+
+    let results = db.search_specs_filtered("telemetry", Some(&Boundary::Work), None)?;
+
+    // 1000 specs round-robin over 3 boundaries: indices 1, 4, 7, ... are work
+    assert_eq!(results.len(), 333);
+    assert!(results.iter().all(|r| r.boundary == "work"));
+
+    let results = db.search_specs_filtered(
+        "telemetry",
+        Some(&Boundary::Work),
+        Some(&WorkflowStage::Design),
+    )?;
+
+    assert!(!results.is_empty());
+    assert!(results
+        .iter()
+        .all(|r| r.boundary == "work" && r.stage == "design"));
+
+    Ok(())
+}
+
+#[test]
+fn test_search_indexes_acceptance_and_rejected_alternatives() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    let mut spec = SpecData::new(
+        "spec-indexed".to_string(),
+        "search-project".to_string(),
+        "Indexed Spec".to_string(),
+        Boundary::Personal,
+    );
+    spec.tasks.push(Task {
+        id: "task-1".to_string(),
+        requirement_ids: vec!["req-1".to_string()],
+        title: "Build export".to_string(),
+        description: "Write the exporter".to_string(),
+        status: TaskStatus::Pending,
+        assignee: None,
+        acceptance: vec!["Output passes the zanzibarcheck".to_string()],
+        scenario_ids: vec![],
+    });
+    spec.decisions.push(Decision {
+        id: "dec-1".to_string(),
+        title: "Storage".to_string(),
+        context: "Need storage".to_string(),
+        decision: "Use SQLite".to_string(),
+        rationale: "Embedded".to_string(),
+        alternatives_rejected: vec!["Quokkabase cluster".to_string()],
+        date: "2024-01-15".to_string(),
+    });
+    db.insert_spec(&spec)?;
+
+    let by_acceptance = db.search_specs("zanzibarcheck")?;
+    assert_eq!(by_acceptance.len(), 1);
+    assert_eq!(by_acceptance[0].id, "spec-indexed");
+
+    let by_alternative = db.search_specs("quokkabase")?;
+    assert_eq!(by_alternative.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_search_can_include_decisions_and_reviews() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    let mut spec = SpecData::new(
+        "spec-noted".to_string(),
+        "notes".to_string(),
+        "Noted spec".to_string(),
+        Boundary::Work,
+    );
+    spec.decisions.push(Decision {
+        id: "dec-1".to_string(),
+        title: "Queue".to_string(),
+        context: "Need a queue".to_string(),
+        decision: "Use Postgres".to_string(),
+        rationale: "Pangolinic durability guarantees".to_string(),
+        alternatives_rejected: vec![],
+        date: "2024-01-15".to_string(),
+    });
+    db.insert_spec(&spec)?;
+    let mut review = ReviewManager::create_review(
+        "spec-noted".to_string(),
+        "alice@example.com".to_string(),
+        "bob@example.com".to_string(),
+    );
+    review.comment = Some("Wombatish naming throughout".to_string());
+    db.save_review(&review)?;
+
+    // The rationale and review comment are not part of the spec body index
+    assert!(db.search_specs("pangolinic")?.is_empty());
+    assert!(db.search_specs("wombatish")?.is_empty());
+
+    let decisions: SearchSources = "decisions".parse().unwrap();
+    let found = db.search_specs_including("pangolinic", decisions)?;
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, "spec-noted");
+    assert!(db
+        .search_specs_including("wombatish", decisions)?
+        .is_empty());
+
+    let both: SearchSources = "decisions,reviews".parse().unwrap();
+    assert_eq!(db.search_specs_including("wombatish", both)?.len(), 1);
+    // Matching the body and a decision still lists the spec once
+    assert_eq!(db.search_specs_including("queue", both)?.len(), 1);
+
+    // A rebuilt index keeps both sources
+    db.rebuild_fts()?;
+    assert_eq!(db.search_specs_including("pangolinic", both)?.len(), 1);
+    assert_eq!(db.search_specs_including("wombatish", both)?.len(), 1);
+
+    assert!("decisions,comments".parse::<SearchSources>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_list_specs_orderings() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    // (id, project, stage, created_at, updated_at)
+    let fixtures = [
+        ("spec-a", "zeta", WorkflowStage::Tasks, 100, 400),
+        ("spec-b", "alpha", WorkflowStage::Implemented, 300, 200),
+        ("spec-c", "mid", WorkflowStage::Requirements, 200, 300),
+        ("spec-d", "beta", WorkflowStage::Design, 400, 100),
+    ];
+    for (id, project, stage, created, updated) in fixtures {
+        let mut spec = SpecData::new(
+            id.to_string(),
+            project.to_string(),
+            format!("Spec {}", id),
+            Boundary::Personal,
+        );
+        spec.stage = stage;
+        spec.history.created_at = created;
+        spec.history.updated_at = updated;
+        db.insert_spec(&spec)?;
+    }
+
+    let ids = |order: SpecOrder| -> Result<Vec<String>> {
+        Ok(db
+            .list_specs(None, None, order)?
+            .into_iter()
+            .map(|r| r.id)
+            .collect())
+    };
+
+    assert_eq!(
+        ids(SpecOrder::UpdatedDesc)?,
+        vec!["spec-a", "spec-c", "spec-b", "spec-d"]
+    );
+    assert_eq!(
+        ids(SpecOrder::CreatedDesc)?,
+        vec!["spec-d", "spec-b", "spec-c", "spec-a"]
+    );
+    assert_eq!(
+        ids(SpecOrder::ProjectAsc)?,
+        vec!["spec-b", "spec-d", "spec-c", "spec-a"]
+    );
+    assert_eq!(
+        ids(SpecOrder::StageOrder)?,
+        vec!["spec-c", "spec-d", "spec-a", "spec-b"]
+    );
+
+    assert_eq!("stage".parse::<SpecOrder>(), Ok(SpecOrder::StageOrder));
+    assert_eq!(SpecOrder::StageOrder.next(), SpecOrder::UpdatedDesc);
+
+    Ok(())
+}
+
+fn requirement(id: &str, title: &str) -> Requirement {
+    Requirement {
+        id: id.to_string(),
+        capability: "search".to_string(),
+        title: title.to_string(),
+        shall: "The system SHALL find things".to_string(),
+        rationale: None,
+        priority: Priority::Must,
+        tags: vec![],
+        scenarios: vec![],
+        history: vec![],
+        verification: Default::default(),
+    }
+}
+
+#[test]
+fn test_regex_search_matches_requirement_ids() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    let mut numbered = SpecData::new(
+        "spec-numbered".to_string(),
+        "regex-project".to_string(),
+        "Numbered".to_string(),
+        Boundary::Work,
+    );
+    numbered.requirements.push(requirement("req-1", "Short id"));
+    numbered
+        .requirements
+        .push(requirement("req-204", "Long id"));
+    db.insert_spec(&numbered)?;
+
+    let mut plain = SpecData::new(
+        "spec-plain".to_string(),
+        "regex-project".to_string(),
+        "Plain".to_string(),
+        Boundary::Personal,
+    );
+    plain.requirements.push(requirement("req-12", "Two digits"));
+    db.insert_spec(&plain)?;
+
+    let re = compile_pattern(r"^req-\d{3}$")?;
+    let matches = regex_search(&db, &re, None, None, None)?;
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].spec_id, "spec-numbered");
+    assert_eq!(matches[0].path, "/requirements/1/id");
+    assert_eq!(matches[0].matched, "req-204");
+
+    // Boundary filter and FTS pre-filter narrow the candidates
+    let re = compile_pattern(r"req-\d+")?;
+    let personal = regex_search(&db, &re, None, Some(&Boundary::Personal), None)?;
+    assert!(personal.iter().all(|m| m.spec_id == "spec-plain"));
+    let prefiltered = regex_search(&db, &re, Some("digits"), None, None)?;
+    assert!(!prefiltered.is_empty());
+    assert!(prefiltered.iter().all(|m| m.spec_id == "spec-plain"));
+
+    Ok(())
+}
+
+#[test]
+fn test_highlight_search_points_at_matched_shall() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    let mut spec = SpecData::new(
+        "spec-highlight".to_string(),
+        "highlight-project".to_string(),
+        "Highlight".to_string(),
+        Boundary::Work,
+    );
+    spec.requirements.push(requirement("req-1", "Login"));
+    let mut storage = requirement("req-2", "Storage");
+    storage.shall = "The system SHALL encrypt tokens at rest".to_string();
+    spec.requirements.push(storage);
+    db.insert_spec(&spec)?;
+
+    let hits = highlight_search(&db, "encrypt", None, None, Page::default())?;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].spec_id, "spec-highlight");
+    assert_eq!(hits[0].matches.len(), 1);
+
+    let field = &hits[0].matches[0];
+    assert_eq!(field.path, "/requirements/1/shall");
+    assert_eq!(field.start, 17);
+    assert_eq!(field.end, 24);
+    assert!(field.snippet.contains("encrypt tokens"));
+
+    // Prefix queries match the same way FTS5 does
+    let hits = highlight_search(&db, "encr*", None, None, Page::default())?;
+    assert_eq!(hits[0].matches[0].path, "/requirements/1/shall");
+
+    Ok(())
+}
+
+#[test]
+fn test_regex_pattern_guards() {
+    assert!(compile_pattern("(unclosed").is_err());
+    assert!(compile_pattern(&"a".repeat(MAX_PATTERN_LEN + 1)).is_err());
+    // Compiles to a program far larger than the size limit
+    assert!(compile_pattern(r"(\w{100}){100}").is_err());
+}
+
+#[test]
+fn test_rebuild_fts_restores_search() -> Result<()> {
+    let (_temp, paths, db) = setup()?;
+
+    for i in 0..3 {
+        let spec = SpecData::new(
+            format!("spec-reindex-{}", i),
+            "reindex-project".to_string(),
+            format!("Heliotrope {}", i),
+            Boundary::Work,
+        );
+        db.insert_spec(&spec)?;
+    }
+    assert_eq!(db.search_specs("heliotrope")?.len(), 3);
+
+    // Simulate drift: the index loses every row
+    let conn = rusqlite::Connection::open(&paths.db_file)?;
+    conn.execute("DELETE FROM specs_fts", [])?;
+    drop(conn);
+    assert!(db.search_specs("heliotrope")?.is_empty());
+
+    let report = db.rebuild_fts()?;
+    assert_eq!(report.indexed, 3);
+    assert!(report.skipped.is_empty());
+    assert_eq!(db.search_specs("heliotrope")?.len(), 3);
+
+    // Rebuilding again must not duplicate index rows
+    db.rebuild_fts()?;
+    assert_eq!(db.search_specs("heliotrope")?.len(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_insert_duplicate_spec_id_is_typed_and_leaves_index_intact() -> Result<()> {
+    let (_temp, paths, db) = setup()?;
+
+    let original = SpecData::new(
+        "spec-dup".to_string(),
+        "dup-project".to_string(),
+        "Marigold".to_string(),
+        Boundary::Work,
+    );
+    db.insert_spec(&original)?;
+
+    let mut duplicate = original.clone();
+    duplicate.name = "Quillwort".to_string();
+    let err = db.insert_spec(&duplicate).unwrap_err();
+    match err.downcast_ref::<DbError>() {
+        Some(DbError::SpecAlreadyExists(id)) => assert_eq!(id, "spec-dup"),
+        other => panic!("expected SpecAlreadyExists, got {:?}", other),
+    }
+
+    // The failed insert must not have indexed the duplicate's content
+    assert!(db.search_specs("quillwort")?.is_empty());
+    assert_eq!(db.search_specs("marigold")?.len(), 1);
+    let conn = rusqlite::Connection::open(&paths.db_file)?;
+    let fts_rows: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM specs_fts WHERE id = 'spec-dup'",
+        [],
+        |row| row.get(0),
+    )?;
+    assert_eq!(fts_rows, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_owner_and_team_filter_listings_and_are_searchable() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    let owners = [
+        (Some("ada"), Some("platform")),
+        (Some("grace"), Some("platform")),
+        (Some("ada"), None),
+        (None, None),
+    ];
+    for (i, (owner, team)) in owners.iter().enumerate() {
+        let mut spec = SpecData::new(
+            format!("spec-owned-{}", i),
+            "owned-project".to_string(),
+            format!("Owned {}", i),
+            Boundary::Work,
+        );
+        spec.owner = owner.map(str::to_string);
+        spec.team = team.map(str::to_string);
+        db.insert_spec(&spec)?;
+    }
+
+    let ids = |filter: SpecFilter| -> Result<Vec<String>> {
+        let mut ids: Vec<String> = db
+            .list_specs_filtered(&filter, SpecOrder::default())?
+            .into_iter()
+            .map(|row| row.id)
+            .collect();
+        ids.sort();
+        Ok(ids)
+    };
+    assert_eq!(
+        ids(SpecFilter {
+            owner: Some("ada".to_string()),
+            ..Default::default()
+        })?,
+        vec!["spec-owned-0", "spec-owned-2"]
+    );
+    assert_eq!(
+        ids(SpecFilter {
+            owner: Some("ada".to_string()),
+            team: Some("platform".to_string()),
+            ..Default::default()
+        })?,
+        vec!["spec-owned-0"]
+    );
+    assert!(ids(SpecFilter {
+        owner: Some("nobody".to_string()),
+        ..Default::default()
+    })?
+    .is_empty());
+
+    let found: Vec<String> = db
+        .search_specs("grace")?
+        .into_iter()
+        .map(|row| row.id)
+        .collect();
+    assert_eq!(found, vec!["spec-owned-1"]);
+    assert_eq!(db.search_specs("platform")?.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_backup_and_restore_round_trip() -> Result<()> {
+    let (temp, paths, db) = setup()?;
+
+    for i in 0..3 {
+        let mut spec = SpecData::new(
+            format!("spec-backup-{}", i),
+            "backup-project".to_string(),
+            format!("Marigold {}", i),
+            Boundary::Personal,
+        );
+        spec.requirements
+            .push(requirement("req-1", "Keep everything"));
+        db.insert_spec(&spec)?;
+    }
+    let before = db.list_specs(None, None, SpecOrder::ProjectAsc)?;
+
+    let backup = temp.path().join("backup.db");
+    db.backup(&backup)?;
+    drop(db);
+
+    // Wipe: start again from an empty database
+    fs::remove_file(&paths.db_file)?;
+    let mut db = Database::init(&paths)?;
+    assert!(db.list_specs(None, None, SpecOrder::ProjectAsc)?.is_empty());
+
+    db.restore(&backup)?;
+    let after = db.list_specs(None, None, SpecOrder::ProjectAsc)?;
+    assert_eq!(after.len(), before.len());
+    for (a, b) in before.iter().zip(&after) {
+        assert_eq!(a.id, b.id);
+        assert_eq!(a.data, b.data);
+        assert_eq!(a.updated_at, b.updated_at);
+    }
+    // The search index comes back with the rows
+    assert_eq!(db.search_specs("marigold")?.len(), 3);
+
+    // Anything that isn't a manifold database is refused
+    let bogus = temp.path().join("bogus.db");
+    rusqlite::Connection::open(&bogus)?.execute("CREATE TABLE t (x)", [])?;
+    let err = db.restore(&bogus).unwrap_err();
+    assert!(
+        err.to_string().contains("not a manifold database"),
+        "{}",
+        err
+    );
+    assert_eq!(db.list_specs(None, None, SpecOrder::ProjectAsc)?.len(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_get_spec_data_parses_or_reports_missing() -> Result<()> {
+    let (_temp, paths, db) = setup()?;
+
+    assert!(db.get_spec_data("spec-missing")?.is_none());
+
+    let mut spec = SpecData::new(
+        "spec-typed".to_string(),
+        "typed-project".to_string(),
+        "Typed".to_string(),
+        Boundary::Work,
+    );
+    spec.requirements.push(requirement("req-1", "Parsed"));
+    db.insert_spec(&spec)?;
+
+    let loaded = db.get_spec_data("spec-typed")?.expect("spec exists");
+    assert_eq!(loaded.spec_id, "spec-typed");
+    assert_eq!(loaded.requirements[0].title, "Parsed");
+
+    // A row that doesn't deserialize is an error, not "not found"
+    let conn = rusqlite::Connection::open(&paths.db_file)?;
+    conn.execute(
+        "UPDATE specs SET data = '{\"spec_id\": 1}' WHERE id = 'spec-typed'",
+        [],
+    )?;
+    drop(conn);
+    let err = db.get_spec_data("spec-typed").unwrap_err();
+    assert!(err.to_string().contains("could not be parsed"), "{}", err);
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+// Integration tests for spec templates
+// Tests saving a spec as a template and instantiating it with fresh ids
+
+use anyhow::Result;
+use manifold::models::{
+    Boundary, Decision, Priority, Requirement, Scenario, SpecData, Task, TaskStatus,
+    VerificationStatus,
+};
+use manifold::templates::{self, SpecTemplate};
+use manifold::validation::{IdPatterns, IdScheme, SpecValidator};
+use tempfile::TempDir;
+
+fn create_source_spec() -> SpecData {
+    let mut spec = SpecData::new(
+        "source-amber-anchor".to_string(),
+        "source-project".to_string(),
+        "Source Spec".to_string(),
+        Boundary::Work,
+    );
+
+    spec.requirements.push(Requirement {
+        id: "req-7".to_string(),
+        capability: "auth".to_string(),
+        title: "Login".to_string(),
+        shall: "The system SHALL authenticate users".to_string(),
+        rationale: None,
+        priority: Priority::Must,
+        tags: vec![],
+        scenarios: vec![Scenario {
+            id: "sc-12".to_string(),
+            name: "Valid credentials".to_string(),
+            given: vec!["a registered user".to_string()],
+            when: "they log in".to_string(),
+            then: vec!["a session is created".to_string()],
+            edge_cases: vec![],
+        }],
+        history: vec![],
+        verification: VerificationStatus::Verified,
+    });
+    spec.tasks.push(Task {
+        id: "task-4".to_string(),
+        requirement_ids: vec!["req-7".to_string()],
+        title: "Build login form".to_string(),
+        description: "Form with validation".to_string(),
+        status: TaskStatus::Completed,
+        assignee: Some("alice".to_string()),
+        acceptance: vec![],
+        scenario_ids: vec!["req-7/sc-12".to_string()],
+    });
+    spec.decisions.push(Decision {
+        id: "dec-3".to_string(),
+        title: "Session storage".to_string(),
+        context: "Need sessions".to_string(),
+        decision: "Use cookies".to_string(),
+        rationale: "Simple".to_string(),
+        alternatives_rejected: vec![],
+        date: "2024-01-15".to_string(),
+    });
+
+    spec
+}
+
+#[test]
+fn test_save_template_and_create_spec() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let dir = temp_dir.path().join("templates");
+
+    let template = SpecTemplate::from_spec(
+        "auth-skeleton",
+        &create_source_spec(),
+        &IdPatterns::default(),
+    )?;
+    templates::save_template(&dir, &template)?;
+    assert_eq!(templates::list_templates(&dir)?, vec!["auth-skeleton"]);
+
+    // Stored template carries no progress or timestamps from the source
+    let loaded = templates::load_template(&dir, "auth-skeleton")?;
+    assert_eq!(loaded.tasks[0].status, TaskStatus::Pending);
+    assert!(loaded.tasks[0].assignee.is_none());
+    assert!(loaded.decisions[0].date.is_empty());
+    assert_eq!(
+        loaded.requirements[0].verification,
+        VerificationStatus::NotStarted
+    );
+
+    let spec = loaded.instantiate(
+        "new-bold-beacon".to_string(),
+        "new-project".to_string(),
+        "New Spec".to_string(),
+        Boundary::Personal,
+        &IdPatterns::default(),
+    );
+
+    assert_eq!(spec.spec_id, "new-bold-beacon");
+    assert_eq!(spec.project, "new-project");
+    assert_eq!(spec.requirements[0].id, "req-1");
+    assert_eq!(spec.requirements[0].scenarios[0].id, "sc-1");
+    assert_eq!(spec.tasks[0].id, "task-1");
+    assert_eq!(spec.tasks[0].requirement_ids, vec!["req-1"]);
+    assert_eq!(spec.tasks[0].scenario_ids, vec!["req-1/sc-1"]);
+    manifold::validation::validate_scenario_links(&spec)?;
+    assert_eq!(spec.decisions[0].id, "dec-1");
+    assert!(!spec.decisions[0].date.is_empty());
+    assert_eq!(spec.requirements[0].title, "Login");
+
+    Ok(())
+}
+
+#[test]
+fn test_template_name_and_missing_template() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let dir = temp_dir.path().join("templates");
+
+    assert!(
+        SpecTemplate::from_spec("../escape", &create_source_spec(), &IdPatterns::default())
+            .is_err()
+    );
+    assert!(templates::load_template(&dir, "missing").is_err());
+    assert!(templates::list_templates(&dir)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_template_ids_follow_configured_scheme() -> Result<()> {
+    let scheme = |kind: &str| IdScheme::new(&format!("^{}-[0-9]{{3}}$", kind), kind, 3);
+    let ids = IdPatterns {
+        requirement: scheme("REQ")?,
+        scenario: scheme("SC")?,
+        task: scheme("TASK")?,
+        decision: scheme("DEC")?,
+    };
+
+    let template = SpecTemplate::from_spec("auth-skeleton", &create_source_spec(), &ids)?;
+    let spec = template.instantiate(
+        "new-calm-cedar".to_string(),
+        "new-project".to_string(),
+        "New Spec".to_string(),
+        Boundary::Work,
+        &ids,
+    );
+
+    assert_eq!(spec.requirements[0].id, "REQ-001");
+    assert_eq!(spec.requirements[0].scenarios[0].id, "SC-001");
+    assert_eq!(spec.tasks[0].id, "TASK-001");
+    assert_eq!(spec.tasks[0].requirement_ids, vec!["REQ-001"]);
+    assert_eq!(spec.tasks[0].scenario_ids, vec!["REQ-001/SC-001"]);
+    assert_eq!(spec.decisions[0].id, "DEC-001");
+    let schema: serde_json::Value = serde_json::from_str(include_str!("../schemas/core.json"))?;
+    SpecValidator::new(&schema, &ids)?.validate(&spec)?;
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+// Unit tests for the TUI detail tab filter
+// Tests substring matching of requirements and tasks
+
+use manifold::models::{Priority, Requirement, Task, TaskStatus};
+use manifold::tui::filter::{requirement_matches, task_matches};
+
+fn requirement() -> Requirement {
+    Requirement {
+        id: "req-001".to_string(),
+        capability: "auth".to_string(),
+        title: "Token refresh".to_string(),
+        shall: "The system SHALL rotate session tokens hourly".to_string(),
+        rationale: None,
+        priority: Priority::Must,
+        tags: vec![],
+        scenarios: vec![],
+        history: vec![],
+        verification: Default::default(),
+    }
+}
+
+fn task() -> Task {
+    Task {
+        id: "task-001".to_string(),
+        requirement_ids: vec!["req-001".to_string()],
+        title: "Wire up scheduler".to_string(),
+        description: "Run the rotation job from cron".to_string(),
+        status: TaskStatus::Pending,
+        assignee: None,
+        acceptance: vec![],
+        scenario_ids: vec![],
+    }
+}
+
+#[test]
+fn test_requirement_filter_matches_title_and_shall() {
+    let req = requirement();
+
+    assert!(requirement_matches(&req, "refresh"));
+    assert!(requirement_matches(&req, "ROTATE SESSION"));
+    assert!(requirement_matches(&req, "req-001"));
+    assert!(!requirement_matches(&req, "cron"));
+}
+
+#[test]
+fn test_task_filter_matches_title_and_description() {
+    let task = task();
+
+    assert!(task_matches(&task, "Scheduler"));
+    assert!(task_matches(&task, "from cron"));
+    assert!(!task_matches(&task, "rotate session"));
+}
+
+#[test]
+fn test_blank_filter_matches_everything() {
+    assert!(requirement_matches(&requirement(), ""));
+    assert!(task_matches(&task(), "   "));
+}
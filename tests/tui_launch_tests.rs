@@ -0,0 +1,90 @@
+// Integration tests for TUI launch options
+// Tests the initial boundary filter and spec selection
+
+mod common;
+
+use anyhow::Result;
+use manifold::config::ManifoldPaths;
+use manifold::models::{Boundary, SpecData};
+use manifold::tui::{TuiApp, TuiOptions};
+use tempfile::TempDir;
+
+/// Setup a manifold holding one spec per boundary
+fn setup() -> Result<(TempDir, ManifoldPaths)> {
+    let (temp_dir, paths, db) = common::setup()?;
+    for (id, boundary) in [
+        ("spec-home", Boundary::Personal),
+        ("spec-work-a", Boundary::Work),
+        ("spec-work-b", Boundary::Work),
+        ("spec-corp", Boundary::Company),
+    ] {
+        db.insert_spec(&SpecData::new(
+            id.to_string(),
+            "launch".to_string(),
+            format!("Launch {}", id),
+            boundary,
+        ))?;
+    }
+
+    Ok((temp_dir, paths))
+}
+
+#[test]
+fn test_tui_opens_unfiltered_by_default() -> Result<()> {
+    let (_temp, paths) = setup()?;
+    let app = TuiApp::new(&paths, TuiOptions::default())?;
+
+    assert_eq!(app.filter_boundary(), None);
+    assert_eq!(app.spec_ids().len(), 4);
+    assert!(app.selected_spec_id().is_some());
+    assert_eq!(app.selected_tab_title(), "Overview");
+    Ok(())
+}
+
+#[test]
+fn test_tui_launch_options_set_filter_and_selection() -> Result<()> {
+    let (_temp, paths) = setup()?;
+    let app = TuiApp::new(
+        &paths,
+        TuiOptions {
+            boundary: Some(Boundary::Work),
+            spec: Some("spec-work-b".to_string()),
+        },
+    )?;
+
+    assert_eq!(app.filter_boundary(), Some("work"));
+    let mut ids = app.spec_ids();
+    ids.sort();
+    assert_eq!(ids, vec!["spec-work-a", "spec-work-b"]);
+    assert_eq!(app.selected_spec_id(), Some("spec-work-b"));
+    assert_eq!(app.selected_tab_title(), "Overview");
+    Ok(())
+}
+
+#[test]
+fn test_tui_rejects_spec_outside_filter_or_missing() -> Result<()> {
+    let (_temp, paths) = setup()?;
+    let filtered_out = TuiApp::new(
+        &paths,
+        TuiOptions {
+            boundary: Some(Boundary::Work),
+            spec: Some("spec-home".to_string()),
+        },
+    );
+    let message = filtered_out.err().unwrap().to_string();
+    assert!(message.contains("not in the work boundary"), "{}", message);
+
+    let missing = TuiApp::new(
+        &paths,
+        TuiOptions {
+            boundary: None,
+            spec: Some("spec-nope".to_string()),
+        },
+    );
+    assert!(missing
+        .err()
+        .unwrap()
+        .to_string()
+        .contains("Spec not found"));
+    Ok(())
+}
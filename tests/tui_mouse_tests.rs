@@ -0,0 +1,48 @@
+// Unit tests for TUI mouse hit-testing
+// Tests mapping click positions to spec list rows and detail tabs
+
+use manifold::tui::mouse::{list_row_at, tab_at};
+use ratatui::layout::Rect;
+
+#[test]
+fn test_list_row_at_maps_y_to_index() {
+    // Bordered list at (0, 3), 10 rows tall: items occupy y = 4..=11
+    let area = Rect::new(0, 3, 30, 10);
+
+    assert_eq!(list_row_at(area, 0, 4, 20), Some(0));
+    assert_eq!(list_row_at(area, 0, 11, 20), Some(7));
+
+    // Scrolled down by 5, the first visible row is item 5
+    assert_eq!(list_row_at(area, 5, 4, 20), Some(5));
+    assert_eq!(list_row_at(area, 5, 9, 20), Some(10));
+}
+
+#[test]
+fn test_list_row_at_ignores_borders_and_empty_rows() {
+    let area = Rect::new(0, 3, 30, 10);
+
+    // Top and bottom borders
+    assert_eq!(list_row_at(area, 0, 3, 20), None);
+    assert_eq!(list_row_at(area, 0, 12, 20), None);
+    // Below the last item
+    assert_eq!(list_row_at(area, 0, 6, 2), None);
+    assert_eq!(list_row_at(area, 0, 5, 2), Some(1));
+}
+
+#[test]
+fn test_tab_at_maps_x_to_tab() {
+    let titles = ["Overview", "Tasks", "History"];
+    // Inner row is y = 1, starting at x = 11
+    let area = Rect::new(10, 0, 60, 3);
+
+    // " Overview " spans x = 11..21, divider at 21
+    assert_eq!(tab_at(area, &titles, 11, 1), Some(0));
+    assert_eq!(tab_at(area, &titles, 20, 1), Some(0));
+    assert_eq!(tab_at(area, &titles, 21, 1), None);
+    // " Tasks " spans x = 22..29
+    assert_eq!(tab_at(area, &titles, 22, 1), Some(1));
+    assert_eq!(tab_at(area, &titles, 30, 1), Some(2));
+    // Past the last tab, and on the border
+    assert_eq!(tab_at(area, &titles, 50, 1), None);
+    assert_eq!(tab_at(area, &titles, 15, 0), None);
+}
@@ -0,0 +1,55 @@
+// Integration tests for TUI auto-refresh
+// Tests that reloading keeps the selected spec when the list reorders
+
+mod common;
+
+use anyhow::Result;
+use manifold::config::ManifoldPaths;
+use manifold::db::Database;
+use manifold::models::{Boundary, SpecData};
+use manifold::tui::{TuiApp, TuiOptions};
+use tempfile::TempDir;
+
+/// Setup a manifold with three specs, newest first: spec-c, spec-b, spec-a
+fn setup() -> Result<(TempDir, ManifoldPaths, Database)> {
+    let (temp_dir, paths, db) = common::setup()?;
+    for (offset, id) in ["spec-a", "spec-b", "spec-c"].into_iter().enumerate() {
+        let mut spec = SpecData::new(
+            id.to_string(),
+            "refresh".to_string(),
+            format!("Refresh {}", id),
+            Boundary::Personal,
+        );
+        spec.history.updated_at = 1_700_000_000 + offset as i64;
+        db.insert_spec(&spec)?;
+    }
+
+    Ok((temp_dir, paths, db))
+}
+
+#[test]
+fn test_auto_refresh_keeps_selection_when_specs_reorder() -> Result<()> {
+    let (_temp, paths, db) = setup()?;
+    let mut app = TuiApp::new(
+        &paths,
+        TuiOptions {
+            spec: Some("spec-b".to_string()),
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(app.spec_ids(), vec!["spec-c", "spec-b", "spec-a"]);
+
+    // Nothing changed yet
+    assert!(!app.auto_refresh()?);
+
+    // Another process edits spec-a, moving it to the top
+    let mut spec = db.get_spec_data("spec-a")?.expect("spec-a");
+    spec.name = "Edited elsewhere".to_string();
+    spec.history.updated_at = 1_800_000_000;
+    db.update_spec(&spec)?;
+
+    assert!(app.auto_refresh()?);
+    assert_eq!(app.spec_ids(), vec!["spec-a", "spec-c", "spec-b"]);
+    assert_eq!(app.selected_spec_id(), Some("spec-b"));
+    Ok(())
+}
@@ -0,0 +1,68 @@
+// Unit tests for TUI theme resolution
+// Tests presets, per-role overrides from config.toml, and NO_COLOR
+
+use anyhow::Result;
+use manifold::config::Config;
+use manifold::tui::theme::{Theme, ThemePreset};
+use ratatui::style::{Color, Modifier};
+
+#[test]
+fn test_configured_role_overrides_preset() -> Result<()> {
+    let config: Config = toml::from_str(
+        r##"
+        default_boundary = "personal"
+
+        [tui.theme]
+        preset = "colorblind"
+        error = "lightmagenta"
+        highlight = "#005f87"
+        "##,
+    )?;
+    assert_eq!(config.tui.theme.preset, ThemePreset::Colorblind);
+
+    let theme = Theme::resolve(&config.tui.theme, false)?;
+
+    assert_eq!(theme.error.fg, Some(Color::LightMagenta));
+    assert_eq!(theme.highlight.bg, Some(Color::Rgb(0x00, 0x5f, 0x87)));
+    // Roles left alone come from the preset
+    assert_eq!(theme.warning.fg, Some(Color::Rgb(230, 159, 0)));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_color_is_rejected() -> Result<()> {
+    let config: Config = toml::from_str(
+        r#"
+        default_boundary = "personal"
+
+        [tui.theme]
+        title = "not-a-color"
+        "#,
+    )?;
+
+    let err = Theme::resolve(&config.tui.theme, false).unwrap_err();
+    assert!(err.to_string().contains("not-a-color"));
+
+    Ok(())
+}
+
+#[test]
+fn test_no_color_uses_plain_styles() -> Result<()> {
+    let config: Config = toml::from_str(
+        r#"
+        default_boundary = "personal"
+
+        [tui.theme]
+        error = "red"
+        "#,
+    )?;
+
+    let theme = Theme::resolve(&config.tui.theme, true)?;
+
+    assert_eq!(theme.error.fg, None);
+    assert_eq!(theme.highlight.bg, None);
+    assert!(theme.highlight.add_modifier.contains(Modifier::REVERSED));
+
+    Ok(())
+}
@@ -0,0 +1,351 @@
+// Integration tests for spec validation
+// Tests id uniqueness and other structural checks
+
+use manifold::config::LintConfig;
+use manifold::models::{
+    Boundary, Decision, Priority, Requirement, Scenario, SpecData, Task, TaskStatus,
+};
+use manifold::validation::{
+    find_similar_requirements, validate_all, IdPatterns, SpecValidation, SpecValidator,
+};
+
+fn create_test_spec() -> SpecData {
+    SpecData::new(
+        "test-spec".to_string(),
+        "test-project".to_string(),
+        "Test Spec".to_string(),
+        Boundary::Personal,
+    )
+}
+
+fn requirement(id: &str, scenario_ids: &[&str]) -> Requirement {
+    Requirement {
+        id: id.to_string(),
+        capability: "test".to_string(),
+        title: format!("Requirement {}", id),
+        shall: "The system SHALL do something".to_string(),
+        rationale: None,
+        priority: Priority::Must,
+        tags: vec![],
+        scenarios: scenario_ids
+            .iter()
+            .map(|sc| Scenario {
+                id: sc.to_string(),
+                name: format!("Scenario {}", sc),
+                given: vec!["a precondition".to_string()],
+                when: "an action happens".to_string(),
+                then: vec!["an outcome".to_string()],
+                edge_cases: vec![],
+            })
+            .collect(),
+        history: vec![],
+        verification: Default::default(),
+    }
+}
+
+fn task(id: &str) -> Task {
+    Task {
+        id: id.to_string(),
+        requirement_ids: vec!["req-1".to_string()],
+        title: format!("Task {}", id),
+        description: "Do the work".to_string(),
+        status: TaskStatus::Pending,
+        assignee: None,
+        acceptance: vec![],
+        scenario_ids: vec![],
+    }
+}
+
+fn decision(id: &str) -> Decision {
+    Decision {
+        id: id.to_string(),
+        title: format!("Decision {}", id),
+        context: "Context".to_string(),
+        decision: "Decided".to_string(),
+        rationale: "Because".to_string(),
+        alternatives_rejected: vec![],
+        date: "2024-01-15".to_string(),
+    }
+}
+
+#[test]
+fn test_unique_ids_pass() {
+    let mut spec = create_test_spec();
+    spec.requirements.push(requirement("req-1", &["sc-1"]));
+    spec.requirements.push(requirement("req-2", &["sc-2"]));
+    spec.tasks.push(task("task-1"));
+    spec.decisions.push(decision("dec-1"));
+
+    assert!(spec.validate_ids_unique_across_types().is_ok());
+}
+
+#[test]
+fn test_scenario_id_reused_across_requirements() {
+    let mut spec = create_test_spec();
+    spec.requirements.push(requirement("req-1", &["sc-1"]));
+    spec.requirements
+        .push(requirement("req-2", &["sc-2", "sc-1"]));
+
+    let duplicates = spec.validate_ids_unique_across_types().unwrap_err();
+
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(duplicates[0].id, "sc-1");
+    assert_eq!(
+        duplicates[0].locations,
+        vec![
+            "/requirements/0/scenarios/0/id".to_string(),
+            "/requirements/1/scenarios/1/id".to_string(),
+        ]
+    );
+    assert!(duplicates[0].to_string().contains("sc-1"));
+}
+
+#[test]
+fn test_id_shared_between_requirement_and_task() {
+    let mut spec = create_test_spec();
+    spec.requirements.push(requirement("shared-1", &[]));
+    spec.tasks.push(task("task-1"));
+    spec.tasks.push(task("shared-1"));
+
+    let duplicates = spec.validate_ids_unique_across_types().unwrap_err();
+
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(
+        duplicates[0].locations,
+        vec!["/requirements/0/id".to_string(), "/tasks/1/id".to_string()]
+    );
+}
+
+#[test]
+fn test_lint_flags_non_iso_decision_date() {
+    let mut spec = create_test_spec();
+    spec.decisions.push(decision("dec-1"));
+    let mut stamped = decision("dec-2");
+    stamped.date = "2024-01-15T09:30:00Z".to_string();
+    spec.decisions.push(stamped);
+    let mut loose = decision("dec-3");
+    loose.date = "Jan 15, 2024".to_string();
+    spec.decisions.push(loose);
+
+    let warnings = manifold::validation::lint_spec(&spec);
+    let date_warnings: Vec<_> = warnings.iter().filter(|w| w.contains("ISO-8601")).collect();
+
+    assert_eq!(date_warnings.len(), 1);
+    assert!(date_warnings[0].starts_with("dec-3:"));
+    assert!(date_warnings[0].contains("Jan 15, 2024"));
+}
+
+#[test]
+fn test_coverage_of_partially_complete_spec() {
+    let mut spec = create_test_spec();
+    assert_eq!(manifold::validation::coverage(&spec).score, 0.0);
+
+    // req-1 has a scenario and a task; req-2 has neither
+    spec.requirements.push(requirement("req-1", &["sc-1"]));
+    spec.requirements.push(requirement("req-2", &[]));
+    let mut accepted = task("task-1");
+    accepted.acceptance = vec!["Login succeeds".to_string()];
+    spec.tasks.push(accepted);
+    spec.tasks.push(task("task-2"));
+    spec.tasks.push(task("task-3"));
+    spec.tasks.push(task("task-4"));
+
+    let report = manifold::validation::coverage(&spec);
+
+    assert_eq!(report.requirements, 2);
+    assert_eq!(report.requirements_with_scenarios, 1);
+    assert_eq!(report.requirements_traced, 1);
+    assert_eq!(report.tasks, 4);
+    assert_eq!(report.tasks_with_acceptance, 1);
+    // (50% + 50% + 25%) / 3
+    assert!((report.score - 41.666).abs() < 0.01, "{}", report.score);
+
+    // Without tasks only the requirement ratios count
+    spec.tasks.clear();
+    let report = manifold::validation::coverage(&spec);
+    assert_eq!(report.requirements_traced, 0);
+    assert!((report.score - 25.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_size_lint_names_dominant_section() {
+    use manifold::models::PatchEntry;
+    use manifold::validation::{lint_spec_size, spec_size};
+
+    let mut spec = create_test_spec();
+    spec.requirements.push(requirement("req-1", &["sc-1"]));
+    assert_eq!(lint_spec_size(&spec, 64 * 1024), None);
+
+    // A runaway patch log dwarfs everything else
+    for i in 0..2000 {
+        spec.history.patches.push(PatchEntry {
+            timestamp: i,
+            actor: "llm-session".to_string(),
+            op: "replace".to_string(),
+            path: "/requirements/0/shall".to_string(),
+            summary: "Reworded the requirement once more".repeat(2),
+        });
+    }
+
+    let size = spec_size(&spec);
+    assert_eq!(size.largest_section, "history");
+    assert!(size.bytes > 64 * 1024);
+
+    let warning = lint_spec_size(&spec, 64 * 1024).expect("oversized spec is flagged");
+    assert!(warning.contains("over the 64 KiB limit"), "{}", warning);
+    assert!(warning.contains("'history'"), "{}", warning);
+}
+
+#[test]
+fn test_similar_requirements_are_flagged() {
+    use manifold::validation::lint_similar_requirements;
+
+    let mut spec = create_test_spec();
+    let shalls = [
+        (
+            "req-1",
+            "The system SHALL send a password reset email within 5 minutes",
+        ),
+        (
+            "req-2",
+            "Within 5 minutes the system SHALL email a password reset link",
+        ),
+        ("req-3", "The system SHALL export monthly reports as PDF"),
+        ("req-4", "Admins MUST be able to revoke API tokens"),
+    ];
+    for (id, shall) in shalls {
+        let mut req = requirement(id, &["sc-1"]);
+        req.shall = shall.to_string();
+        spec.requirements.push(req);
+    }
+
+    let similar = find_similar_requirements(&spec, 0.8);
+    assert_eq!(similar.len(), 1);
+    assert_eq!(
+        (similar[0].first.as_str(), similar[0].second.as_str()),
+        ("req-1", "req-2")
+    );
+    assert!(similar[0].similarity >= 0.8 && similar[0].similarity < 1.0);
+
+    let warnings = lint_similar_requirements(&spec, 0.8);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].starts_with("req-2:"), "{}", warnings[0]);
+
+    // Unrelated statements sharing only boilerplate stay below the threshold
+    assert!(find_similar_requirements(&spec, 0.3)
+        .iter()
+        .all(|pair| pair.first != "req-3" || pair.second != "req-4"));
+    // Above 1.0 nothing is ever flagged
+    assert!(find_similar_requirements(&spec, 1.01).is_empty());
+}
+
+#[test]
+fn test_verification_defaults_to_not_started() {
+    use manifold::models::VerificationStatus;
+
+    let json = r#"{
+        "id": "req-1",
+        "capability": "core",
+        "title": "Old requirement",
+        "shall": "The system SHALL work",
+        "priority": "must",
+        "scenarios": []
+    }"#;
+    let mut req: Requirement = serde_json::from_str(json).unwrap();
+    assert_eq!(req.verification, VerificationStatus::NotStarted);
+    // Unverified requirements serialize as before
+    assert!(!serde_json::to_string(&req)
+        .unwrap()
+        .contains("verification"));
+
+    req.verification = "in-progress".parse().unwrap();
+    let value = serde_json::to_value(&req).unwrap();
+    assert_eq!(value["verification"], "in_progress");
+
+    let mut spec = create_test_spec();
+    spec.requirements.push(req);
+    spec.requirements.push(requirement("req-2", &[]));
+    spec.requirements[1].verification = VerificationStatus::Verified;
+    assert_eq!(
+        manifold::validation::coverage(&spec).requirements_verified,
+        1
+    );
+}
+
+#[test]
+fn test_task_scenario_links_are_checked() {
+    use manifold::validation::{lint_spec, uncovered_scenarios, validate_scenario_links};
+
+    let mut spec = create_test_spec();
+    spec.requirements
+        .push(requirement("req-1", &["sc-1", "sc-2"]));
+    spec.requirements.push(requirement("req-2", &["sc-3"]));
+    let mut linked = task("task-1");
+    linked.scenario_ids = vec!["req-1/sc-1".to_string(), "req-2/sc-3".to_string()];
+    spec.tasks.push(linked);
+    validate_scenario_links(&spec).unwrap();
+
+    // req-1/sc-2 has no task
+    assert_eq!(uncovered_scenarios(&spec), vec!["req-1/sc-2".to_string()]);
+    let warnings = lint_spec(&spec);
+    assert!(warnings.contains(&"req-1/sc-2: Scenario not covered by any task".to_string()));
+
+    // A scenario under the wrong requirement doesn't resolve
+    spec.tasks[0].scenario_ids.push("req-2/sc-1".to_string());
+    let err = validate_scenario_links(&spec).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Task task-1 references unknown scenario req-2/sc-1"
+    );
+    assert!(lint_spec(&spec)
+        .iter()
+        .any(|w| w == "task-1: References non-existent scenario req-2/sc-1"));
+
+    // Specs that don't link scenarios at all aren't nagged
+    spec.tasks[0].scenario_ids.clear();
+    assert!(!lint_spec(&spec)
+        .iter()
+        .any(|w| w.contains("not covered by any task")));
+}
+
+#[test]
+fn test_parallel_validation_matches_sequential() -> anyhow::Result<()> {
+    let schema: serde_json::Value = serde_json::from_str(include_str!("../schemas/core.json"))?;
+    let validator = SpecValidator::new(&schema, &IdPatterns::default())?;
+
+    let mut specs = Vec::new();
+    for i in 0..40 {
+        let mut spec = create_test_spec();
+        spec.spec_id = format!("spec-{:02}", 39 - i);
+        spec.requirements.push(requirement("req-1", &["sc-1"]));
+        match i % 4 {
+            // Valid, but a requirement without a task draws lint warnings
+            0 => {}
+            1 => spec.tasks.push(task("task-1")),
+            // Malformed task id fails the schema
+            2 => spec.tasks.push(task("TASK_1")),
+            // Dangling scenario reference fails the structural checks
+            _ => {
+                let mut linked = task("task-1");
+                linked.scenario_ids.push("req-1/sc-9".to_string());
+                spec.tasks.push(linked);
+            }
+        }
+        specs.push(spec);
+    }
+
+    let mut sequential: Vec<SpecValidation> = specs
+        .iter()
+        .map(|spec| SpecValidation::check(spec, &validator, &LintConfig::default()))
+        .collect();
+    sequential.sort_by(|a, b| a.spec_id.cmp(&b.spec_id));
+
+    let parallel = validate_all(&specs, &validator, &LintConfig::default());
+    assert_eq!(parallel, sequential);
+    assert_eq!(parallel[0].spec_id, "spec-00");
+    assert_eq!(parallel.iter().filter(|r| !r.schema_valid).count(), 20);
+    assert!(parallel
+        .iter()
+        .any(|r| r.schema_valid && !r.warnings.is_empty()));
+    Ok(())
+}
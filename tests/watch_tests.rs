@@ -0,0 +1,139 @@
+// Integration tests for manifold watch
+// Tests change detection against the stored updated_at and read-only access
+
+mod common;
+
+use anyhow::Result;
+use common::setup;
+use manifold::commands::ChangeDetector;
+use manifold::db::Database;
+use manifold::models::{Boundary, SpecData};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_change_detected_after_debounce() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    let mut spec = SpecData::new(
+        "watched".to_string(),
+        "live".to_string(),
+        "Watched spec".to_string(),
+        Boundary::Work,
+    );
+    spec.history.updated_at = 1_000;
+    db.insert_spec(&spec)?;
+
+    let debounce = Duration::from_millis(300);
+    let start = Instant::now();
+    let mut detector = ChangeDetector::new(db.spec_updated_at("watched")?.unwrap(), debounce);
+
+    // Nothing changed yet
+    assert!(!detector.observe(db.spec_updated_at("watched")?.unwrap(), start));
+
+    // A burst of writes only settles once the last one has aged
+    for ts in [1_001, 1_002] {
+        spec.history.updated_at = ts;
+        db.update_spec(&spec)?;
+        let stored = db.spec_updated_at("watched")?.unwrap();
+        assert!(!detector.observe(stored, start + Duration::from_millis(100)));
+    }
+    let stored = db.spec_updated_at("watched")?.unwrap();
+    assert_eq!(stored, 1_002);
+    assert!(!detector.observe(stored, start + Duration::from_millis(200)));
+    assert!(detector.observe(stored, start + Duration::from_millis(400)));
+
+    // Reported once, then quiet again
+    assert!(!detector.observe(stored, start + Duration::from_millis(900)));
+
+    assert_eq!(db.spec_updated_at("missing")?, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_export_watch_triggers_on_exported_specs_only() -> Result<()> {
+    use manifold::commands::export_stamp;
+
+    let (_temp, _paths, db) = setup()?;
+    let mut specs = Vec::new();
+    for id in ["exported", "other"] {
+        let mut spec = SpecData::new(
+            id.to_string(),
+            "docs".to_string(),
+            format!("Spec {}", id),
+            Boundary::Work,
+        );
+        spec.history.updated_at = 1_000;
+        db.insert_spec(&spec)?;
+        specs.push(spec);
+    }
+
+    let debounce = Duration::from_millis(300);
+    let start = Instant::now();
+    let mut single = ChangeDetector::new(export_stamp(&db, "exported")?, debounce);
+    let mut all = ChangeDetector::new(export_stamp(&db, "all")?, debounce);
+
+    // Editing another spec only regenerates the 'all' export
+    specs[1].history.updated_at = 2_000;
+    db.update_spec(&specs[1])?;
+    assert!(!single.observe(export_stamp(&db, "exported")?, start));
+    assert!(!all.observe(export_stamp(&db, "all")?, start));
+    let later = start + Duration::from_millis(400);
+    assert!(!single.observe(export_stamp(&db, "exported")?, later));
+    assert!(all.observe(export_stamp(&db, "all")?, later));
+
+    // A new spec changes the count even with an older timestamp
+    let mut added = specs[0].clone();
+    added.spec_id = "added".to_string();
+    added.history.updated_at = 500;
+    db.insert_spec(&added)?;
+    assert_eq!(export_stamp(&db, "all")?, (3, 2_000));
+    assert!(!all.observe(export_stamp(&db, "all")?, later));
+    assert!(all.observe(
+        export_stamp(&db, "all")?,
+        later + Duration::from_millis(400)
+    ));
+
+    // Editing the exported spec regenerates it once the edit settles
+    specs[0].history.updated_at = 3_000;
+    db.update_spec(&specs[0])?;
+    assert!(!single.observe(export_stamp(&db, "exported")?, later));
+    assert!(single.observe(
+        export_stamp(&db, "exported")?,
+        later + Duration::from_millis(300)
+    ));
+
+    assert!(export_stamp(&db, "missing").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_readonly_handle_reads_but_refuses_writes() -> Result<()> {
+    let (_temp, paths, db) = setup()?;
+
+    let mut spec = SpecData::new(
+        "ro-spec".to_string(),
+        "readers".to_string(),
+        "Read only".to_string(),
+        Boundary::Work,
+    );
+    db.insert_spec(&spec)?;
+
+    let reader = Database::open_readonly(&paths)?;
+    assert_eq!(reader.get_spec("ro-spec")?.unwrap().project, "readers");
+    assert_eq!(reader.list_spec_ids()?, vec!["ro-spec".to_string()]);
+    reader.invalidate_cache()?;
+
+    spec.name = "Changed".to_string();
+    let err = reader.update_spec(&spec).unwrap_err();
+    assert!(err.to_string().contains("read-only"));
+    assert!(reader.rebuild_fts().is_err());
+
+    // The writer is not blocked while the reader is open
+    db.update_spec(&spec)?;
+    let stored = reader.get_spec("ro-spec")?.unwrap().parse()?;
+    assert_eq!(stored.name, "Changed");
+
+    Ok(())
+}
@@ -0,0 +1,434 @@
+// Integration tests for workflow event tracking
+// Tests workflow event logging, filtering, and pagination
+
+mod common;
+
+use anyhow::Result;
+use common::setup;
+use manifold::commands::{bulk_advance, BulkAdvanceOptions};
+use manifold::db::Database;
+use manifold::models::{Boundary, Priority, Requirement, SpecData, WorkflowStage};
+use std::fs;
+
+fn create_test_spec(spec_id: &str) -> SpecData {
+    SpecData::new(
+        spec_id.to_string(),
+        "test-project".to_string(),
+        "Test Spec".to_string(),
+        Boundary::Personal,
+    )
+}
+
+/// Log a mix of transition and validation failure events
+fn log_sample_events(db: &Database, spec_id: &str) -> Result<()> {
+    db.log_workflow_event(
+        spec_id,
+        "requirements",
+        "validation_failed:no requirements",
+        "user",
+        100,
+        None,
+    )?;
+    db.log_workflow_event(
+        spec_id,
+        "design",
+        "transition:requirements:design",
+        "user",
+        200,
+        Some("Advanced from requirements to design"),
+    )?;
+    db.log_workflow_event(
+        spec_id,
+        "design",
+        "validation_failed:no decisions",
+        "mcp",
+        300,
+        None,
+    )?;
+    db.log_workflow_event(
+        spec_id,
+        "tasks",
+        "transition:design:tasks",
+        "mcp",
+        400,
+        Some("Advanced from design to tasks"),
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_workflow_events_unfiltered() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+    db.insert_spec(&create_test_spec("spec-events"))?;
+    log_sample_events(&db, "spec-events")?;
+
+    let events = db.get_workflow_events("spec-events", None, None, None)?;
+
+    assert_eq!(events.len(), 4);
+    // Newest first
+    assert_eq!(events[0].timestamp, 400);
+    assert_eq!(events[3].timestamp, 100);
+
+    Ok(())
+}
+
+#[test]
+fn test_workflow_events_prefix_excludes_validation_failures() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+    db.insert_spec(&create_test_spec("spec-prefix"))?;
+    log_sample_events(&db, "spec-prefix")?;
+
+    let events = db.get_workflow_events("spec-prefix", None, None, Some("transition:"))?;
+
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|e| e.event.starts_with("transition:")));
+    assert!(!events
+        .iter()
+        .any(|e| e.event.starts_with("validation_failed:")));
+
+    Ok(())
+}
+
+#[test]
+fn test_workflow_events_limit_and_since() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+    db.insert_spec(&create_test_spec("spec-paged"))?;
+    log_sample_events(&db, "spec-paged")?;
+
+    let limited = db.get_workflow_events("spec-paged", Some(1), None, None)?;
+    assert_eq!(limited.len(), 1);
+    assert_eq!(limited[0].timestamp, 400);
+
+    let recent = db.get_workflow_events("spec-paged", None, Some(300), None)?;
+    assert_eq!(recent.len(), 2);
+    assert!(recent.iter().all(|e| e.timestamp >= 300));
+
+    Ok(())
+}
+
+#[test]
+fn test_workflow_stage_all_in_canonical_order() {
+    use manifold::models::WorkflowStage;
+
+    let stages = WorkflowStage::all();
+    let names: Vec<String> = stages.iter().map(|s| s.to_string()).collect();
+    assert_eq!(
+        names,
+        vec!["requirements", "design", "tasks", "approval", "implemented"]
+    );
+
+    for (i, stage) in stages.iter().enumerate() {
+        assert_eq!(stage.order(), i);
+        assert!(!stage.icon().is_empty());
+    }
+}
+
+fn create_ready_spec(spec_id: &str) -> SpecData {
+    let mut spec = create_test_spec(spec_id);
+    spec.requirements.push(Requirement {
+        id: "req-1".to_string(),
+        capability: "core".to_string(),
+        title: "Core behaviour".to_string(),
+        shall: "The system SHALL work".to_string(),
+        rationale: None,
+        priority: Priority::Must,
+        tags: vec![],
+        scenarios: vec![],
+        history: vec![],
+        verification: Default::default(),
+    });
+    spec
+}
+
+fn stage_of(db: &Database, spec_id: &str) -> Result<String> {
+    Ok(db.get_spec(spec_id)?.expect("spec exists").stage)
+}
+
+#[test]
+fn test_bulk_advance_only_ready_specs() -> Result<()> {
+    let (_temp, _paths, db) = setup()?;
+
+    db.insert_spec(&create_ready_spec("spec-ready"))?;
+    db.insert_spec(&create_test_spec("spec-empty"))?;
+
+    let mut awaiting_approval = create_test_spec("spec-approval");
+    awaiting_approval.stage = WorkflowStage::Approval;
+    db.insert_spec(&awaiting_approval)?;
+
+    let mut done = create_test_spec("spec-done");
+    done.stage = WorkflowStage::Implemented;
+    db.insert_spec(&done)?;
+
+    // Dry run reports without changing anything
+    let options = BulkAdvanceOptions {
+        dry_run: true,
+        ..Default::default()
+    };
+    let preview = bulk_advance(&db, &options)?;
+    assert_eq!(preview.advanced.len(), 1);
+    assert_eq!(stage_of(&db, "spec-ready")?, "requirements");
+
+    let report = bulk_advance(&db, &BulkAdvanceOptions::default())?;
+
+    let advanced: Vec<&str> = report.advanced.iter().map(|s| s.spec_id.as_str()).collect();
+    assert_eq!(advanced, vec!["spec-ready"]);
+    assert_eq!(report.advanced[0].to, WorkflowStage::Design);
+
+    let mut blocked: Vec<&str> = report.blocked.iter().map(|s| s.spec_id.as_str()).collect();
+    blocked.sort();
+    assert_eq!(blocked, vec!["spec-approval", "spec-empty"]);
+
+    assert_eq!(stage_of(&db, "spec-ready")?, "design");
+    assert_eq!(stage_of(&db, "spec-empty")?, "requirements");
+    assert_eq!(stage_of(&db, "spec-approval")?, "approval");
+    assert_eq!(stage_of(&db, "spec-done")?, "implemented");
+
+    // The approval gate only opens when asked
+    let options = BulkAdvanceOptions {
+        target_stage: Some(WorkflowStage::Implemented),
+        include_approval: true,
+        ..Default::default()
+    };
+    let report = bulk_advance(&db, &options)?;
+    assert_eq!(report.advanced.len(), 1);
+    assert_eq!(stage_of(&db, "spec-approval")?, "implemented");
+
+    Ok(())
+}
+
+#[test]
+fn test_min_coverage_gates_approval() -> Result<()> {
+    use manifold::models::{Task, TaskStatus};
+    use manifold::workflow::{WorkflowEngine, WorkflowPolicy};
+
+    let mut spec = create_ready_spec("spec-gated");
+    spec.stage = WorkflowStage::Tasks;
+    spec.tasks.push(Task {
+        id: "task-1".to_string(),
+        requirement_ids: vec!["req-1".to_string()],
+        title: "Build it".to_string(),
+        description: "Do the work".to_string(),
+        status: TaskStatus::Pending,
+        assignee: None,
+        acceptance: vec![],
+        scenario_ids: vec![],
+    });
+
+    // No scenario and no acceptance criteria: (0% + 100% + 0%) / 3
+    let policy = WorkflowPolicy {
+        min_coverage: Some(50.0),
+        ..Default::default()
+    };
+    let err = WorkflowEngine::can_advance_with(&spec, &policy).unwrap_err();
+    assert!(err.to_string().contains("coverage 33%"));
+
+    // Without a policy the built-in rules still pass
+    assert_eq!(WorkflowEngine::can_advance(&spec)?, WorkflowStage::Approval);
+
+    spec.tasks[0].acceptance = vec!["It works".to_string()];
+    assert_eq!(
+        WorkflowEngine::can_advance_with(&spec, &policy)?,
+        WorkflowStage::Approval
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_transition_hook_runs_with_spec_and_stages() -> Result<()> {
+    use manifold::workflow::hooks::TransitionHook;
+    use manifold::workflow::WorkflowPolicy;
+
+    let (temp, _paths, db) = setup()?;
+    db.insert_spec(&create_ready_spec("spec-hooked"))?;
+
+    let log = temp.path().join("hook.log");
+    let script = temp.path().join("hook.sh");
+    fs::write(
+        &script,
+        format!(
+            "#!/bin/sh\necho \"$@ $MANIFOLD_ACTOR\" >> {}\n",
+            log.display()
+        ),
+    )?;
+
+    let mut policy = WorkflowPolicy::default();
+    policy.hooks.insert(
+        "requirements->design".to_string(),
+        TransitionHook {
+            command: Some(format!("sh {}", script.display())),
+            webhook: None,
+        },
+    );
+    // Non-matching and failing hooks don't block the transition
+    policy.hooks.insert(
+        "*->implemented".to_string(),
+        TransitionHook {
+            command: Some(format!("echo wrong >> {}", log.display())),
+            webhook: None,
+        },
+    );
+    policy.hooks.insert(
+        "*->design".to_string(),
+        TransitionHook {
+            command: Some("exit 3".to_string()),
+            webhook: None,
+        },
+    );
+
+    let options = BulkAdvanceOptions {
+        policy,
+        actor: Some("alice".to_string()),
+        ..Default::default()
+    };
+    let report = bulk_advance(&db, &options)?;
+    assert_eq!(report.advanced.len(), 1);
+    assert_eq!(stage_of(&db, "spec-hooked")?, "design");
+
+    assert_eq!(
+        fs::read_to_string(&log)?,
+        "spec-hooked requirements design alice\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_transition_hook_key_matching() {
+    use manifold::workflow::hooks::matches;
+
+    assert!(matches("tasks->approval", "tasks", "approval"));
+    assert!(matches("* -> implemented", "approval", "implemented"));
+    assert!(matches("design->*", "design", "tasks"));
+    assert!(!matches("tasks->approval", "design", "tasks"));
+    assert!(!matches("approval", "tasks", "approval"));
+}
+
+#[test]
+fn test_justified_skip_waives_skipped_stage_but_enforces_prerequisites() -> Result<()> {
+    use manifold::workflow::{WorkflowEngine, WorkflowError, WorkflowEvent, WorkflowPolicy};
+
+    let policy = WorkflowPolicy::default();
+
+    // Leaving requirements still needs a requirement, skip or not
+    let empty = create_test_spec("spec-empty");
+    let err =
+        WorkflowEngine::skip_to_with(&empty, WorkflowStage::Tasks, "trivial", &policy).unwrap_err();
+    assert!(err.to_string().contains("no requirements"), "{}", err);
+
+    // A justification is required
+    let spec = create_ready_spec("spec-trivial");
+    let err = WorkflowEngine::skip_to_with(&spec, WorkflowStage::Tasks, "  ", &policy).unwrap_err();
+    assert!(matches!(err, WorkflowError::ValidationFailed(_)));
+
+    // Design has no decisions; its rule is waived and recorded
+    let transition =
+        WorkflowEngine::skip_to_with(&spec, WorkflowStage::Tasks, "Trivial spec", &policy)?;
+    assert_eq!(transition.to, WorkflowStage::Tasks);
+    assert_eq!(transition.waived.len(), 1);
+    assert!(
+        transition
+            .event
+            .as_string()
+            .starts_with("skipped:requirements:tasks"),
+        "{}",
+        transition.event.as_string()
+    );
+    assert!(
+        matches!(transition.event, WorkflowEvent::Skipped(_, _, ref why) if why == "Trivial spec")
+    );
+    assert!(transition.details().contains("waived:"));
+
+    // Approval is never skipped
+    let mut at_tasks = create_ready_spec("spec-at-tasks");
+    at_tasks.stage = WorkflowStage::Tasks;
+    let err =
+        WorkflowEngine::skip_to_with(&at_tasks, WorkflowStage::Implemented, "ship it", &policy)
+            .unwrap_err();
+    assert!(err.to_string().contains("cannot be skipped"), "{}", err);
+
+    Ok(())
+}
+
+#[test]
+fn test_require_verified_gates_implemented() -> Result<()> {
+    use manifold::models::VerificationStatus;
+    use manifold::workflow::{WorkflowEngine, WorkflowPolicy};
+
+    let mut spec = create_ready_spec("spec-verified");
+    spec.stage = WorkflowStage::Approval;
+    spec.requirements.push(Requirement {
+        id: "req-2".to_string(),
+        capability: "core".to_string(),
+        title: "Nice to have".to_string(),
+        shall: "The system SHOULD be pleasant".to_string(),
+        rationale: None,
+        priority: Priority::Could,
+        tags: vec![],
+        scenarios: vec![],
+        history: vec![],
+        verification: VerificationStatus::Failed,
+    });
+
+    let policy = WorkflowPolicy {
+        require_verified: true,
+        ..Default::default()
+    };
+    let err = WorkflowEngine::can_advance_with(&spec, &policy).unwrap_err();
+    assert!(err.to_string().contains("not verified: req-1"), "{}", err);
+
+    // Off by default
+    assert_eq!(
+        WorkflowEngine::can_advance(&spec)?,
+        WorkflowStage::Implemented
+    );
+
+    // Only must requirements count
+    spec.requirements[0].verification = VerificationStatus::Verified;
+    assert_eq!(
+        WorkflowEngine::can_advance_with(&spec, &policy)?,
+        WorkflowStage::Implemented
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_stored_workflow_events_parse_into_typed_form() -> Result<()> {
+    use manifold::workflow::WorkflowEvent;
+
+    // As logged by `workflow advance` when a stage check fails
+    let (_temp, _paths, db) = setup()?;
+    db.insert_spec(&SpecData::new(
+        "spec-typed".to_string(),
+        "typed".to_string(),
+        "Typed".to_string(),
+        Boundary::Work,
+    ))?;
+    db.log_workflow_event(
+        "spec-typed",
+        "requirements",
+        "validation_failed:No requirements defined: add one first",
+        "alice",
+        1_700_000_000,
+        None,
+    )?;
+    let rows = db.get_workflow_events("spec-typed", None, None, None)?;
+    let event: WorkflowEvent = rows[0].event.parse().unwrap();
+    assert!(matches!(
+        &event,
+        WorkflowEvent::ValidationFailed(msg) if msg == "No requirements defined: add one first"
+    ));
+    assert_eq!(event.event_type(), "validation_failed");
+    assert_eq!(event.payload(), "No requirements defined: add one first");
+
+    let transition: WorkflowEvent = "transition:requirements:design".parse().unwrap();
+    assert!(matches!(
+        transition,
+        WorkflowEvent::Transition(WorkflowStage::Requirements, WorkflowStage::Design)
+    ));
+    assert_eq!(transition.as_string(), "transition:requirements:design");
+    assert!("transition:requirements".parse::<WorkflowEvent>().is_err());
+    assert!("renamed:spec".parse::<WorkflowEvent>().is_err());
+    Ok(())
+}